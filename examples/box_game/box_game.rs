@@ -87,11 +87,7 @@ pub fn setup_system(
     mut materials: ResMut<Assets<StandardMaterial>>,
     session: Res<Session<BoxConfig>>,
 ) {
-    let num_players = match &*session {
-        Session::SyncTest(s) => s.num_players(),
-        Session::P2P(s) => s.num_players(),
-        Session::Spectator(s) => s.num_players(),
-    };
+    let num_players = session.num_players();
 
     // A ground plane
     commands.spawn(PbrBundle {
@@ -99,20 +99,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn print_events_system(mut session: ResMut<Session<BoxConfig>>) {
-    match session.as_mut() {
-        Session::P2P(s) => {
-            for event in s.events() {
-                match event {
-                    GgrsEvent::Disconnected { .. } | GgrsEvent::NetworkInterrupted { .. } => {
-                        warn!("GGRS event: {event:?}")
-                    }
-                    GgrsEvent::DesyncDetected { .. } => error!("GGRS event: {event:?}"),
-                    _ => info!("GGRS event: {event:?}"),
-                }
+fn print_events_system(mut events: EventReader<SessionEvent<BoxConfig>>) {
+    for event in events.read() {
+        match **event {
+            GgrsEvent::Disconnected { .. } | GgrsEvent::NetworkInterrupted { .. } => {
+                warn!("GGRS event: {:?}", **event)
             }
+            GgrsEvent::DesyncDetected { .. } => error!("GGRS event: {:?}", **event),
+            _ => info!("GGRS event: {:?}", **event),
         }
-        _ => panic!("This example focuses on p2p."),
     }
 }
 
@@ -69,14 +69,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn print_events_system(mut session: ResMut<Session<BoxConfig>>) {
-    match session.as_mut() {
-        Session::Spectator(s) => {
-            for event in s.events() {
-                println!("GGRS Event: {:?}", event);
-            }
-        }
-        _ => panic!("This example focuses on spectators."),
+fn print_events_system(mut events: EventReader<SessionEvent<BoxConfig>>) {
+    for event in events.read() {
+        println!("GGRS Event: {:?}", **event);
     }
 }
 
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use bevy_ggrs::prelude::*;
+use bevy_ggrs::{prelude::*, GgrsInitSchedule, InitialChecksum};
 use clap::Parser;
 
 mod box_game;
@@ -50,7 +50,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .rollback_component_with_copy::<Velocity>()
         // Transform only implement Clone, so instead we'll use that to snapshot and rollback with
         .rollback_component_with_clone::<Transform>()
-        .add_systems(Startup, setup_system)
+        // the initial world is spawned once the session is present, identically on every peer
+        .add_systems(GgrsInitSchedule, setup_system)
+        .add_systems(Update, print_initial_checksum)
         // these systems will be executed as part of the advance frame update
         .add_systems(GgrsSchedule, (move_cube_system, increase_frame_system))
         // add your GGRS session
@@ -61,3 +63,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+fn print_initial_checksum(checksum: Res<InitialChecksum>) {
+    if let (true, Some(checksum)) = (checksum.is_changed(), checksum.0) {
+        info!("Initial checksum: {checksum:X}");
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::{
+    prelude::*,
+    utils::{Duration, HashMap},
+};
+use bevy_ggrs::{
+    prelude::*, LoadWorld, LocalInputs, LocalPlayers, NetworkSimulation, RollbackFrameCount,
+};
+use clap::Parser;
+use ggrs::UdpNonBlockingSocket;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    thread,
+    time::Instant,
+};
+
+/// Runs two headless peers within a single process, connected over a loopback socket with
+/// simulated network conditions, and reports how often each of them rolls back.
+///
+/// ## Basic usage:
+///
+/// cargo run --example local_latency -- --latency 80 --jitter 20 --packet-loss 0.05
+#[derive(Parser)]
+struct Args {
+    /// The udp port to bind to for the first peer. The second peer binds to the next port.
+    #[clap(long, default_value = "7000")]
+    port: u16,
+
+    /// Simulated latency in milliseconds, added to every message sent by either peer.
+    #[clap(long, default_value = "60")]
+    latency: u64,
+
+    /// Maximum random delay in milliseconds, added on top of the latency.
+    #[clap(long, default_value = "10")]
+    jitter: u64,
+
+    /// Chance of dropping a message, between 0 and 1.
+    #[clap(long, default_value = "0.0")]
+    packet_loss: f32,
+
+    /// How long inputs should be kept before they are deployed.
+    #[clap(short, long, default_value = "2")]
+    input_delay: usize,
+
+    /// How far ahead we should simulate when we don't get any input from a player.
+    #[clap(long, default_value = "8")]
+    max_prediction: usize,
+
+    /// How many seconds to run for.
+    #[clap(short, long, default_value = "10")]
+    seconds: u64,
+}
+
+type Config = GgrsConfig<u8>;
+
+const FPS: usize = 60;
+
+/// A position moved by the inputs of each player, so mispredicted inputs change the state.
+#[derive(Resource, Default, Clone, Copy)]
+struct Positions([i32; 2]);
+
+/// How often this peer rolled back.
+#[derive(Resource, Default)]
+struct Rollbacks(usize);
+
+/// Changes the input of every local player every half second, which remote peers have to predict.
+fn read_local_inputs(mut commands: Commands, local_players: Res<LocalPlayers>, time: Res<Time>) {
+    let input = ((time.elapsed_seconds() * 2.) as u8) % 2;
+
+    let local_inputs = local_players
+        .0
+        .iter()
+        .map(|&handle| (handle, input))
+        .collect::<HashMap<_, _>>();
+
+    commands.insert_resource(LocalInputs::<Config>(local_inputs));
+}
+
+fn move_players(mut positions: ResMut<Positions>, inputs: Res<PlayerInputs<Config>>) {
+    for (position, (input, _)) in positions.0.iter_mut().zip(inputs.iter()) {
+        *position += if *input == 0 { -1 } else { 1 };
+    }
+}
+
+fn count_rollbacks(mut rollbacks: ResMut<Rollbacks>) {
+    rollbacks.0 += 1;
+}
+
+fn create_peer(
+    args: &Args,
+    local: (usize, SocketAddr),
+    remote: (usize, SocketAddr),
+) -> Result<App, Box<dyn std::error::Error>> {
+    let simulation = NetworkSimulation {
+        latency: Duration::from_millis(args.latency),
+        jitter: Duration::from_millis(args.jitter),
+        packet_loss: args.packet_loss,
+        seed: local.1.port() as u64,
+    };
+
+    let builder = SessionBuilder::<Config>::new()
+        .with_num_players(2)
+        .with_input_delay(args.input_delay)
+        .with_max_prediction_window(args.max_prediction)?
+        .with_fps(FPS)?
+        .add_player(PlayerType::Local, local.0)?
+        .add_player(PlayerType::Remote(remote.1), remote.0)?;
+
+    let socket = UdpNonBlockingSocket::bind_to_port(local.1.port())?;
+    let session = simulation.start_p2p_session(builder, socket)?;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<Config>::default())
+        .set_rollback_schedule_fps(FPS)
+        .rollback_resource_with_copy::<Positions>()
+        .init_resource::<Positions>()
+        .init_resource::<Rollbacks>()
+        .add_systems(ReadInputs, read_local_inputs)
+        .add_systems(GgrsSchedule, move_players)
+        .add_systems(LoadWorld, count_rollbacks)
+        .insert_resource(Session::P2P(session));
+
+    Ok(app)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let player1 = (0, SocketAddr::new(localhost, args.port));
+    let player2 = (1, SocketAddr::new(localhost, args.port + 1));
+
+    let mut peers = [
+        create_peer(&args, player1, player2)?,
+        create_peer(&args, player2, player1)?,
+    ];
+
+    let start = Instant::now();
+    let mut next_report = Duration::from_secs(1);
+
+    while start.elapsed() < Duration::from_secs(args.seconds) {
+        for peer in &mut peers {
+            peer.update();
+        }
+
+        if start.elapsed() >= next_report {
+            next_report += Duration::from_secs(1);
+
+            for (handle, peer) in peers.iter().enumerate() {
+                let frame = peer.world.resource::<RollbackFrameCount>().0;
+                let rollbacks = peer.world.resource::<Rollbacks>().0;
+                println!("peer {handle}: frame {frame}, {rollbacks} rollback(s)");
+            }
+        }
+
+        thread::sleep(Duration::from_secs_f64(1. / FPS as f64));
+    }
+
+    Ok(())
+}
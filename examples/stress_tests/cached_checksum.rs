@@ -0,0 +1,92 @@
+use bevy::{prelude::*, utils::Instant};
+use bevy_ggrs::{prelude::*, RollbackFrameCount, SaveWorld};
+use clap::Parser;
+
+/// Benchmark comparing cached and uncached component checksums on a mostly static world.
+///
+/// ## Basic usage:
+///
+/// cargo run --release --example cached_checksum -- --entities 100000 --changed 100
+#[derive(Parser, Resource, Clone, Copy)]
+struct Args {
+    /// How many rollback entities to spawn.
+    #[clap(short, long, default_value = "100000")]
+    entities: u32,
+
+    /// How many entities change between each saved frame.
+    #[clap(short, long, default_value = "100")]
+    changed: u32,
+
+    /// How many frames to save.
+    #[clap(short, long, default_value = "600")]
+    frames: i32,
+}
+
+type Config = GgrsConfig<u8>;
+
+#[derive(Component, Clone, Copy, Hash)]
+struct Position(u32, u32);
+
+fn create_app(args: Args, cached: bool) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<Config>::default())
+        .rollback_component_with_copy::<Position>();
+
+    if cached {
+        app.checksum_component_with_hash_cached::<Position>();
+    } else {
+        app.checksum_component_with_hash::<Position>();
+    }
+
+    app.insert_resource(args)
+        .add_systems(Startup, spawn_entities)
+        .update();
+
+    app
+}
+
+fn spawn_entities(mut commands: Commands, args: Res<Args>) {
+    for i in 0..args.entities {
+        commands.spawn(Position(i, i)).add_rollback();
+    }
+}
+
+fn run(args: Args, cached: bool) {
+    let mut app = create_app(args, cached);
+    let mut query = app.world.query::<&mut Position>();
+
+    let start = Instant::now();
+
+    for frame in 0..args.frames {
+        app.world.resource_mut::<RollbackFrameCount>().0 = frame;
+
+        for mut position in query.iter_mut(&mut app.world).take(args.changed as usize) {
+            position.0 = position.0.wrapping_add(1);
+        }
+
+        app.world.run_schedule(SaveWorld);
+    }
+
+    let elapsed = start.elapsed();
+
+    println!(
+        "{}: {} frames took {elapsed:?} ({:?} per frame)",
+        if cached { "cached" } else { "uncached" },
+        args.frames,
+        elapsed / args.frames.max(1) as u32
+    );
+}
+
+fn main() {
+    let args = Args::parse();
+
+    println!(
+        "{} entities, {} changed per frame",
+        args.entities, args.changed
+    );
+
+    run(args, false);
+    run(args, true);
+}
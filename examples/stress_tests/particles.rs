@@ -292,30 +292,25 @@ fn despawn_particles(mut commands: Commands, mut particles: Query<(Entity, &mut
     }
 }
 
-fn print_events_system(mut session: ResMut<Session<Config>>, args: Res<Args>) {
-    match session.as_mut() {
-        Session::P2P(s) => {
-            for event in s.events() {
-                match event {
-                    GgrsEvent::Disconnected { .. } | GgrsEvent::NetworkInterrupted { .. } => {
-                        warn!("GGRS event: {event:?}")
-                    }
-                    GgrsEvent::DesyncDetected {
-                        local_checksum,
-                        remote_checksum,
-                        frame,
-                        ..
-                    } => {
-                        if args.continue_after_desync {
-                            error!("Desync on frame {frame}. Local checksum: {local_checksum:X}, remote checksum: {remote_checksum:X}");
-                        } else {
-                            panic!("Desync on frame {frame}. Local checksum: {local_checksum:X}, remote checksum: {remote_checksum:X}");
-                        }
-                    }
-                    _ => info!("GGRS event: {event:?}"),
+fn print_events_system(mut events: EventReader<SessionEvent<Config>>, args: Res<Args>) {
+    for event in events.read() {
+        match **event {
+            GgrsEvent::Disconnected { .. } | GgrsEvent::NetworkInterrupted { .. } => {
+                warn!("GGRS event: {:?}", **event)
+            }
+            GgrsEvent::DesyncDetected {
+                local_checksum,
+                remote_checksum,
+                frame,
+                ..
+            } => {
+                if args.continue_after_desync {
+                    error!("Desync on frame {frame}. Local checksum: {local_checksum:X}, remote checksum: {remote_checksum:X}");
+                } else {
+                    panic!("Desync on frame {frame}. Local checksum: {local_checksum:X}, remote checksum: {remote_checksum:X}");
                 }
             }
+            _ => info!("GGRS event: {:?}", **event),
         }
-        _ => panic!("This example focuses on p2p."),
     }
 }
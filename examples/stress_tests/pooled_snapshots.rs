@@ -0,0 +1,105 @@
+use bevy::{prelude::*, utils::Instant};
+use bevy_ggrs::{
+    prelude::*, ComponentSnapshotPlugin, CopyStrategy, LoadWorld, PooledComponentSnapshotPlugin,
+    RollbackFrameCount, SaveWorld,
+};
+use clap::Parser;
+
+/// Benchmark comparing map based and pooled component snapshots, with every entity changing
+/// each frame.
+///
+/// ## Basic usage:
+///
+/// cargo run --release --example pooled_snapshots -- --entities 10000
+#[derive(Parser, Resource, Clone, Copy)]
+struct Args {
+    /// How many rollback entities to spawn.
+    #[clap(short, long, default_value = "10000")]
+    entities: u32,
+
+    /// How many frames to save.
+    #[clap(short, long, default_value = "600")]
+    frames: i32,
+
+    /// How many frames to roll back every frame.
+    #[clap(short, long, default_value = "2")]
+    rollback: i32,
+}
+
+type Config = GgrsConfig<u8>;
+
+#[derive(Component, Clone, Copy)]
+struct Position(u32, u32);
+
+fn create_app(args: Args, pooled: bool) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<Config>::default());
+
+    if pooled {
+        app.add_plugins(PooledComponentSnapshotPlugin::<CopyStrategy<Position>>::default());
+    } else {
+        app.add_plugins(ComponentSnapshotPlugin::<CopyStrategy<Position>>::default());
+    }
+
+    app.insert_resource(args)
+        .add_systems(Startup, spawn_entities)
+        .update();
+
+    app
+}
+
+fn spawn_entities(mut commands: Commands, args: Res<Args>) {
+    for i in 0..args.entities {
+        commands.spawn(Position(i, i)).add_rollback();
+    }
+}
+
+fn run(args: Args, pooled: bool) {
+    let mut app = create_app(args, pooled);
+    let mut query = app.world.query::<&mut Position>();
+
+    let start = Instant::now();
+
+    for frame in 0..args.frames {
+        for mut position in query.iter_mut(&mut app.world) {
+            position.0 = position.0.wrapping_add(1);
+        }
+
+        app.world.resource_mut::<RollbackFrameCount>().0 = frame;
+        app.world.run_schedule(SaveWorld);
+
+        // Roll back, as a P2P session would when a remote input arrives late
+        if args.rollback > 0 && frame % args.rollback == 0 && frame >= args.rollback {
+            app.world.resource_mut::<RollbackFrameCount>().0 = frame - args.rollback;
+            app.world.run_schedule(LoadWorld);
+
+            for resimulated in frame - args.rollback + 1..=frame {
+                app.world.resource_mut::<RollbackFrameCount>().0 = resimulated;
+                app.world.run_schedule(SaveWorld);
+            }
+        }
+    }
+
+    let elapsed = start.elapsed();
+
+    println!(
+        "{}: {} frames took {elapsed:?} ({:?} per frame)",
+        if pooled { "pooled" } else { "map" },
+        args.frames,
+        elapsed / args.frames.max(1) as u32
+    );
+}
+
+fn main() {
+    let args = Args::parse();
+
+    println!(
+        "{} entities, rolling back {} frames",
+        args.entities, args.rollback
+    );
+
+    run(args, false);
+    run(args, true);
+}
@@ -0,0 +1,196 @@
+use std::hash::{Hash, Hasher};
+
+use bevy::{
+    ecs::schedule::{InternedSystemSet, NodeId, ScheduleGraph},
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{checksum_hasher, GgrsSchedule};
+
+/// A [`Resource`] which, when present as a [`Session`](`crate::Session`) starts, runs all
+/// independent systems of the [`GgrsSchedule`] in an order chosen by the provided `seed`.
+///
+/// Systems which are not ordered relative to each other may run in any order, which can change
+/// between builds or as unrelated systems are added. A simulation which accidentally depends on
+/// that order can pass for a long time and then desync. Ambiguity detection only catches systems
+/// accessing the same data, so it misses systems marked as ambiguous, or which only interact
+/// through [`Commands`]. With a seed, every independent system is chained in a random order which
+/// respects all existing ordering constraints, so running a `SyncTest` session with a few seeds
+/// reveals order dependent bugs as differing [`Checksum`](`crate::Checksum`)s. The same seed always
+/// produces the same order for the same set of systems.
+///
+/// The order is chosen once, when the first session starts, and systems added to the
+/// [`GgrsSchedule`] afterwards are not reordered. Systems added more than once, such as
+/// [`apply_deferred`], cannot be referred to individually and are left in place.
+///
+/// This is intended for tests, and is only available with the `chaos-schedule` feature.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ChaosSchedule};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// let seed = std::env::var("CHAOS_SEED").map_or(0, |seed| seed.parse().unwrap());
+///
+/// app.set_chaos_schedule_seed(seed);
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChaosSchedule {
+    /// The seed used to order independent systems.
+    pub seed: u64,
+}
+
+impl ChaosSchedule {
+    /// Creates a [`ChaosSchedule`] using the provided `seed`.
+    pub const fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+/// Marks the [`GgrsSchedule`] as already reordered by a [`ChaosSchedule`].
+#[derive(Resource)]
+struct ChaosScheduleApplied(u64);
+
+/// Reorders the [`GgrsSchedule`] according to the [`ChaosSchedule`], if any.
+pub(crate) fn apply(world: &mut World) {
+    let Some(&ChaosSchedule { seed }) = world.get_resource::<ChaosSchedule>() else {
+        return;
+    };
+
+    if let Some(applied) = world.get_resource::<ChaosScheduleApplied>() {
+        if applied.0 != seed {
+            warn!(
+                "GgrsSchedule was already reordered using seed {}, ignoring seed {seed}.",
+                applied.0
+            );
+        }
+        return;
+    }
+
+    world.insert_resource(ChaosScheduleApplied(seed));
+
+    let mut schedules = world.resource_mut::<Schedules>();
+    let Some(schedule) = schedules.get_mut(GgrsSchedule) else {
+        return;
+    };
+
+    let order = shuffled_systems(schedule.graph(), seed);
+
+    for pair in order.windows(2) {
+        // Ignoring deferred keeps sync points where they would be without a seed
+        schedule.add_systems(
+            (|| {})
+                .after_ignore_deferred(pair[0])
+                .before_ignore_deferred(pair[1]),
+        );
+    }
+
+    debug!(
+        "Chaos schedule with seed {seed} chained {} system(s)",
+        order.len()
+    );
+}
+
+/// Returns the [`SystemTypeSet`](`bevy::ecs::schedule::SystemTypeSet`) of every uniquely
+/// identifiable system, in a random order consistent with all existing ordering constraints.
+fn shuffled_systems(graph: &ScheduleGraph, seed: u64) -> Vec<InternedSystemSet> {
+    let mut counts = HashMap::<InternedSystemSet, usize>::default();
+    let mut systems = Vec::new();
+
+    for (node, system, _) in graph.systems() {
+        let Some(&set) = system.default_system_sets().first() else {
+            continue;
+        };
+
+        *counts.entry(set).or_default() += 1;
+        systems.push((node, set));
+    }
+
+    systems.retain(|(_, set)| counts[set] == 1);
+
+    let after = successors(graph);
+
+    let mut remaining = systems;
+    let mut order = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        // A system may run next once no remaining system must run before it
+        let available = remaining
+            .iter()
+            .enumerate()
+            .filter(|(_, (node, _))| {
+                !remaining
+                    .iter()
+                    .any(|(other, _)| after.get(other).is_some_and(|after| after.contains(node)))
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        let mut hasher = checksum_hasher();
+        (seed, order.len()).hash(&mut hasher);
+        let pick = available[(hasher.finish() % available.len() as u64) as usize];
+
+        order.push(remaining.remove(pick).1);
+    }
+
+    order
+}
+
+/// Collects every node which must run after each system, following the dependencies between
+/// systems, and between the sets containing them.
+fn successors(graph: &ScheduleGraph) -> HashMap<NodeId, HashSet<NodeId>> {
+    let mut parents = HashMap::<NodeId, Vec<NodeId>>::default();
+    let mut children = HashMap::<NodeId, Vec<NodeId>>::default();
+
+    for (parent, child, _) in graph.hierarchy().graph().all_edges() {
+        parents.entry(child).or_default().push(parent);
+        children.entry(parent).or_default().push(child);
+    }
+
+    let dependencies = graph.dependency().graph();
+
+    let expand = |start: NodeId, edges: &HashMap<NodeId, Vec<NodeId>>| {
+        let mut found = HashSet::new();
+        found.insert(start);
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for &next in edges.get(&node).into_iter().flatten() {
+                if found.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        found
+    };
+
+    graph
+        .systems()
+        .map(|(system, _, _)| {
+            let mut after = HashSet::new();
+            let mut visited = HashSet::new();
+            let mut stack = expand(system, &parents).into_iter().collect::<Vec<_>>();
+
+            while let Some(node) = stack.pop() {
+                if !visited.insert(node) {
+                    continue;
+                }
+
+                // Every member of a node ordered after this one also runs after the system
+                for next in dependencies.neighbors(node) {
+                    for member in expand(next, &children) {
+                        if after.insert(member) {
+                            stack.extend(expand(member, &parents));
+                        }
+                    }
+                }
+            }
+
+            (system, after)
+        })
+        .collect()
+}
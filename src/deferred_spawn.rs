@@ -0,0 +1,196 @@
+use std::marker::PhantomData;
+
+use bevy::{asset::UntypedAssetId, ecs::system::CommandQueue, prelude::*};
+use ggrs::{Config, InputStatus};
+
+use crate::{GgrsApp, GgrsSchedule, PlayerInputs};
+
+/// The [`SystemSet`] in the [`GgrsSchedule`] in which the [`DeferredSpawnPlugin`] resolves
+/// [`DeferredSpawns`].
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct DeferredSpawnSet;
+
+struct DeferredSpawn {
+    assets: Vec<UntypedHandle>,
+    spawn: Box<dyn Fn(&mut Commands) + Send + Sync>,
+}
+
+/// A queue of spawns waiting for assets to finish loading, resolved on a frame every peer agrees
+/// on by the [`DeferredSpawnPlugin`].
+///
+/// Assets finish loading at different times on every peer, so spawning rollback entities as soon
+/// as an asset is loaded desyncs the session. Instead, every peer reports how many spawns it is
+/// ready for as part of its input, obtained through [`DeferredSpawns::ready_count`]. Spawns are
+/// then performed in the [`GgrsSchedule`] once all players have reported being ready for them,
+/// which happens on the same frame for every peer.
+///
+/// Spawns must be queued in the same order on every peer, for example while setting up the
+/// game or from within the [`GgrsSchedule`]. The handles of queued assets are kept alive by the
+/// queue. A spawn waiting on an asset which fails to load is never performed.
+#[derive(Resource, Default)]
+pub struct DeferredSpawns {
+    queue: Vec<DeferredSpawn>,
+}
+
+impl DeferredSpawns {
+    /// Queues `spawn` to be run once all `assets` (and their dependencies) have been loaded by
+    /// every peer. Spawns are always performed in the order they were queued in.
+    pub fn push(
+        &mut self,
+        assets: impl IntoIterator<Item = UntypedHandle>,
+        spawn: impl Fn(&mut Commands) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.queue.push(DeferredSpawn {
+            assets: assets.into_iter().collect(),
+            spawn: Box::new(spawn),
+        });
+        self
+    }
+
+    /// The amount of queued spawns, including those already performed.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if no spawns have been queued.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// The amount of spawns, counted from the start of the queue, whose assets have all been
+    /// loaded locally. Include this in the local input of every player.
+    pub fn ready_count(&self, asset_server: &AssetServer) -> u32 {
+        self.queue
+            .iter()
+            .take_while(|spawn| {
+                spawn.assets.iter().all(|handle| {
+                    asset_server.is_loaded_with_dependencies(UntypedAssetId::from(handle))
+                })
+            })
+            .count() as u32
+    }
+}
+
+/// The amount of [`DeferredSpawns`] performed so far. This resource is rolled back, so spawns
+/// performed during a predicted frame are performed again when re-simulating it.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResolvedDeferredSpawns(pub u32);
+
+/// A [`Plugin`] which performs [`DeferredSpawns`] in the [`GgrsSchedule`] once every player has
+/// reported being ready for them, using the provided function to read the
+/// [`ready_count`](`DeferredSpawns::ready_count`) from a player's input.
+///
+/// Inputs of disconnected players are ignored.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, DeferredSpawnPlugin, DeferredSpawns, LocalInputs, LocalPlayers};
+/// # use bevy::utils::HashMap;
+/// #
+/// #[repr(C)]
+/// #[derive(Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct MyInput {
+///     buttons: u32,
+///     spawns_ready: u32,
+/// }
+///
+/// type MyConfig = GgrsConfig<MyInput>;
+///
+/// #[derive(Component, Clone)]
+/// struct Level(Handle<Image>);
+///
+/// fn queue_level(mut spawns: ResMut<DeferredSpawns>, asset_server: Res<AssetServer>) {
+///     let image: Handle<Image> = asset_server.load("level.png");
+///     let level = Level(image.clone());
+///
+///     spawns.push([image.untyped()], move |commands| {
+///         commands.spawn(level.clone()).add_rollback();
+///     });
+/// }
+///
+/// fn read_local_inputs(
+///     mut commands: Commands,
+///     spawns: Res<DeferredSpawns>,
+///     asset_server: Res<AssetServer>,
+///     players: Res<LocalPlayers>,
+/// ) {
+///     let input = MyInput {
+///         buttons: 0,
+///         spawns_ready: spawns.ready_count(&asset_server),
+///     };
+///
+///     let inputs = players.0.iter().map(|&handle| (handle, input)).collect::<HashMap<_, _>>();
+///     commands.insert_resource(LocalInputs::<MyConfig>(inputs));
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(DeferredSpawnPlugin::<MyConfig>::new(|input| input.spawns_ready))
+///     .add_systems(Startup, queue_level)
+///     .add_systems(ReadInputs, read_local_inputs);
+/// ```
+pub struct DeferredSpawnPlugin<C: Config> {
+    ready_count: fn(&C::Input) -> u32,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: Config> DeferredSpawnPlugin<C> {
+    /// Creates a plugin reading the amount of spawns a player is ready for using `ready_count`.
+    pub fn new(ready_count: fn(&C::Input) -> u32) -> Self {
+        Self {
+            ready_count,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: Config> Plugin for DeferredSpawnPlugin<C> {
+    fn build(&self, app: &mut App) {
+        let ready_count = self.ready_count;
+
+        app.init_resource::<DeferredSpawns>()
+            .init_resource::<ResolvedDeferredSpawns>()
+            .rollback_resource_with_copy::<ResolvedDeferredSpawns>()
+            .add_systems(
+                GgrsSchedule,
+                (move |world: &mut World| resolve_deferred_spawns::<C>(world, ready_count))
+                    .in_set(DeferredSpawnSet),
+            );
+    }
+}
+
+fn resolve_deferred_spawns<C: Config>(world: &mut World, ready_count: fn(&C::Input) -> u32) {
+    let Some(inputs) = world.get_resource::<PlayerInputs<C>>() else {
+        return;
+    };
+
+    let Some(ready) = inputs
+        .iter()
+        .filter(|(_, status)| *status != InputStatus::Disconnected)
+        .map(|(input, _)| ready_count(input))
+        .min()
+    else {
+        return;
+    };
+
+    let resolved = world.resource::<ResolvedDeferredSpawns>().0;
+
+    if ready <= resolved {
+        return;
+    }
+
+    let mut queue = CommandQueue::default();
+
+    world.resource_scope(|world, spawns: Mut<DeferredSpawns>| {
+        let ready = (ready as usize).min(spawns.queue.len());
+        let mut commands = Commands::new(&mut queue, world);
+
+        for spawn in &spawns.queue[resolved as usize..ready] {
+            (spawn.spawn)(&mut commands);
+        }
+
+        world.resource_mut::<ResolvedDeferredSpawns>().0 = ready as u32;
+    });
+
+    queue.apply(world);
+}
@@ -0,0 +1,279 @@
+use std::{
+    borrow::Borrow,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use bevy::reflect::Reflect;
+
+/// A map which always iterates in ascending order of its keys, for storing keyed gameplay state
+/// within rolled back [`Resources`](`bevy::prelude::Resource`) and [`Components`](`bevy::prelude::Component`).
+///
+/// # Hazard
+///
+/// The iteration order of a [`HashMap`](`bevy::utils::HashMap`) depends on its hasher and its
+/// history of insertions and removals, which differs between peers, and between a world and its
+/// restored snapshot. Any simulation logic iterating a [`HashMap`](`bevy::utils::HashMap`), such
+/// as applying damage until a budget runs out, will eventually desync. A [`HashMap`](`bevy::utils::HashMap`)
+/// does not implement [`Hash`] either, so it can not be checksummed directly.
+///
+/// A [`DeterministicMap`] keeps its entries sorted by key, so it iterates identically on every
+/// peer, no matter the order entries were inserted in. It is [`Clone`], [`Hash`] and [`Reflect`],
+/// so it can be rolled back and checksummed with any of the provided strategies. Its [`Hash`]
+/// covers every entry in order of their keys.
+///
+/// Entries are stored in a sorted [`Vec`], so lookups are logarithmic, while insertions and
+/// removals move the entries after them. This suits the small maps typical of gameplay state.
+/// To order a collection by anything other than its keys, use [`deterministic_sort`](`crate::deterministic_sort`).
+/// Applying a reflected map never leaves entries of the previous value behind, even though
+/// [applying](`Reflect::apply`) a reflected [`Vec`] only grows it.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, DeterministicMap};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// #[derive(Resource, Clone, Default, Hash)]
+/// struct Scores(DeterministicMap<usize, u32>);
+///
+/// fn award_points(mut scores: ResMut<Scores>) {
+///     for (_, score) in scores.0.iter_mut() {
+///         *score += 1;
+///     }
+/// }
+///
+/// app.init_resource::<Scores>()
+///     .rollback_resource_with_clone::<Scores>()
+///     .checksum_resource_with_hash::<Scores>()
+///     .add_systems(GgrsSchedule, award_points);
+/// # }
+/// ```
+#[derive(Reflect)]
+pub struct DeterministicMap<K, V> {
+    /// Entries in ascending order of their keys, without duplicates. Only the first `len` entries
+    /// are part of the map, any further entries are left over from applying a shorter map.
+    entries: Vec<(K, V)>,
+    /// The amount of entries in the map.
+    len: usize,
+}
+
+impl<K, V> Default for DeterministicMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for DeterministicMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries().to_vec(),
+            len: self.len(),
+        }
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for DeterministicMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries() == other.entries()
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for DeterministicMap<K, V> {}
+
+impl<K: Hash, V: Hash> Hash for DeterministicMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entries().hash(state);
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for DeterministicMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> DeterministicMap<K, V> {
+    /// Creates an empty map.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// The entries of the map, in ascending order of their keys.
+    fn entries(&self) -> &[(K, V)] {
+        &self.entries[..self.len.min(self.entries.len())]
+    }
+
+    /// The entries of the map, in ascending order of their keys, discarding any left over
+    /// entries. The length must be updated after changing the amount of entries.
+    fn entries_mut(&mut self) -> &mut Vec<(K, V)> {
+        self.entries.truncate(self.len);
+        &mut self.entries
+    }
+
+    /// The amount of entries in this map.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Returns `true` if this map holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.len = 0;
+    }
+
+    /// Iterate over all entries in ascending order of their keys.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator + '_ {
+        self.entries().iter().map(|(key, value)| (key, value))
+    }
+
+    /// Iterate mutably over all entries in ascending order of their keys.
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (&K, &mut V)> + ExactSizeIterator + '_ {
+        self.entries_mut()
+            .iter_mut()
+            .map(|(key, value)| (&*key, value))
+    }
+
+    /// Iterate over all keys in ascending order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + '_ {
+        self.entries().iter().map(|(key, _)| key)
+    }
+
+    /// Iterate over all values in ascending order of their keys.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator + '_ {
+        self.entries().iter().map(|(_, value)| value)
+    }
+
+    /// Iterate mutably over all values in ascending order of their keys.
+    pub fn values_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator + '_ {
+        self.entries_mut().iter_mut().map(|(_, value)| value)
+    }
+
+    /// Keeps only the entries for which `predicate` returns `true`, visiting them in ascending
+    /// order of their keys.
+    pub fn retain(&mut self, mut predicate: impl FnMut(&K, &mut V) -> bool) {
+        let entries = self.entries_mut();
+        entries.retain_mut(|(key, value)| predicate(key, value));
+        self.len = entries.len();
+    }
+}
+
+impl<K: Ord, V> DeterministicMap<K, V> {
+    fn position<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entries()
+            .binary_search_by(|(probe, _)| probe.borrow().cmp(key))
+    }
+
+    /// Inserts a value for the provided key, returning the value it replaced, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.position(&key) {
+            Ok(index) => Some(std::mem::replace(&mut self.entries[index].1, value)),
+            Err(index) => {
+                self.entries_mut().insert(index, (key, value));
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    /// Removes the entry for the provided key, returning its value, if any.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.position(key).ok()?;
+        let (_, value) = self.entries_mut().remove(index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Get the value for the provided key, if any.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.position(key).ok()?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Get the value for the provided key mutably, if any.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.position(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Get the value for the provided key mutably, first inserting the result of `insert` if
+    /// there is none.
+    pub fn get_or_insert_with(&mut self, key: K, insert: impl FnOnce() -> V) -> &mut V {
+        let index = match self.position(&key) {
+            Ok(index) => index,
+            Err(index) => {
+                self.entries_mut().insert(index, (key, insert()));
+                self.len += 1;
+                index
+            }
+        };
+
+        &mut self.entries[index].1
+    }
+
+    /// Returns `true` if this map holds an entry for the provided key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.position(key).is_ok()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for DeterministicMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for DeterministicMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> IntoIterator for DeterministicMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.entries.truncate(self.len);
+        self.entries.into_iter()
+    }
+}
@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+
+/// Sorts a slice in ascending order of a key, identically on every peer, for ordering gameplay
+/// collections such as the [`Vec`]s of entities and ids kept within rolled back
+/// [`Resources`](`bevy::prelude::Resource`).
+///
+/// # Hazard
+///
+/// Any sorting within the [`GgrsSchedule`](`crate::GgrsSchedule`) must be deterministic, as the
+/// order decides which element is processed first. There are three common ways a sort is not:
+///
+/// - An unstable sort, such as [`slice::sort_unstable`], may order equal elements differently
+///   depending on the order they started in.
+/// - A comparator which is not a total order, such as [`f32::partial_cmp`] unwrapped or defaulted
+///   to [`Ordering::Equal`] for `NaN`, leaves the result dependent on the sorting algorithm.
+/// - A key which differs between peers, such as an [`Entity`](`bevy::prelude::Entity`) or a
+///   [`Rollback`](`crate::Rollback`), whose ids are assigned differently on every peer.
+///
+/// This is a stable sort, and its key must implement [`Ord`], so only total orders are accepted.
+/// Use [`TotalOrder`] to sort by floating point numbers, and [`RollbackOrdered::order`](`crate::RollbackOrdered::order`)
+/// to sort by entity. As the sort is stable, equal keys keep the order they started in, so either
+/// make the key unique, or only sort collections whose order is already deterministic, such as
+/// one stored within a [`DeterministicMap`](`crate::DeterministicMap`).
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, deterministic_sort, RollbackOrdered, TotalOrder};
+/// use std::cmp::{Ordering, Reverse};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// #[derive(Component, Clone, Copy)]
+/// struct Threat(f32);
+///
+/// #[derive(Resource, Clone, Default)]
+/// struct Targets(Vec<(Entity, f32)>);
+///
+/// // Incorrect: entities are collected in an order which differs between peers, ties between
+/// // equal threats are broken arbitrarily, and a NaN threat leaves the order undefined.
+/// fn target_incorrectly(mut targets: ResMut<Targets>, threats: Query<(Entity, &Threat)>) {
+///     targets.0 = threats.iter().map(|(entity, threat)| (entity, threat.0)).collect();
+///     targets
+///         .0
+///         .sort_unstable_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+/// }
+///
+/// // Correct: the highest threat comes first, with ties broken by the order of each entity,
+/// // which is identical on every peer.
+/// fn target_correctly(
+///     mut targets: ResMut<Targets>,
+///     threats: Query<(Entity, &Rollback, &Threat)>,
+///     order: Res<RollbackOrdered>,
+/// ) {
+///     let mut threats = threats.iter().collect::<Vec<_>>();
+///     deterministic_sort(&mut threats, |&(_, &rollback, threat)| {
+///         (Reverse(TotalOrder(threat.0)), order.order(rollback))
+///     });
+///
+///     targets.0 = threats
+///         .into_iter()
+///         .map(|(entity, _, threat)| (entity, threat.0))
+///         .collect();
+/// }
+///
+/// app.init_resource::<Targets>()
+///     .rollback_resource_with_clone::<Targets>()
+///     .add_systems(GgrsSchedule, target_correctly);
+/// # }
+/// ```
+pub fn deterministic_sort<T, K: Ord>(slice: &mut [T], key: impl FnMut(&T) -> K) {
+    slice.sort_by_key(key);
+}
+
+/// Orders floating point numbers by [`total_cmp`](`f32::total_cmp`), so they can be used as a
+/// key for [`deterministic_sort`].
+///
+/// Unlike [`PartialOrd`], this is a total order: `-0.0` sorts before `0.0`, and `NaN` sorts
+/// before every negative number or after every positive number, depending on its sign.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TotalOrder<T>(pub T);
+
+macro_rules! impl_total_order {
+    ($($float:ty),*) => {
+        $(
+            impl PartialEq for TotalOrder<$float> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl Eq for TotalOrder<$float> {}
+
+            impl PartialOrd for TotalOrder<$float> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for TotalOrder<$float> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_total_order!(f32, f64);
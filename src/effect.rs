@@ -0,0 +1,170 @@
+use std::{collections::BTreeMap, marker::PhantomData};
+
+use bevy::prelude::*;
+
+use crate::{ConfirmedFrameCount, LoadWorld, LoadWorldSet, RollbackFrameCount};
+
+/// Label for the system of every [`GgrsEffectPlugin`] emitting confirmed effects, which runs in
+/// [`PreUpdate`] once the [`GgrsSchedule`](`crate::GgrsSchedule`) has been advanced for this update.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct GgrsEffectSet;
+
+/// A queue of effects `E`, such as sounds or particles, enqueued by the simulation and emitted
+/// as [`Event`]s once the frame they occurred on has been confirmed, see [`GgrsEffectPlugin`].
+///
+/// Predicted frames are simulated again on every rollback, so triggering effects directly from
+/// the [`GgrsSchedule`](`crate::GgrsSchedule`) plays them once per rollback. Effects enqueued
+/// here are instead discarded whenever the frame they occurred on is rolled back, and enqueued
+/// again while it is being simulated again, so each effect is emitted exactly once.
+///
+/// This queue is not rolled back itself, and its contents never affect the simulation.
+#[derive(Resource)]
+pub struct GgrsEffectQueue<E> {
+    pending: BTreeMap<i32, Vec<E>>,
+}
+
+impl<E> Default for GgrsEffectQueue<E> {
+    fn default() -> Self {
+        Self { pending: default() }
+    }
+}
+
+impl<E> GgrsEffectQueue<E> {
+    /// Enqueues an effect which occurred on the provided frame, usually the current
+    /// [`RollbackFrameCount`]. Effects of the same frame are emitted in the order they were
+    /// enqueued in.
+    pub fn push(&mut self, frame: i32, effect: E) -> &mut Self {
+        self.pending.entry(frame).or_default().push(effect);
+        self
+    }
+
+    /// The amount of effects waiting for their frame to be confirmed.
+    pub fn len(&self) -> usize {
+        self.pending.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if no effects are waiting for their frame to be confirmed.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Iterate over all effects waiting for their frame to be confirmed as `(frame, effect)`,
+    /// oldest first. Use this to preview effects before they are emitted.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &E)> + '_ {
+        self.pending
+            .iter()
+            .flat_map(|(&frame, effects)| effects.iter().map(move |effect| (frame, effect)))
+    }
+
+    /// Discards all effects which occurred after the provided frame.
+    pub(crate) fn discard_after(&mut self, frame: i32) {
+        self.pending.split_off(&(frame + 1));
+    }
+
+    /// Removes all effects which occurred on or before the provided frame, oldest first.
+    pub(crate) fn take_until(&mut self, frame: i32) -> impl Iterator<Item = E> {
+        let later = self.pending.split_off(&(frame + 1));
+        std::mem::replace(&mut self.pending, later)
+            .into_values()
+            .flatten()
+    }
+}
+
+/// A [`Plugin`] which emits effects `E` enqueued in the [`GgrsEffectQueue<E>`] as [`Event`]s,
+/// once the frame they occurred on has been confirmed by every peer.
+///
+/// Effects are emitted in the [`GgrsEffectSet`], outside of the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`), and always in the order of the frames they occurred
+/// on. Waiting for confirmation delays effects by the current
+/// [`PredictionDepth`](`crate::PredictionDepth`), but an effect is never emitted for a frame
+/// which is later corrected. Frames are only confirmed in P2P and spectator sessions.
+///
+/// Systems enqueueing the same effect type within the [`GgrsSchedule`](`crate::GgrsSchedule`)
+/// access the same queue, so they must be ordered relative to each other.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsEffectPlugin, GgrsEffectQueue, RollbackFrameCount};
+/// #
+/// #[derive(Component, Clone, Copy)]
+/// struct Health(u32);
+///
+/// #[derive(Event)]
+/// struct HitSpark {
+///     position: Vec3,
+/// }
+///
+/// fn take_damage(
+///     frame: Res<RollbackFrameCount>,
+///     mut sparks: ResMut<GgrsEffectQueue<HitSpark>>,
+///     mut query: Query<(&Transform, &mut Health)>,
+/// ) {
+///     for (transform, mut health) in query.iter_mut() {
+///         health.0 = health.0.saturating_sub(1);
+///
+///         // played once the hit is confirmed, no matter how often it is rolled back
+///         sparks.push(
+///             frame.0,
+///             HitSpark {
+///                 position: transform.translation,
+///             },
+///         );
+///     }
+/// }
+///
+/// fn spawn_sparks(mut sparks: EventReader<HitSpark>) {
+///     for spark in sparks.read() {
+///         info!("Spawning a hit spark at {}", spark.position);
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsEffectPlugin::<HitSpark>::default())
+///     .add_systems(GgrsSchedule, take_damage)
+///     .add_systems(Update, spawn_sparks);
+/// ```
+pub struct GgrsEffectPlugin<E: Event> {
+    _phantom: PhantomData<E>,
+}
+
+impl<E: Event> Default for GgrsEffectPlugin<E> {
+    fn default() -> Self {
+        Self {
+            _phantom: default(),
+        }
+    }
+}
+
+impl<E: Event> Plugin for GgrsEffectPlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GgrsEffectQueue<E>>()
+            .add_event::<E>()
+            .add_systems(
+                LoadWorld,
+                discard_rolled_back_effects::<E>.in_set(LoadWorldSet::Data),
+            )
+            .add_systems(PreUpdate, emit_confirmed_effects::<E>.in_set(GgrsEffectSet));
+    }
+}
+
+/// Discards effects of frames which are about to be simulated again.
+fn discard_rolled_back_effects<E: Event>(
+    frame: Res<RollbackFrameCount>,
+    mut queue: ResMut<GgrsEffectQueue<E>>,
+) {
+    queue.discard_after(frame.0);
+}
+
+/// Emits effects of all confirmed frames.
+fn emit_confirmed_effects<E: Event>(
+    frame: Res<RollbackFrameCount>,
+    confirmed_frame: Res<ConfirmedFrameCount>,
+    mut queue: ResMut<GgrsEffectQueue<E>>,
+    mut events: EventWriter<E>,
+) {
+    // effects ahead of the current frame belong to a session which has since been replaced
+    queue.discard_after(frame.0);
+
+    events.send_batch(queue.take_until(confirmed_frame.0));
+}
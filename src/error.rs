@@ -0,0 +1,268 @@
+use std::{error::Error, fmt};
+
+use bevy::prelude::*;
+use ggrs::{GgrsError, PlayerHandle};
+
+/// Errors which can occur while running a [`Session`](`crate::Session`).
+///
+/// These are sent as a [`SessionError`] event, allowing specific conditions to be handled
+/// without parsing log output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BevyGgrsError {
+    /// An error raised by the [`Session`](`crate::Session`) itself.
+    Ggrs(GgrsError),
+    /// GGRS requested loading a frame for which no snapshot is held, such as when a rollback
+    /// spans further than the depth of the snapshot storage.
+    ///
+    /// The [`World`] and [`RollbackFrameCount`](`crate::RollbackFrameCount`) are left untouched
+    /// rather than restoring the wrong frame, and the frames GGRS requested to advance next are
+    /// skipped, so the [`World`] no longer matches the other peers. Recover by resynchronizing,
+    /// such as by starting a new [`Session`](`crate::Session`) from a state shared by every peer.
+    SnapshotMissing {
+        /// The frame requested to be loaded.
+        frame: i32,
+    },
+    /// GGRS requested saving a frame other than the current [`RollbackFrameCount`](`crate::RollbackFrameCount`).
+    ///
+    /// The frame is not saved, as the [`World`] does not hold its state, so rolling back to it
+    /// later raises [`SnapshotMissing`](`BevyGgrsError::SnapshotMissing`).
+    FrameMismatch {
+        /// The frame requested by GGRS.
+        expected: i32,
+        /// The current [`RollbackFrameCount`](`crate::RollbackFrameCount`).
+        found: i32,
+    },
+    /// No [`LocalInputs`](`crate::LocalInputs`) were provided by the [`ReadInputs`](`crate::ReadInputs`)
+    /// schedule, so the frame could not be advanced.
+    MissingLocalInputs,
+    /// No inputs are recorded to re-advance from the provided frame.
+    InputsMissing {
+        /// The frame inputs were required from.
+        frame: i32,
+    },
+    /// The frame requested to be shown has not been advanced yet.
+    /// See [`preview_frame`](`crate::preview_frame`).
+    FrameInFuture {
+        /// The frame requested to be shown.
+        frame: i32,
+    },
+    /// The checksum recomputed after loading a frame differs from the checksum saved for it, so
+    /// the checksum covers state which the snapshots do not restore.
+    /// See [`SnapshotChecksumVerification`](`crate::SnapshotChecksumVerification`).
+    ChecksumDrift {
+        /// The frame which was loaded.
+        frame: i32,
+        /// The checksum saved for the frame, which GGRS compares between peers.
+        saved: u128,
+        /// The checksum recomputed from the restored state.
+        restored: u128,
+    },
+    /// No snapshot was captured with the provided name.
+    /// See [`restore_named_snapshot`](`crate::restore_named_snapshot`).
+    NamedSnapshotMissing {
+        /// The name of the snapshot requested to be restored.
+        name: String,
+    },
+}
+
+impl fmt::Display for BevyGgrsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BevyGgrsError::Ggrs(error) => write!(f, "{error}"),
+            BevyGgrsError::SnapshotMissing { frame } => {
+                write!(
+                    f,
+                    "Could not load frame {frame}: no snapshot is held for it."
+                )
+            }
+            BevyGgrsError::FrameMismatch { expected, found } => write!(
+                f,
+                "GGRS requested saving frame {expected}, but the current frame is {found}."
+            ),
+            BevyGgrsError::InputsMissing { frame } => {
+                write!(
+                    f,
+                    "Could not re-advance from frame {frame}: no inputs are recorded."
+                )
+            }
+            BevyGgrsError::FrameInFuture { frame } => {
+                write!(f, "Could not show frame {frame}: it has not been advanced yet.")
+            }
+            BevyGgrsError::ChecksumDrift {
+                frame,
+                saved,
+                restored,
+            } => write!(
+                f,
+                "Frame {frame} was saved with checksum {saved:X}, but restoring it results in checksum {restored:X}."
+            ),
+            BevyGgrsError::NamedSnapshotMissing { name } => write!(
+                f,
+                "Could not restore snapshot \"{name}\": no snapshot was captured with that name."
+            ),
+            BevyGgrsError::MissingLocalInputs => write!(
+                f,
+                "No local player inputs found. Did you insert systems into the ReadInputs schedule?"
+            ),
+        }
+    }
+}
+
+impl Error for BevyGgrsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BevyGgrsError::Ggrs(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<GgrsError> for BevyGgrsError {
+    fn from(error: GgrsError) -> Self {
+        BevyGgrsError::Ggrs(error)
+    }
+}
+
+/// An [`Event`] raised whenever a [`BevyGgrsError`] occurs while running the current
+/// [`Session`](`crate::Session`).
+///
+/// Waiting for remote inputs at the prediction threshold is part of normal operation, so it is
+/// not raised here. It is counted by the [`SessionStats`](`crate::SessionStats`) instead.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, BevyGgrsError, SessionError};
+/// # use bevy_ggrs::ggrs::GgrsError;
+/// #
+/// fn handle_errors(mut errors: EventReader<SessionError>) {
+///     for SessionError(error) in errors.read() {
+///         if let BevyGgrsError::Ggrs(GgrsError::NotSynchronized) = error {
+///             info!("Still synchronizing, retrying next frame");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone, PartialEq, Eq, Deref)]
+pub struct SessionError(pub BevyGgrsError);
+
+/// Raises an error returned by a system in the [`LoadWorld`](`crate::LoadWorld`) schedule, such
+/// as a missing snapshot, as a [`SessionError`] rather than panicking mid-rollback.
+pub(crate) fn report_load_error(
+    In(result): In<Result<(), BevyGgrsError>>,
+    errors: Option<ResMut<Events<SessionError>>>,
+) {
+    let Err(error) = result else {
+        return;
+    };
+
+    error!("{error}");
+
+    if let Some(mut errors) = errors {
+        errors.send(SessionError(error));
+    }
+}
+
+/// Errors found by [`SessionConfig::builder`](`crate::SessionConfig::builder`) while validating a
+/// [`SessionConfig`](`crate::SessionConfig`) against the players added to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionConfigError {
+    /// The amount of players added, excluding spectators, differs from the amount declared.
+    PlayerCount {
+        /// The declared [`num_players`](`crate::SessionConfig::num_players`).
+        declared: usize,
+        /// The amount of players added.
+        added: usize,
+    },
+    /// A player handle is not below the amount of players, or a spectator handle is.
+    InvalidHandle {
+        /// The handle which was added.
+        handle: PlayerHandle,
+        /// The declared [`num_players`](`crate::SessionConfig::num_players`).
+        num_players: usize,
+        /// Whether the handle was added for a spectator.
+        spectator: bool,
+    },
+    /// The [`fps`](`crate::SessionConfig::fps`) is `0`.
+    ZeroFps,
+    /// An error raised by GGRS while building the session.
+    Ggrs(GgrsError),
+}
+
+impl fmt::Display for SessionConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionConfigError::PlayerCount { declared, added } => write!(
+                f,
+                "The session declares {declared} players, but {added} players were added."
+            ),
+            SessionConfigError::InvalidHandle {
+                handle,
+                num_players,
+                spectator: false,
+            } => write!(
+                f,
+                "Player handle {handle} is out of range: players must use handles 0 to {}.",
+                num_players.saturating_sub(1)
+            ),
+            SessionConfigError::InvalidHandle {
+                handle,
+                num_players,
+                spectator: true,
+            } => write!(
+                f,
+                "Spectator handle {handle} is out of range: spectators must use handles of \
+                {num_players} or above."
+            ),
+            SessionConfigError::ZeroFps => write!(f, "The session fps must not be 0."),
+            SessionConfigError::Ggrs(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for SessionConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SessionConfigError::Ggrs(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<GgrsError> for SessionConfigError {
+    fn from(error: GgrsError) -> Self {
+        SessionConfigError::Ggrs(error)
+    }
+}
+
+/// Errors found by [`Replay::from_bytes`](`crate::Replay::from_bytes`) while reading a replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The bytes are not a valid replay for this input type.
+    Malformed,
+    /// The replay was recorded with a different set of rolled back types or
+    /// [versions](`crate::GgrsApp::set_rollback_version`), so playing it back would not reproduce
+    /// the recorded match.
+    RegistrationMismatch {
+        /// The [`fingerprint`](`crate::RollbackRegistrationFingerprint::fingerprint`) of the
+        /// current rollback setup.
+        expected: u64,
+        /// The fingerprint stored in the replay.
+        found: u64,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Malformed => write!(f, "The bytes are not a valid replay."),
+            ReplayError::RegistrationMismatch { expected, found } => write!(
+                f,
+                "The replay was recorded with rollback registration {found:X}, but the current \
+                registration is {expected:X}."
+            ),
+        }
+    }
+}
+
+impl Error for ReplayError {}
@@ -0,0 +1,488 @@
+//! Deterministic fixed-point math for use within the [`GgrsSchedule`](`crate::GgrsSchedule`).
+//!
+//! Floating point operations can produce different results across platforms, compilers and
+//! optimization levels, which will eventually desync peers. The types in this module are backed
+//! by integers, so every operation, including [`sqrt`](`Fixed::sqrt`) and trigonometry,
+//! produces bit-identical results everywhere.
+//!
+//! Both [`Fixed`] and [`FixedVec2`] are [`Copy`], [`Hash`] and [`Reflect`], so components built
+//! from them can be rolled back and checksummed with any of the provided strategies.
+//!
+//! # Examples
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_ggrs::{prelude::*, fixed::{Fixed, FixedVec2}};
+//! #
+//! # type MyInputType = u8;
+//! #
+//! # fn start(session: Session<GgrsConfig<MyInputType>>) {
+//! # let mut app = App::new();
+//! #[derive(Component, Clone, Copy, Default, Hash)]
+//! struct Position(FixedVec2);
+//!
+//! #[derive(Component, Clone, Copy, Default, Hash)]
+//! struct Velocity(FixedVec2);
+//!
+//! fn movement(mut query: Query<(&mut Position, &Velocity)>) {
+//!     for (mut position, velocity) in query.iter_mut() {
+//!         position.0 += velocity.0 / Fixed::from_int(60);
+//!     }
+//! }
+//!
+//! // Rendering can convert to floats freely, as it has no effect on the simulation
+//! fn sync_transform(mut query: Query<(&mut Transform, &Position)>) {
+//!     for (mut transform, position) in query.iter_mut() {
+//!         transform.translation = position.0.to_vec2().extend(0.);
+//!     }
+//! }
+//!
+//! app.rollback_component_with_copy::<Position>()
+//!     .rollback_component_with_copy::<Velocity>()
+//!     .checksum_component_with_hash::<Position>()
+//!     .add_systems(GgrsSchedule, movement)
+//!     .add_systems(Update, sync_transform);
+//! # }
+//! ```
+
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use bevy::{math::Vec2, reflect::Reflect};
+
+/// The number of fractional bits in a [`Fixed`].
+const FRAC_BITS: u32 = 32;
+
+/// A signed fixed-point number with 32 integer and 32 fractional bits.
+///
+/// Arithmetic follows the rules of the underlying `i64`: overflowing an addition or subtraction
+/// panics in debug builds, and division by zero always panics.
+#[derive(Reflect, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// The value `0`.
+    pub const ZERO: Self = Self(0);
+    /// The value `1`.
+    pub const ONE: Self = Self(1 << FRAC_BITS);
+    /// The value `0.5`.
+    pub const HALF: Self = Self(1 << (FRAC_BITS - 1));
+    /// The smallest positive value which can be represented.
+    pub const EPSILON: Self = Self(1);
+    /// The smallest value which can be represented.
+    pub const MIN: Self = Self(i64::MIN);
+    /// The largest value which can be represented.
+    pub const MAX: Self = Self(i64::MAX);
+    /// Archimedes' constant (π).
+    pub const PI: Self = Self(13_493_037_705);
+    /// The full circle constant (τ = 2π).
+    pub const TAU: Self = Self(26_986_075_409);
+    /// π/2
+    pub const FRAC_PI_2: Self = Self(6_746_518_852);
+
+    /// Create a [`Fixed`] from its raw bit representation.
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    /// The raw bit representation of this value.
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Create a [`Fixed`] from an integer.
+    pub const fn from_int(value: i32) -> Self {
+        Self((value as i64) << FRAC_BITS)
+    }
+
+    /// Create a [`Fixed`] from the ratio `numerator / denominator`, rounding towards zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero.
+    pub const fn from_ratio(numerator: i32, denominator: i32) -> Self {
+        Self((((numerator as i128) << FRAC_BITS) / denominator as i128) as i64)
+    }
+
+    /// Create a [`Fixed`] from an [`f32`], truncating any precision which cannot be represented.
+    ///
+    /// The conversion itself is deterministic, but the provided value may not be. Prefer
+    /// [`from_int`](`Fixed::from_int`) and [`from_ratio`](`Fixed::from_ratio`) within the simulation.
+    pub fn from_f32(value: f32) -> Self {
+        Self((value as f64 * Self::ONE.0 as f64) as i64)
+    }
+
+    /// Convert this value to an [`f32`], suitable for rendering.
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Convert this value to an [`f64`], suitable for rendering.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::ONE.0 as f64
+    }
+
+    /// The largest integer less than or equal to this value.
+    pub const fn floor(self) -> Self {
+        Self(self.0 & !(Self::ONE.0 - 1))
+    }
+
+    /// The smallest integer greater than or equal to this value.
+    pub const fn ceil(self) -> Self {
+        Self(self.0 + (Self::ONE.0 - 1)).floor()
+    }
+
+    /// The nearest integer to this value, rounding half-way cases towards positive infinity.
+    pub const fn round(self) -> Self {
+        Self(self.0 + Self::HALF.0).floor()
+    }
+
+    /// The fractional part of this value, always in the range `[0, 1)`.
+    pub const fn fract(self) -> Self {
+        Self(self.0 & (Self::ONE.0 - 1))
+    }
+
+    /// The integer part of this value, rounding towards negative infinity.
+    pub const fn to_int(self) -> i32 {
+        (self.0 >> FRAC_BITS) as i32
+    }
+
+    /// The absolute value of this value.
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// `-1`, `0` or `1` depending on the sign of this value.
+    pub const fn signum(self) -> Self {
+        Self::from_int(self.0.signum() as i32)
+    }
+
+    /// The square root of this value, rounded down. Negative values return [`Fixed::ZERO`].
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+
+        Self(isqrt((self.0 as u128) << FRAC_BITS) as i64)
+    }
+
+    /// The sine of this value, in radians.
+    pub fn sin(self) -> Self {
+        // Reduce to [-π, π]...
+        let mut x = Self(self.0.rem_euclid(Self::TAU.0));
+        if x > Self::PI {
+            x -= Self::TAU;
+        }
+
+        // ...and then to [-π/2, π/2] using the symmetry of sine around ±π/2
+        if x > Self::FRAC_PI_2 {
+            x = Self::PI - x;
+        } else if x < -Self::FRAC_PI_2 {
+            x = -Self::PI - x;
+        }
+
+        // Taylor series up to x^11, accurate to within 1e-7 on this range
+        const COEFFICIENTS: [Fixed; 5] = [
+            Fixed::from_ratio(-1, 6),
+            Fixed::from_ratio(1, 120),
+            Fixed::from_ratio(-1, 5_040),
+            Fixed::from_ratio(1, 362_880),
+            Fixed::from_ratio(-1, 39_916_800),
+        ];
+
+        let x2 = x * x;
+
+        let series = COEFFICIENTS
+            .iter()
+            .rev()
+            .fold(Self::ZERO, |acc, &coefficient| coefficient + x2 * acc);
+
+        x + x * x2 * series
+    }
+
+    /// The cosine of this value, in radians.
+    pub fn cos(self) -> Self {
+        Self(self.0.rem_euclid(Self::TAU.0) + Self::FRAC_PI_2.0).sin()
+    }
+
+    /// The sine and cosine of this value, in radians.
+    pub fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    /// The tangent of this value, in radians.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cosine of this value is exactly zero.
+    pub fn tan(self) -> Self {
+        let (sin, cos) = self.sin_cos();
+        sin / cos
+    }
+}
+
+/// Integer square root, rounded down.
+fn isqrt(value: u128) -> u128 {
+    let mut remainder = value;
+    let mut result = 0u128;
+    let mut bit = 1u128 << (u128::BITS - 2);
+
+    while bit > value {
+        bit >>= 2;
+    }
+
+    while bit != 0 {
+        if remainder >= result + bit {
+            remainder -= result + bit;
+            result = (result >> 1) + bit;
+        } else {
+            result >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    result
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(((self.0 as i128 * rhs.0 as i128) >> FRAC_BITS) as i64)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self((((self.0 as i128) << FRAC_BITS) / rhs.0 as i128) as i64)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Fixed {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Sum for Fixed {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+/// A 2-dimensional vector of [`Fixed`] values.
+#[derive(Reflect, Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct FixedVec2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+    /// All zeroes.
+    pub const ZERO: Self = Self::new(Fixed::ZERO, Fixed::ZERO);
+    /// All ones.
+    pub const ONE: Self = Self::new(Fixed::ONE, Fixed::ONE);
+    /// A unit vector pointing along the positive X axis.
+    pub const X: Self = Self::new(Fixed::ONE, Fixed::ZERO);
+    /// A unit vector pointing along the positive Y axis.
+    pub const Y: Self = Self::new(Fixed::ZERO, Fixed::ONE);
+
+    /// Create a new vector.
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        Self { x, y }
+    }
+
+    /// Create a vector with all elements set to `value`.
+    pub const fn splat(value: Fixed) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Create a vector from a [`Vec2`]. See [`Fixed::from_f32`] for caveats.
+    pub fn from_vec2(value: Vec2) -> Self {
+        Self::new(Fixed::from_f32(value.x), Fixed::from_f32(value.y))
+    }
+
+    /// Convert this vector to a [`Vec2`], suitable for rendering.
+    pub fn to_vec2(self) -> Vec2 {
+        Vec2::new(self.x.to_f32(), self.y.to_f32())
+    }
+
+    /// The dot product of this vector and `rhs`.
+    pub fn dot(self, rhs: Self) -> Fixed {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    /// The squared length of this vector.
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    /// The length of this vector.
+    pub fn length(self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+
+    /// The distance between this vector and `rhs`.
+    pub fn distance(self, rhs: Self) -> Fixed {
+        (self - rhs).length()
+    }
+
+    /// This vector scaled to a length of one, or [`FixedVec2::ZERO`] if its length is zero.
+    pub fn normalize_or_zero(self) -> Self {
+        let length = self.length();
+
+        if length == Fixed::ZERO {
+            return Self::ZERO;
+        }
+
+        self / length
+    }
+
+    /// A unit vector pointing in the direction of the provided angle, in radians.
+    pub fn from_angle(angle: Fixed) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, sin)
+    }
+
+    /// This vector rotated by the provided unit vector, such as one from [`FixedVec2::from_angle`].
+    pub fn rotate(self, rhs: Self) -> Self {
+        Self::new(
+            self.x * rhs.x - self.y * rhs.y,
+            self.y * rhs.x + self.x * rhs.y,
+        )
+    }
+}
+
+impl Add for FixedVec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for FixedVec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<Fixed> for FixedVec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Fixed) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Div<Fixed> for FixedVec2 {
+    type Output = Self;
+
+    fn div(self, rhs: Fixed) -> Self {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl Neg for FixedVec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for FixedVec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for FixedVec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<Fixed> for FixedVec2 {
+    fn mul_assign(&mut self, rhs: Fixed) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<Fixed> for FixedVec2 {
+    fn div_assign(&mut self, rhs: Fixed) {
+        *self = *self / rhs;
+    }
+}
+
+impl Sum for FixedVec2 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
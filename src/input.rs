@@ -0,0 +1,416 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    marker::PhantomData,
+};
+
+use bevy::{prelude::*, utils::HashMap};
+use ggrs::{Config, PlayerHandle};
+
+use crate::{LocalInputs, ReadInputs};
+
+/// Ordered stages of the [`ReadInputs`] schedule, as configured by the [`PartialInputsPlugin`].
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum ReadInputsSet {
+    /// Systems contributing to the [`PartialLocalInputs`] run here.
+    Collect,
+    /// The [`PartialLocalInputs`] are merged into the [`LocalInputs`] here.
+    Finalize,
+}
+
+/// Partial inputs for local players, contributed by several input sources and merged into the
+/// [`LocalInputs`] at the end of the [`ReadInputs`] schedule by the [`PartialInputsPlugin`].
+///
+/// Each contribution is keyed by an `order`, and contributions for a player are always merged in
+/// ascending order, regardless of the order the contributing systems ran in.
+#[derive(Resource)]
+pub struct PartialLocalInputs<C: Config> {
+    contributions: BTreeMap<(PlayerHandle, u32), C::Input>,
+}
+
+impl<C: Config> Default for PartialLocalInputs<C> {
+    fn default() -> Self {
+        Self {
+            contributions: default(),
+        }
+    }
+}
+
+impl<C: Config> PartialLocalInputs<C> {
+    /// Contributes a partial input for the provided player, replacing any previous contribution
+    /// with the same `order`.
+    pub fn insert(&mut self, handle: PlayerHandle, order: u32, input: C::Input) -> &mut Self {
+        self.contributions.insert((handle, order), input);
+        self
+    }
+
+    /// Get the partial input contributed for the provided player with the provided `order`.
+    pub fn get(&self, handle: PlayerHandle, order: u32) -> Option<&C::Input> {
+        self.contributions.get(&(handle, order))
+    }
+
+    /// Returns `true` if no partial inputs have been contributed.
+    pub fn is_empty(&self) -> bool {
+        self.contributions.is_empty()
+    }
+
+    /// Removes all contributions, merging them per player in ascending `order` using `merge`.
+    pub fn finalize(
+        &mut self,
+        mut merge: impl FnMut(&mut C::Input, C::Input),
+    ) -> BTreeMap<PlayerHandle, C::Input> {
+        let mut inputs = BTreeMap::new();
+
+        for ((handle, _), input) in std::mem::take(&mut self.contributions) {
+            match inputs.get_mut(&handle) {
+                Some(merged) => merge(merged, input),
+                None => {
+                    inputs.insert(handle, input);
+                }
+            }
+        }
+
+        inputs
+    }
+}
+
+/// A [`Plugin`] which merges [`PartialLocalInputs`] from several input sources into the
+/// [`LocalInputs`], using the provided merge function.
+///
+/// Systems reading a single input source should be added to the [`ReadInputs`] schedule in
+/// [`ReadInputsSet::Collect`]. Any [`LocalInputs`] inserted directly are kept for players without
+/// partial inputs.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, LocalPlayers, PartialInputsPlugin, PartialLocalInputs, ReadInputsSet};
+/// #
+/// type MyConfig = GgrsConfig<u8>;
+///
+/// const KEYBOARD: u32 = 0;
+/// const GAMEPAD: u32 = 1;
+///
+/// fn read_keyboard(mut inputs: ResMut<PartialLocalInputs<MyConfig>>, players: Res<LocalPlayers>) {
+///     for &handle in &players.0 {
+///         inputs.insert(handle, KEYBOARD, 0b01);
+///     }
+/// }
+///
+/// fn read_gamepad(mut inputs: ResMut<PartialLocalInputs<MyConfig>>, players: Res<LocalPlayers>) {
+///     for &handle in &players.0 {
+///         inputs.insert(handle, GAMEPAD, 0b10);
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(PartialInputsPlugin::<MyConfig>::new(|merged, input| *merged |= input))
+///     .add_systems(
+///         ReadInputs,
+///         (read_keyboard, read_gamepad).in_set(ReadInputsSet::Collect),
+///     );
+/// ```
+pub struct PartialInputsPlugin<C: Config> {
+    merge: fn(&mut C::Input, C::Input),
+    _phantom: PhantomData<C>,
+}
+
+impl<C: Config> PartialInputsPlugin<C> {
+    /// Creates a plugin merging partial inputs using `merge`, which is called with the input
+    /// merged so far and the next contribution in ascending order.
+    pub fn new(merge: fn(&mut C::Input, C::Input)) -> Self {
+        Self {
+            merge,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: Config> Plugin for PartialInputsPlugin<C> {
+    fn build(&self, app: &mut App) {
+        let merge = self.merge;
+
+        app.init_resource::<PartialLocalInputs<C>>()
+            .configure_sets(
+                ReadInputs,
+                (ReadInputsSet::Collect, ReadInputsSet::Finalize).chain(),
+            )
+            .add_systems(
+                ReadInputs,
+                (move |world: &mut World| finalize_local_inputs::<C>(world, merge))
+                    .in_set(ReadInputsSet::Finalize),
+            );
+    }
+}
+
+fn finalize_local_inputs<C: Config>(world: &mut World, merge: fn(&mut C::Input, C::Input)) {
+    let Some(mut partial) = world.get_resource_mut::<PartialLocalInputs<C>>() else {
+        return;
+    };
+
+    if partial.is_empty() {
+        return;
+    }
+
+    let merged = partial.finalize(merge);
+
+    match world.get_resource_mut::<LocalInputs<C>>() {
+        Some(mut local_inputs) => local_inputs.0.extend(merged),
+        None => world.insert_resource(LocalInputs::<C>(merged.into_iter().collect())),
+    }
+}
+
+/// How an [`InputSource`] composes with the [`ReadInputs`] schedule.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputSourceMode {
+    /// The [`ReadInputs`] schedule is not run, so the provider supplies all [`LocalInputs`].
+    #[default]
+    Replace,
+    /// The [`ReadInputs`] schedule runs first, after which the provided inputs are merged into the
+    /// [`LocalInputs`] it produced. Provided inputs take precedence for players both supply.
+    Merge,
+}
+
+/// A provider of [`LocalInputs`] which the [`GgrsPlugin`](`crate::GgrsPlugin`) calls whenever it
+/// reads the inputs of a frame, such as an AI bot, a recorded replay or a scripted test.
+///
+/// Unlike systems in the [`ReadInputs`] schedule, a provider is not tied to polling devices or to
+/// wall-clock time: it is called exactly once for every frame advanced, with exclusive access to
+/// the [`World`]. The [`LocalPlayers`](`crate::LocalPlayers`) are up to date when it is called.
+/// See [`InputSourceMode`] for how it composes with the [`ReadInputs`] schedule.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{prelude::*, utils::HashMap};
+/// # use bevy_ggrs::{prelude::*, InputSource, LocalPlayers, RollbackFrameCount};
+/// #
+/// type MyConfig = GgrsConfig<u8>;
+///
+/// # let mut app = App::new();
+/// // a bot pressing every button in turn
+/// app.insert_resource(InputSource::<MyConfig>::new(|world| {
+///     let frame = world.resource::<RollbackFrameCount>().0;
+///
+///     world
+///         .resource::<LocalPlayers>()
+///         .0
+///         .iter()
+///         .map(|&handle| (handle, 1 << (frame % 8)))
+///         .collect::<HashMap<_, _>>()
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct InputSource<C: Config> {
+    provider: Box<dyn FnMut(&mut World) -> HashMap<PlayerHandle, C::Input> + Send + Sync>,
+    mode: InputSourceMode,
+}
+
+impl<C: Config> InputSource<C> {
+    /// Creates a source replacing the [`ReadInputs`] schedule with the provided function.
+    pub fn new(
+        provider: impl FnMut(&mut World) -> HashMap<PlayerHandle, C::Input> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            provider: Box::new(provider),
+            mode: default(),
+        }
+    }
+
+    /// Sets how this source composes with the [`ReadInputs`] schedule.
+    pub fn with_mode(mut self, mode: InputSourceMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// How this source composes with the [`ReadInputs`] schedule.
+    pub fn mode(&self) -> InputSourceMode {
+        self.mode
+    }
+}
+
+/// A transform applied to every local input right before it is added to the [`Session`](`crate::Session`),
+/// such as clamping an analog stick or discarding impossible button combinations.
+///
+/// The transform receives the handle of the local player owning each input, and runs after the
+/// [`ReadInputs`] schedule and any [`InputSource`]. Only the transformed input is ever sent to
+/// remote peers, which never see the original input. This makes it a purely local normalization
+/// step: remote peers can not verify it was applied, so it does not prevent a modified client from
+/// sending unsanitized inputs. Simulation logic must still tolerate any input it may receive.
+///
+/// The transform must be deterministic, as a [`SyncTestSession`](`ggrs::SyncTestSession`) expects
+/// the same inputs whenever it reads them.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, LocalInputTransform};
+/// #
+/// type MyConfig = GgrsConfig<u8>;
+///
+/// const LEFT: u8 = 1 << 0;
+/// const RIGHT: u8 = 1 << 1;
+///
+/// # let mut app = App::new();
+/// // pressing left and right at once cancels out
+/// app.insert_resource(LocalInputTransform::<MyConfig>::new(|_handle, input| {
+///     if *input & (LEFT | RIGHT) == LEFT | RIGHT {
+///         *input &= !(LEFT | RIGHT);
+///     }
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct LocalInputTransform<C: Config> {
+    transform: Box<dyn Fn(PlayerHandle, &mut C::Input) + Send + Sync>,
+}
+
+impl<C: Config> LocalInputTransform<C> {
+    /// Creates a transform applying the provided function to every local input.
+    pub fn new(transform: impl Fn(PlayerHandle, &mut C::Input) + Send + Sync + 'static) -> Self {
+        Self {
+            transform: Box::new(transform),
+        }
+    }
+}
+
+/// Input delay added to local players on top of the input delay the [`Session`](`crate::Session`)
+/// was built with, which can be adjusted at any time, such as to let players trade latency for
+/// fewer rollbacks mid-match.
+///
+/// The total delay of a local player is the delay passed to
+/// [`SessionBuilder::with_input_delay`](`ggrs::SessionBuilder::with_input_delay`) plus the
+/// [`delay`](`LocalInputDelay::delay`) set here. Each frame, the input read for a player is queued,
+/// and the input read that many frames ago is added to the [`Session`](`crate::Session`) instead.
+///
+/// # Safety of delay changes
+///
+/// GGRS always receives exactly one input per local player and frame, so the stream of inputs
+/// every peer confirms stays consistent, and adjusting the delay here requires no coordination
+/// with remote peers. Only the adjusting player's own inputs are affected:
+///
+/// - Increasing the delay by `n` frames repeats the oldest queued input `n` times.
+/// - Decreasing the delay by `n` frames discards the `n` oldest queued inputs, so a button
+///   pressed for fewer than `n` frames may never be seen.
+///
+/// The input delay of the [`Session`](`crate::Session`) itself is fixed once built. Changing it
+/// requires building a new session, which every peer must agree to start at the same frame, such
+/// as using [`start_session_at_frame`](`crate::start_session_at_frame`).
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, LocalInputDelay, LocalPlayers};
+/// #
+/// type MyConfig = GgrsConfig<u8>;
+///
+/// fn increase_delay(mut delay: ResMut<LocalInputDelay<MyConfig>>, players: Res<LocalPlayers>) {
+///     for &handle in &players.0 {
+///         let current = delay.delay(handle);
+///         delay.set_delay(handle, current + 1);
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.init_resource::<LocalInputDelay<MyConfig>>()
+///     .add_systems(Update, increase_delay.run_if(run_once()));
+/// ```
+#[derive(Resource)]
+pub struct LocalInputDelay<C: Config> {
+    delays: HashMap<PlayerHandle, usize>,
+    queued: HashMap<PlayerHandle, VecDeque<C::Input>>,
+}
+
+impl<C: Config> Default for LocalInputDelay<C> {
+    fn default() -> Self {
+        Self {
+            delays: default(),
+            queued: default(),
+        }
+    }
+}
+
+impl<C: Config> LocalInputDelay<C> {
+    /// The delay added to the inputs of the provided local player, in frames.
+    pub fn delay(&self, handle: PlayerHandle) -> usize {
+        self.delays.get(&handle).copied().unwrap_or_default()
+    }
+
+    /// Sets the delay added to the inputs of the provided local player, in frames, taking effect
+    /// with the next input read. See [`LocalInputDelay`] for how changes affect the inputs.
+    pub fn set_delay(&mut self, handle: PlayerHandle, delay: usize) -> &mut Self {
+        self.delays.insert(handle, delay);
+        self
+    }
+
+    /// Iterate over the delay of every local player with a delay set.
+    pub fn iter(&self) -> impl Iterator<Item = (PlayerHandle, usize)> + '_ {
+        self.delays.iter().map(|(&handle, &delay)| (handle, delay))
+    }
+
+    /// The amount of inputs read for the provided local player which have not been added to the
+    /// [`Session`](`crate::Session`) yet.
+    pub fn queued(&self, handle: PlayerHandle) -> usize {
+        self.queued.get(&handle).map_or(0, VecDeque::len)
+    }
+
+    /// Queues the freshly read inputs, replacing each with the input to add to the session.
+    fn apply(&mut self, inputs: &mut HashMap<PlayerHandle, C::Input>) {
+        for (&handle, input) in inputs.iter_mut() {
+            let delay = self.delay(handle);
+            let queued = self.queued.entry(handle).or_default();
+
+            queued.push_back(*input);
+
+            while queued.len() > delay + 1 {
+                queued.pop_front();
+            }
+
+            while queued.len() < delay + 1 {
+                let oldest = queued[0];
+                queued.push_front(oldest);
+            }
+
+            *input = queued.pop_front().unwrap();
+        }
+    }
+}
+
+/// Reads the [`LocalInputs`] of the next frame, by running the [`ReadInputs`] schedule and calling
+/// the [`InputSource`], as configured. Any [`LocalInputTransform`] is applied afterwards, followed
+/// by any [`LocalInputDelay`].
+pub(crate) fn read_local_inputs<C: Config>(world: &mut World) -> Option<LocalInputs<C>> {
+    let mode = world
+        .get_resource::<InputSource<C>>()
+        .map(InputSource::mode);
+
+    if mode != Some(InputSourceMode::Replace) {
+        world.run_schedule(ReadInputs);
+    }
+
+    let mut local_inputs = world.remove_resource::<LocalInputs<C>>();
+
+    if mode.is_some() {
+        let provided =
+            world.resource_scope(|world, mut source: Mut<InputSource<C>>| (source.provider)(world));
+
+        local_inputs
+            .get_or_insert_with(|| LocalInputs(default()))
+            .0
+            .extend(provided);
+    }
+
+    if let (Some(local_inputs), Some(transform)) = (
+        local_inputs.as_mut(),
+        world.get_resource::<LocalInputTransform<C>>(),
+    ) {
+        for (&handle, input) in local_inputs.0.iter_mut() {
+            (transform.transform)(handle, input);
+        }
+    }
+
+    if let Some(local_inputs) = local_inputs.as_mut() {
+        if let Some(mut delay) = world.get_resource_mut::<LocalInputDelay<C>>() {
+            delay.apply(&mut local_inputs.0);
+        }
+    }
+
+    local_inputs
+}
@@ -0,0 +1,189 @@
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use bevy::prelude::*;
+use ggrs::{Config, GgrsEvent};
+
+use crate::{
+    checksum_hasher, schedule_systems::run_ggrs_schedules, AdvanceWorld, AdvanceWorldSet,
+    PlayerInputs, RollbackFrameCount, SessionEvent, SessionStartFrame, UnregisteredMutationCheck,
+};
+
+/// Checksums of the inputs used to reach each recent frame, recorded by the
+/// [`InputChecksumPlugin`].
+///
+/// Each checksum folds in the checksum of the previous frame, so it covers every input since the
+/// start of the [`Session`](`crate::Session`). Peers which agree on the input checksum of a frame
+/// advanced with identical inputs up to that point.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct InputChecksums {
+    checksums: BTreeMap<i32, u128>,
+}
+
+impl InputChecksums {
+    /// The maximum amount of frames an input checksum is retained for.
+    pub const DEPTH: usize = 600;
+
+    /// Get the input checksum of the provided frame, if it is still retained.
+    pub fn get(&self, frame: i32) -> Option<u128> {
+        self.checksums.get(&frame).copied()
+    }
+
+    /// Records the checksum of the inputs used to advance to the provided frame. Checksums of
+    /// later frames are discarded, as they are about to be re-advanced after a rollback.
+    pub fn record(&mut self, frame: i32, inputs: u64) -> u128 {
+        let previous = self.get(frame - 1).unwrap_or_default();
+
+        let mut hasher = checksum_hasher();
+        previous.hash(&mut hasher);
+        inputs.hash(&mut hasher);
+        let checksum = hasher.finish() as u128;
+
+        self.checksums.split_off(&frame);
+        self.checksums.insert(frame, checksum);
+
+        while self.checksums.len() > Self::DEPTH {
+            self.checksums.pop_first();
+        }
+
+        checksum
+    }
+
+    /// Discards all recorded input checksums.
+    pub fn clear(&mut self) {
+        self.checksums.clear();
+    }
+}
+
+/// An [`Event`] sent alongside every [`GgrsEvent::DesyncDetected`], adding the [`InputChecksums`]
+/// of the local peer for the desynchronized frame.
+///
+/// GGRS only exchanges the state checksum, so the input checksum of the remote peer has to be
+/// compared manually, such as by logging it on both peers. If both peers agree on the input
+/// checksum, the simulation is not deterministic. Otherwise, the inputs themselves differed, such
+/// as when an input type is serialized differently per platform.
+#[derive(Event, Debug)]
+pub struct DesyncChecksums<T: Config> {
+    /// The frame which desynchronized.
+    pub frame: i32,
+    /// The state [`Checksum`](`crate::Checksum`) of the local peer.
+    pub local_checksum: u128,
+    /// The state [`Checksum`](`crate::Checksum`) of the remote peer.
+    pub remote_checksum: u128,
+    /// The input checksum of the local peer, or `None` if it is no longer retained.
+    pub input_checksum: Option<u128>,
+    /// The address of the remote peer.
+    pub addr: T::Address,
+}
+
+/// A [`Plugin`] which records [`InputChecksums`] for every frame advanced, and sends
+/// [`DesyncChecksums`] whenever a desync is detected, helping to tell apart desyncs caused by
+/// the simulation from those caused by mismatched inputs.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, DesyncChecksums, InputChecksumPlugin};
+/// #
+/// type MyConfig = GgrsConfig<u8>;
+///
+/// fn log_desyncs(mut desyncs: EventReader<DesyncChecksums<MyConfig>>) {
+///     for desync in desyncs.read() {
+///         error!(
+///             "Desync on frame {} with input checksum {:X?}",
+///             desync.frame, desync.input_checksum
+///         );
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<MyConfig>::default())
+///     .add_plugins(InputChecksumPlugin::<MyConfig>::default())
+///     .add_systems(Update, log_desyncs);
+/// ```
+pub struct InputChecksumPlugin<C: Config> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C: Config> Default for InputChecksumPlugin<C> {
+    fn default() -> Self {
+        Self {
+            _phantom: default(),
+        }
+    }
+}
+
+impl<C: Config> InputChecksumPlugin<C>
+where
+    C::Input: Hash,
+{
+    /// A [`System`] recording the checksum of the [`PlayerInputs`] used to advance to the
+    /// current frame.
+    pub fn record(
+        mut checksums: ResMut<InputChecksums>,
+        inputs: Res<PlayerInputs<C>>,
+        frame: Res<RollbackFrameCount>,
+    ) {
+        let mut hasher = checksum_hasher();
+
+        // the status is left out, as a peer may still be predicting an input another has confirmed
+        for (input, _) in inputs.iter() {
+            input.hash(&mut hasher);
+        }
+
+        let checksum = checksums.record(frame.0, hasher.finish());
+
+        trace!("Frame {} has input checksum {checksum:X}", frame.0);
+    }
+
+    /// A [`System`] sending [`DesyncChecksums`] for every [`GgrsEvent::DesyncDetected`].
+    pub fn report_desyncs(
+        mut events: EventReader<SessionEvent<C>>,
+        checksums: Res<InputChecksums>,
+        start: Option<Res<SessionStartFrame>>,
+        mut desyncs: EventWriter<DesyncChecksums<C>>,
+    ) {
+        let start = start.map_or(0, |start| start.0);
+
+        for event in events.read() {
+            if let GgrsEvent::DesyncDetected {
+                frame,
+                local_checksum,
+                remote_checksum,
+                addr,
+            } = &event.0
+            {
+                let frame = start + *frame;
+
+                desyncs.send(DesyncChecksums {
+                    frame,
+                    local_checksum: *local_checksum,
+                    remote_checksum: *remote_checksum,
+                    input_checksum: checksums.get(frame),
+                    addr: addr.clone(),
+                });
+            }
+        }
+    }
+}
+
+impl<C: Config> Plugin for InputChecksumPlugin<C>
+where
+    C::Input: Hash,
+{
+    fn build(&self, app: &mut App) {
+        // input checksums are recorded while advancing, but deliberately survive rollbacks
+        UnregisteredMutationCheck::ignore_in::<InputChecksums>(app);
+
+        app.init_resource::<InputChecksums>()
+            .add_event::<DesyncChecksums<C>>()
+            .add_systems(AdvanceWorld, Self::record.in_set(AdvanceWorldSet::First))
+            .add_systems(
+                PreUpdate,
+                Self::report_desyncs.after(run_ggrs_schedules::<C>),
+            );
+    }
+}
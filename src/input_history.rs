@@ -0,0 +1,166 @@
+use std::{collections::VecDeque, marker::PhantomData};
+
+use bevy::prelude::*;
+use ggrs::{Config, PlayerHandle};
+
+use crate::{AdvanceWorld, AdvanceWorldSet, CloneStrategy, PlayerInputs, ResourceSnapshotPlugin};
+
+/// The [`PlayerInputs`] of the most recent frames, recorded by the [`InputHistoryPlugin`] before
+/// the [`GgrsSchedule`](`crate::GgrsSchedule`) runs.
+///
+/// The history is rolled back alongside the rest of the simulation, so it always holds the
+/// inputs which lead to the current frame, even after predicted inputs were corrected. This makes
+/// it suitable for input buffers, such as accepting a move which was input a few frames early.
+#[derive(Resource)]
+pub struct GgrsInputHistory<C: Config> {
+    /// Inputs of each player, newest at the front.
+    inputs: Vec<VecDeque<C::Input>>,
+    /// Maximum amount of frames to retain per player.
+    depth: usize,
+}
+
+impl<C: Config> Clone for GgrsInputHistory<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inputs: self.inputs.clone(),
+            depth: self.depth,
+        }
+    }
+}
+
+impl<C: Config> GgrsInputHistory<C> {
+    /// Creates an empty history retaining the provided amount of frames per player.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            inputs: default(),
+            depth,
+        }
+    }
+
+    /// Get the maximum amount of frames retained per player.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Records the inputs of a new frame, one per player in order of their [`PlayerHandle`].
+    pub fn push(&mut self, inputs: impl IntoIterator<Item = C::Input>) -> &mut Self {
+        for (handle, input) in inputs.into_iter().enumerate() {
+            if handle >= self.inputs.len() {
+                self.inputs.resize_with(handle + 1, default);
+            }
+
+            let history = &mut self.inputs[handle];
+
+            history.push_front(input);
+            history.truncate(self.depth);
+        }
+
+        self
+    }
+
+    /// Get the input of the provided player from `frames_ago` frames before the current one,
+    /// where `0` is the input of the current frame.
+    pub fn get(&self, handle: PlayerHandle, frames_ago: usize) -> Option<&C::Input> {
+        self.inputs.get(handle)?.get(frames_ago)
+    }
+
+    /// Iterate over the retained inputs of the provided player, newest first.
+    pub fn iter(&self, handle: PlayerHandle) -> impl Iterator<Item = &C::Input> + '_ {
+        self.inputs.get(handle).into_iter().flatten()
+    }
+
+    /// Returns `true` if `predicate` holds for an input of the provided player within the last
+    /// `frames` frames, including the current one.
+    pub fn any_within(
+        &self,
+        handle: PlayerHandle,
+        frames: usize,
+        predicate: impl Fn(&C::Input) -> bool,
+    ) -> bool {
+        self.iter(handle).take(frames).any(predicate)
+    }
+
+    /// Returns `true` if `predicate` started to hold for the provided player within the last
+    /// `frames` frames, including the current one. This is the usual meaning of a button being
+    /// pressed, as opposed to [held](`GgrsInputHistory::any_within`).
+    ///
+    /// A press on the oldest retained frame counts, as the frame before it is unknown, so `frames`
+    /// should be less than the [`depth`](`GgrsInputHistory::depth`).
+    pub fn pressed_within(
+        &self,
+        handle: PlayerHandle,
+        frames: usize,
+        predicate: impl Fn(&C::Input) -> bool,
+    ) -> bool {
+        (0..frames).any(|frames_ago| {
+            self.get(handle, frames_ago).is_some_and(&predicate)
+                && !self.get(handle, frames_ago + 1).is_some_and(&predicate)
+        })
+    }
+
+    /// Discards all recorded inputs.
+    pub fn clear(&mut self) {
+        self.inputs.clear();
+    }
+}
+
+/// A [`Plugin`] which records the [`PlayerInputs`] of every frame into a [`GgrsInputHistory`],
+/// and rolls it back.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsInputHistory, InputHistoryPlugin};
+/// #
+/// type MyConfig = GgrsConfig<u8>;
+///
+/// const PUNCH: u8 = 0b1;
+///
+/// fn punch(history: Res<GgrsInputHistory<MyConfig>>) {
+///     // a punch input up to 4 frames early is still accepted
+///     if history.pressed_within(0, 5, |input| input & PUNCH != 0) {
+///         // ...
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<MyConfig>::default())
+///     .add_plugins(InputHistoryPlugin::<MyConfig>::new(8))
+///     .add_systems(GgrsSchedule, punch);
+/// ```
+pub struct InputHistoryPlugin<C: Config> {
+    depth: usize,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: Config> InputHistoryPlugin<C> {
+    /// The default amount of frames retained per player.
+    pub const DEFAULT_DEPTH: usize = 16;
+
+    /// Creates a plugin retaining the provided amount of frames per player.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// A [`System`] recording the [`PlayerInputs`] used to advance to the current frame.
+    pub fn record(mut history: ResMut<GgrsInputHistory<C>>, inputs: Res<PlayerInputs<C>>) {
+        history.push(inputs.iter().map(|&(input, _)| input));
+    }
+}
+
+impl<C: Config> Default for InputHistoryPlugin<C> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DEPTH)
+    }
+}
+
+impl<C: Config> Plugin for InputHistoryPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GgrsInputHistory::<C>::new(self.depth))
+            .add_plugins(ResourceSnapshotPlugin::<CloneStrategy<GgrsInputHistory<C>>>::default())
+            .add_systems(AdvanceWorld, Self::record.in_set(AdvanceWorldSet::First));
+    }
+}
@@ -0,0 +1,336 @@
+use std::marker::PhantomData;
+
+use bevy::{prelude::*, utils::HashMap};
+use ggrs::{Config, PlayerHandle};
+
+use crate::{InterpolationAlpha, LocalInputs, Rollback, RollbackFrameCount};
+
+/// Linear interpolation between two values, used by the [`ComponentInterpolationPlugin`].
+pub trait Lerp {
+    /// Interpolates between `self` at `t = 0.0` and `other` at `t = 1.0`.
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t as f64
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec2::lerp(*self, *other, t)
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec3::lerp(*self, *other, t)
+    }
+}
+
+impl Lerp for Vec4 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec4::lerp(*self, *other, t)
+    }
+}
+
+impl Lerp for Quat {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self.slerp(*other, t)
+    }
+}
+
+impl Lerp for Transform {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Transform {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+}
+
+/// A render-only [`Component`] holding the value of the [`Component`] `C` interpolated between
+/// the previous and the current rollback frame, maintained by the [`ComponentInterpolationPlugin`].
+///
+/// This is never rolled back, and must never be read by the simulation.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deref)]
+pub struct Interpolated<C>(pub C);
+
+/// The values of `C` on the previous and the current rollback frame.
+#[derive(Component)]
+struct InterpolationState<C> {
+    previous: C,
+    current: C,
+    frame: i32,
+}
+
+/// Label for the systems of every [`ComponentInterpolationPlugin`], which run in [`PreUpdate`]
+/// once the [`GgrsSchedule`](`crate::GgrsSchedule`) has been advanced for this update.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct InterpolationSet;
+
+/// A [`Plugin`] which interpolates the [`Component`] `C` of [`Rollback`] entities between the
+/// previous and the current rollback frame for smooth rendering, using the [`InterpolationAlpha`].
+/// The result is written to [`Interpolated<C>`], leaving `C` itself untouched.
+///
+/// Interpolation runs outside of the [`GgrsSchedule`](`crate::GgrsSchedule`), so it never feeds
+/// back into the simulation. Visuals lag up to one frame behind the simulation as a result. Every
+/// interpolation plugin shares the same [`InterpolationAlpha`], so they always agree.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ComponentInterpolationPlugin, Interpolated, Lerp};
+/// #
+/// #[derive(Component, Clone, Copy)]
+/// struct Health(f32);
+///
+/// impl Lerp for Health {
+///     fn lerp(&self, other: &Self, t: f32) -> Self {
+///         Health(Lerp::lerp(&self.0, &other.0, t))
+///     }
+/// }
+///
+/// fn draw_health_bars(query: Query<&Interpolated<Health>>) {
+///     for &Interpolated(Health(health)) in query.iter() {
+///         info!("Drawing a health bar at {health}");
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(ComponentInterpolationPlugin::<Health>::default())
+///     .add_systems(Update, draw_health_bars);
+/// ```
+pub struct ComponentInterpolationPlugin<C: Component + Clone> {
+    lerp: fn(&C, &C, f32) -> C,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: Component + Clone + Lerp> Default for ComponentInterpolationPlugin<C> {
+    fn default() -> Self {
+        Self::new(C::lerp)
+    }
+}
+
+impl<C: Component + Clone> ComponentInterpolationPlugin<C> {
+    /// Creates a plugin interpolating using the provided `lerp` function, for types which do not
+    /// implement [`Lerp`].
+    pub fn new(lerp: fn(&C, &C, f32) -> C) -> Self {
+        Self {
+            lerp,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: Component + Clone> Plugin for ComponentInterpolationPlugin<C> {
+    fn build(&self, app: &mut App) {
+        let lerp = self.lerp;
+
+        let interpolate = move |mut commands: Commands,
+                                alpha: Res<InterpolationAlpha>,
+                                frame: Res<RollbackFrameCount>,
+                                mut query: Query<
+            (
+                Entity,
+                &C,
+                Option<&mut InterpolationState<C>>,
+                Option<&mut Interpolated<C>>,
+            ),
+            With<Rollback>,
+        >| {
+            for (entity, component, state, interpolated) in query.iter_mut() {
+                let Some(mut state) = state else {
+                    commands.entity(entity).insert((
+                        InterpolationState {
+                            previous: component.clone(),
+                            current: component.clone(),
+                            frame: frame.0,
+                        },
+                        Interpolated(component.clone()),
+                    ));
+                    continue;
+                };
+
+                if state.frame != frame.0 {
+                    state.previous = std::mem::replace(&mut state.current, component.clone());
+                    state.frame = frame.0;
+                }
+
+                let value = lerp(&state.previous, &state.current, alpha.0);
+
+                match interpolated {
+                    Some(mut interpolated) => interpolated.0 = value,
+                    None => {
+                        commands.entity(entity).insert(Interpolated(value));
+                    }
+                }
+            }
+        };
+
+        let cleanup =
+            |mut commands: Commands,
+             query: Query<Entity, (With<InterpolationState<C>>, Without<C>)>| {
+                for entity in query.iter() {
+                    commands
+                        .entity(entity)
+                        .remove::<(InterpolationState<C>, Interpolated<C>)>();
+                }
+            };
+
+        app.add_systems(PreUpdate, (interpolate, cleanup).in_set(InterpolationSet));
+    }
+}
+
+/// Inputs of local players sampled this update, which the simulation has not consumed yet.
+///
+/// Fill this resource every update before the [`InterpolationSet`], typically by sampling the
+/// same devices your [`ReadInputs`](`crate::ReadInputs`) systems read. It is only used by the
+/// [`LocalInputPredictionPlugin`] for visuals, and never sent to other players. Players without a
+/// visual input fall back to their most recent [`LocalInputs`].
+#[derive(Resource)]
+pub struct VisualInputs<T: Config>(pub HashMap<PlayerHandle, T::Input>);
+
+impl<T: Config> Default for VisualInputs<T> {
+    fn default() -> Self {
+        Self(default())
+    }
+}
+
+/// Marks a [`Rollback`] entity as controlled by a local player, so its visuals respond to that
+/// player's [`VisualInputs`] before the simulation does, see [`LocalInputPredictionPlugin`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocalPrediction {
+    /// The handle of the local player controlling this entity.
+    pub handle: PlayerHandle,
+}
+
+/// A render-only [`Component`] holding the value of the [`Component`] `C` with the pending local
+/// input partially applied, maintained by the [`LocalInputPredictionPlugin`].
+///
+/// This is never rolled back, and must never be read by the simulation.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deref)]
+pub struct Predicted<C>(pub C);
+
+/// A [`Plugin`] which hides local input latency by applying the pending input of a local player
+/// to the visuals of the [`Component`] `C` immediately, before the rollback frame it belongs to is
+/// simulated. The result is written to [`Predicted<C>`], leaving `C` itself untouched.
+///
+/// The provided `predict` function receives the current simulated value of `C`, the pending input
+/// from the [`VisualInputs`], and the [`InterpolationAlpha`] as the fraction of the next frame
+/// which has elapsed. It should return the value `C` would have after that fraction of a frame
+/// with the input applied, so `1.0` should roughly match the next simulated value.
+///
+/// The prediction is derived from the simulated value every update and never accumulated, which
+/// reconciles it with the simulation: once the frame has been simulated, or corrected by a
+/// rollback, the next update starts from the authoritative value again. Only entities with a
+/// [`LocalPrediction`] are predicted. Prediction runs in the [`InterpolationSet`], outside of the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`), so it never feeds back into the simulation.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{prelude::*, utils::HashMap};
+/// # use bevy_ggrs::{
+/// #     prelude::*, InterpolationSet, LocalInputPredictionPlugin, LocalPlayers, Predicted,
+/// #     VisualInputs,
+/// # };
+/// #
+/// type MyConfig = GgrsConfig<i8>;
+///
+/// #[derive(Component, Clone, Copy)]
+/// struct Position(f32);
+///
+/// fn predict(position: &Position, input: &i8, alpha: f32) -> Position {
+///     Position(position.0 + *input as f32 * alpha)
+/// }
+///
+/// fn sample_inputs(mut inputs: ResMut<VisualInputs<MyConfig>>, players: Res<LocalPlayers>) {
+///     for &handle in &players.0 {
+///         inputs.0.insert(handle, 1);
+///     }
+/// }
+///
+/// fn draw(query: Query<&Predicted<Position>>) {
+///     for &Predicted(Position(position)) in query.iter() {
+///         info!("Drawing a player at {position}");
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(LocalInputPredictionPlugin::<Position, MyConfig>::new(predict))
+///     .add_systems(PreUpdate, sample_inputs.before(InterpolationSet))
+///     .add_systems(Update, draw);
+/// ```
+pub struct LocalInputPredictionPlugin<C: Component + Clone, T: Config> {
+    predict: fn(&C, &T::Input, f32) -> C,
+    _phantom: PhantomData<(C, T)>,
+}
+
+impl<C: Component + Clone, T: Config> LocalInputPredictionPlugin<C, T> {
+    /// Creates a plugin predicting visuals using the provided `predict` function.
+    pub fn new(predict: fn(&C, &T::Input, f32) -> C) -> Self {
+        Self {
+            predict,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: Component + Clone, T: Config> Plugin for LocalInputPredictionPlugin<C, T> {
+    fn build(&self, app: &mut App) {
+        let predict = self.predict;
+
+        let apply = move |mut commands: Commands,
+                          alpha: Res<InterpolationAlpha>,
+                          visual_inputs: Res<VisualInputs<T>>,
+                          local_inputs: Option<Res<LocalInputs<T>>>,
+                          mut query: Query<
+            (Entity, &C, &LocalPrediction, Option<&mut Predicted<C>>),
+            With<Rollback>,
+        >| {
+            for (entity, component, prediction, predicted) in query.iter_mut() {
+                let input = visual_inputs.0.get(&prediction.handle).or_else(|| {
+                    local_inputs
+                        .as_ref()
+                        .and_then(|inputs| inputs.0.get(&prediction.handle))
+                });
+
+                let value = match input {
+                    Some(input) => predict(component, input, alpha.0),
+                    None => component.clone(),
+                };
+
+                match predicted {
+                    Some(mut predicted) => predicted.0 = value,
+                    None => {
+                        commands.entity(entity).insert(Predicted(value));
+                    }
+                }
+            }
+        };
+
+        let cleanup = |mut commands: Commands,
+                       query: Query<
+            Entity,
+            (
+                With<Predicted<C>>,
+                Or<(Without<C>, Without<LocalPrediction>)>,
+            ),
+        >| {
+            for entity in query.iter() {
+                commands.entity(entity).remove::<Predicted<C>>();
+            }
+        };
+
+        app.init_resource::<VisualInputs<T>>()
+            .add_systems(PreUpdate, (apply, cleanup).in_set(InterpolationSet));
+    }
+}
@@ -13,125 +13,1526 @@ use bevy::{
     prelude::*,
     utils::{Duration, HashMap},
 };
-use ggrs::{Config, InputStatus, P2PSession, PlayerHandle, SpectatorSession, SyncTestSession};
-use std::{fmt::Debug, hash::Hash, marker::PhantomData, net::SocketAddr};
+#[cfg(feature = "spectator")]
+use ggrs::SpectatorSession;
+#[cfg(feature = "synctest")]
+use ggrs::SyncTestSession;
+use ggrs::{
+    Config, GgrsError, GgrsEvent, InputStatus, P2PSession, PlayerHandle, PlayerType, SessionBuilder,
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    net::SocketAddr,
+};
 
 pub use ggrs;
 
+#[cfg(feature = "chaos-schedule")]
+pub use chaos::ChaosSchedule;
+pub use deferred_spawn::*;
+pub use deterministic_map::*;
+pub use deterministic_sort::*;
+pub use effect::*;
+pub use error::*;
+pub use input::*;
+pub use input_checksum::*;
+pub use input_history::*;
+pub use interpolation::*;
+pub use network_simulation::*;
+pub use replay::*;
 pub use rollback::*;
+pub use rollback_commands::*;
+#[cfg(feature = "scene")]
+pub use scene::*;
+#[cfg(feature = "forced-rollback")]
+pub use schedule_systems::force_rollback;
+#[cfg(feature = "spectator")]
+pub use schedule_systems::promote_spectator;
+pub use schedule_systems::{
+    advance_frame, bench_advance, close_session, end_preview, preview_frame, seek_to_frame,
+    start_session_at_frame,
+};
 pub use snapshot::*;
+pub use status::*;
+pub use tagged_input::*;
 pub use time::*;
 
+#[cfg(feature = "chaos-schedule")]
+pub(crate) mod chaos;
+pub(crate) mod deferred_spawn;
+pub(crate) mod deterministic_map;
+pub(crate) mod deterministic_sort;
+pub(crate) mod effect;
+pub(crate) mod error;
+pub mod fixed;
+pub(crate) mod input;
+pub(crate) mod input_checksum;
+pub(crate) mod input_history;
+pub(crate) mod interpolation;
+pub(crate) mod network_simulation;
+pub(crate) mod replay;
 pub(crate) mod rollback;
+pub(crate) mod rollback_commands;
+#[cfg(feature = "scene")]
+pub(crate) mod scene;
 pub(crate) mod schedule_systems;
 pub(crate) mod snapshot;
+pub(crate) mod status;
+pub(crate) mod tagged_input;
 pub(crate) mod time;
 
 pub mod prelude {
     pub use crate::{
         snapshot::prelude::*, AddRollbackCommandExtension, GgrsApp, GgrsConfig, GgrsPlugin,
-        GgrsSchedule, GgrsTime, PlayerInputs, ReadInputs, Rollback, Session,
+        GgrsSchedule, GgrsTime, PlayerInputs, ReadInputs, Rollback, RollbackEntities, Session,
+        SessionEvent, SessionType,
     };
     pub use ggrs::{GgrsEvent, PlayerType, SessionBuilder};
 }
 
-/// A sensible default [GGRS Config](`ggrs::Config`) type suitable for most applications.
-///
-/// If you require a more specialized configuration, you can create your own type implementing
-/// [`Config`](`ggrs::Config`).
-#[derive(Debug)]
-pub struct GgrsConfig<Input, Address = SocketAddr, State = u8> {
-    _phantom: PhantomData<(Input, Address, State)>,
+/// A sensible default [GGRS Config](`ggrs::Config`) type suitable for most applications.
+///
+/// If you require a more specialized configuration, you can create your own type implementing
+/// [`Config`](`ggrs::Config`).
+#[derive(Debug)]
+pub struct GgrsConfig<Input, Address = SocketAddr, State = u8> {
+    _phantom: PhantomData<(Input, Address, State)>,
+}
+
+impl<Input, Address, State> Config for GgrsConfig<Input, Address, State>
+where
+    Self: 'static,
+    Input: Send + Sync + PartialEq + bytemuck::Pod,
+    Address: Send + Sync + Debug + Hash + Eq + Clone,
+    State: Send + Sync + Clone,
+{
+    type Input = Input;
+    type State = State;
+    type Address = Address;
+}
+
+const DEFAULT_FPS: usize = 60;
+
+/// Label for the schedule which advances the rollback simulation by a single frame.
+///
+/// Commands queued within this schedule, including those queued in reaction to [`Event`]s sent
+/// within it, are applied before the frame is saved. Components they insert are therefore part
+/// of the snapshot of that frame, and are removed again when rolling back past it. Use the
+/// [`GgrsScheduleSet`] sets to apply commands at deterministic points within the frame as well.
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct GgrsSchedule;
+
+/// Label for a schedule which runs once as soon as a [`Session`] is present, before it is
+/// advanced for the first time. Spawn the initial rollback entities of your game here.
+///
+/// Every peer must start from an identical world. This schedule runs single-threaded, so
+/// [`Rollback`] ids are assigned in the same order on every peer. Once it completes, the
+/// [`InitialChecksum`] of the world is computed, which peers can exchange to verify they
+/// start from the same state.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsInitSchedule};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// #[derive(Component, Clone, Copy, Hash)]
+/// struct Health(u32);
+///
+/// fn spawn_players(mut commands: Commands) {
+///     commands.spawn(Health(100)).add_rollback();
+///     commands.spawn(Health(100)).add_rollback();
+/// }
+///
+/// app.rollback_component_with_copy::<Health>()
+///     .checksum_component_with_hash::<Health>()
+///     .add_systems(GgrsInitSchedule, spawn_players);
+/// ```
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct GgrsInitSchedule;
+
+/// The [`Checksum`] of the world after the [`GgrsInitSchedule`] has run for the current
+/// [`Session`], or `None` if it has not run yet or [`DisableSnapshots`] is present. Only types
+/// registered for checksums contribute.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InitialChecksum(pub Option<u128>);
+
+/// Defines the Session that the GGRS Plugin should expect as a resource.
+///
+/// [`P2P`](`Session::P2P`) is always available. The [`SyncTest`](`Session::SyncTest`) and
+/// [`Spectator`](`Session::Spectator`) variants, along with their handling, are only compiled with
+/// the `synctest` and `spectator` features respectively, which are enabled by default. Disable
+/// default features to leave them out of builds which only ever use a [`P2PSession`].
+///
+/// A [`Replay`](`Session::Replay`) plays back the inputs recorded by a [`ReplayRecorder`] without
+/// any network connection.
+#[allow(clippy::large_enum_variant)]
+#[derive(Resource)]
+pub enum Session<T: Config> {
+    #[cfg(feature = "synctest")]
+    SyncTest(SyncTestSession<T>),
+    P2P(P2PSession<T>),
+    #[cfg(feature = "spectator")]
+    Spectator(SpectatorSession<T>),
+    Replay(ReplaySession<T>),
+}
+
+impl<T: Config> Session<T> {
+    /// The [`SessionType`] of this [`Session`].
+    pub fn session_type(&self) -> SessionType {
+        match self {
+            #[cfg(feature = "synctest")]
+            Session::SyncTest(_) => SessionType::SyncTest,
+            Session::P2P(_) => SessionType::P2P,
+            #[cfg(feature = "spectator")]
+            Session::Spectator(_) => SessionType::Spectator,
+            Session::Replay(_) => SessionType::Replay,
+        }
+    }
+
+    /// The number of players in this [`Session`], excluding spectators.
+    pub fn num_players(&self) -> usize {
+        match self {
+            #[cfg(feature = "synctest")]
+            Session::SyncTest(session) => session.num_players(),
+            Session::P2P(session) => session.num_players(),
+            #[cfg(feature = "spectator")]
+            Session::Spectator(session) => session.num_players(),
+            Session::Replay(session) => session.num_players(),
+        }
+    }
+
+    /// The maximum amount of frames this [`Session`] may predict ahead, or `None` for a
+    /// [`SpectatorSession`] or [`ReplaySession`], which never predict.
+    pub fn max_prediction(&self) -> Option<usize> {
+        match self {
+            #[cfg(feature = "synctest")]
+            Session::SyncTest(session) => Some(session.max_prediction()),
+            Session::P2P(session) => Some(session.max_prediction()),
+            #[cfg(feature = "spectator")]
+            Session::Spectator(_) => None,
+            Session::Replay(_) => None,
+        }
+    }
+}
+
+/// The parameters a [`Session`] was built with, which GGRS does not expose once it is built.
+///
+/// Build a [`SessionBuilder`] using [`apply`](`SessionConfig::apply`), then insert this as a
+/// [`Resource`] alongside the [`Session`], so the same parameters can be read back for UI or
+/// validation. Once a [`Session`] starts, the [`GgrsPlugin`] warns if the [`RollbackFrameRate`] or
+/// the number of players differ from this configuration.
+///
+/// The defaults match those of a [`SessionBuilder`].
+///
+/// Use [`builder`](`SessionConfig::builder`) to add the players as well, which validates the
+/// configuration against them first. This reports mistakes such as declaring two players but
+/// adding three as a [`SessionConfigError`], rather than as an error from deep within GGRS.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, SessionConfig};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// # fn start(mut commands: Commands) -> Result<(), Box<dyn std::error::Error>> {
+/// let config = SessionConfig {
+///     num_players: 2,
+///     input_delay: 2,
+///     ..default()
+/// };
+///
+/// let builder = config.apply(SessionBuilder::<MyConfig>::new())?;
+/// # let _ = builder;
+///
+/// commands.insert_resource(config);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionConfig {
+    /// The number of players, excluding spectators.
+    pub num_players: usize,
+    /// The frame rate used for GGRS time synchronization, which should match the [`RollbackFrameRate`].
+    pub fps: usize,
+    /// The maximum amount of frames which may be predicted ahead. `0` runs in lockstep.
+    pub max_prediction: usize,
+    /// The amount of frames local inputs are delayed by.
+    pub input_delay: usize,
+    /// Whether GGRS only requests saving the newest confirmed frame, rather than every frame.
+    /// This saves less often, at the cost of rolling back further.
+    pub sparse_saving: bool,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            num_players: 2,
+            fps: DEFAULT_FPS,
+            max_prediction: 8,
+            input_delay: 0,
+            sparse_saving: false,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Applies this configuration to the provided [`SessionBuilder`].
+    pub fn apply<T: Config>(
+        &self,
+        builder: SessionBuilder<T>,
+    ) -> Result<SessionBuilder<T>, GgrsError> {
+        builder
+            .with_num_players(self.num_players)
+            .with_input_delay(self.input_delay)
+            .with_sparse_saving_mode(self.sparse_saving)
+            .with_max_prediction_window(self.max_prediction)?
+            .with_fps(self.fps)
+    }
+
+    /// Creates a [`SessionBuilder`] with this configuration applied and the provided players
+    /// added, after checking that:
+    ///
+    /// - the amount of players added, excluding spectators, matches the
+    ///   [`num_players`](`SessionConfig::num_players`),
+    /// - every player handle is below the [`num_players`](`SessionConfig::num_players`), and
+    ///   every spectator handle is not,
+    /// - the [`fps`](`SessionConfig::fps`) is not `0`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, SessionConfig, SessionConfigError};
+    /// #
+    /// # type MyConfig = GgrsConfig<u8>;
+    /// #
+    /// let config = SessionConfig {
+    ///     num_players: 2,
+    ///     ..default()
+    /// };
+    ///
+    /// let error = config
+    ///     .builder::<MyConfig>([
+    ///         (PlayerType::Local, 0),
+    ///         (PlayerType::Local, 1),
+    ///         (PlayerType::Local, 2),
+    ///     ])
+    ///     .err();
+    ///
+    /// assert_eq!(
+    ///     error,
+    ///     Some(SessionConfigError::PlayerCount { declared: 2, added: 3 })
+    /// );
+    /// ```
+    pub fn builder<T: Config>(
+        &self,
+        players: impl IntoIterator<Item = (PlayerType<T::Address>, PlayerHandle)>,
+    ) -> Result<SessionBuilder<T>, SessionConfigError> {
+        let players = players.into_iter().collect::<Vec<_>>();
+
+        if self.fps == 0 {
+            return Err(SessionConfigError::ZeroFps);
+        }
+
+        let added = players
+            .iter()
+            .filter(|(kind, _)| !matches!(kind, PlayerType::Spectator(_)))
+            .count();
+
+        if added != self.num_players {
+            return Err(SessionConfigError::PlayerCount {
+                declared: self.num_players,
+                added,
+            });
+        }
+
+        for (kind, handle) in &players {
+            let spectator = matches!(kind, PlayerType::Spectator(_));
+
+            if spectator == (*handle < self.num_players) {
+                return Err(SessionConfigError::InvalidHandle {
+                    handle: *handle,
+                    num_players: self.num_players,
+                    spectator,
+                });
+            }
+        }
+
+        let mut builder = self.apply(SessionBuilder::new())?;
+
+        for (kind, handle) in players {
+            builder = builder.add_player(kind, handle)?;
+        }
+
+        Ok(builder)
+    }
+}
+
+/// The kind of [`Session`] currently in use, kept in sync by the [`GgrsPlugin`] every frame.
+///
+/// The [`Session`] resource is temporarily removed while the rollback schedules are run, so
+/// prefer this over checking for the [`Session`] when only the kind of session is required.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionType {
+    /// No [`Session`] has been started.
+    #[default]
+    None,
+    SyncTest,
+    P2P,
+    Spectator,
+    Replay,
+}
+
+/// An [`Event`] sent when the [`SessionType`] changes between frames, such as when replacing a
+/// [`SyncTestSession`] with a [`P2PSession`], or when a [`Session`] is started or removed (where
+/// `old` or `new` is [`SessionType::None`]).
+///
+/// Replacing a [`Session`] with another of the same kind is not detected.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionReplaced {
+    /// The kind of [`Session`] in use during the previous frame.
+    pub old: SessionType,
+    /// The kind of [`Session`] now in use.
+    pub new: SessionType,
+}
+
+/// A run condition which is `true` while the current [`SessionType`] matches the provided one.
+///
+/// This allows save and load systems to behave differently per kind of session. For example,
+/// a [`SyncTestSession`] saves and loads every frame, so expensive verification can be limited
+/// to it while keeping a [`P2PSession`] fast.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, in_session_type, SaveWorld, SaveWorldSet};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// fn validate_navigation_mesh() {
+///     // An expensive check which should never fail in production
+/// }
+///
+/// app.add_systems(
+///     SaveWorld,
+///     validate_navigation_mesh
+///         .before(SaveWorldSet::Snapshot)
+///         .run_if(in_session_type(SessionType::SyncTest)),
+/// );
+/// # }
+/// ```
+pub fn in_session_type(
+    session_type: SessionType,
+) -> impl FnMut(Option<Res<SessionType>>) -> bool + Clone {
+    move |current: Option<Res<SessionType>>| current.is_some_and(|current| *current == session_type)
+}
+
+/// Inputs for all players for the frame currently being advanced, indexed by [`PlayerHandle`].
+/// Only available within the [`GgrsSchedule`].
+///
+/// # Prediction
+///
+/// When the input of a remote player has not arrived yet, GGRS predicts it by repeating the
+/// last input it received from that player, marking it as [`InputStatus::Predicted`]. Once the
+/// real input arrives, GGRS compares it against its own prediction and rolls back only if they
+/// differ. Because of this, the prediction cannot be overridden from within the simulation:
+/// acting differently on a predicted input than on the same confirmed input will desync peers
+/// whenever the prediction turns out to be correct.
+///
+/// Instead, design your input type so that repeating the last input is a safe guess. Encode
+/// the _state_ of buttons (held or not) rather than _edges_ (pressed this frame), and derive
+/// edges inside the simulation from rolled-back state. Inputs of disconnected players
+/// ([`InputStatus::Disconnected`]) are always [zeroed](`bytemuck::Zeroable`), so make sure
+/// an all-zero input means "release all buttons".
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// const INPUT_FIRE: u8 = 1 << 0;
+///
+/// type MyInputType = u8;
+///
+/// // The buttons held on the previous frame are part of the rolled-back state...
+/// #[derive(Component, Clone, Copy, Default)]
+/// struct PreviousInput(u8);
+///
+/// #[derive(Component)]
+/// struct Player {
+///     handle: usize,
+/// }
+///
+/// // ...so "just pressed" can be derived deterministically, even while inputs are predicted.
+/// fn fire(
+///     inputs: Res<PlayerInputs<GgrsConfig<MyInputType>>>,
+///     mut players: Query<(&Player, &mut PreviousInput)>,
+/// ) {
+///     for (player, mut previous) in players.iter_mut() {
+///         let input = inputs[player.handle].0;
+///
+///         if input & INPUT_FIRE != 0 && previous.0 & INPUT_FIRE == 0 {
+///             // Fire once, even if GGRS repeats this input for the next few frames
+///         }
+///
+///         previous.0 = input;
+///     }
+/// }
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// app.rollback_component_with_copy::<PreviousInput>()
+///     .add_systems(GgrsSchedule, fire);
+/// # }
+/// ```
+// TODO: more specific name to avoid conflicts?
+#[derive(Resource, Deref, DerefMut)]
+pub struct PlayerInputs<T: Config>(Vec<(T::Input, InputStatus)>);
+
+impl<T: Config> PlayerInputs<T> {
+    /// Returns `true` if no input of the current frame is [predicted](`InputStatus::Predicted`),
+    /// which is also available to systems as the [`FrameConfirmed`] resource.
+    ///
+    /// Inputs are confirmed in order, so once every input of a frame is confirmed, so are the
+    /// inputs of all frames before it. The frame is then advanced exactly as every other peer
+    /// advances it, and a [`P2PSession`] will not roll it back again. This makes it safe to
+    /// commit irreversible actions, such as reporting a match result, from within the
+    /// [`GgrsSchedule`]. A [`SyncTestSession`](`ggrs::SyncTestSession`) only ever provides
+    /// confirmed inputs, but still re-advances frames to compare checksums, so such actions must
+    /// tolerate being repeated.
+    pub fn all_confirmed(&self) -> bool {
+        self.0
+            .iter()
+            .all(|(_, status)| !matches!(status, InputStatus::Predicted))
+    }
+}
+
+/// Whether every input of the frame currently being advanced is confirmed, updated before every
+/// frame is advanced. See [`PlayerInputs::all_confirmed`] for details.
+///
+/// This describes the inputs of the frame itself, whereas the [`ConfirmedFrameCount`] tracks
+/// the newest frame whose inputs are confirmed, which may be ahead of or behind the frame being
+/// advanced. When re-advancing frames after a rollback, a frame may be advanced with confirmed
+/// inputs well before the [`ConfirmedFrameCount`] has caught up to it.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, FrameConfirmed};
+/// #
+/// #[derive(Component, Clone, Copy)]
+/// struct Health(u32);
+///
+/// fn report_knockouts(confirmed: Res<FrameConfirmed>, players: Query<&Health>) {
+///     if !**confirmed {
+///         // This frame may still be rolled back, try again once it is confirmed
+///         return;
+///     }
+///
+///     for health in &players {
+///         if health.0 == 0 {
+///             // Safe to submit to a leaderboard
+///         }
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default());
+/// # app.add_systems(GgrsSchedule, report_knockouts);
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deref)]
+pub struct FrameConfirmed(pub(crate) bool);
+
+/// The fixed timestep state of the [`GgrsPlugin`], deciding when the next frame is advanced.
+///
+/// Time is accumulated every update, and a frame is advanced whenever a full frame of time has
+/// been accumulated. Tooling requiring frame-perfect control, such as TAS or speedrun practice
+/// tools, can read and write the accumulator here, or use [`advance_frame`] to step manually.
+///
+/// Writing the accumulator of a live [`P2PSession`] changes when frames are advanced locally,
+/// which peers will not agree on. This may stall the session, or desync it if the simulation
+/// depends on wall-clock time.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, FixedTimestepData};
+/// #
+/// #[derive(Resource)]
+/// struct Paused;
+///
+/// // Never accumulate time while paused, so frames only advance when stepped manually
+/// fn hold_frame(mut time_data: ResMut<FixedTimestepData>) {
+///     time_data.clear_accumulator();
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_systems(Update, hold_frame.run_if(resource_exists::<Paused>));
+/// ```
+#[derive(Resource, Copy, Clone, Debug)]
+pub struct FixedTimestepData {
+    /// accumulated time. once enough time has been accumulated, an update is executed
+    accumulator: Duration,
+    /// boolean to see if we should run slow to let remote clients catch up
+    run_slow: bool,
+    /// how many frames ahead of remote clients are tolerated before running slow
+    run_slow_threshold: i32,
+    /// the frame rate the current session was built with
+    session_framerate: Option<usize>,
+    /// the frame rate last compared against the frame rate of the current session
+    checked_framerate: Option<usize>,
+    /// whether a session was present during the previous update
+    had_session: bool,
+    /// smoothed duration of a frame in seconds, see [`FramePacingSmoothing`]
+    smoothed_delta: f64,
+    /// smoothed time accumulated towards the next update in seconds
+    smoothed_overstep: f64,
+    /// time elapsed since remote clients were last polled, see [`NetworkPollCadence`]
+    since_poll: Duration,
+    /// wall-clock time a single update may spend advancing frames
+    frame_budget: Option<Duration>,
+}
+
+impl FixedTimestepData {
+    /// The time accumulated towards advancing the next frame.
+    pub fn accumulator(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// Overwrites the time accumulated towards advancing the next frame. Every full frame of time
+    /// is advanced during the next update.
+    pub fn set_accumulator(&mut self, accumulator: Duration) -> &mut Self {
+        self.accumulator = accumulator;
+        self
+    }
+
+    /// Discards all accumulated time, so no frame is advanced until a full frame of time has
+    /// passed again.
+    pub fn clear_accumulator(&mut self) -> &mut Self {
+        self.set_accumulator(Duration::ZERO)
+    }
+
+    /// The frame rate the current [`Session`] was built with, which GGRS uses for time
+    /// synchronization, or `None` without a session. This is read from the [`SessionConfig`] if
+    /// present when the session starts, and assumed to be the [`RollbackFrameRate`] otherwise.
+    pub fn session_frame_rate(&self) -> Option<usize> {
+        self.session_framerate
+    }
+
+    /// Returns `true` while frames take longer to accumulate, as the [`P2PSession`] is further
+    /// ahead of remote clients than the [`run_slow_threshold`](`FixedTimestepData::run_slow_threshold`).
+    pub fn is_running_slow(&self) -> bool {
+        self.run_slow
+    }
+
+    /// How many frames a [`P2PSession`] may be ahead of remote clients before running slow.
+    pub fn run_slow_threshold(&self) -> i32 {
+        self.run_slow_threshold
+    }
+
+    /// Sets how many frames a [`P2PSession`] may be ahead of remote clients before running slow.
+    /// Negative thresholds are treated as `0`, which is the default.
+    ///
+    /// While running slow, every frame takes 10% longer to accumulate, letting remote clients
+    /// catch up. With a threshold of `0`, being a single frame ahead already slows down, so the
+    /// session may alternate between both speeds every few frames, which players perceive as
+    /// jitter. A threshold of `1` or `2` frames tolerates small differences, and only slows down
+    /// once they persist, at the cost of remaining up to that many frames ahead.
+    pub fn set_run_slow_threshold(&mut self, frames: i32) -> &mut Self {
+        self.run_slow_threshold = frames.max(0);
+        self
+    }
+
+    /// The wall-clock time a single update may spend advancing frames, if limited.
+    pub fn frame_budget(&self) -> Option<Duration> {
+        self.frame_budget
+    }
+
+    /// Limits the wall-clock time a single update may spend advancing frames. `None`, the
+    /// default, never limits it.
+    ///
+    /// Once the budget is exceeded, no further frames are advanced during the update, an error is
+    /// logged, and the accumulator is clamped to a single frame, so the time left over does not
+    /// build up. Unlike [`SimulationPacing::max_catchup_frames`], this accounts for frames which
+    /// are costly to advance, such as after a long rollback. At least one frame is always
+    /// advanced when due, so an overrun budget slows the simulation down rather than stalling it.
+    pub fn set_frame_budget(&mut self, budget: Option<Duration>) -> &mut Self {
+        self.frame_budget = budget;
+        self
+    }
+}
+
+impl Default for FixedTimestepData {
+    fn default() -> Self {
+        Self {
+            accumulator: Duration::ZERO,
+            run_slow: false,
+            run_slow_threshold: 0,
+            session_framerate: None,
+            checked_framerate: None,
+            had_session: false,
+            smoothed_delta: 0.,
+            smoothed_overstep: 0.,
+            since_poll: Duration::ZERO,
+            frame_budget: None,
+        }
+    }
+}
+
+/// Keeps track of the current frame the rollback simulation is in
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RollbackFrameCount(pub i32);
+
+impl From<RollbackFrameCount> for i32 {
+    fn from(value: RollbackFrameCount) -> i32 {
+        value.0
+    }
+}
+
+/// The most recently confirmed frame. Any information for frames stored before this point can be safely discarded.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConfirmedFrameCount(i32);
+
+impl From<ConfirmedFrameCount> for i32 {
+    fn from(value: ConfirmedFrameCount) -> i32 {
+        value.0
+    }
+}
+
+/// The logical frame the current [`Session`] started at, see [`start_session_at_frame`].
+///
+/// GGRS always counts frames from `0`, so every frame reported by GGRS is offset by this value
+/// before it reaches the [`RollbackFrameCount`], the [`ConfirmedFrameCount`] and the snapshots.
+/// Frames found within a [`SessionEvent`] are reported by GGRS directly, and are not offset.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deref)]
+pub struct SessionStartFrame(pub(crate) i32);
+
+/// The maximum prediction window for this [`Session`], provided as a concrete [`Resource`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MaxPredictionWindow(usize);
+
+/// How many frames the current [`P2PSession`] is predicting ahead of the last confirmed frame,
+/// where `0` means the current frame is fully confirmed. This is updated every step.
+///
+/// If this is consistently close to the [`MaxPredictionWindow`], the session is prediction-bound,
+/// and increasing the maximum prediction window may reduce stalls.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deref)]
+pub struct PredictionDepth(pub(crate) usize);
+
+/// How many frames the current [`SpectatorSession`] is behind the host, updated every step. A
+/// value above `0` while [`SpectatorCatchup`] is present means the spectator is fast-forwarding.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deref)]
+pub struct SpectatorLag(pub(crate) usize);
+
+/// When present, a [`SpectatorSession`] behind the host advances up to `max_catchup_frames`
+/// frames per step instead of one, until it has caught up. Otherwise, a spectator falling behind
+/// (such as after the app was suspended) only catches up through the catchup speed it was built
+/// with, see [`SessionBuilder::with_catchup_speed`](`ggrs::SessionBuilder::with_catchup_speed`).
+///
+/// The cap bounds the work done in a single update, so catching up never freezes the app for long.
+/// See [`SpectatorLag`] to indicate fast-forwarding to the user.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpectatorCatchup {
+    /// The maximum amount of frames advanced per step while behind the host.
+    pub max_catchup_frames: usize,
+}
+
+impl Default for SpectatorCatchup {
+    fn default() -> Self {
+        Self {
+            max_catchup_frames: 4,
+        }
+    }
+}
+
+/// Statistics about the current [`Session`], reset once it ends.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionStats {
+    /// How many steps of a [`P2PSession`] could not advance because it reached the
+    /// [`MaxPredictionWindow`] while waiting for remote inputs. Skips are not raised as a
+    /// [`SessionError`]. If this grows steadily, the session is starved of remote inputs.
+    pub prediction_threshold_skips: u64,
+}
+
+/// What to do with the accumulated time of a step skipped because the [`P2PSession`] reached the
+/// [`MaxPredictionWindow`]. When absent, [`PredictionThresholdBehavior::Skip`] is used.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PredictionThresholdBehavior {
+    /// The time of the skipped step is consumed, so the frame is dropped.
+    #[default]
+    Skip,
+    /// The time of the skipped step is kept, so the step is retried on the next update. This
+    /// recovers sooner from prediction starvation, at the cost of advancing several frames at
+    /// once afterwards.
+    Retry,
+}
+
+/// Gates whether the [`GgrsPlugin`] advances the [`Session`] at all. When absent, it is active.
+///
+/// While inactive, the [`Session`] is only polled to keep remote clients alive: no frames are
+/// advanced, no snapshots are saved or loaded, and no [`SessionEvent`]s are forwarded until it is
+/// active again. Unlike pausing through [`SimulationPacing`], the [`FixedTimestepData`] is left
+/// untouched, so time is not accumulated either. Use [`GgrsResumeBehavior`] to decide whether the
+/// time accumulated before deactivating is kept for when it is active again.
+///
+/// This is intended for loading screens or custom state machines, and can be toggled by systems
+/// using any run conditions. Remote peers keep advancing until they reach their prediction window,
+/// after which they stall until the local peer is active again. Remaining inactive for longer than
+/// the disconnect timeout of the [`Session`] is fine, as polling keeps the connection alive, but
+/// peers will fall far behind each other, so all peers should generally deactivate together.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsActive};
+/// #
+/// #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+/// enum GameState {
+///     #[default]
+///     Loading,
+///     Playing,
+/// }
+///
+/// fn activate(mut commands: Commands) {
+///     commands.insert_resource(GgrsActive(true));
+/// }
+///
+/// fn deactivate(mut commands: Commands) {
+///     commands.insert_resource(GgrsActive(false));
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.init_state::<GameState>();
+///
+/// app.add_systems(OnEnter(GameState::Playing), activate)
+///     .add_systems(OnExit(GameState::Playing), deactivate);
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GgrsActive(pub bool);
+
+impl Default for GgrsActive {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// What happens to the accumulated time of the [`FixedTimestepData`] while [`GgrsActive`] is
+/// `false`. When absent, [`GgrsResumeBehavior::Preserve`] is used.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GgrsResumeBehavior {
+    /// The accumulated time is kept, so advancing resumes exactly where it stopped.
+    #[default]
+    Preserve,
+    /// The accumulated time is cleared, so the first frame after resuming is only advanced once a
+    /// full frame of time has passed again.
+    Reset,
+}
+
+/// An [`Event`] forwarding a [`GgrsEvent`] raised by the current [`Session`].
+///
+/// Events are drained from the [`Session`] every frame after polling remote clients, so
+/// [`P2PSession::events`] and [`SpectatorSession::events`] will not yield any events. Read
+/// these instead using an [`EventReader`].
+///
+/// # Migrating
+///
+/// Systems which drained the events from the [`Session`] themselves silently stop receiving
+/// any, and must read them as [`SessionEvent`]s instead:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, SessionEvent};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// // Before
+/// fn print_events_from_session(mut session: ResMut<Session<MyConfig>>) {
+///     if let Session::P2P(session) = &mut *session {
+///         for event in session.events() {
+///             info!("GGRS event: {event:?}");
+///         }
+///     }
+/// }
+///
+/// // After
+/// fn print_events(mut events: EventReader<SessionEvent<MyConfig>>) {
+///     for event in events.read() {
+///         info!("GGRS event: {:?}", event.0);
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Deref)]
+pub struct SessionEvent<T: Config>(pub GgrsEvent<T>);
+
+/// A summary of a single [`GgrsRequest`](`ggrs::GgrsRequest`) issued by the current [`Session`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionRequest {
+    /// The world was saved for the provided frame.
+    SaveGameState { frame: i32 },
+    /// The world was rolled back to the provided frame.
+    LoadGameState { frame: i32 },
+    /// The world was advanced to the provided frame.
+    AdvanceFrame { frame: i32 },
+}
+
+/// An [`Event`] listing every request issued by the current [`Session`] in a single step, in the
+/// order they are processed. This reveals the interleaving of saves, loads and advances which
+/// make up a rollback.
+///
+/// This event is opt-in: it is only sent once it has been added to the [`App`].
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, SessionRequests};
+/// #
+/// # let mut app = App::new();
+/// app.add_event::<SessionRequests>();
+///
+/// fn print_requests(mut requests: EventReader<SessionRequests>) {
+///     for requests in requests.read() {
+///         info!("{:?}", requests.0);
+///     }
+/// }
+/// # app.add_systems(Update, print_requests);
+/// ```
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct SessionRequests(pub Vec<SessionRequest>);
+
+/// Timing of a single schedule run by the [`GgrsPlugin`]. See [`RollbackTimings`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ScheduleTiming {
+    /// How long the most recent run took.
+    pub last: Duration,
+    /// An exponential moving average of how long each run took.
+    pub average: Duration,
+    /// How many runs have been recorded.
+    pub count: u64,
+}
+
+impl ScheduleTiming {
+    /// The weight given to the most recent run when updating the [`average`](`ScheduleTiming::average`).
+    pub const SMOOTHING: f64 = 0.1;
+
+    /// Records a single run of the schedule.
+    pub fn record(&mut self, elapsed: Duration) -> &mut Self {
+        self.average = match self.count {
+            0 => elapsed,
+            _ => self.average.mul_f64(1. - Self::SMOOTHING) + elapsed.mul_f64(Self::SMOOTHING),
+        };
+        self.last = elapsed;
+        self.count += 1;
+        self
+    }
+}
+
+/// A [`Resource`] timing every run of the [`SaveWorld`], [`LoadWorld`] and [`AdvanceWorld`]
+/// schedules, useful to find what makes rollback-heavy frames expensive.
+///
+/// Timings are opt-in: they are only recorded while this [`Resource`] is present.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, RollbackTimings};
+/// #
+/// # let mut app = App::new();
+/// app.init_resource::<RollbackTimings>();
+///
+/// fn print_timings(timings: Res<RollbackTimings>) {
+///     info!("Saving takes {:?} on average", timings.save.average);
+///     info!("Loading takes {:?} on average", timings.load.average);
+/// }
+/// # app.add_systems(Update, print_timings);
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct RollbackTimings {
+    /// Timing of the [`SaveWorld`] schedule.
+    pub save: ScheduleTiming,
+    /// Timing of the [`LoadWorld`] schedule.
+    pub load: ScheduleTiming,
+    /// Timing of the [`AdvanceWorld`] schedule.
+    pub advance: ScheduleTiming,
+}
+
+/// The most recent [`GgrsEvent::WaitRecommendation`] raised by the current [`Session`], if
+/// it has not been followed yet. This crate follows recommendations by running slower until
+/// the local client is no longer ahead of its peers, at which point this is cleared.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WaitRecommendation(Option<u32>);
+
+impl WaitRecommendation {
+    /// The amount of frames GGRS recommended to skip to let remote clients catch up.
+    pub fn skip_frames(&self) -> Option<u32> {
+        self.0
+    }
+}
+
+/// When present, snapshots are only taken on frames which are a multiple of this interval,
+/// rather than every frame. Set this using [`GgrsApp::set_snapshot_interval`].
+///
+/// When GGRS requests loading a frame without a snapshot, the nearest prior snapshot is loaded
+/// instead, and the simulation is fast-forwarded to the requested frame using the inputs recorded
+/// since then. To ensure that snapshot is still available, the [`ConfirmedFrameCount`] is rounded
+/// down to a multiple of this interval.
+///
+/// This greatly reduces the cost of saving, at the expense of re-simulating up to `interval - 1`
+/// extra frames on every rollback. It is therefore intended for sessions where rollbacks are rare
+/// or impossible, such as an authoritative peer or a session without prediction. Only use this
+/// when prediction is disabled, or rollbacks are guaranteed not to exceed the snapshot interval.
+/// Skipped frames provide no checksum, so desync detection only covers snapshot frames.
+#[derive(Resource, Clone, Copy, Debug, Hash, Deref)]
+pub struct SnapshotInterval(pub(crate) usize);
+
+impl SnapshotInterval {
+    /// The nearest frame at or before the provided frame on which a snapshot is taken.
+    pub fn snapshot_frame(&self, frame: i32) -> i32 {
+        let interval = self.0.max(1) as i32;
+        frame - frame.rem_euclid(interval)
+    }
+}
+
+/// When present, every snapshot storage retains snapshots covering this many seconds of
+/// rollback time, rather than a fixed amount of frames. Set this using
+/// [`GgrsApp::set_snapshot_retention`].
+///
+/// The retained [`frames`](`SnapshotRetention::frames`) are computed from the
+/// [`RollbackFrameRate`] before every save, so they follow any change of the frame rate. They
+/// never fall below the [`MaxPredictionWindow`], as every predicted frame must remain available
+/// to roll back to. Snapshots before the [`ConfirmedFrameCount`] are still discarded, so this is
+/// an upper bound on the retained history.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct SnapshotRetention {
+    seconds: f32,
+    frames: usize,
+}
+
+impl SnapshotRetention {
+    /// Retain snapshots covering the provided amount of seconds.
+    pub fn from_secs(seconds: f32) -> Self {
+        let mut retention = Self { seconds, frames: 1 };
+        retention.apply(DEFAULT_FPS, 0);
+        retention
+    }
+
+    /// The amount of seconds of rollback time retained.
+    pub fn seconds(&self) -> f32 {
+        self.seconds
+    }
+
+    /// The effective amount of frames retained, as of the last save.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Converts the retained seconds into frames at the provided frame rate, retaining at least
+    /// `max_prediction` frames.
+    fn apply(&mut self, fps: usize, max_prediction: usize) {
+        let frames = (self.seconds.max(0.) * fps as f32).ceil() as usize;
+        self.frames = frames.max(max_prediction).max(1);
+    }
+
+    /// A system updating the effective [`frames`](`SnapshotRetention::frames`) from the current
+    /// [`RollbackFrameRate`] and [`MaxPredictionWindow`].
+    pub fn update(
+        mut retention: ResMut<Self>,
+        frame_rate: Option<Res<RollbackFrameRate>>,
+        max_prediction: Option<Res<MaxPredictionWindow>>,
+    ) {
+        let fps = frame_rate.map_or(DEFAULT_FPS, |frame_rate| frame_rate.0);
+        let max_prediction = max_prediction.map_or(0, |max_prediction| max_prediction.0);
+
+        // Only trigger change detection when the effective depth changes
+        let mut updated = *retention;
+        updated.apply(fps, max_prediction);
+        retention.set_if_neq(updated);
+    }
+}
+
+/// How far the accumulated time has progressed towards the next rollback frame, between `0.0` and
+/// `1.0`. Use this to interpolate visuals between the previous and the current frame.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, PartialOrd, Deref)]
+pub struct InterpolationAlpha(pub(crate) f32);
+
+/// When present, the [`InterpolationAlpha`] is smoothed over time. Set this using
+/// [`GgrsApp::set_frame_pacing_smoothing`].
+///
+/// With a render rate close to the rollback frame rate, the amount of frames advanced per update
+/// oscillates (1, 2, 1, 1, 2, ...), which causes visible micro-stutter in interpolated visuals.
+/// Smoothing spreads the accumulated time more evenly, similar to a smoothed frame time.
+///
+/// The contained factor is the weight given to the most recent update, between `0.0` and `1.0`,
+/// where `1.0` disables smoothing. Only the [`InterpolationAlpha`] is affected: the amount of
+/// frames advanced over time is unchanged, as is the simulation itself.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, PartialOrd, Deref)]
+pub struct FramePacingSmoothing(pub f64);
+
+impl Default for FramePacingSmoothing {
+    fn default() -> Self {
+        Self(0.1)
+    }
+}
+
+/// Controls how quickly the [`GgrsSchedule`] catches up with elapsed time, and whether it runs
+/// at all while the app is backgrounded. Set it using [`GgrsApp::set_simulation_pacing`].
+///
+/// Combined with the [`RollbackFrameRate`], this allows polling the network and rendering at a
+/// modest rate, while simulating at a standard rate by advancing several frames per update. The
+/// [`Session`] is always polled, even while backgrounded, so connections are kept alive.
+///
+/// Pacing never changes the outcome of the simulation, only how quickly frames are advanced
+/// locally. The [`RollbackFrameRate`] however must be identical for all peers, so it should only
+/// be changed before a [`Session`] starts. In a [`P2PSession`], a peer advancing fewer frames
+/// than its remotes holds them back once they exceed their prediction window, so they stall until
+/// it catches up. Backgrounding therefore only suits local sessions, or games where all peers
+/// pause together.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, SimulationPacing};
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default());
+/// app.set_rollback_schedule_fps(60)
+///     .set_simulation_pacing(SimulationPacing {
+///         max_catchup_frames: Some(4),
+///         backgrounded_fps: Some(0),
+///         backgrounded: false,
+///     });
+///
+/// // Pause the simulation while the app is not visible, keeping the session alive
+/// fn on_background(mut pacing: ResMut<SimulationPacing>) {
+///     pacing.backgrounded = true;
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SimulationPacing {
+    /// The maximum amount of frames advanced in a single update. Any time left over after
+    /// advancing this many frames is dropped, rather than catching up over the next updates.
+    /// `None` always catches up fully.
+    pub max_catchup_frames: Option<usize>,
+    /// The rate at which frames are advanced while [`backgrounded`](`SimulationPacing::backgrounded`).
+    /// `Some(0)` pauses the simulation, while `None` ignores backgrounding. This does not change
+    /// the [`RollbackFrameRate`], so each frame still represents the same amount of game time.
+    pub backgrounded_fps: Option<usize>,
+    /// Whether the app is currently backgrounded.
+    pub backgrounded: bool,
+}
+
+impl Default for SimulationPacing {
+    fn default() -> Self {
+        Self {
+            max_catchup_frames: None,
+            backgrounded_fps: Some(0),
+            backgrounded: false,
+        }
+    }
+}
+
+impl SimulationPacing {
+    /// Scales the time elapsed in an update to the rate at which frames should be advanced.
+    pub fn scale_delta(&self, delta: Duration, framerate: usize) -> Duration {
+        match self.backgrounded_fps {
+            Some(fps) if self.backgrounded && fps < framerate => {
+                delta.mul_f64(fps as f64 / framerate as f64)
+            }
+            _ => delta,
+        }
+    }
+}
+
+/// Controls how often the [`Session`] polls remote clients, independent of the rate at which the
+/// app updates. Set it using [`GgrsApp::set_network_poll_cadence`].
+///
+/// Polling receives messages from remote clients, such as synchronization and disconnects, and
+/// sends any pending messages. Remote clients are polled once at least
+/// [`interval`](`NetworkPollCadence::interval`) has passed since the previous poll, as measured by
+/// [`Time`], so tests injecting time using a [`TimeUpdateStrategy`](`bevy::time::TimeUpdateStrategy`)
+/// control polling as well. Updates which advance a frame always poll first, so skipped polls never
+/// delay the simulation.
+///
+/// The default interval of zero polls every update, which minimizes latency. At high frame rates,
+/// a longer interval avoids polling far more often than messages arrive, at the cost of noticing
+/// messages up to one interval later while no frame is advanced. Intervals longer than a frame of
+/// the [`RollbackFrameRate`] make no difference during a running session, as every frame polls.
+/// No poll can happen between updates, so at low frame rates remote clients are polled once per
+/// update, regardless of the interval.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{prelude::*, utils::Duration};
+/// # use bevy_ggrs::{prelude::*, NetworkPollCadence};
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default());
+/// // Rendering at 240 fps, polling every 4ms is plenty
+/// app.set_network_poll_cadence(NetworkPollCadence {
+///     interval: Duration::from_millis(4),
+/// });
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetworkPollCadence {
+    /// The minimum time between polls, unless a frame is about to be advanced.
+    pub interval: Duration,
+}
+
+/// When present, the [`SaveWorld`] and [`LoadWorld`] schedules are never run, while the
+/// [`GgrsSchedule`] still advances at a fixed timestep for any type of [`Session`].
+///
+/// This trusts that no rollback will occur, which is useful for tutorials, replay verification,
+/// or isolating whether a bug is caused by the simulation or by snapshotting. Should a rollback
+/// be requested regardless, a warning is logged and only the [`RollbackFrameCount`] is restored.
+/// No checksums are provided while snapshots are disabled.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, DisableSnapshots};
+/// #
+/// # let mut app = App::new();
+/// app.insert_resource(DisableSnapshots);
+/// ```
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisableSnapshots;
+
+/// When present, the inputs used to advance every frame since the oldest retained snapshot are
+/// recorded, so [`seek_to_frame`] can land on any frame from there up to the current one. Set
+/// this using [`GgrsApp::enable_seeking`].
+///
+/// Only retained snapshots can be seeked from, so increase the depth of the snapshot storage
+/// to scrub further back, such as in a replay viewer.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Seekable;
+
+/// Present while a past frame is shown using [`preview_frame`], until [`end_preview`] returns to
+/// the present frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RewindPreview {
+    /// The frame being shown.
+    pub(crate) frame: i32,
+    /// The frame to return to once the preview ends.
+    pub(crate) present: i32,
+    /// The frame of the snapshot the preview was loaded from.
+    pub(crate) snapshot: i32,
+}
+
+impl RewindPreview {
+    /// The frame being shown.
+    pub fn frame(&self) -> i32 {
+        self.frame
+    }
+
+    /// The frame to return to once the preview ends.
+    pub fn present(&self) -> i32 {
+        self.present
+    }
+}
+
+/// Present while a lockstep [`Session`] is waiting for remote inputs before it can advance.
+///
+/// A [`P2PSession`] built with a maximum prediction window of `0` runs in lockstep: it only
+/// advances once the inputs of every player for the next frame have arrived, so it never predicts
+/// or rolls back. No snapshots are taken in lockstep, lowering CPU usage, but no checksums are
+/// provided either. Lockstep requires an input delay of at least one frame, otherwise peers wait
+/// on each other indefinitely.
+///
+/// A single lagging peer stalls every other peer, so use this to show that the game is waiting.
+/// GGRS does not report which remote inputs are still missing, so the stall lists every remote
+/// player. Their pings, such as from [`GgrsStatus::ping`], help tell which one is lagging.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, LockstepStall};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// # fn build() -> Result<(), Box<dyn std::error::Error>> {
+/// let builder = SessionBuilder::<MyConfig>::new()
+///     .with_max_prediction_window(0)?
+///     .with_input_delay(2);
+/// # Ok(())
+/// # }
+///
+/// fn show_stall(stall: Option<Res<LockstepStall>>) {
+///     if let Some(stall) = stall {
+///         info!("Waiting for players {:?} on frame {}", stall.remote_players, stall.frame);
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LockstepStall {
+    /// The frame which is waiting to be advanced.
+    pub frame: i32,
+    /// Every remote player, any of which may not have provided their inputs for this frame yet.
+    pub remote_players: Vec<PlayerHandle>,
+    /// The amount of consecutive steps this frame could not be advanced.
+    pub steps: u32,
+}
+
+/// The outcome of a rollback forced using [`force_rollback`].
+#[cfg(feature = "forced-rollback")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ForcedRollback {
+    /// The frame which was rolled back to.
+    pub frame: i32,
+    /// The amount of frames re-simulated to return to the original frame.
+    pub resimulated: usize,
+    /// The [`Checksum`] of the original frame before rolling back.
+    pub checksum_before: Option<u128>,
+    /// The [`Checksum`] of the original frame after re-simulating it.
+    pub checksum_after: Option<u128>,
+}
+
+#[cfg(feature = "forced-rollback")]
+impl ForcedRollback {
+    /// Returns `true` if re-simulating produced the same [`Checksum`] as the original run.
+    pub fn is_deterministic(&self) -> bool {
+        self.checksum_before == self.checksum_after
+    }
 }
 
-impl<Input, Address, State> Config for GgrsConfig<Input, Address, State>
-where
-    Self: 'static,
-    Input: Send + Sync + PartialEq + bytemuck::Pod,
-    Address: Send + Sync + Debug + Hash + Eq + Clone,
-    State: Send + Sync + Clone,
-{
-    type Input = Input;
-    type State = State;
-    type Address = Address;
+/// Inputs used to advance each frame since the oldest retained snapshot, recorded while a
+/// [`SnapshotInterval`] is in use, or the [`World`] is [`Seekable`].
+#[derive(Resource)]
+pub(crate) struct SnapshotIntervalInputs<T: Config>(VecDeque<(i32, Vec<(T::Input, InputStatus)>)>);
+
+impl<T: Config> Default for SnapshotIntervalInputs<T> {
+    fn default() -> Self {
+        Self(default())
+    }
 }
 
-const DEFAULT_FPS: usize = 60;
+/// Inputs from local players. You have to fill this resource in the ReadInputs schedule.
+#[derive(Resource)]
+pub struct LocalInputs<C: Config>(pub HashMap<PlayerHandle, C::Input>);
 
-#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
-pub struct GgrsSchedule;
+/// Handles for the local players, you can use this when writing an input system.
+#[derive(Resource, Default)]
+pub struct LocalPlayers(pub Vec<PlayerHandle>);
 
-/// Defines the Session that the GGRS Plugin should expect as a resource.
-#[allow(clippy::large_enum_variant)]
+/// The role of a player within a [`P2PSession`], see [`PlayerRoster`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayerKind {
+    /// A player on this peer.
+    Local,
+    /// A player on a remote peer.
+    Remote,
+    /// A spectator, which receives inputs but provides none.
+    Spectator,
+}
+
+/// Information about a single player of a [`P2PSession`], see [`PlayerRoster`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerInfo<A> {
+    /// The role of this player.
+    pub kind: PlayerKind,
+    /// The address of the peer of a remote player or spectator, once known.
+    pub addr: Option<A>,
+}
+
+/// The players of the current [`P2PSession`] by [`PlayerHandle`], including spectators, kept up to
+/// date by the [`GgrsPlugin`]. This is purely informational, such as for debug UI or to correlate
+/// network statistics with peers.
+///
+/// GGRS does not expose the address of a handle directly, so the address of a remote player or
+/// spectator is filled in once the [`P2PSession`] raises an event for it, which happens as soon as
+/// it starts synchronizing. This resource is removed once the [`P2PSession`] ends.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, PlayerRoster};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn print_roster(roster: Option<Res<PlayerRoster<MyConfig>>>) {
+///     for (handle, player) in roster.iter().flat_map(|roster| roster.iter()) {
+///         info!("Player {handle} is {:?} at {:?}", player.kind, player.addr);
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_systems(Update, print_roster);
+/// ```
 #[derive(Resource)]
-pub enum Session<T: Config> {
-    SyncTest(SyncTestSession<T>),
-    P2P(P2PSession<T>),
-    Spectator(SpectatorSession<T>),
+pub struct PlayerRoster<T: Config> {
+    players: BTreeMap<PlayerHandle, PlayerInfo<T::Address>>,
 }
 
-// TODO: more specific name to avoid conflicts?
-#[derive(Resource, Deref, DerefMut)]
-pub struct PlayerInputs<T: Config>(Vec<(T::Input, InputStatus)>);
+impl<T: Config> PlayerRoster<T> {
+    /// Get the information about the player with the provided handle.
+    pub fn get(&self, handle: PlayerHandle) -> Option<&PlayerInfo<T::Address>> {
+        self.players.get(&handle)
+    }
 
-#[derive(Resource, Copy, Clone, Debug)]
-struct FixedTimestepData {
-    /// accumulated time. once enough time has been accumulated, an update is executed
-    accumulator: Duration,
-    /// boolean to see if we should run slow to let remote clients catch up
-    run_slow: bool,
+    /// Iterate over all players in ascending order of their handle.
+    pub fn iter(&self) -> impl Iterator<Item = (PlayerHandle, &PlayerInfo<T::Address>)> + '_ {
+        self.players
+            .iter()
+            .map(|(&handle, player)| (handle, player))
+    }
+
+    /// Records the role of the provided handle, keeping any address already known.
+    pub(crate) fn set_kind(&mut self, handle: PlayerHandle, kind: PlayerKind) {
+        self.players
+            .entry(handle)
+            .and_modify(|player| player.kind = kind)
+            .or_insert(PlayerInfo { kind, addr: None });
+    }
+
+    /// Records the address of the provided handle, if it is a known remote player or spectator.
+    pub(crate) fn set_addr(&mut self, handle: PlayerHandle, addr: T::Address) {
+        if let Some(player) = self.players.get_mut(&handle) {
+            if player.kind != PlayerKind::Local {
+                player.addr = Some(addr);
+            }
+        }
+    }
 }
 
-impl Default for FixedTimestepData {
+impl<T: Config> Default for PlayerRoster<T> {
     fn default() -> Self {
-        Self {
-            accumulator: Duration::ZERO,
-            run_slow: false,
-        }
+        Self { players: default() }
     }
 }
 
-/// Keeps track of the current frame the rollback simulation is in
-#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct RollbackFrameCount(pub i32);
+/// An [`Event`] sent when the [`LocalPlayers`] of the current [`Session`] change, such as after
+/// reconnecting. This is not sent when the local players are first assigned for a [`Session`],
+/// so it never fires for a stable session.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct LocalPlayersChanged {
+    /// The handles of the local players before the change.
+    pub previous: Vec<PlayerHandle>,
+    /// The handles of the local players after the change.
+    pub current: Vec<PlayerHandle>,
+}
 
-impl From<RollbackFrameCount> for i32 {
-    fn from(value: RollbackFrameCount) -> i32 {
-        value.0
-    }
+/// An [`Event`] sent when the connection to a remote peer of the current [`Session`] is
+/// interrupted, or resumes after an interruption. See [`NetworkInterruptions`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum NetworkInterruption<A> {
+    /// No messages have been received from the peer at `addr` for a while, such as during
+    /// transient packet loss. Unless the connection resumes, the peer is disconnected once the
+    /// `disconnect_timeout` has passed.
+    Interrupted {
+        /// The address of the peer.
+        addr: A,
+        /// The time left before the peer is disconnected.
+        disconnect_timeout: Duration,
+    },
+    /// Messages from the peer at `addr` are received again.
+    Resumed {
+        /// The address of the peer.
+        addr: A,
+    },
 }
 
-/// The most recently confirmed frame. Any information for frames stored before this point can be safely discarded.
-#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ConfirmedFrameCount(i32);
+/// The remote peers of the current [`Session`] whose connection is currently interrupted, with
+/// the time left before they are disconnected, kept up to date by the [`GgrsPlugin`].
+///
+/// GGRS raises [`GgrsEvent::NetworkInterrupted`] once no messages have been received from a peer
+/// for the disconnect notify delay of the [`SessionBuilder`], and disconnects it once its
+/// disconnect timeout passes without the connection resuming. An interruption is not a
+/// disconnect, so it suits a "reconnecting" indicator rather than ending the match. Each
+/// interruption is also sent as a [`NetworkInterruption`] event.
+///
+/// The countdown is measured using [`Time`], so it only approximates the timeout GGRS measures
+/// itself. Peers are removed once they resume, or are disconnected.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, NetworkInterruptions};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn reconnecting_ui(interruptions: Res<NetworkInterruptions<MyConfig>>) {
+///     for (addr, remaining) in interruptions.iter() {
+///         info!("Reconnecting to {addr} ({}s)", remaining.as_secs());
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_systems(Update, reconnecting_ui);
+/// ```
+#[derive(Resource)]
+pub struct NetworkInterruptions<T: Config> {
+    remaining: HashMap<T::Address, Duration>,
+}
 
-impl From<ConfirmedFrameCount> for i32 {
-    fn from(value: ConfirmedFrameCount) -> i32 {
-        value.0
+impl<T: Config> NetworkInterruptions<T> {
+    /// The time left before the interrupted peer at `addr` is disconnected, or `None` if its
+    /// connection is not interrupted.
+    pub fn get(&self, addr: &T::Address) -> Option<Duration> {
+        self.remaining.get(addr).copied()
     }
-}
 
-/// The maximum prediction window for this [`Session`], provided as a concrete [`Resource`].
-#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct MaxPredictionWindow(usize);
+    /// Iterate over all interrupted peers, with the time left before they are disconnected.
+    pub fn iter(&self) -> impl Iterator<Item = (&T::Address, Duration)> + '_ {
+        self.remaining
+            .iter()
+            .map(|(addr, &remaining)| (addr, remaining))
+    }
 
-/// Inputs from local players. You have to fill this resource in the ReadInputs schedule.
-#[derive(Resource)]
-pub struct LocalInputs<C: Config>(pub HashMap<PlayerHandle, C::Input>);
+    /// Returns `true` if no connection is currently interrupted.
+    pub fn is_empty(&self) -> bool {
+        self.remaining.is_empty()
+    }
 
-/// Handles for the local players, you can use this when writing an input system.
-#[derive(Resource, Default)]
-pub struct LocalPlayers(pub Vec<PlayerHandle>);
+    /// Counts down the time left for every interrupted peer.
+    pub(crate) fn tick(&mut self, delta: Duration) {
+        for remaining in self.remaining.values_mut() {
+            *remaining = remaining.saturating_sub(delta);
+        }
+    }
+}
+
+impl<T: Config> Default for NetworkInterruptions<T> {
+    fn default() -> Self {
+        Self {
+            remaining: default(),
+        }
+    }
+}
 
-/// Label for the schedule which reads the inputs for the current frame
+/// Label for the schedule which reads the inputs for the current frame. To supply inputs from
+/// a non-realtime source instead, such as a bot, see [`InputSource`].
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct ReadInputs;
 
 /// Label for the schedule which loads and overwrites a snapshot of the world.
+///
+/// This schedule is run for every [`LoadGameState`](`ggrs::GgrsRequest::LoadGameState`) request,
+/// after the [`RollbackFrameCount`] has been set to the frame being loaded. It is the only way
+/// state is restored: every snapshot plugin, such as [`ComponentSnapshotPlugin`], loads its data
+/// within it. To restore state not covered by the provided plugins, add a system loading it from
+/// your own storage, keyed by the [`RollbackFrameCount`], to [`LoadWorldSet::Data`].
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct LoadWorld;
 
 /// Label for the schedule which saves a snapshot of the current world.
+///
+/// This schedule is run for every [`SaveGameState`](`ggrs::GgrsRequest::SaveGameState`) request,
+/// while the [`RollbackFrameCount`] matches the frame being saved. The [`Checksum`] present after
+/// it has run is handed to GGRS, so the checksum compared between peers is produced by the same
+/// schedule taking the snapshots. Snapshots are taken within [`SaveWorldSet::Snapshot`], including
+/// any custom ones, followed by computing checksums within [`SaveWorldSet::Checksum`].
+///
+/// Only state which is both checksummed and snapshotted is verified and restored consistently. Use
+/// [`SnapshotChecksumVerification`] to find checksummed state which is not restored.
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct SaveWorld;
 
@@ -194,13 +1595,32 @@ impl<C: Config> Plugin for GgrsPlugin<C> {
     fn build(&self, app: &mut App) {
         app.init_resource::<RollbackFrameCount>()
             .init_resource::<ConfirmedFrameCount>()
+            .init_resource::<FrameConfirmed>()
             .init_resource::<MaxPredictionWindow>()
+            .init_resource::<PredictionDepth>()
+            .init_resource::<SessionStartFrame>()
+            .init_resource::<SpectatorLag>()
+            .init_resource::<SessionStats>()
+            .init_resource::<GgrsStatus>()
             .init_resource::<RollbackOrdered>()
             .init_resource::<LocalPlayers>()
             .init_resource::<FixedTimestepData>()
+            .init_resource::<WaitRecommendation>()
+            .init_resource::<SessionType>()
+            .init_resource::<InitialChecksum>()
+            .init_resource::<InterpolationAlpha>()
+            .init_resource::<NetworkInterruptions<C>>()
+            .add_event::<SessionEvent<C>>()
+            .add_event::<SessionError>()
+            .add_event::<LocalPlayersChanged>()
+            .add_event::<SessionReplaced>()
+            .add_event::<NetworkInterruption<C::Address>>()
             .init_schedule(ReadInputs)
             .init_schedule(LoadWorld)
-            .init_schedule(SaveWorld)
+            .edit_schedule(SaveWorld, |schedule| {
+                // Snapshots of different types are independent, so they are taken in parallel
+                schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+            })
             .edit_schedule(AdvanceWorld, |schedule| {
                 // AdvanceWorld is mostly a facilitator for GgrsSchedule, so SingleThreaded avoids overhead
                 // This can be overridden if desired.
@@ -212,15 +1632,34 @@ impl<C: Config> Plugin for GgrsPlugin<C> {
                     ..default()
                 });
             })
+            .edit_schedule(GgrsInitSchedule, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+                schedule.set_build_settings(ScheduleBuildSettings {
+                    ambiguity_detection: LogLevel::Error,
+                    ..default()
+                });
+            })
             .add_systems(
                 PreUpdate,
                 schedule_systems::run_ggrs_schedules::<C>.after(InputSystem),
             )
+            .add_systems(
+                SaveWorld,
+                SnapshotRetention::update
+                    .run_if(resource_exists::<SnapshotRetention>)
+                    .before(SaveWorldSet::Snapshot),
+            )
+            .configure_sets(
+                PreUpdate,
+                (InterpolationSet, GgrsEffectSet).after(schedule_systems::run_ggrs_schedules::<C>),
+            )
             .add_plugins((
                 SnapshotSetPlugin,
                 ChecksumPlugin,
                 EntitySnapshotPlugin,
                 EntityChecksumPlugin,
+                NoRollbackPlugin,
+                RollbackCommandsPlugin,
                 GgrsTimePlugin,
                 ResourceSnapshotPlugin::<CloneStrategy<RollbackOrdered>>::default(),
                 ComponentSnapshotPlugin::<ReflectStrategy<Parent>>::default(),
@@ -257,6 +1696,32 @@ pub trait GgrsApp {
     where
         Type: Resource + Clone;
 
+    /// Registers a component type for saving and loading from the world. This
+    /// stores a baseline snapshot followed by per-frame deltas of changed components.
+    /// See [`ComponentDeltaSnapshotPlugin`] for details.
+    fn rollback_component_with_delta<Type>(&mut self) -> &mut Self
+    where
+        Type: Component + Clone + PartialEq;
+
+    /// Registers a component type for saving and loading from the world. This
+    /// stores a baseline snapshot followed by per-frame deltas of components which changed
+    /// according to `eq`. See [`ComponentDeltaSnapshotPlugin::with_eq`] for details.
+    fn rollback_component_with_delta_eq<Type>(&mut self, eq: fn(&Type, &Type) -> bool) -> &mut Self
+    where
+        Type: Component + Clone + PartialEq;
+
+    /// Registers a component type for saving and loading from the world. This
+    /// uses a pair of transform functions to snapshot the component as the type `As`.
+    /// See [`ComponentSnapshotTransformPlugin`] for details.
+    fn rollback_component_with_transform<Type, As>(
+        &mut self,
+        store: for<'a> fn(&'a Type) -> As,
+        load: for<'a> fn(&'a As) -> Type,
+    ) -> &mut Self
+    where
+        Type: Component,
+        As: Send + Sync + 'static;
+
     /// Registers a component type for saving and loading from the world. This
     /// uses [`reflection`](`Reflect`) based snapshots for rollback.
     ///
@@ -277,20 +1742,84 @@ pub trait GgrsApp {
     where
         Type: Resource + Reflect + FromWorld;
 
-    /// Set the frequency that game updates should be performed at.
+    /// Registers the [`State`] and [`NextState`] of a [`States`] type for saving and loading from
+    /// the world. See [`StateSnapshotPlugin`] for applying transitions deterministically.
+    fn rollback_state<Type>(&mut self) -> &mut Self
+    where
+        Type: States;
+
+    /// Attaches a schema version to a rolled back component or resource type. Increment this
+    /// whenever its layout changes between releases. See [`RollbackRegistrationFingerprint`].
+    fn set_rollback_version<Type>(&mut self, version: u16) -> &mut Self
+    where
+        Type: 'static;
+
+    /// Never remove a rolled back component type from an entity when loading a snapshot lacking
+    /// it. See [`KeepOnRollback`] for details.
+    fn keep_on_rollback<Type>(&mut self) -> &mut Self
+    where
+        Type: Component;
+
+    /// Set the frequency that game updates should be performed at. This must match the frame
+    /// rate of the [`Session`], see [`RollbackFrameRate::configure`] and [`set_fps`].
     fn set_rollback_schedule_fps(&mut self, fps: usize) -> &mut Self;
 
+    /// Only take snapshots every `interval` frames. See [`SnapshotInterval`] for details.
+    fn set_snapshot_interval(&mut self, interval: usize) -> &mut Self;
+
+    /// Retain snapshots covering the provided amount of seconds, at the current frame rate.
+    /// See [`SnapshotRetention`] for details.
+    fn set_snapshot_retention(&mut self, seconds: f32) -> &mut Self;
+
+    /// Record the inputs of every retained frame, so the [`World`] can be [seeked](`seek_to_frame`).
+    /// See [`Seekable`] for details.
+    fn enable_seeking(&mut self) -> &mut Self;
+
+    /// Smooth the [`InterpolationAlpha`] using the provided factor. See [`FramePacingSmoothing`]
+    /// for details.
+    fn set_frame_pacing_smoothing(&mut self, factor: f64) -> &mut Self;
+
+    /// Limits how quickly frames are advanced to catch up, and whether they are advanced while
+    /// backgrounded. See [`SimulationPacing`] for details.
+    fn set_simulation_pacing(&mut self, pacing: SimulationPacing) -> &mut Self;
+
+    /// Limits how often remote clients are polled. See [`NetworkPollCadence`] for details.
+    fn set_network_poll_cadence(&mut self, cadence: NetworkPollCadence) -> &mut Self;
+
+    /// Run independent systems of the [`GgrsSchedule`] in an order chosen by the provided seed.
+    /// See [`ChaosSchedule`] for details.
+    #[cfg(feature = "chaos-schedule")]
+    fn set_chaos_schedule_seed(&mut self, seed: u64) -> &mut Self;
+
     /// Adds a component type to the checksum generation pipeline using [`Hash`].
     fn checksum_component_with_hash<Type>(&mut self) -> &mut Self
     where
         Type: Component + Hash;
 
+    /// Adds a component type to the checksum generation pipeline using [`Hash`], only hashing
+    /// changed components again. See [`CachedComponentChecksumPlugin`] for details.
+    fn checksum_component_with_hash_cached<Type>(&mut self) -> &mut Self
+    where
+        Type: Component + Hash;
+
+    /// Only checksum rollback entities flagged as a [`ChecksumContributor`]. See
+    /// [`ChecksumContributorsOnly`] for details.
+    fn checksum_contributors_only(&mut self) -> &mut Self;
+
+    /// Only checksum rollback entities once they were spawned on a frame every peer has
+    /// confirmed. See [`ChecksumConfirmedEntitiesOnly`] for details, including the desyncs this
+    /// may miss.
+    fn checksum_confirmed_entities_only(&mut self) -> &mut Self;
+
     /// Updates a component after rollback using [`MapEntities`].
     fn update_component_with_map_entities<Type>(&mut self) -> &mut Self
     where
         Type: Component + MapEntities;
 
     /// Adds a resource type to the checksum generation pipeline using [`Hash`].
+    ///
+    /// A [`HashMap`] iterates in a different order on every peer, so store keyed state as a
+    /// [`DeterministicMap`] instead, which hashes its entries in order of their keys.
     fn checksum_resource_with_hash<Type>(&mut self) -> &mut Self
     where
         Type: Resource + Hash;
@@ -300,6 +1829,12 @@ pub trait GgrsApp {
     where
         Type: Resource + MapEntities;
 
+    /// Updates a resource after rollback, mapping every [`Entity`] found using [`Reflect`].
+    /// See [`ResourceReflectMapEntitiesPlugin`] for details.
+    fn update_resource_with_reflect_map_entities<Type>(&mut self) -> &mut Self
+    where
+        Type: Resource + Reflect;
+
     /// Adds a component type to the checksum generation pipeline.
     fn checksum_component<Type>(&mut self, hasher: for<'a> fn(&'a Type) -> u64) -> &mut Self
     where
@@ -309,11 +1844,89 @@ pub trait GgrsApp {
     fn checksum_resource<Type>(&mut self, hasher: for<'a> fn(&'a Type) -> u64) -> &mut Self
     where
         Type: Resource;
+
+    /// Warns whenever a resource which is not rolled back is mutated while advancing a frame.
+    /// See [`UnregisteredMutationCheck`] for details.
+    fn warn_on_unregistered_mutations(&mut self) -> &mut Self;
+
+    /// Verifies that the checksum of every loaded frame matches the checksum saved for it.
+    /// See [`SnapshotChecksumVerification`] for details.
+    fn verify_snapshot_checksums(&mut self) -> &mut Self;
+
+    /// Never warns about mutations of a resource type which is not rolled back.
+    /// See [`UnregisteredMutationCheck`] for details.
+    fn ignore_unregistered_mutations<Type>(&mut self) -> &mut Self
+    where
+        Type: Resource;
 }
 
 impl GgrsApp for App {
     fn set_rollback_schedule_fps(&mut self, fps: usize) -> &mut Self {
-        self.world.insert_resource(RollbackFrameRate(fps));
+        set_fps(&mut self.world, fps);
+
+        self
+    }
+
+    fn set_rollback_version<Type>(&mut self, version: u16) -> &mut Self
+    where
+        Type: 'static,
+    {
+        self.world
+            .get_resource_or_insert_with::<RollbackRegistrationFingerprint>(default)
+            .set_version::<Type>(version);
+
+        self
+    }
+
+    fn keep_on_rollback<Type>(&mut self) -> &mut Self
+    where
+        Type: Component,
+    {
+        self.init_resource::<KeepOnRollback<Type>>()
+    }
+
+    fn set_snapshot_interval(&mut self, interval: usize) -> &mut Self {
+        self.world
+            .insert_resource(SnapshotInterval(interval.max(1)));
+
+        self
+    }
+
+    fn set_snapshot_retention(&mut self, seconds: f32) -> &mut Self {
+        self.world
+            .insert_resource(SnapshotRetention::from_secs(seconds));
+
+        self
+    }
+
+    fn enable_seeking(&mut self) -> &mut Self {
+        self.world.insert_resource(Seekable);
+
+        self
+    }
+
+    fn set_frame_pacing_smoothing(&mut self, factor: f64) -> &mut Self {
+        self.world
+            .insert_resource(FramePacingSmoothing(factor.clamp(f64::EPSILON, 1.)));
+
+        self
+    }
+
+    fn set_simulation_pacing(&mut self, pacing: SimulationPacing) -> &mut Self {
+        self.world.insert_resource(pacing);
+
+        self
+    }
+
+    fn set_network_poll_cadence(&mut self, cadence: NetworkPollCadence) -> &mut Self {
+        self.world.insert_resource(cadence);
+
+        self
+    }
+
+    #[cfg(feature = "chaos-schedule")]
+    fn set_chaos_schedule_seed(&mut self, seed: u64) -> &mut Self {
+        self.world.insert_resource(ChaosSchedule::new(seed));
 
         self
     }
@@ -332,6 +1945,13 @@ impl GgrsApp for App {
         self.add_plugins(ResourceSnapshotPlugin::<ReflectStrategy<Type>>::default())
     }
 
+    fn rollback_state<Type>(&mut self) -> &mut Self
+    where
+        Type: States,
+    {
+        self.add_plugins(StateSnapshotPlugin::<Type>::default())
+    }
+
     fn rollback_component_with_copy<Type>(&mut self) -> &mut Self
     where
         Type: Component + Copy,
@@ -360,6 +1980,34 @@ impl GgrsApp for App {
         self.add_plugins(ResourceSnapshotPlugin::<CloneStrategy<Type>>::default())
     }
 
+    fn rollback_component_with_delta<Type>(&mut self) -> &mut Self
+    where
+        Type: Component + Clone + PartialEq,
+    {
+        self.add_plugins(ComponentDeltaSnapshotPlugin::<Type>::default())
+    }
+
+    fn rollback_component_with_delta_eq<Type>(&mut self, eq: fn(&Type, &Type) -> bool) -> &mut Self
+    where
+        Type: Component + Clone + PartialEq,
+    {
+        self.add_plugins(ComponentDeltaSnapshotPlugin::<Type>::with_eq(eq))
+    }
+
+    fn rollback_component_with_transform<Type, As>(
+        &mut self,
+        store: for<'a> fn(&'a Type) -> As,
+        load: for<'a> fn(&'a As) -> Type,
+    ) -> &mut Self
+    where
+        Type: Component,
+        As: Send + Sync + 'static,
+    {
+        self.add_plugins(ComponentSnapshotTransformPlugin::<Type, As>::new(
+            store, load,
+        ))
+    }
+
     fn checksum_component_with_hash<Type>(&mut self) -> &mut Self
     where
         Type: Component + Hash,
@@ -367,6 +2015,23 @@ impl GgrsApp for App {
         self.add_plugins(ComponentChecksumPlugin::<Type>::default())
     }
 
+    fn checksum_component_with_hash_cached<Type>(&mut self) -> &mut Self
+    where
+        Type: Component + Hash,
+    {
+        self.add_plugins(CachedComponentChecksumPlugin::<Type>::default())
+    }
+
+    fn checksum_contributors_only(&mut self) -> &mut Self {
+        self.world.insert_resource(ChecksumContributorsOnly);
+
+        self
+    }
+
+    fn checksum_confirmed_entities_only(&mut self) -> &mut Self {
+        self.add_plugins(ConfirmedChecksumPlugin)
+    }
+
     fn update_component_with_map_entities<Type>(&mut self) -> &mut Self
     where
         Type: Component + MapEntities,
@@ -388,6 +2053,13 @@ impl GgrsApp for App {
         self.add_plugins(ResourceMapEntitiesPlugin::<Type>::default())
     }
 
+    fn update_resource_with_reflect_map_entities<Type>(&mut self) -> &mut Self
+    where
+        Type: Resource + Reflect,
+    {
+        self.add_plugins(ResourceReflectMapEntitiesPlugin::<Type>::default())
+    }
+
     fn checksum_component<Type>(&mut self, hasher: for<'a> fn(&'a Type) -> u64) -> &mut Self
     where
         Type: Component,
@@ -401,4 +2073,27 @@ impl GgrsApp for App {
     {
         self.add_plugins(ResourceChecksumPlugin::<Type>(hasher))
     }
+
+    fn warn_on_unregistered_mutations(&mut self) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with::<UnregisteredMutationCheck>(default)
+            .set_enabled(true);
+
+        self
+    }
+
+    fn verify_snapshot_checksums(&mut self) -> &mut Self {
+        self.init_resource::<SnapshotChecksumVerification>()
+    }
+
+    fn ignore_unregistered_mutations<Type>(&mut self) -> &mut Self
+    where
+        Type: Resource,
+    {
+        self.world
+            .get_resource_or_insert_with::<UnregisteredMutationCheck>(default)
+            .ignore::<Type>();
+
+        self
+    }
 }
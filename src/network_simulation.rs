@@ -0,0 +1,173 @@
+use std::hash::Hash;
+
+use bevy::utils::{Duration, Instant};
+use ggrs::{Config, GgrsError, Message, NonBlockingSocket, P2PSession, SessionBuilder};
+
+/// Network conditions to simulate on the messages sent through a [`SimulatedSocket`], such as to
+/// exercise rollbacks using two sessions on the same machine, connected over a loopback socket.
+///
+/// Every sent message is delayed by the [`latency`](`NetworkSimulation::latency`) plus a random
+/// amount up to the [`jitter`](`NetworkSimulation::jitter`), or dropped entirely with a chance of
+/// [`packet_loss`](`NetworkSimulation::packet_loss`). Only outgoing messages are affected, so
+/// simulate the same conditions on every session to affect both directions.
+///
+/// The defaults simulate a perfect network.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use bevy::{prelude::*, utils::Duration};
+/// # use bevy_ggrs::{prelude::*, NetworkSimulation};
+/// # use ggrs::UdpNonBlockingSocket;
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// # fn start(mut commands: Commands) -> Result<(), Box<dyn std::error::Error>> {
+/// let builder = SessionBuilder::<MyConfig>::new()
+///     .with_num_players(2)
+///     .add_player(PlayerType::Local, 0)?
+///     .add_player(PlayerType::Remote("127.0.0.1:7001".parse()?), 1)?;
+///
+/// let simulation = NetworkSimulation {
+///     latency: Duration::from_millis(60),
+///     jitter: Duration::from_millis(20),
+///     packet_loss: 0.05,
+///     ..default()
+/// };
+///
+/// let socket = UdpNonBlockingSocket::bind_to_port(7000)?;
+/// let session = simulation.start_p2p_session(builder, socket)?;
+///
+/// commands.insert_resource(Session::P2P(session));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkSimulation {
+    /// The delay added to every sent message.
+    pub latency: Duration,
+    /// The maximum random delay added on top of the [`latency`](`NetworkSimulation::latency`).
+    /// Messages with differing delays may arrive out of order, as they would over UDP.
+    pub jitter: Duration,
+    /// The chance of dropping a sent message, between `0.0` and `1.0`.
+    pub packet_loss: f32,
+    /// The seed used for the random jitter and packet loss, so a simulation can be repeated.
+    pub seed: u64,
+}
+
+impl Default for NetworkSimulation {
+    fn default() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            jitter: Duration::ZERO,
+            packet_loss: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+impl NetworkSimulation {
+    /// Wraps the provided socket, simulating these network conditions on every message sent
+    /// through it.
+    pub fn wrap<A, S>(&self, socket: S) -> SimulatedSocket<A, S>
+    where
+        A: Clone + PartialEq + Eq + Hash + Send + Sync,
+        S: NonBlockingSocket<A>,
+    {
+        SimulatedSocket {
+            socket,
+            simulation: *self,
+            rng: self.seed,
+            in_flight: Vec::new(),
+        }
+    }
+
+    /// Starts a [`P2PSession`] from the provided [`SessionBuilder`], simulating these network
+    /// conditions on the provided socket.
+    pub fn start_p2p_session<T: Config>(
+        &self,
+        builder: SessionBuilder<T>,
+        socket: impl NonBlockingSocket<T::Address> + 'static,
+    ) -> Result<P2PSession<T>, GgrsError> {
+        builder.start_p2p_session(self.wrap(socket))
+    }
+}
+
+/// A [`NonBlockingSocket`] simulating the conditions of a [`NetworkSimulation`] on top of
+/// another socket, created using [`NetworkSimulation::wrap`].
+///
+/// Delayed messages are only passed on once the socket is used again, which happens whenever the
+/// [`Session`](`crate::Session`) polls its remote clients, at least once per update. The
+/// simulated latency is therefore rounded up to the time between updates.
+pub struct SimulatedSocket<A, S> {
+    socket: S,
+    simulation: NetworkSimulation,
+    rng: u64,
+    in_flight: Vec<(Instant, A, Message)>,
+}
+
+impl<A, S> SimulatedSocket<A, S>
+where
+    A: Clone + PartialEq + Eq + Hash + Send + Sync,
+    S: NonBlockingSocket<A>,
+{
+    /// The network conditions currently simulated.
+    pub fn simulation(&self) -> &NetworkSimulation {
+        &self.simulation
+    }
+
+    /// The amount of sent messages which are still delayed.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Passes every message whose delay has passed on to the wrapped socket, in order of arrival.
+    fn flush(&mut self) {
+        let now = Instant::now();
+
+        let (mut arrived, in_flight) = std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(arrival, _, _)| *arrival <= now);
+
+        self.in_flight = in_flight;
+        arrived.sort_by_key(|(arrival, _, _)| *arrival);
+
+        for (_, addr, msg) in arrived {
+            self.socket.send_to(&msg, &addr);
+        }
+    }
+
+    /// A random value between `0.0` and `1.0`, using SplitMix64.
+    fn random(&mut self) -> f32 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        (z >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+impl<A, S> NonBlockingSocket<A> for SimulatedSocket<A, S>
+where
+    A: Clone + PartialEq + Eq + Hash + Send + Sync,
+    S: NonBlockingSocket<A>,
+{
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        if self.random() < self.simulation.packet_loss {
+            return;
+        }
+
+        let delay = self.simulation.latency + self.simulation.jitter.mul_f32(self.random());
+
+        self.in_flight
+            .push((Instant::now() + delay, addr.clone(), msg.clone()));
+        self.flush();
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        self.flush();
+        self.socket.receive_all_messages()
+    }
+}
@@ -0,0 +1,347 @@
+use std::mem::size_of;
+
+use bevy::prelude::*;
+use ggrs::{Config, InputStatus};
+
+use crate::{
+    ConfirmedFrameCount, ReplayError, RollbackFrameCount, RollbackRegistrationFingerprint,
+};
+
+/// The confirmed inputs of every player for a range of frames, recorded by a [`ReplayRecorder`].
+///
+/// Advancing a [`World`] holding the state of the [`start_frame`](`Replay::start_frame`) with
+/// these inputs reproduces the recorded match exactly. Play it back using a [`ReplaySession`].
+///
+/// The [`RollbackRegistrationFingerprint`] in use while recording is stored along with the
+/// inputs, so replays recorded with different rolled back types or versions are rejected when
+/// read back, rather than silently playing back differently.
+pub struct Replay<C: Config> {
+    fingerprint: u64,
+    start_frame: i32,
+    frames: Vec<Vec<(C::Input, InputStatus)>>,
+}
+
+impl<C: Config> Clone for Replay<C> {
+    fn clone(&self) -> Self {
+        Self {
+            fingerprint: self.fingerprint,
+            start_frame: self.start_frame,
+            frames: self.frames.clone(),
+        }
+    }
+}
+
+impl<C: Config> Replay<C> {
+    /// The [`fingerprint`](`RollbackRegistrationFingerprint::fingerprint`) of the rollback setup
+    /// this replay was recorded with.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The frame the first recorded inputs were advanced from.
+    pub fn start_frame(&self) -> i32 {
+        self.start_frame
+    }
+
+    /// The amount of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frames are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The amount of players inputs are recorded for.
+    pub fn num_players(&self) -> usize {
+        self.frames.first().map_or(0, Vec::len)
+    }
+
+    /// Get the inputs used to advance from the provided frame, one per player in order of their
+    /// [`PlayerHandle`](`ggrs::PlayerHandle`).
+    pub fn inputs(&self, frame: i32) -> Option<&[(C::Input, InputStatus)]> {
+        let index = usize::try_from(frame - self.start_frame).ok()?;
+        self.frames.get(index).map(Vec::as_slice)
+    }
+}
+
+impl<C: Config> Replay<C>
+where
+    C::Input: bytemuck::Pod,
+{
+    /// Serializes this replay into bytes, which can be read back using [`Replay::from_bytes`].
+    ///
+    /// Inputs are stored as their raw bytes, so only read replays back on a platform with the
+    /// same endianness, using the same input type.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let num_players = self.num_players();
+        let mut bytes =
+            Vec::with_capacity(20 + self.frames.len() * num_players * (1 + size_of::<C::Input>()));
+
+        bytes.extend(self.fingerprint.to_le_bytes());
+        bytes.extend(self.start_frame.to_le_bytes());
+        bytes.extend((num_players as u32).to_le_bytes());
+        bytes.extend((self.frames.len() as u32).to_le_bytes());
+
+        for frame in &self.frames {
+            for (input, status) in frame {
+                bytes.push(match status {
+                    InputStatus::Confirmed => 0,
+                    InputStatus::Predicted => 1,
+                    InputStatus::Disconnected => 2,
+                });
+                bytes.extend_from_slice(bytemuck::bytes_of(input));
+            }
+        }
+
+        bytes
+    }
+
+    /// Deserializes a replay written by [`Replay::to_bytes`], checking it was recorded with the
+    /// provided rollback setup, usually the [`RollbackRegistrationFingerprint`] resource.
+    ///
+    /// Returns [`ReplayError::RegistrationMismatch`] if the replay was recorded with different
+    /// rolled back types or [versions](`crate::GgrsApp::set_rollback_version`), and
+    /// [`ReplayError::Malformed`] if the bytes are not a valid replay for this input type.
+    pub fn from_bytes(
+        bytes: &[u8],
+        registration: &RollbackRegistrationFingerprint,
+    ) -> Result<Self, ReplayError> {
+        let read = |offset: usize| -> Result<[u8; 4], ReplayError> {
+            bytes
+                .get(offset..offset + 4)
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or(ReplayError::Malformed)
+        };
+
+        let fingerprint = bytes
+            .get(..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ReplayError::Malformed)?;
+
+        let expected = registration.fingerprint();
+
+        if fingerprint != expected {
+            return Err(ReplayError::RegistrationMismatch {
+                expected,
+                found: fingerprint,
+            });
+        }
+
+        let start_frame = i32::from_le_bytes(read(8)?);
+        let num_players = u32::from_le_bytes(read(12)?) as usize;
+        let len = u32::from_le_bytes(read(16)?) as usize;
+
+        let stride = 1 + size_of::<C::Input>();
+        let body = &bytes[20..];
+
+        // every recorded frame holds at least one input, so it cannot claim more than the body
+        if (num_players == 0 && len > 0) || len > body.len() {
+            return Err(ReplayError::Malformed);
+        }
+
+        let expected_len = len
+            .checked_mul(num_players)
+            .and_then(|inputs| inputs.checked_mul(stride));
+
+        if expected_len != Some(body.len()) {
+            return Err(ReplayError::Malformed);
+        }
+
+        let mut inputs = body.chunks_exact(stride).map(|chunk| {
+            let status = match chunk[0] {
+                0 => InputStatus::Confirmed,
+                1 => InputStatus::Predicted,
+                2 => InputStatus::Disconnected,
+                _ => return Err(ReplayError::Malformed),
+            };
+
+            Ok((bytemuck::pod_read_unaligned(&chunk[1..]), status))
+        });
+
+        let frames = (0..len)
+            .map(|_| inputs.by_ref().take(num_players).collect())
+            .collect::<Result<_, ReplayError>>()?;
+
+        Ok(Self {
+            fingerprint,
+            start_frame,
+            frames,
+        })
+    }
+}
+
+/// A [`Resource`] which, while present, records the inputs of every player for each frame
+/// advanced by the current [`Session`](`crate::Session`), such as to build a [`Replay`] of a
+/// [`P2PSession`](`ggrs::P2PSession`).
+///
+/// Unlike the [`LocalInputs`](`crate::LocalInputs`), this covers the inputs of remote players as
+/// well. Inputs of frames which are re-advanced after a rollback replace the predicted ones, and
+/// only frames up to the [`ConfirmedFrameCount`] are included in the [`Replay`], so it holds
+/// exactly the inputs every peer agreed on.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, close_session, ReplayRecorder};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// # let mut app = App::new();
+/// app.insert_resource(ReplayRecorder::<MyConfig>::default());
+///
+/// fn end_match(world: &mut World) {
+///     let replay = world.resource::<ReplayRecorder<MyConfig>>().replay();
+///     close_session::<MyConfig>(world);
+///
+///     std::fs::write("match.replay", replay.to_bytes()).ok();
+/// }
+/// ```
+#[derive(Resource)]
+pub struct ReplayRecorder<C: Config> {
+    fingerprint: u64,
+    start_frame: Option<i32>,
+    frames: Vec<Vec<(C::Input, InputStatus)>>,
+    confirmed_frame: i32,
+}
+
+impl<C: Config> Default for ReplayRecorder<C> {
+    fn default() -> Self {
+        Self {
+            fingerprint: RollbackRegistrationFingerprint::default().fingerprint(),
+            start_frame: None,
+            frames: default(),
+            confirmed_frame: i32::MIN,
+        }
+    }
+}
+
+impl<C: Config> ReplayRecorder<C> {
+    /// The recorded inputs of every confirmed frame.
+    pub fn replay(&self) -> Replay<C> {
+        let start_frame = self.start_frame.unwrap_or_default();
+        let confirmed = self
+            .confirmed_frame
+            .saturating_sub(start_frame)
+            .saturating_add(1);
+        let len = usize::try_from(confirmed)
+            .unwrap_or_default()
+            .min(self.frames.len());
+
+        Replay {
+            fingerprint: self.fingerprint,
+            start_frame,
+            frames: self.frames[..len].to_vec(),
+        }
+    }
+
+    /// Records the inputs used to advance from the provided frame, discarding any inputs
+    /// recorded for later frames, as they are about to be re-advanced.
+    fn record(&mut self, frame: i32, inputs: &[(C::Input, InputStatus)], confirmed_frame: i32) {
+        let start_frame = *self.start_frame.get_or_insert(frame);
+
+        let Ok(index) = usize::try_from(frame - start_frame) else {
+            return;
+        };
+
+        self.frames.truncate(index);
+
+        if self.frames.len() == index {
+            self.frames.push(inputs.to_vec());
+        }
+
+        self.confirmed_frame = self.confirmed_frame.max(confirmed_frame);
+    }
+}
+
+/// Records the inputs about to be advanced with into the [`ReplayRecorder`], if any.
+pub(crate) fn record_replay_inputs<C: Config>(
+    world: &mut World,
+    inputs: &[(C::Input, InputStatus)],
+) {
+    let frame = world
+        .get_resource::<RollbackFrameCount>()
+        .map(|frame| frame.0)
+        .unwrap_or_default();
+    let confirmed_frame = world
+        .get_resource::<ConfirmedFrameCount>()
+        .map_or(-1, |confirmed| confirmed.0);
+
+    let fingerprint = world
+        .get_resource::<RollbackRegistrationFingerprint>()
+        .map(RollbackRegistrationFingerprint::fingerprint)
+        .unwrap_or_default();
+
+    if let Some(mut recorder) = world.get_resource_mut::<ReplayRecorder<C>>() {
+        if recorder.start_frame.is_none() {
+            recorder.fingerprint = fingerprint;
+        }
+
+        recorder.record(frame, inputs, confirmed_frame);
+    }
+}
+
+/// Plays back a [`Replay`] as a [`Session`](`crate::Session`), advancing one recorded frame per
+/// step without any network connection. No rollback ever occurs, so no snapshots are taken.
+///
+/// The [`World`] must hold the state of the [`start_frame`](`Replay::start_frame`) of the replay,
+/// such as a freshly set up match. Use [`start_session_at_frame`](`crate::start_session_at_frame`)
+/// to insert replays which do not start at frame `0`. Once every frame has been advanced, the
+/// session stays [finished](`ReplaySession::is_finished`) without advancing further.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, Replay, ReplaySession, RollbackRegistrationFingerprint};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn watch_replay(mut commands: Commands, registration: Res<RollbackRegistrationFingerprint>) {
+///     let bytes = std::fs::read("match.replay").unwrap_or_default();
+///
+///     match Replay::<MyConfig>::from_bytes(&bytes, &registration) {
+///         Ok(replay) => commands.insert_resource(Session::Replay(ReplaySession::new(replay))),
+///         Err(error) => warn!("{error}"),
+///     }
+/// }
+/// ```
+pub struct ReplaySession<C: Config> {
+    replay: Replay<C>,
+    next: usize,
+}
+
+impl<C: Config> ReplaySession<C> {
+    /// Creates a session playing back the provided replay from its first frame.
+    pub fn new(replay: Replay<C>) -> Self {
+        Self { replay, next: 0 }
+    }
+
+    /// The replay being played back.
+    pub fn replay(&self) -> &Replay<C> {
+        &self.replay
+    }
+
+    /// The amount of players in the replay.
+    pub fn num_players(&self) -> usize {
+        self.replay.num_players()
+    }
+
+    /// The frame the next recorded inputs advance from.
+    pub fn current_frame(&self) -> i32 {
+        self.replay.start_frame + self.next as i32
+    }
+
+    /// Returns `true` once every recorded frame has been advanced.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.replay.len()
+    }
+
+    /// Takes the inputs of the next recorded frame, if any remain.
+    pub(crate) fn next_inputs(&mut self) -> Option<Vec<(C::Input, InputStatus)>> {
+        let inputs = self.replay.frames.get(self.next)?.clone();
+        self.next += 1;
+        Some(inputs)
+    }
+}
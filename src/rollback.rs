@@ -1,14 +1,27 @@
 use bevy::utils::HashMap;
 use bevy::{
-    ecs::system::{EntityCommand, EntityCommands},
+    ecs::{
+        archetype::Archetypes,
+        component::Components,
+        entity::Entities,
+        system::{EntityCommand, EntityCommands, SystemParam},
+    },
     prelude::*,
 };
+use std::hash::Hash;
+
+use crate::EntityMappingAudit;
 
 /// This component flags an entity as being included in the rollback save/load schedule with GGRS.
 ///
 /// You must use the [`AddRollbackCommand`] when spawning an entity to add this component. Alternatively,
 /// you can use the `add_rollback()` extension method provided by [`AddRollbackCommandExtension`].
-#[derive(Component, Hash, PartialEq, Eq, Clone, Copy, Debug)]
+///
+/// This is the stable identity of an entity across frames and peers. Rolling back may despawn and
+/// recreate entities, after which their [`Entity`] id can differ, both from before the rollback and
+/// between peers. Never keep a raw [`Entity`] across frames, unless it is mapped after loading.
+/// See [`EntityMappingAudit`](`crate::EntityMappingAudit`) for details.
+#[derive(Component, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 pub struct Rollback(Entity);
 
 impl Rollback {
@@ -18,6 +31,43 @@ impl Rollback {
     }
 }
 
+/// A [`Component`] which identifies an entity stably across peers and rollbacks, used to match
+/// entities to their snapshots. This is implemented for [`Rollback`], which is used by default.
+///
+/// Projects with an existing stable id component can implement this for it, and snapshot
+/// components keyed by that id instead using a [`ComponentSnapshotPlugin`](`crate::ComponentSnapshotPlugin`).
+/// Entities matched by such a key are never spawned or despawned on rollback, and are always
+/// considered within any [`RollbackScope`](`crate::RollbackScope`).
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ComponentSnapshotPlugin, CopyStrategy, RollbackKey};
+/// #
+/// #[derive(Component, Clone, Copy, PartialEq, Eq, Hash)]
+/// struct NetworkId(u32);
+///
+/// impl RollbackKey for NetworkId {}
+///
+/// #[derive(Component, Clone, Copy)]
+/// struct Health(u32);
+///
+/// # let mut app = App::new();
+/// app.add_plugins(ComponentSnapshotPlugin::<CopyStrategy<Health>, NetworkId>::default());
+/// ```
+pub trait RollbackKey: Component + Copy + Eq + Hash {
+    /// The [`Rollback`] this key corresponds to, if any.
+    fn as_rollback(&self) -> Option<&Rollback> {
+        None
+    }
+}
+
+impl RollbackKey for Rollback {
+    fn as_rollback(&self) -> Option<&Rollback> {
+        Some(self)
+    }
+}
+
 /// An [`EntityCommand`] which adds a [`Rollback`] component to an entity.
 pub struct AddRollbackCommand;
 
@@ -82,6 +132,83 @@ impl RollbackOrdered {
             .expect("Rollback requested was not created using AddRollbackCommand!")
     }
 
+    /// Collects the provided items into a [`Vec`] sorted by the order of their [`Rollback`],
+    /// which is identical across peers and rollbacks, unlike the iteration order of a [`Query`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any [`Rollback`] was not created using [`AddRollbackCommand`].
+    pub fn sorted<'a, T>(
+        &self,
+        items: impl IntoIterator<Item = (&'a Rollback, T)>,
+    ) -> Vec<(Rollback, T)> {
+        let mut items = items
+            .into_iter()
+            .map(|(&rollback, item)| (rollback, item))
+            .collect::<Vec<_>>();
+
+        // Rollback orders are unique, so an unstable sort is still deterministic
+        items.sort_unstable_by_key(|&(rollback, _)| self.order(rollback));
+
+        items
+    }
+
+    /// Calls `f` once for every unordered pair of the provided items, in an order which is
+    /// identical across peers and rollbacks. Within each pair, the first item is the one with the
+    /// lower [`Rollback`] order.
+    ///
+    /// Any computation over entities whose result depends on the order it is performed in, such
+    /// as resolving collisions between pairs, must iterate in a stable order like this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any [`Rollback`] was not created using [`AddRollbackCommand`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, RollbackOrdered};
+    /// #
+    /// #[derive(Component, Clone, Copy)]
+    /// struct Position(Vec2);
+    ///
+    /// const RADIUS: f32 = 1.;
+    ///
+    /// fn resolve_collisions(
+    ///     mut query: Query<(&Rollback, &mut Position)>,
+    ///     order: Res<RollbackOrdered>,
+    /// ) {
+    ///     order.for_each_pair(query.iter_mut(), |(_, a), (_, b)| {
+    ///         let offset = b.0 - a.0;
+    ///         let overlap = 2. * RADIUS - offset.length();
+    ///
+    ///         if overlap > 0. {
+    ///             let push = offset.normalize_or_zero() * overlap / 2.;
+    ///             a.0 -= push;
+    ///             b.0 += push;
+    ///         }
+    ///     });
+    /// }
+    /// # let mut app = App::new();
+    /// # app.add_systems(GgrsSchedule, resolve_collisions);
+    /// ```
+    pub fn for_each_pair<'a, T>(
+        &self,
+        items: impl IntoIterator<Item = (&'a Rollback, T)>,
+        mut f: impl FnMut((Rollback, &mut T), (Rollback, &mut T)),
+    ) {
+        let mut items = self.sorted(items);
+
+        for index in 1..items.len() {
+            let (head, tail) = items.split_at_mut(index);
+            let (first_rollback, first) = &mut head[index - 1];
+
+            for (second_rollback, second) in tail.iter_mut() {
+                f((*first_rollback, first), (*second_rollback, second));
+            }
+        }
+    }
+
     /// Get the number of registered [`Rollback`] entities.
     pub fn len(&self) -> usize {
         self.order.len()
@@ -92,3 +219,92 @@ impl RollbackOrdered {
         self.order.is_empty()
     }
 }
+
+/// A [`SystemParam`] listing every entity participating in rollback, along with its [`Rollback`]
+/// and the rolled back components it currently holds, such as for an in-game rollback inspector.
+///
+/// Entities are listed in the order of their [`Rollback`], which is identical across peers.
+/// Only read access to the [`Rollback`] components is required, so this can be used alongside
+/// any other system parameters.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// fn inspect_rollback(entities: RollbackEntities) {
+///     for (entity, rollback) in entities.iter() {
+///         info!("{entity:?} ({rollback:?}): {:?}", entities.components(entity));
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_systems(Update, inspect_rollback);
+/// ```
+#[derive(SystemParam)]
+pub struct RollbackEntities<'w, 's> {
+    query: Query<'w, 's, (Entity, &'static Rollback)>,
+    ordered: Option<Res<'w, RollbackOrdered>>,
+    audit: Option<Res<'w, EntityMappingAudit>>,
+    entities: &'w Entities,
+    archetypes: &'w Archetypes,
+    components: &'w Components,
+}
+
+impl<'w, 's> RollbackEntities<'w, 's> {
+    /// Iterate over every rollback entity with its [`Rollback`], in the order of the [`Rollback`].
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Rollback)> + '_ {
+        let mut entities = self
+            .query
+            .iter()
+            .map(|(entity, &rollback)| (entity, rollback))
+            .collect::<Vec<_>>();
+
+        if let Some(ordered) = self.ordered.as_deref() {
+            entities.sort_unstable_by_key(|&(_, rollback)| ordered.order(rollback));
+        }
+
+        entities.into_iter()
+    }
+
+    /// The [`Rollback`] of the provided entity, if it participates in rollback.
+    pub fn get(&self, entity: Entity) -> Option<Rollback> {
+        self.query.get(entity).ok().map(|(_, &rollback)| rollback)
+    }
+
+    /// The amount of rollback entities.
+    pub fn len(&self) -> usize {
+        self.query.iter().count()
+    }
+
+    /// Returns `true` if there are no rollback entities.
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    /// The names of all rolled back component types the provided entity currently holds, in
+    /// sorted order. Only types registered for rollback, such as using
+    /// [`GgrsApp::rollback_component_with_clone`](`crate::GgrsApp::rollback_component_with_clone`),
+    /// are included.
+    pub fn components(&self, entity: Entity) -> Vec<&'static str> {
+        let (Some(audit), Some(location)) = (self.audit.as_deref(), self.entities.get(entity))
+        else {
+            return Vec::new();
+        };
+
+        let Some(archetype) = self.archetypes.get(location.archetype_id) else {
+            return Vec::new();
+        };
+
+        let mut names = audit
+            .rolled_back()
+            .filter_map(|(type_id, name)| {
+                let id = self.components.get_id(type_id)?;
+                archetype.contains(id).then_some(name)
+            })
+            .collect::<Vec<_>>();
+
+        names.sort_unstable();
+        names
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::{
+    ecs::{
+        entity::Entities,
+        system::{CommandQueue, Deferred, SystemBuffer, SystemMeta, SystemParam},
+    },
+    prelude::*,
+};
+
+use crate::{AddRollbackCommandExtension, AdvanceWorld, AdvanceWorldSet};
+
+/// A [`SystemParam`] for spawning and despawning rollback entities from within the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`), and the recommended way to create them.
+///
+/// Unlike [`Commands`], whose effects are applied whenever the schedule next flushes deferred
+/// operations, these commands are all applied together once the [`GgrsSchedule`](`crate::GgrsSchedule`)
+/// has completed, right before the frame is saved. The saved snapshot therefore always includes
+/// the spawned entities, so rolling back reconstructs them, and no system advancing the frame
+/// observes them until the following frame.
+///
+/// Every spawned entity is given a [`Rollback`](`crate::Rollback`) as part of the same flush, in
+/// the order of the systems in the schedule, and then in the order of the spawns within each
+/// system. As this order does not depend on which systems happened to run first, the
+/// [`RollbackOrdered::order`](`crate::RollbackOrdered::order`) of every spawned entity is
+/// identical on every peer, and when re-simulating a frame.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, RollbackCommands};
+/// #
+/// #[derive(Component, Clone, Copy)]
+/// struct Bullet {
+///     lifetime: u32,
+/// }
+///
+/// fn fire(mut commands: RollbackCommands) {
+///     commands.spawn(Bullet { lifetime: 60 });
+/// }
+///
+/// fn expire(mut commands: RollbackCommands, mut bullets: Query<(Entity, &mut Bullet)>) {
+///     for (entity, mut bullet) in &mut bullets {
+///         bullet.lifetime -= 1;
+///
+///         if bullet.lifetime == 0 {
+///             commands.despawn(entity);
+///         }
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default());
+/// app.rollback_component_with_copy::<Bullet>()
+///     .add_systems(GgrsSchedule, (fire, expire).chain());
+/// ```
+#[derive(SystemParam)]
+pub struct RollbackCommands<'w, 's> {
+    queue: Deferred<'s, RollbackCommandQueue>,
+    entities: &'w Entities,
+}
+
+impl<'w, 's> RollbackCommands<'w, 's> {
+    /// Queues spawning an entity with the provided [`Bundle`] and a [`Rollback`](`crate::Rollback`),
+    /// returning the [`Entity`] it will be spawned as.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        self.commands().spawn(bundle).add_rollback().id()
+    }
+
+    /// Queues inserting the provided [`Bundle`] into an entity.
+    pub fn insert(&mut self, entity: Entity, bundle: impl Bundle) -> &mut Self {
+        self.commands().entity(entity).insert(bundle);
+        self
+    }
+
+    /// Queues removing the [`Bundle`] `B` from an entity.
+    pub fn remove<B: Bundle>(&mut self, entity: Entity) -> &mut Self {
+        self.commands().entity(entity).remove::<B>();
+        self
+    }
+
+    /// Queues despawning an entity.
+    pub fn despawn(&mut self, entity: Entity) -> &mut Self {
+        self.commands().entity(entity).despawn();
+        self
+    }
+
+    /// Queues despawning an entity and all of its descendants.
+    pub fn despawn_recursive(&mut self, entity: Entity) -> &mut Self {
+        self.commands().entity(entity).despawn_recursive();
+        self
+    }
+
+    /// The [`Commands`] writing to this queue, for any operation not covered above. These are
+    /// applied along with all other [`RollbackCommands`].
+    pub fn commands(&mut self) -> Commands<'_, '_> {
+        Commands::new_from_entities(&mut self.queue.0, self.entities)
+    }
+}
+
+/// The commands written by a single system using [`RollbackCommands`], moved into the
+/// [`PendingRollbackCommands`] whenever that system's deferred operations are applied.
+#[derive(Default)]
+pub struct RollbackCommandQueue(CommandQueue);
+
+impl SystemBuffer for RollbackCommandQueue {
+    fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+        world
+            .get_resource_or_insert_with::<PendingRollbackCommands>(default)
+            .0
+            .append(&mut self.0);
+    }
+}
+
+/// The [`RollbackCommands`] of the current frame, waiting to be applied once the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`) has completed.
+#[derive(Resource, Default)]
+pub struct PendingRollbackCommands(CommandQueue);
+
+impl PendingRollbackCommands {
+    /// Applies all pending [`RollbackCommands`].
+    pub fn apply(world: &mut World) {
+        let Some(mut pending) = world.get_resource_mut::<PendingRollbackCommands>() else {
+            return;
+        };
+
+        let mut queue = std::mem::take(&mut pending.0);
+        queue.apply(world);
+    }
+}
+
+/// A [`Plugin`] which applies all [`RollbackCommands`] of a frame after the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`), and before the frame is saved.
+pub struct RollbackCommandsPlugin;
+
+impl Plugin for RollbackCommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingRollbackCommands>().add_systems(
+            AdvanceWorld,
+            PendingRollbackCommands::apply
+                .after(AdvanceWorldSet::Main)
+                .before(AdvanceWorldSet::Last),
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::{
+    ecs::{
+        entity::EntityHashMap,
+        system::{Command, EntityCommand},
+    },
+    prelude::*,
+};
+
+use crate::AddRollbackCommand;
+
+/// A [`Command`] which spawns the contents of a [`DynamicScene`] immediately, adding a
+/// [`Rollback`](`crate::Rollback`) component to every spawned [`Entity`].
+///
+/// Scenes spawned through a [`DynamicSceneBundle`] are instantiated by the [`SceneSpawner`]
+/// outside of the [`GgrsSchedule`](`crate::GgrsSchedule`), so their entities are neither
+/// rolled back nor spawned deterministically. Instead, this writes the scene into the [`World`]
+/// when the command is applied, within the frame being simulated, and assigns [`Rollback`](`crate::Rollback`)
+/// ids in the order entities appear in the scene.
+///
+/// The scene must already be loaded. Load any scenes required by the simulation before starting
+/// the [`Session`](`crate::Session`), as asset loading is not deterministic.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, SpawnRollbackScene};
+/// #
+/// #[derive(Resource)]
+/// struct BoxPrefab(Handle<DynamicScene>);
+///
+/// fn spawn_box(mut commands: Commands, prefab: Res<BoxPrefab>) {
+///     commands.add(SpawnRollbackScene(prefab.0.clone()));
+/// }
+/// ```
+pub struct SpawnRollbackScene(pub Handle<DynamicScene>);
+
+impl Command for SpawnRollbackScene {
+    fn apply(self, world: &mut World) {
+        let mut entity_map = EntityHashMap::default();
+
+        let spawned = world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            let Some(scene) = scenes.get(&self.0) else {
+                error!("Could not spawn rollback scene: scene is not loaded");
+                return Vec::new();
+            };
+
+            if let Err(error) = scene.write_to_world(world, &mut entity_map) {
+                error!("Could not spawn rollback scene: {error}");
+                return Vec::new();
+            }
+
+            // The entity map is unordered, so the order of the scene itself is used instead
+            scene
+                .entities
+                .iter()
+                .filter_map(|entity| entity_map.get(&entity.entity).copied())
+                .collect()
+        });
+
+        for entity in spawned {
+            AddRollbackCommand.apply(entity, world);
+        }
+    }
+}
@@ -1,14 +1,42 @@
+#[cfg(feature = "spectator")]
+use crate::SpectatorCatchup;
 use crate::{
-    AdvanceWorld, Checksum, ConfirmedFrameCount, FixedTimestepData, LoadWorld, LocalInputs,
-    LocalPlayers, MaxPredictionWindow, PlayerInputs, ReadInputs, RollbackFrameCount,
-    RollbackFrameRate, SaveWorld, Session,
+    input::read_local_inputs, replay::record_replay_inputs, AdvanceWorld, BevyGgrsError, Checksum,
+    ConfirmedFrameCount, DisableSnapshots, FixedTimestepData, FrameConfirmed, FramePacingSmoothing,
+    GgrsActive, GgrsComponentSnapshots, GgrsInitSchedule, GgrsResumeBehavior, GgrsStatus,
+    InitialChecksum, InputSource, InterpolationAlpha, LoadWorld, LocalInputs, LocalPlayers,
+    LocalPlayersChanged, LockstepStall, MaxPredictionWindow, NetworkInterruption,
+    NetworkInterruptions, NetworkPollCadence, PlayerInputs, PlayerKind, PlayerRoster,
+    PredictionDepth, PredictionThresholdBehavior, ReadInputs, ReplaySession, RewindPreview,
+    RollbackFrameCount, RollbackFrameRate, RollbackTimings, SaveWorld, Seekable, Session,
+    SessionConfig, SessionError, SessionEvent, SessionReplaced, SessionRequest, SessionRequests,
+    SessionStartFrame, SessionStats, SessionType, SimulationPacing, SnapshotChecksumVerification,
+    SnapshotInterval, SnapshotIntervalInputs, SnapshotStorages, SpectatorLag,
+    UnregisteredMutationCheck, WaitRecommendation,
 };
-use bevy::{prelude::*, utils::Duration};
+use bevy::{
+    prelude::*,
+    utils::{Duration, HashMap, Instant},
+};
+#[cfg(feature = "spectator")]
+use ggrs::SpectatorSession;
+#[cfg(feature = "synctest")]
+use ggrs::SyncTestSession;
 use ggrs::{
-    Config, GgrsError, GgrsRequest, P2PSession, SessionState, SpectatorSession, SyncTestSession,
+    Config, GgrsError, GgrsEvent, GgrsRequest, InputStatus, P2PSession, PlayerHandle, SessionState,
 };
 
 pub(crate) fn run_ggrs_schedules<T: Config>(world: &mut World) {
+    let inactive = world
+        .get_resource::<GgrsActive>()
+        .is_some_and(|active| !active.0);
+
+    if inactive || world.contains_resource::<RewindPreview>() {
+        run_inactive::<T>(world);
+        return;
+    }
+
+    let started = Instant::now();
     let framerate: usize = **world.get_resource_or_insert_with::<RollbackFrameRate>(default);
 
     let mut time_data = world
@@ -20,27 +48,158 @@ pub(crate) fn run_ggrs_schedules<T: Config>(world: &mut World) {
         .expect("Time resource not found, did you remove it?")
         .delta();
 
+    let pacing = world
+        .get_resource::<SimulationPacing>()
+        .copied()
+        .unwrap_or_default();
+
     let mut fps_delta = 1. / framerate as f64;
     if time_data.run_slow {
         fps_delta *= 1.1;
     }
-    time_data.accumulator = time_data.accumulator.saturating_add(delta);
+    time_data.accumulator = time_data
+        .accumulator
+        .saturating_add(pacing.scale_delta(delta, framerate));
+
+    let poll_interval = world
+        .get_resource::<NetworkPollCadence>()
+        .map(|cadence| cadence.interval)
+        .unwrap_or_default();
+
+    // poll remotes and send responses, unless polled recently and no frame is due
+    time_data.since_poll = time_data.since_poll.saturating_add(delta);
+    let frame_due = time_data.accumulator.as_secs_f64() > fps_delta;
+    let poll = frame_due || time_data.since_poll >= poll_interval;
+
+    if poll {
+        time_data.since_poll = Duration::ZERO;
+    }
+
+    let mut events = Vec::new();
+    let mut caught_up = true;
+
+    let session_type = world
+        .get_resource::<Session<T>>()
+        .map(Session::session_type)
+        .unwrap_or_default();
+
+    let previous_type = *world.get_resource_or_insert_with::<SessionType>(default);
+
+    if previous_type != session_type {
+        world.insert_resource(session_type);
+        world.send_event(SessionReplaced {
+            old: previous_type,
+            new: session_type,
+        });
+    }
+
+    // GGRS time synchronization keeps the frame rate the session was built with
+    if session_type == SessionType::None || previous_type != session_type {
+        time_data.session_framerate = None;
+        time_data.checked_framerate = None;
+    }
+
+    if session_type != SessionType::None {
+        let session_framerate = *time_data.session_framerate.get_or_insert_with(|| {
+            world
+                .get_resource::<SessionConfig>()
+                .map_or(framerate, |config| config.fps)
+        });
+
+        if session_framerate != framerate && time_data.checked_framerate != Some(framerate) {
+            warn!(
+                "The RollbackFrameRate is {framerate}, but the Session was built with \
+                {session_framerate} fps. GGRS time synchronization will not match the rate frames \
+                are advanced at, and peers will desync unless they all use the same frame rate. \
+                Rebuild the Session using RollbackFrameRate::configure."
+            );
+        }
+
+        time_data.checked_framerate = Some(framerate);
+    }
+
+    let has_session = session_type != SessionType::None;
+
+    if has_session && !time_data.had_session {
+        validate_session_config::<T>(world);
+        run_init_schedule(world);
+    }
+
+    let mut roster = None;
 
-    // no matter what, poll remotes and send responses
     if let Some(mut session) = world.get_resource_mut::<Session<T>>() {
         match &mut *session {
-            Session::P2P(session) => {
+            Session::P2P(session) if poll => {
                 session.poll_remote_clients();
+                events.extend(session.events());
+                caught_up = session.frames_ahead() <= 0;
+                roster = Some(player_roster_updates(session, &events));
             }
-            Session::Spectator(session) => {
+            Session::P2P(session) => caught_up = session.frames_ahead() <= 0,
+            #[cfg(feature = "spectator")]
+            Session::Spectator(session) if poll => {
                 session.poll_remote_clients();
+                events.extend(session.events());
             }
+            #[allow(unreachable_patterns)]
             _ => {}
         }
     }
 
+    match roster {
+        Some((kinds, addresses)) => {
+            let mut roster = world.get_resource_or_insert_with(PlayerRoster::<T>::default);
+
+            for (handle, kind) in kinds {
+                roster.set_kind(handle, kind);
+            }
+
+            for (handle, addr) in addresses {
+                roster.set_addr(handle, addr);
+            }
+        }
+        // a skipped poll has no updates, so the roster is kept as is
+        None if poll => {
+            world.remove_resource::<PlayerRoster<T>>();
+        }
+        None => {}
+    }
+
+    if let Some(mut interruptions) = world.get_resource_mut::<NetworkInterruptions<T>>() {
+        interruptions.tick(delta);
+    }
+
+    handle_events(world, events, caught_up);
+
     // if we accumulated enough time, do steps
+    let mut steps = 0;
     while time_data.accumulator.as_secs_f64() > fps_delta {
+        if pacing
+            .max_catchup_frames
+            .is_some_and(|max_frames| steps >= max_frames)
+        {
+            // drop the time left over, rather than falling further behind on the next update
+            time_data.accumulator = Duration::from_secs_f64(fps_delta);
+            break;
+        }
+
+        let elapsed = started.elapsed();
+
+        if steps > 0
+            && time_data
+                .frame_budget
+                .is_some_and(|budget| elapsed > budget)
+        {
+            error!(
+                "Advancing {steps} frame(s) took {elapsed:?}, exceeding the frame budget. \
+                Dropping the remaining time to avoid falling further behind."
+            );
+            time_data.accumulator = Duration::from_secs_f64(fps_delta);
+            break;
+        }
+
+        steps += 1;
+
         // decrease accumulator
         time_data.accumulator = time_data
             .accumulator
@@ -49,37 +208,758 @@ pub(crate) fn run_ggrs_schedules<T: Config>(world: &mut World) {
         // depending on the session type, doing a single update looks a bit different
         let session = world.remove_resource::<Session<T>>();
         match session {
+            #[cfg(feature = "synctest")]
             Some(Session::SyncTest(s)) => run_synctest::<T>(world, s),
             Some(Session::P2P(session)) => {
-                // if we are ahead, run slow
-                time_data.run_slow = session.frames_ahead() > 0;
+                // if we are too far ahead, run slow
+                time_data.run_slow = session.frames_ahead() > time_data.run_slow_threshold;
+
+                let skipped = run_p2p(world, session);
+
+                let retry = world
+                    .get_resource::<PredictionThresholdBehavior>()
+                    .is_some_and(|&behavior| behavior == PredictionThresholdBehavior::Retry);
 
-                run_p2p(world, session);
+                if skipped && retry {
+                    // keep the time of this step, and retry it on the next update
+                    time_data.accumulator = time_data
+                        .accumulator
+                        .saturating_add(Duration::from_secs_f64(fps_delta));
+                    steps -= 1;
+                    break;
+                }
             }
+            #[cfg(feature = "spectator")]
             Some(Session::Spectator(s)) => run_spectator(world, s),
-            _ => {
-                // No session has been started yet, reset time data and snapshots
+            Some(Session::Replay(s)) => run_replay(world, s),
+            None => {
+                // No session has been started yet, don't build up time
                 time_data.accumulator = Duration::ZERO;
                 time_data.run_slow = false;
-                world.insert_resource(LocalPlayers::default());
-                world.insert_resource(RollbackFrameCount(0));
-                world.insert_resource(ConfirmedFrameCount(-1));
-                world.insert_resource(MaxPredictionWindow(8));
             }
         }
     }
 
+    let smoothing = world.get_resource::<FramePacingSmoothing>().copied();
+    let alpha = update_interpolation_alpha(&mut time_data, smoothing, delta, fps_delta, steps);
+    world
+        .get_resource_or_insert_with::<InterpolationAlpha>(default)
+        .0 = alpha;
+
+    // Only reset once a session has ended, so state prepared before starting one is kept
+    if time_data.had_session && !has_session {
+        world.insert_resource(InitialChecksum(None));
+        world.insert_resource(LocalPlayers::default());
+        world.insert_resource(RollbackFrameCount(0));
+        world.insert_resource(ConfirmedFrameCount(-1));
+        world.insert_resource(SessionStartFrame(0));
+        world.insert_resource(MaxPredictionWindow(8));
+        world.insert_resource(PredictionDepth(0));
+        world.insert_resource(SpectatorLag(0));
+        world.insert_resource(SessionStats::default());
+        world.insert_resource(NetworkInterruptions::<T>::default());
+        world.insert_resource(GgrsStatus::default());
+    }
+
+    crate::status::update_status::<T>(world, delta);
+
+    time_data.had_session = has_session;
+
     world.insert_resource(time_data);
 }
 
+/// Polls the [`Session`] while [`GgrsActive`] is `false`, without advancing it.
+fn run_inactive<T: Config>(world: &mut World) {
+    match world.get_resource_mut::<Session<T>>().as_deref_mut() {
+        Some(Session::P2P(session)) => session.poll_remote_clients(),
+        #[cfg(feature = "spectator")]
+        Some(Session::Spectator(session)) => session.poll_remote_clients(),
+        _ => {}
+    }
+
+    let reset = world
+        .get_resource::<GgrsResumeBehavior>()
+        .is_some_and(|&behavior| behavior == GgrsResumeBehavior::Reset);
+
+    if reset {
+        if let Some(mut time_data) = world.get_resource_mut::<FixedTimestepData>() {
+            time_data.clear_accumulator();
+        }
+    }
+}
+
+/// Runs exactly `frames` steps of the [`Session`] in the provided [`World`], independent of
+/// wall-clock time and the [`RollbackFrameRate`], returning the total time taken.
+///
+/// Instead of running the [`ReadInputs`] schedule or calling the [`InputSource`], `inputs` is
+/// called with the index of each step to provide the [`LocalInputs`] for that step. Otherwise, this uses the same save, load and
+/// advance paths as regular operation, making it suitable for benchmarking the cost of rollback.
+/// A [`SyncTestSession`] is recommended for this, as it will save and load every frame.
+///
+/// # Panics
+///
+/// Panics if no [`Session`] is present in the [`World`].
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{prelude::*, utils::HashMap};
+/// # use bevy_ggrs::{prelude::*, bench_advance};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default())
+///     .insert_resource(session);
+///
+/// let elapsed = bench_advance::<GgrsConfig<MyInputType>>(&mut app.world, 100, |_| {
+///     HashMap::from([(0, 0)])
+/// });
+///
+/// info!("100 frames took {elapsed:?}");
+/// # }
+/// ```
+pub fn bench_advance<T: Config>(
+    world: &mut World,
+    frames: usize,
+    mut inputs: impl FnMut(usize) -> HashMap<PlayerHandle, T::Input>,
+) -> Duration {
+    // Swap out the ReadInputs schedule and any InputSource, as inputs are provided directly
+    let read_inputs = world
+        .resource_mut::<Schedules>()
+        .insert(Schedule::new(ReadInputs));
+    let input_source = world.remove_resource::<InputSource<T>>();
+
+    let start = Instant::now();
+
+    for frame in 0..frames {
+        world.insert_resource(LocalInputs::<T>(inputs(frame)));
+
+        match world.remove_resource::<Session<T>>() {
+            #[cfg(feature = "synctest")]
+            Some(Session::SyncTest(s)) => run_synctest::<T>(world, s),
+            Some(Session::P2P(s)) => {
+                run_p2p(world, s);
+            }
+            #[cfg(feature = "spectator")]
+            Some(Session::Spectator(s)) => run_spectator(world, s),
+            Some(Session::Replay(s)) => run_replay(world, s),
+            None => panic!("No GGRS Session found to advance. Did you insert one?"),
+        }
+    }
+
+    let elapsed = start.elapsed();
+
+    if let Some(read_inputs) = read_inputs {
+        world.resource_mut::<Schedules>().insert(read_inputs);
+    }
+
+    if let Some(input_source) = input_source {
+        world.insert_resource(input_source);
+    }
+
+    elapsed
+}
+
+/// Advances the [`Session`] in the provided [`World`] by exactly one step right away, regardless
+/// of wall-clock time, and clears the accumulator of the [`FixedTimestepData`]. Returns `false`
+/// if no [`Session`] is present.
+///
+/// The [`ReadInputs`] schedule is run as usual. Clearing the accumulator ensures the next frame is
+/// only advanced after a full frame of time has passed again, so tooling can step the simulation
+/// deterministically. As with writing the accumulator, doing so on a live [`P2PSession`] may
+/// stall or desync it.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, advance_frame};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn step_on_key(world: &mut World) {
+///     if world.resource::<ButtonInput<KeyCode>>().just_pressed(KeyCode::Period) {
+///         advance_frame::<MyConfig>(world);
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_systems(Update, step_on_key);
+/// ```
+pub fn advance_frame<T: Config>(world: &mut World) -> bool {
+    let advanced = match world.remove_resource::<Session<T>>() {
+        #[cfg(feature = "synctest")]
+        Some(Session::SyncTest(s)) => {
+            run_synctest::<T>(world, s);
+            true
+        }
+        Some(Session::P2P(s)) => {
+            run_p2p(world, s);
+            true
+        }
+        #[cfg(feature = "spectator")]
+        Some(Session::Spectator(s)) => {
+            run_spectator(world, s);
+            true
+        }
+        Some(Session::Replay(s)) => {
+            run_replay(world, s);
+            true
+        }
+        None => false,
+    };
+
+    if let Some(mut time_data) = world.get_resource_mut::<FixedTimestepData>() {
+        time_data.clear_accumulator();
+    }
+
+    advanced
+}
+
+/// Removes the current [`Session`], first disconnecting all remote players and spectators of a
+/// [`P2PSession`] and polling it one last time to flush any pending messages.
+///
+/// Peers are notified of the disconnect on a best-effort basis, as delivery over UDP is not
+/// guaranteed. Peers which miss it still detect the disconnect once their timeout elapses.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, close_session};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// #[derive(Resource)]
+/// struct MatchOver;
+///
+/// fn end_match(mut commands: Commands) {
+///     commands.add(close_session::<MyConfig>);
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_systems(Update, end_match.run_if(resource_exists::<MatchOver>));
+/// ```
+pub fn close_session<T: Config>(world: &mut World) {
+    match world.remove_resource::<Session<T>>() {
+        Some(Session::P2P(mut session)) => {
+            let handles = session
+                .remote_player_handles()
+                .into_iter()
+                .chain(session.spectator_handles());
+
+            for handle in handles {
+                // players which already disconnected can not be disconnected again
+                if let Err(error) = session.disconnect_player(handle) {
+                    debug!("Could not disconnect player {handle}: {error}");
+                }
+            }
+
+            session.poll_remote_clients();
+        }
+        #[cfg(feature = "spectator")]
+        Some(Session::Spectator(mut session)) => session.poll_remote_clients(),
+        #[cfg(feature = "synctest")]
+        Some(Session::SyncTest(_)) => {}
+        Some(Session::Replay(_)) => {}
+        None => {}
+    }
+}
+
+/// Promotes the local peer from spectator to player, replacing the current [`SpectatorSession`]
+/// with the provided [`P2PSession`] while preserving the state of the [`World`].
+///
+/// The [`SpectatorSession`] is first closed using [`close_session`]. As a new [`P2PSession`]
+/// starts from frame `0`, the [`RollbackFrameCount`] is reset to match, so [`Time<GgrsTime>`](`crate::GgrsTime`)
+/// restarts as well. The [`GgrsInitSchedule`] is not run again, and the [`SessionType`] changing
+/// raises a [`SessionReplaced`] event.
+///
+/// # Frame synchronization
+///
+/// GGRS sessions have fixed roles, so every existing player has to start a new [`P2PSession`]
+/// including the promoted player as well, and all peers must do so from an identical world:
+///
+/// 1. A player (such as the host) picks a switch frame, which must already be confirmed, and sends
+///    it to every peer, including the spectator.
+/// 2. Every peer stops advancing once its [`RollbackFrameCount`] reaches the switch frame. As a
+///    spectator only ever advances confirmed frames, its world is now identical to the players'.
+/// 3. Every player closes its session using [`close_session`], while the spectator calls this
+///    function. Compare the [`Checksum`] of the world before continuing, if desired.
+/// 4. Every peer starts advancing the new [`P2PSession`] from frame `0`.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, promote_spectator, ggrs::P2PSession};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn join_match(world: &mut World, session: P2PSession<MyConfig>) {
+///     // every peer has reached the agreed switch frame
+///     promote_spectator::<MyConfig>(world, session);
+/// }
+/// ```
+#[cfg(feature = "spectator")]
+pub fn promote_spectator<T: Config>(world: &mut World, session: P2PSession<T>) {
+    if !matches!(
+        world.get_resource::<Session<T>>(),
+        Some(Session::Spectator(_))
+    ) {
+        warn!("Promoting to a P2PSession, but the current Session is not a SpectatorSession");
+    }
+
+    close_session::<T>(world);
+
+    debug!(
+        "promoting spectator to player on frame {}",
+        world.resource::<RollbackFrameCount>().0
+    );
+
+    world.insert_resource(RollbackFrameCount(0));
+    world.insert_resource(ConfirmedFrameCount(-1));
+    world.insert_resource(SessionStartFrame(0));
+    world.insert_resource(SpectatorLag(0));
+    world.insert_resource(Session::P2P(session));
+}
+
+/// Inserts the provided [`Session`], counting its frames from `frame` instead of `0`. Use this to
+/// resume a match from a saved state, so frame-dependent logic, such as [`Time<GgrsTime>`](`crate::GgrsTime`),
+/// and the snapshots continue from the frame the state was saved on.
+///
+/// GGRS itself always starts from frame `0`, so the [`SessionStartFrame`] is added to every frame
+/// it reports. The world is expected to hold the state of `frame` already, which is saved as the
+/// first snapshot of the session. Any current [`Session`] is closed first using [`close_session`].
+///
+/// Inputs are exchanged by their GGRS frame, so every peer, including spectators, must start
+/// from the same frame and the same state. Peers which disagree on the starting frame will run
+/// different frame-dependent logic for the same inputs, and desync.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, start_session_at_frame};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn resume_match(world: &mut World, session: Session<MyConfig>, saved_frame: i32) {
+///     // the saved state of the match has already been restored into the world
+///     start_session_at_frame::<MyConfig>(world, session, saved_frame);
+/// }
+/// ```
+pub fn start_session_at_frame<T: Config>(world: &mut World, session: Session<T>, frame: i32) {
+    close_session::<T>(world);
+
+    debug!("starting session on frame {frame}");
+
+    world.insert_resource(RollbackFrameCount(frame));
+    world.insert_resource(ConfirmedFrameCount(frame - 1));
+    world.insert_resource(SessionStartFrame(frame));
+    world.insert_resource(session);
+}
+
+/// Forces the [`World`] to roll back to the provided retained frame, and re-simulates every frame
+/// since using the inputs they were originally advanced with, even though no misprediction has
+/// occurred. The [`Checksum`] of the current frame is compared before and after, so a mismatch
+/// reveals a system which is not deterministic under rollback.
+///
+/// Every re-simulated frame is saved again, exactly as GGRS would during a regular rollback. This
+/// must not be called while the [`GgrsPlugin`](`crate::GgrsPlugin`) is running its schedules, such
+/// as from within the [`GgrsSchedule`](`crate::GgrsSchedule`).
+///
+/// This is intentionally disruptive, and is only available with the `forced-rollback` feature.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, force_rollback, RollbackFrameCount};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn stress_rollback(world: &mut World) {
+///     let frame = world.resource::<RollbackFrameCount>().0 - 4;
+///
+///     match force_rollback::<MyConfig>(world, frame) {
+///         Ok(forced) if !forced.is_deterministic() => error!("Desync after {forced:?}"),
+///         Ok(_) => {}
+///         Err(error) => warn!("{error}"),
+///     }
+/// }
+/// # let mut app = App::new();
+/// # app.add_systems(Update, stress_rollback);
+/// ```
+#[cfg(feature = "forced-rollback")]
+pub fn force_rollback<T: Config>(
+    world: &mut World,
+    frame: i32,
+) -> Result<crate::ForcedRollback, BevyGgrsError> {
+    let current_frame = world
+        .get_resource::<RollbackFrameCount>()
+        .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+        .0;
+
+    let retained = world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .is_some_and(|snapshots| snapshots.contains(frame))
+        && SnapshotStorages::contain(world, frame);
+
+    if frame >= current_frame || !retained {
+        return Err(BevyGgrsError::SnapshotMissing { frame });
+    }
+
+    let inputs = world
+        .get_resource::<SnapshotIntervalInputs<T>>()
+        .map(|history| {
+            history
+                .0
+                .iter()
+                .filter(|&&(recorded, _)| recorded >= frame && recorded < current_frame)
+                .map(|(_, inputs)| inputs.clone())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if inputs.len() != (current_frame - frame) as usize {
+        return Err(BevyGgrsError::InputsMissing { frame });
+    }
+
+    // saving the current frame is harmless, as it replaces an identical snapshot
+    world.run_schedule(SaveWorld);
+    let checksum_before = world.get_resource::<Checksum>().map(|checksum| checksum.0);
+
+    debug!("forcing rollback from frame {current_frame} to frame {frame}");
+
+    world.resource_mut::<RollbackFrameCount>().0 = frame;
+    world.run_schedule(LoadWorld);
+
+    let resimulated = inputs.len();
+
+    world.schedule_scope(AdvanceWorld, |world, schedule| {
+        for inputs in inputs {
+            advance_world::<T>(world, schedule, inputs);
+            world.run_schedule(SaveWorld);
+        }
+    });
+
+    let checksum_after = world.get_resource::<Checksum>().map(|checksum| checksum.0);
+
+    Ok(crate::ForcedRollback {
+        frame,
+        resimulated,
+        checksum_before,
+        checksum_after,
+    })
+}
+
+/// Moves the [`World`] to exactly the provided frame, by loading the newest retained snapshot at
+/// or before it and fast-forwarding using the inputs recorded since then. Returns the frame of
+/// the snapshot which was loaded.
+///
+/// Inputs are only recorded while the [`World`] is [`Seekable`], so any frame from the oldest
+/// retained snapshot up to the current [`RollbackFrameCount`] can be seeked to. As with any
+/// rollback, loading a snapshot discards every snapshot saved after it, while the recorded inputs
+/// are kept. Later frames therefore remain reachable, but seeking forward again fast-forwards all
+/// the way from the loaded snapshot.
+///
+/// This is intended for replay viewers. The [`Session`] is not informed, so only seek while it is
+/// not being advanced, and seek back to the newest frame before advancing it again. As the
+/// discarded snapshots are not saved again, the [`Session`] can no longer roll back to any frame
+/// after the loaded snapshot. This must not be called while the
+/// [`GgrsPlugin`](`crate::GgrsPlugin`) is running its schedules.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, seek_to_frame};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// #[derive(Resource)]
+/// struct Scrubber {
+///     frame: i32,
+/// }
+///
+/// fn scrub(world: &mut World) {
+///     let frame = world.resource::<Scrubber>().frame;
+///
+///     if let Err(error) = seek_to_frame::<MyConfig>(world, frame) {
+///         warn!("{error}");
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.enable_seeking().add_systems(Update, scrub);
+/// ```
+pub fn seek_to_frame<T: Config>(world: &mut World, target: i32) -> Result<i32, BevyGgrsError> {
+    let snapshot_frame = world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .and_then(|snapshots| snapshots.frames().find(|&frame| frame <= target))
+        .filter(|&frame| SnapshotStorages::contain(world, frame))
+        .ok_or(BevyGgrsError::SnapshotMissing { frame: target })?;
+
+    let inputs = world
+        .get_resource::<SnapshotIntervalInputs<T>>()
+        .map(|history| {
+            history
+                .0
+                .iter()
+                .filter(|&&(recorded, _)| recorded >= snapshot_frame && recorded < target)
+                .map(|(_, inputs)| inputs.clone())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if inputs.len() != (target - snapshot_frame) as usize {
+        return Err(BevyGgrsError::InputsMissing {
+            frame: snapshot_frame,
+        });
+    }
+
+    debug!("seeking to frame {target} from snapshot for frame {snapshot_frame}");
+
+    world
+        .get_resource_mut::<RollbackFrameCount>()
+        .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+        .0 = snapshot_frame;
+    world.run_schedule(LoadWorld);
+
+    world.schedule_scope(AdvanceWorld, |world, schedule| {
+        for inputs in inputs {
+            advance_world::<T>(world, schedule, inputs);
+        }
+    });
+
+    Ok(snapshot_frame)
+}
+
+/// Temporarily shows a past frame, by [seeking](`seek_to_frame`) to it while remembering the
+/// present frame to return to using [`end_preview`]. This is purely local and cosmetic, such as
+/// for a "rewind to see what happened" feature: neither the [`Session`] nor the recorded inputs
+/// are touched.
+///
+/// The [`World`] must be [`Seekable`], and the frame must be reachable from a retained snapshot.
+/// Frames after the present frame have not been advanced yet, so they are rejected with
+/// [`BevyGgrsError::FrameInFuture`].
+/// While the [`RewindPreview`] is present, the [`RollbackFrameCount`] shows the previewed frame
+/// and the [`GgrsPlugin`](`crate::GgrsPlugin`) only polls the [`Session`], as if [`GgrsActive`]
+/// were `false`. The simulation must not be advanced by any other means during a preview, such as
+/// using [`advance_frame`], as the [`Session`] would desync from the previewed world.
+///
+/// Calling this again during a preview shows another frame, keeping the same present frame. This
+/// must not be called while the [`GgrsPlugin`](`crate::GgrsPlugin`) is running its schedules.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, end_preview, preview_frame, RollbackFrameCount};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn rewind(world: &mut World) {
+///     let frame = world.resource::<RollbackFrameCount>().0 - 30;
+///
+///     if let Err(error) = preview_frame::<MyConfig>(world, frame) {
+///         warn!("{error}");
+///     }
+/// }
+///
+/// fn resume(world: &mut World) {
+///     if let Err(error) = end_preview::<MyConfig>(world) {
+///         error!("{error}");
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.enable_seeking().add_systems(Update, (rewind, resume).chain());
+/// ```
+pub fn preview_frame<T: Config>(world: &mut World, frame: i32) -> Result<(), BevyGgrsError> {
+    let present = match world.get_resource::<RewindPreview>() {
+        Some(preview) => preview.present,
+        None => {
+            world
+                .get_resource::<RollbackFrameCount>()
+                .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+                .0
+        }
+    };
+
+    if frame > present {
+        return Err(BevyGgrsError::FrameInFuture { frame });
+    }
+
+    let snapshot_frame = world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .and_then(|snapshots| snapshots.frames().find(|&saved| saved <= frame))
+        .filter(|&saved| SnapshotStorages::contain(world, saved))
+        .ok_or(BevyGgrsError::SnapshotMissing { frame })?;
+
+    // the present must remain reachable once the preview ends
+    let recorded = recorded_inputs::<T>(world, snapshot_frame, present).len();
+
+    if recorded != (present - snapshot_frame) as usize {
+        return Err(BevyGgrsError::InputsMissing {
+            frame: snapshot_frame,
+        });
+    }
+
+    let snapshot = seek_to_frame::<T>(world, frame)?;
+
+    debug!("previewing frame {frame}, returning to frame {present} afterwards");
+
+    world.insert_resource(RewindPreview {
+        frame,
+        present,
+        snapshot,
+    });
+
+    Ok(())
+}
+
+/// Ends the [`RewindPreview`] started by [`preview_frame`], restoring the present frame by loading
+/// the snapshot the preview was shown from and fast-forwarding using the recorded inputs. Does
+/// nothing if no preview is shown.
+///
+/// Snapshots discarded while previewing are saved again while fast-forwarding, so the [`Session`]
+/// can roll back as far as before the preview.
+pub fn end_preview<T: Config>(world: &mut World) -> Result<(), BevyGgrsError> {
+    let Some(preview) = world.remove_resource::<RewindPreview>() else {
+        return Ok(());
+    };
+
+    let inputs = recorded_inputs::<T>(world, preview.snapshot, preview.present);
+
+    if inputs.len() != (preview.present - preview.snapshot) as usize {
+        return Err(BevyGgrsError::InputsMissing {
+            frame: preview.snapshot,
+        });
+    }
+
+    debug!(
+        "ending preview of frame {}, returning to frame {}",
+        preview.frame, preview.present
+    );
+
+    world
+        .get_resource_mut::<RollbackFrameCount>()
+        .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+        .0 = preview.snapshot;
+    world.run_schedule(LoadWorld);
+
+    let interval = world.get_resource::<SnapshotInterval>().copied();
+    let start = world
+        .get_resource::<SessionStartFrame>()
+        .map_or(0, |start| start.0);
+
+    world.schedule_scope(AdvanceWorld, |world, schedule| {
+        for inputs in inputs {
+            advance_world::<T>(world, schedule, inputs);
+
+            let frame = world.resource::<RollbackFrameCount>().0;
+
+            if snapshot_frame(interval, start, frame) == frame {
+                world.run_schedule(SaveWorld);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// The inputs recorded for advancing from every frame in `from..to`, oldest first.
+fn recorded_inputs<T: Config>(
+    world: &World,
+    from: i32,
+    to: i32,
+) -> Vec<Vec<(T::Input, InputStatus)>> {
+    world
+        .get_resource::<SnapshotIntervalInputs<T>>()
+        .map(|history| {
+            history
+                .0
+                .iter()
+                .filter(|&&(recorded, _)| recorded >= from && recorded < to)
+                .map(|(_, inputs)| inputs.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn handle_events<T: Config>(
+    world: &mut World,
+    events: Vec<GgrsEvent<T>>,
+    caught_up: bool,
+) {
+    let mut wait_recommendation = world.resource_mut::<WaitRecommendation>();
+
+    // we follow wait recommendations by running slow until we are no longer ahead
+    if caught_up {
+        wait_recommendation.0 = None;
+    }
+
+    for event in events.iter() {
+        if let GgrsEvent::WaitRecommendation { skip_frames } = event {
+            wait_recommendation.0 = Some(*skip_frames);
+        }
+    }
+
+    let mut interruptions = world.get_resource_or_insert_with(NetworkInterruptions::<T>::default);
+    let mut changes = Vec::new();
+
+    for event in events.iter() {
+        match event {
+            GgrsEvent::NetworkInterrupted {
+                addr,
+                disconnect_timeout,
+            } => {
+                let disconnect_timeout = Duration::from_millis(*disconnect_timeout as u64);
+                interruptions
+                    .remaining
+                    .insert(addr.clone(), disconnect_timeout);
+                changes.push(NetworkInterruption::Interrupted {
+                    addr: addr.clone(),
+                    disconnect_timeout,
+                });
+            }
+            GgrsEvent::NetworkResumed { addr } => {
+                interruptions.remaining.remove(addr);
+                changes.push(NetworkInterruption::Resumed { addr: addr.clone() });
+            }
+            GgrsEvent::Disconnected { addr } => {
+                interruptions.remaining.remove(addr);
+            }
+            _ => {}
+        }
+    }
+
+    for change in changes {
+        debug!("{change:?}");
+        world.send_event(change);
+    }
+
+    let start = world
+        .get_resource::<SessionStartFrame>()
+        .map_or(0, |start| start.0);
+    let mut status = world.get_resource_or_insert_with::<GgrsStatus>(default);
+
+    for event in events.iter() {
+        if let GgrsEvent::DesyncDetected { frame, .. } = event {
+            status.record_desync(start + *frame);
+        }
+    }
+
+    for event in events {
+        world.send_event(SessionEvent(event));
+    }
+}
+
+#[cfg(feature = "synctest")]
 pub(crate) fn run_synctest<C: Config>(world: &mut World, mut sess: SyncTestSession<C>) {
-    world.insert_resource(LocalPlayers((0..sess.num_players()).collect()));
+    update_local_players(world, (0..sess.num_players()).collect());
 
     // read local player inputs and register them in the session
-    world.run_schedule(ReadInputs);
-    let local_inputs = world.remove_resource::<LocalInputs<C>>().expect(
-        "No local player inputs found. Did you insert systems into the ReadInputs schedule?",
-    );
+    let Some(local_inputs) = read_local_inputs::<C>(world) else {
+        world.insert_resource(Session::SyncTest(sess));
+        report_error(world, BevyGgrsError::MissingLocalInputs);
+        return;
+    };
     for (handle, input) in local_inputs.0 {
         sess.add_local_input(handle, input)
             .expect("All handles in local_handles should be valid");
@@ -91,11 +971,74 @@ pub(crate) fn run_synctest<C: Config>(world: &mut World, mut sess: SyncTestSessi
 
     match requests {
         Ok(requests) => handle_requests(requests, world),
-        Err(e) => warn!("{e}"),
+        Err(e) => report_error(world, e.into()),
     }
 }
 
-pub(crate) fn run_spectator<T: Config>(world: &mut World, mut sess: SpectatorSession<T>) {
+/// Advances a single recorded frame of a [`ReplaySession`], if any remain.
+pub(crate) fn run_replay<T: Config>(world: &mut World, mut sess: ReplaySession<T>) {
+    update_local_players(world, Vec::new());
+
+    let inputs = sess.next_inputs();
+
+    world.insert_resource(Session::Replay(sess));
+
+    let Some(inputs) = inputs else {
+        return;
+    };
+
+    world.schedule_scope(AdvanceWorld, |world, schedule| {
+        advance_world::<T>(world, schedule, inputs);
+    });
+
+    // every replayed frame is confirmed, so no snapshots are retained for it
+    let frame = world.resource::<RollbackFrameCount>().0;
+    world.insert_resource(ConfirmedFrameCount(frame));
+}
+
+/// Runs a single step of a [`SpectatorSession`], followed by up to
+/// [`SpectatorCatchup::max_catchup_frames`] additional steps while it is behind the host.
+#[cfg(feature = "spectator")]
+pub(crate) fn run_spectator<T: Config>(world: &mut World, sess: SpectatorSession<T>) {
+    let max_frames = world
+        .get_resource::<SpectatorCatchup>()
+        .map_or(1, |catchup| catchup.max_catchup_frames.max(1));
+
+    let mut sess = Some(sess);
+
+    for _ in 0..max_frames {
+        let Some(sess) = sess.take().or_else(|| remove_spectator_session(world)) else {
+            break;
+        };
+
+        let behind = sess.frames_behind_host();
+
+        if !run_spectator_step(world, sess) || behind <= 1 {
+            break;
+        }
+    }
+
+    if let Some(Session::Spectator(sess)) = world.get_resource::<Session<T>>() {
+        let behind = sess.frames_behind_host();
+        world.insert_resource(SpectatorLag(behind));
+    }
+}
+
+/// Removes the [`SpectatorSession`] reinserted by a previous step, if it is still present.
+#[cfg(feature = "spectator")]
+fn remove_spectator_session<T: Config>(world: &mut World) -> Option<SpectatorSession<T>> {
+    match world.remove_resource::<Session<T>>()? {
+        Session::Spectator(sess) => Some(sess),
+        session => {
+            world.insert_resource(session);
+            None
+        }
+    }
+}
+
+/// Runs a single step of a [`SpectatorSession`], returning `true` if it advanced.
+#[cfg(feature = "spectator")]
+fn run_spectator_step<T: Config>(world: &mut World, mut sess: SpectatorSession<T>) -> bool {
     // if session is ready, try to advance the frame
     let running = sess.current_state() == SessionState::Running;
     let requests = running.then(|| sess.advance_frame());
@@ -103,27 +1046,36 @@ pub(crate) fn run_spectator<T: Config>(world: &mut World, mut sess: SpectatorSes
     world.insert_resource(Session::Spectator(sess));
 
     match requests {
-        Some(Ok(requests)) => handle_requests(requests, world),
+        Some(Ok(requests)) => {
+            handle_requests(requests, world);
+            true
+        }
         Some(Err(GgrsError::PredictionThreshold)) => {
-            info!("P2PSpectatorSession: Waiting for input from host.")
+            info!("P2PSpectatorSession: Waiting for input from host.");
+            false
         }
-        Some(Err(e)) => warn!("{e}"),
-        None => {}
-    };
+        Some(Err(e)) => {
+            report_error(world, e.into());
+            false
+        }
+        None => false,
+    }
 }
 
-pub(crate) fn run_p2p<C: Config>(world: &mut World, mut sess: P2PSession<C>) {
-    world.insert_resource(LocalPlayers(sess.local_player_handles()));
+/// Runs a single step of a [`P2PSession`], returning `true` if it was skipped because the
+/// session reached its prediction threshold.
+pub(crate) fn run_p2p<C: Config>(world: &mut World, mut sess: P2PSession<C>) -> bool {
+    update_local_players(world, sess.local_player_handles());
 
     let running = sess.current_state() == SessionState::Running;
 
     if running {
         // get local player inputs
-        world.run_schedule(ReadInputs);
-
-        let local_inputs = world.remove_resource::<LocalInputs<C>>().expect(
-            "No local player inputs found. Did you insert systems into the ReadInputs schedule?",
-        );
+        let Some(local_inputs) = read_local_inputs::<C>(world) else {
+            world.insert_resource(Session::P2P(sess));
+            report_error(world, BevyGgrsError::MissingLocalInputs);
+            return false;
+        };
 
         for (handle, input) in local_inputs.0 {
             sess.add_local_input(handle, input)
@@ -133,21 +1085,72 @@ pub(crate) fn run_p2p<C: Config>(world: &mut World, mut sess: P2PSession<C>) {
 
     let requests = running.then(|| sess.advance_frame());
 
+    let lockstep = sess.max_prediction() == 0;
+    let frame = sess.current_frame();
+    let depth = frame.saturating_sub(sess.confirmed_frame().max(0)).max(0) as usize;
+    let remote_players = sess.remote_player_handles();
+
     world.insert_resource(Session::P2P(sess));
 
+    let mut prediction_depth = world.get_resource_or_insert_with::<PredictionDepth>(default);
+    if prediction_depth.0 != depth {
+        prediction_depth.0 = depth;
+    }
+
+    if lockstep {
+        match requests {
+            Some(Err(GgrsError::PredictionThreshold)) => {
+                let frame = frame
+                    + world
+                        .get_resource::<SessionStartFrame>()
+                        .map_or(0, |start| start.0);
+                let steps = world
+                    .get_resource::<LockstepStall>()
+                    .filter(|stall| stall.frame == frame)
+                    .map_or(0, |stall| stall.steps);
+
+                world.insert_resource(LockstepStall {
+                    frame,
+                    remote_players,
+                    steps: steps + 1,
+                });
+            }
+            _ => {
+                world.remove_resource::<LockstepStall>();
+            }
+        }
+    }
+
+    let skipped = matches!(requests, Some(Err(GgrsError::PredictionThreshold)));
+
+    if skipped {
+        world
+            .get_resource_or_insert_with::<SessionStats>(default)
+            .prediction_threshold_skips += 1;
+    }
+
     match requests {
         Some(Ok(requests)) => handle_requests(requests, world),
+        Some(Err(GgrsError::PredictionThreshold)) if lockstep => {
+            debug!("Lockstep: waiting for remote inputs.");
+        }
         Some(Err(GgrsError::PredictionThreshold)) => {
-            info!("Skipping a frame: PredictionThreshold.")
+            info!("Skipping a frame: PredictionThreshold.");
         }
-        Some(Err(e)) => warn!("{e}"),
+        Some(Err(e)) => report_error(world, e.into()),
         None => {}
     }
+
+    skipped
 }
 
 pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &mut World) {
     let _span = bevy::utils::tracing::info_span!("ggrs", name = "HandleRequests").entered();
 
+    if world.contains_resource::<Events<SessionRequests>>() {
+        send_session_requests(world, &requests);
+    }
+
     // Extracting schedules before processing requests to avoid repeated remove/insert operations
     let mut schedules = world.resource_mut::<Schedules>();
 
@@ -163,8 +1166,32 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
         panic!("Could not extract AdvanceWorld Schedule!");
     };
 
+    let timed = world.contains_resource::<RollbackTimings>();
+    // no rollback can occur in lockstep, so snapshots are never required
+    let lockstep = matches!(
+        world.get_resource::<Session<T>>(),
+        Some(Session::P2P(session)) if session.max_prediction() == 0
+    );
+    let disabled = lockstep || world.contains_resource::<DisableSnapshots>();
+    let seekable = world.contains_resource::<Seekable>();
+    // GGRS counts frames from 0, regardless of the frame the session started at
+    let start = world
+        .get_resource::<SessionStartFrame>()
+        .map_or(0, |start| start.0);
+
+    // set once a rollback cannot be restored, after which the World is no longer advanced
+    let mut aborted = false;
+
     // Run Schedules as Required
     for request in requests {
+        if aborted {
+            // GGRS still expects every requested frame to be saved
+            if let GgrsRequest::SaveGameState { cell, frame } = request {
+                cell.save(frame, None, None);
+            }
+            continue;
+        }
+
         let current_frame = world
             .get_resource::<RollbackFrameCount>()
             .map(|frame| frame.0)
@@ -174,18 +1201,24 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
 
         let max_prediction = match session {
             Some(Session::P2P(s)) => Some(s.max_prediction()),
+            #[cfg(feature = "synctest")]
             Some(Session::SyncTest(s)) => Some(s.max_prediction()),
+            #[cfg(feature = "spectator")]
             Some(Session::Spectator(_)) => Some(0),
+            Some(Session::Replay(_)) => Some(0),
             None => None,
         };
 
         let confirmed_frame = match session {
-            Some(Session::P2P(s)) => Some(s.confirmed_frame()),
+            Some(Session::P2P(s)) => Some(start + s.confirmed_frame()),
+            #[cfg(feature = "synctest")]
             Some(Session::SyncTest(s)) => {
-                let current_frame = current_frame - (s.check_distance() as i32);
-                (current_frame < 0).then_some(current_frame)
+                let current_frame = current_frame - start - (s.check_distance() as i32);
+                (current_frame < 0).then_some(start + current_frame)
             }
+            #[cfg(feature = "spectator")]
             Some(Session::Spectator(_)) => Some(current_frame),
+            Some(Session::Replay(s)) => Some(s.current_frame()),
             None => None,
         };
 
@@ -193,56 +1226,160 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
             world.insert_resource(MaxPredictionWindow(max_prediction));
         }
 
+        let interval = world.get_resource::<SnapshotInterval>().copied();
+
         if let Some(confirmed_frame) = confirmed_frame {
+            // retain the snapshot required to fast-forward to the confirmed frame
+            let confirmed_frame = snapshot_frame(interval, start, confirmed_frame);
+
             world.insert_resource(ConfirmedFrameCount(confirmed_frame));
         }
 
         match request {
-            GgrsRequest::SaveGameState { cell, frame } => {
+            GgrsRequest::SaveGameState { cell, frame } if disabled => {
+                trace!("snapshots disabled, not saving frame {}", start + frame);
+                cell.save(frame, None, None);
+            }
+            GgrsRequest::LoadGameState { frame, .. } if disabled => {
+                let frame = start + frame;
+                warn!("Rollback to frame {frame} requested while snapshots are disabled");
+
+                world
+                    .get_resource_mut::<RollbackFrameCount>()
+                    .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+                    .0 = frame;
+            }
+            GgrsRequest::SaveGameState {
+                cell,
+                frame: ggrs_frame,
+            } => {
+                let frame = start + ggrs_frame;
+
+                if snapshot_frame(interval, start, frame) != frame {
+                    debug!("skipping snapshot for frame {frame}");
+                    cell.save(ggrs_frame, None, None);
+                    continue;
+                }
+
+                // the world does not hold the requested frame, so saving it would corrupt it
+                if frame != current_frame {
+                    report_error(
+                        world,
+                        BevyGgrsError::FrameMismatch {
+                            expected: frame,
+                            found: current_frame,
+                        },
+                    );
+                    cell.save(ggrs_frame, None, None);
+                    continue;
+                }
+
                 let _span =
                     bevy::utils::tracing::info_span!("schedule", name = "SaveWorld").entered();
                 debug!("saving snapshot for frame {frame}");
 
+                let start = timed.then(Instant::now);
+
                 save_world_schedule.run(world);
 
+                if let Some(start) = start {
+                    world
+                        .resource_mut::<RollbackTimings>()
+                        .save
+                        .record(start.elapsed());
+                }
+
                 // look into resources and find the checksum
                 let checksum = world
                     .get_resource::<Checksum>()
                     .map(|&Checksum(checksum)| checksum);
 
+                if let Some(checksum) = checksum {
+                    record_saved_checksum(world, frame, checksum);
+                }
+
                 // we don't really use the buffer provided by GGRS
-                cell.save(frame, None, checksum);
+                cell.save(ggrs_frame, None, checksum);
             }
             GgrsRequest::LoadGameState { frame, .. } => {
+                let frame = start + frame;
                 let _span =
                     bevy::utils::tracing::info_span!("schedule", name = "LoadWorld").entered();
                 // we don't really use the buffer provided by GGRS
                 debug!("restoring snapshot for frame {frame}");
 
+                let snapshot_frame = snapshot_frame(interval, start, frame);
+
+                // loading any other snapshot would silently restore the wrong frame, and
+                // advancing from the current one would simulate on top of an unrelated state
+                if !SnapshotStorages::contain(world, snapshot_frame) {
+                    let error = BevyGgrsError::SnapshotMissing { frame };
+                    error!(
+                        "{error} The rollback spans further than the retained snapshots, so the \
+                        session must be resynchronized."
+                    );
+                    world.send_event(SessionError(error));
+                    aborted = true;
+                    continue;
+                }
+
                 world
                     .get_resource_mut::<RollbackFrameCount>()
                     .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
-                    .0 = frame;
+                    .0 = snapshot_frame;
+
+                let start = timed.then(Instant::now);
 
                 load_world_schedule.run(world);
+
+                if let Some(start) = start {
+                    world
+                        .resource_mut::<RollbackTimings>()
+                        .load
+                        .record(start.elapsed());
+                }
+
+                if let Some(mut status) = world.get_resource_mut::<GgrsStatus>() {
+                    status.record_rollback();
+                }
+
+                if world.contains_resource::<SnapshotChecksumVerification>() {
+                    verify_loaded_checksum(world, &mut save_world_schedule, snapshot_frame);
+                }
+
+                if snapshot_frame != frame {
+                    debug!("fast-forwarding from snapshot for frame {snapshot_frame}");
+
+                    let mut history = world
+                        .remove_resource::<SnapshotIntervalInputs<T>>()
+                        .unwrap_or_default();
+
+                    // inputs from the requested frame onwards are about to be replaced
+                    history.0.retain(|&(recorded, _)| recorded < frame);
+
+                    for (_, inputs) in history
+                        .0
+                        .iter()
+                        .filter(|&&(recorded, _)| recorded >= snapshot_frame)
+                    {
+                        advance_world::<T>(world, &mut advance_world_schedule, inputs.clone());
+                    }
+
+                    world.insert_resource(history);
+                }
             }
             GgrsRequest::AdvanceFrame { inputs } => {
                 let _span =
                     bevy::utils::tracing::info_span!("schedule", name = "AdvanceWorld").entered();
-                let mut frame_count = world
-                    .get_resource_mut::<RollbackFrameCount>()
-                    .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?");
 
-                frame_count.0 += 1;
-                let frame = frame_count.0;
+                // forced rollbacks and seeking re-advance using the recorded inputs as well
+                if interval.is_some() || seekable || cfg!(feature = "forced-rollback") {
+                    record_interval_inputs::<T>(world, &inputs);
+                }
 
-                debug!("advancing to frame: {}", frame);
-                world.insert_resource(PlayerInputs::<T>(inputs));
+                record_replay_inputs::<T>(world, &inputs);
 
-                advance_world_schedule.run(world);
-
-                world.remove_resource::<PlayerInputs<T>>();
-                debug!("frame {frame} completed");
+                advance_world::<T>(world, &mut advance_world_schedule, inputs);
             }
         }
     }
@@ -265,3 +1402,325 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
         panic!("GgrsSchedule Schedule was Duplicated!");
     }
 }
+
+/// Collects the role of every player of a [`P2PSession`], and the addresses of players revealed by
+/// the provided events, to update the [`PlayerRoster`].
+fn player_roster_updates<T: Config>(
+    session: &P2PSession<T>,
+    events: &[GgrsEvent<T>],
+) -> (
+    Vec<(PlayerHandle, PlayerKind)>,
+    Vec<(PlayerHandle, T::Address)>,
+) {
+    let kinds = session
+        .local_player_handles()
+        .into_iter()
+        .map(|handle| (handle, PlayerKind::Local))
+        .chain(
+            session
+                .remote_player_handles()
+                .into_iter()
+                .map(|handle| (handle, PlayerKind::Remote)),
+        )
+        .chain(
+            session
+                .spectator_handles()
+                .into_iter()
+                .map(|handle| (handle, PlayerKind::Spectator)),
+        )
+        .collect();
+
+    let addresses = events
+        .iter()
+        .filter_map(|event| match event {
+            GgrsEvent::Synchronizing { addr, .. }
+            | GgrsEvent::Synchronized { addr }
+            | GgrsEvent::Disconnected { addr }
+            | GgrsEvent::NetworkInterrupted { addr, .. }
+            | GgrsEvent::NetworkResumed { addr }
+            | GgrsEvent::DesyncDetected { addr, .. } => Some(addr),
+            _ => None,
+        })
+        .flat_map(|addr| {
+            session
+                .handles_by_address(addr.clone())
+                .into_iter()
+                .map(move |handle| (handle, addr.clone()))
+        })
+        .collect();
+
+    (kinds, addresses)
+}
+
+/// Updates the [`LocalPlayers`], sending [`LocalPlayersChanged`] if they differ from the previous
+/// frame.
+fn update_local_players(world: &mut World, current: Vec<PlayerHandle>) {
+    let mut local_players = world.get_resource_or_insert_with::<LocalPlayers>(default);
+
+    if local_players.0 == current {
+        return;
+    }
+
+    let previous = std::mem::replace(&mut local_players.0, current.clone());
+
+    if !previous.is_empty() {
+        world.send_event(LocalPlayersChanged { previous, current });
+    }
+}
+
+/// Logs the provided error and raises it as a [`SessionError`].
+fn report_error(world: &mut World, error: BevyGgrsError) {
+    warn!("{error}");
+
+    if let BevyGgrsError::Ggrs(GgrsError::MismatchedChecksum {
+        mismatched_frames, ..
+    }) = &error
+    {
+        let start = world
+            .get_resource::<SessionStartFrame>()
+            .map_or(0, |start| start.0);
+
+        if let (Some(&frame), Some(mut status)) = (
+            mismatched_frames.iter().max(),
+            world.get_resource_mut::<GgrsStatus>(),
+        ) {
+            status.record_desync(start + frame);
+        }
+    }
+
+    world.send_event(SessionError(error));
+}
+
+/// Computes the [`InterpolationAlpha`] after `steps` frames were advanced in this update.
+///
+/// Without smoothing, this is the fraction of a frame left in the accumulator. With smoothing,
+/// the time accumulated towards the next frame is predicted from a smoothed frame time instead,
+/// and only nudged towards the actual accumulator. The accumulator itself is never altered.
+fn update_interpolation_alpha(
+    time_data: &mut FixedTimestepData,
+    smoothing: Option<FramePacingSmoothing>,
+    delta: Duration,
+    fps_delta: f64,
+    steps: usize,
+) -> f32 {
+    let delta = delta.as_secs_f64();
+    let overstep = time_data.accumulator.as_secs_f64().min(fps_delta);
+
+    match smoothing {
+        Some(FramePacingSmoothing(factor)) if time_data.had_session => {
+            let factor = factor.clamp(f64::EPSILON, 1.);
+
+            time_data.smoothed_delta += factor * (delta - time_data.smoothed_delta);
+
+            let predicted =
+                time_data.smoothed_overstep + time_data.smoothed_delta - steps as f64 * fps_delta;
+
+            time_data.smoothed_overstep =
+                (predicted + factor * (overstep - predicted)).clamp(0., fps_delta);
+        }
+        _ => {
+            time_data.smoothed_delta = delta;
+            time_data.smoothed_overstep = overstep;
+        }
+    }
+
+    (time_data.smoothed_overstep / fps_delta).clamp(0., 1.) as f32
+}
+
+/// Warns if the [`Session`] disagrees with the [`SessionConfig`].
+fn validate_session_config<T: Config>(world: &World) {
+    let (Some(config), Some(session)) = (
+        world.get_resource::<SessionConfig>(),
+        world.get_resource::<Session<T>>(),
+    ) else {
+        return;
+    };
+
+    if config.num_players != session.num_players() {
+        warn!(
+            "SessionConfig has {} players, but the Session has {}.",
+            config.num_players,
+            session.num_players()
+        );
+    }
+}
+
+/// Runs the [`GgrsInitSchedule`] for a newly started [`Session`], and records the resulting
+/// [`InitialChecksum`] by saving the initial state of the world.
+fn run_init_schedule(world: &mut World) {
+    let _span = bevy::utils::tracing::info_span!("schedule", name = "GgrsInitSchedule").entered();
+
+    #[cfg(feature = "chaos-schedule")]
+    crate::chaos::apply(world);
+
+    world.run_schedule(GgrsInitSchedule);
+
+    #[cfg(debug_assertions)]
+    crate::EntityMappingAudit::report(world);
+
+    if world.contains_resource::<DisableSnapshots>() {
+        return;
+    }
+
+    // saving the current frame is harmless, as GGRS will save this same state before advancing
+    world.run_schedule(SaveWorld);
+
+    let checksum = world.get_resource::<Checksum>().map(|checksum| checksum.0);
+
+    debug!("initial checksum {checksum:X?}");
+
+    world.insert_resource(InitialChecksum(checksum));
+}
+
+/// Records the checksum saved for the provided frame, if [`SnapshotChecksumVerification`] is enabled.
+fn record_saved_checksum(world: &mut World, frame: i32, checksum: u128) {
+    let depth = world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .map_or(crate::DEFAULT_FPS, |snapshots| snapshots.depth());
+
+    if let Some(mut verification) = world.get_resource_mut::<SnapshotChecksumVerification>() {
+        verification.record(frame, checksum, depth);
+    }
+}
+
+/// Saves the just loaded frame again, reporting a [`BevyGgrsError::ChecksumDrift`] if the
+/// recomputed checksum differs from the one saved for it before.
+fn verify_loaded_checksum(world: &mut World, save_world_schedule: &mut Schedule, frame: i32) {
+    let Some(saved) = world
+        .resource::<SnapshotChecksumVerification>()
+        .saved(frame)
+    else {
+        return;
+    };
+
+    // saving the loaded frame again is harmless, as it replaces an identical snapshot
+    save_world_schedule.run(world);
+
+    let Some(restored) = world.get_resource::<Checksum>().map(|checksum| checksum.0) else {
+        return;
+    };
+
+    if restored == saved {
+        return;
+    }
+
+    world
+        .resource_mut::<SnapshotChecksumVerification>()
+        .record_mismatch();
+
+    let error = BevyGgrsError::ChecksumDrift {
+        frame,
+        saved,
+        restored,
+    };
+    error!("{error} The checksum covers state which is not rolled back.");
+    world.send_event(SessionError(error));
+}
+
+/// The frame of the snapshot to restore the provided frame from. The first frame of a
+/// [`Session`] is always saved, even when it lies between two frames of the [`SnapshotInterval`].
+fn snapshot_frame(interval: Option<SnapshotInterval>, start: i32, frame: i32) -> i32 {
+    match interval {
+        Some(interval) if frame >= start => interval.snapshot_frame(frame).max(start),
+        Some(interval) => interval.snapshot_frame(frame),
+        None => frame,
+    }
+}
+
+fn send_session_requests<T: Config>(world: &mut World, requests: &[GgrsRequest<T>]) {
+    let mut frame = world
+        .get_resource::<RollbackFrameCount>()
+        .map(|frame| frame.0)
+        .unwrap_or_default();
+    let start = world
+        .get_resource::<SessionStartFrame>()
+        .map_or(0, |start| start.0);
+
+    let requests = requests
+        .iter()
+        .map(|request| match request {
+            GgrsRequest::SaveGameState { frame, .. } => SessionRequest::SaveGameState {
+                frame: start + *frame,
+            },
+            GgrsRequest::LoadGameState { frame: loaded, .. } => {
+                frame = start + *loaded;
+                SessionRequest::LoadGameState { frame }
+            }
+            GgrsRequest::AdvanceFrame { .. } => {
+                frame += 1;
+                SessionRequest::AdvanceFrame { frame }
+            }
+        })
+        .collect();
+
+    world.send_event(SessionRequests(requests));
+}
+
+fn advance_world<T: Config>(
+    world: &mut World,
+    schedule: &mut Schedule,
+    inputs: Vec<(T::Input, InputStatus)>,
+) {
+    let mut frame_count = world
+        .get_resource_mut::<RollbackFrameCount>()
+        .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?");
+
+    frame_count.0 += 1;
+    let frame = frame_count.0;
+
+    debug!("advancing to frame: {}", frame);
+    let inputs = PlayerInputs::<T>(inputs);
+    world.insert_resource(FrameConfirmed(inputs.all_confirmed()));
+    world.insert_resource(inputs);
+
+    let mutation_check = UnregisteredMutationCheck::start(world);
+
+    let start = world
+        .contains_resource::<RollbackTimings>()
+        .then(Instant::now);
+
+    schedule.run(world);
+
+    if let Some(since) = mutation_check {
+        UnregisteredMutationCheck::report(world, since);
+    }
+
+    if let Some(start) = start {
+        world
+            .resource_mut::<RollbackTimings>()
+            .advance
+            .record(start.elapsed());
+    }
+
+    world.remove_resource::<PlayerInputs<T>>();
+    debug!("frame {frame} completed");
+}
+
+/// Records the inputs used to advance from the current frame, so it can be fast-forwarded
+/// to from the nearest prior snapshot while a [`SnapshotInterval`] is in use, or seeked to.
+fn record_interval_inputs<T: Config>(world: &mut World, inputs: &[(T::Input, InputStatus)]) {
+    let frame = world
+        .get_resource::<RollbackFrameCount>()
+        .map(|frame| frame.0)
+        .unwrap_or_default();
+
+    let confirmed_frame = world
+        .get_resource::<ConfirmedFrameCount>()
+        .map(|&frame| i32::from(frame))
+        .unwrap_or(i32::MIN);
+
+    let oldest_frame = world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .and_then(|snapshots| snapshots.frames().last())
+        .unwrap_or(i32::MIN);
+
+    let retained_from = confirmed_frame.max(oldest_frame);
+
+    let mut history = world.get_resource_or_insert_with(SnapshotIntervalInputs::<T>::default);
+
+    // inputs before the oldest snapshot which may still be loaded are never replayed
+    history
+        .0
+        .retain(|&(recorded, _)| recorded >= retained_from && recorded < frame);
+    history.0.push_back((frame, inputs.to_vec()));
+}
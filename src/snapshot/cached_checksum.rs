@@ -0,0 +1,261 @@
+use std::hash::{Hash, Hasher};
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{
+    checksum_hasher, checksum_hasher_for, ActiveRollback, ChecksumContributor,
+    ChecksumContributorsOnly, ChecksumFlag, ChecksumPart, ChecksumSpawnFilter, NoRollback,
+    Rollback, RollbackOrdered, RollbackScope, RollbackSpawnFrame, SaveWorld, SaveWorldSet,
+};
+
+/// Contributions of every [`Entity`] to the checksum of a [`Component`], kept between frames by
+/// the [`CachedComponentChecksumPlugin`].
+#[derive(Default)]
+struct ChecksumCache {
+    /// Contribution of each [`Entity`] currently included in the checksum.
+    contributions: HashMap<Entity, u64>,
+    /// The XOR of all contributions.
+    result: u64,
+    /// Whether the cache has been filled at least once.
+    filled: bool,
+    /// Whether [`ChecksumContributorsOnly`] was present when the cache was last updated.
+    restricted: bool,
+    /// Whether [`ChecksumConfirmedEntitiesOnly`](`crate::ChecksumConfirmedEntitiesOnly`) was
+    /// present when the cache was last updated.
+    confirmed_only: bool,
+    /// The frame the cache was last updated for.
+    frame: i32,
+}
+
+impl ChecksumCache {
+    fn remove(&mut self, entity: Entity) {
+        if let Some(contribution) = self.contributions.remove(&entity) {
+            self.result ^= contribution;
+        }
+    }
+
+    fn insert(&mut self, entity: Entity, contribution: u64) {
+        self.remove(entity);
+        self.contributions.insert(entity, contribution);
+        self.result ^= contribution;
+    }
+
+    fn clear(&mut self) {
+        self.contributions.clear();
+        self.result = 0;
+    }
+}
+
+/// A [`Plugin`] which produces the same [`ChecksumPart`] as the [`ComponentChecksumPlugin`](`crate::ComponentChecksumPlugin`),
+/// but caches the contribution of every [`Entity`] between frames. Only entities where `C` has
+/// [changed](`Changed`), was added or was removed are hashed again, so the cost of each checksum
+/// is proportional to the amount of changed entities rather than all entities.
+///
+/// This relies on change detection, so `C` must never be mutated while bypassing it. Every entity
+/// is hashed again when the [`RollbackOrdered`] or the [`RollbackScope`] changes, such as after
+/// loading a snapshot, so this is most effective for worlds which are mostly static. While
+/// [`ChecksumConfirmedEntitiesOnly`](`crate::ChecksumConfirmedEntitiesOnly`) is present, every
+/// entity is also hashed again whenever a frame is saved out of order, and entities are hashed
+/// again on the frame they start contributing.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, CachedComponentChecksumPlugin};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// #[derive(Component, Clone, Copy, Hash)]
+/// struct Wall(u32);
+///
+/// // Walls are numerous, but rarely change
+/// app.rollback_component_with_copy::<Wall>()
+///     .add_plugins(CachedComponentChecksumPlugin::<Wall>::default());
+/// ```
+pub struct CachedComponentChecksumPlugin<C: Component>(pub for<'a> fn(&'a C) -> u64);
+
+fn default_hasher<C: Component + Hash>(component: &C) -> u64 {
+    let mut hasher = checksum_hasher();
+    component.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<C> Default for CachedComponentChecksumPlugin<C>
+where
+    C: Component + Hash,
+{
+    fn default() -> Self {
+        Self(default_hasher::<C>)
+    }
+}
+
+impl<C> Plugin for CachedComponentChecksumPlugin<C>
+where
+    C: Component,
+{
+    fn build(&self, app: &mut App) {
+        let custom_hasher = self.0;
+
+        let update = move |mut commands: Commands,
+                           mut cache: Local<ChecksumCache>,
+                           rollback_ordered: Res<RollbackOrdered>,
+                           scope: Option<Res<RollbackScope>>,
+                           restricted: Option<Res<ChecksumContributorsOnly>>,
+                           spawn_filter: ChecksumSpawnFilter,
+                           mut removed: RemovedComponents<C>,
+                           mut unregistered: RemovedComponents<Rollback>,
+                           mut deactivated: RemovedComponents<ActiveRollback>,
+                           mut included: RemovedComponents<NoRollback>,
+                           mut uncontributed: RemovedComponents<ChecksumContributor>,
+                           changed: Query<
+            Entity,
+            (
+                With<Rollback>,
+                With<C>,
+                Or<(
+                    Changed<C>,
+                    Added<Rollback>,
+                    Added<ActiveRollback>,
+                    Added<NoRollback>,
+                    Added<ChecksumContributor>,
+                )>,
+            ),
+        >,
+                           components: Query<
+            (
+                Entity,
+                &Rollback,
+                &C,
+                Has<ActiveRollback>,
+                Has<ChecksumContributor>,
+                Option<&RollbackSpawnFrame>,
+            ),
+            (
+                With<Rollback>,
+                Without<ChecksumFlag<C>>,
+                Without<NoRollback>,
+            ),
+        >,
+                           mut checksum: Query<
+            &mut ChecksumPart,
+            (Without<Rollback>, With<ChecksumFlag<C>>),
+        >| {
+            let hasher = checksum_hasher_for::<C>();
+
+            let scoped = scope.is_some();
+            let scope_changed = scope.as_ref().is_some_and(|scope| scope.is_changed());
+            let restriction_changed = match &restricted {
+                Some(restricted) => restricted.is_added(),
+                None => cache.restricted,
+            };
+            cache.restricted = restricted.is_some();
+
+            let frame = spawn_filter.frame();
+            let confirmed_only_changed = spawn_filter.is_toggled(cache.confirmed_only);
+            // Which entities contribute depends on the frame, which only advances one at a time
+            let frame_skipped = spawn_filter.is_enabled() && frame != cache.frame + 1;
+            cache.confirmed_only = spawn_filter.is_enabled();
+            cache.frame = frame;
+
+            // The order of every entity may have changed, so everything is hashed again
+            let mut dirty: HashSet<Entity> = if !cache.filled
+                || rollback_ordered.is_changed()
+                || scope_changed
+                || restriction_changed
+                || confirmed_only_changed
+                || frame_skipped
+            {
+                removed.clear();
+                unregistered.clear();
+                deactivated.clear();
+                included.clear();
+                uncontributed.clear();
+                cache.clear();
+                cache.filled = true;
+
+                components.iter().map(|(entity, ..)| entity).collect()
+            } else {
+                removed
+                    .read()
+                    .chain(unregistered.read())
+                    .chain(deactivated.read())
+                    .chain(included.read())
+                    .chain(uncontributed.read())
+                    .chain(changed.iter())
+                    .collect()
+            };
+
+            // Entities spawned exactly long enough ago start contributing on this frame
+            if spawn_filter.is_enabled() {
+                let latest = spawn_filter.latest_spawn_frame();
+
+                dirty.extend(
+                    components
+                        .iter()
+                        .filter(|(.., spawned)| {
+                            spawned.and_then(RollbackSpawnFrame::frame) == Some(latest)
+                        })
+                        .map(|(entity, ..)| entity),
+                );
+            }
+
+            for entity in dirty {
+                cache.remove(entity);
+
+                let Ok((_, &rollback, component, active, contributor, spawned)) =
+                    components.get(entity)
+                else {
+                    continue;
+                };
+
+                // Entities out of scope are not rolled back, and so cannot be compared
+                if scoped && !active {
+                    continue;
+                }
+
+                if !ChecksumContributorsOnly::includes(restricted.as_deref(), contributor) {
+                    continue;
+                }
+
+                // Entities spawned during prediction may not exist on every peer yet
+                if !spawn_filter.includes(spawned) {
+                    continue;
+                }
+
+                let mut hasher = hasher;
+
+                // Hashing the rollback index ensures this hash is unique and stable
+                rollback_ordered.order(rollback).hash(&mut hasher);
+                custom_hasher(component).hash(&mut hasher);
+
+                cache.insert(entity, hasher.finish());
+            }
+
+            let mut hasher = hasher;
+
+            // Hash the XOR'ed result to break commutativity with other types
+            cache.result.hash(&mut hasher);
+
+            let result = ChecksumPart(hasher.finish() as u128);
+
+            trace!(
+                "Component {} has cached checksum {:X}",
+                bevy::utils::get_short_name(std::any::type_name::<C>()),
+                result.0
+            );
+
+            if let Ok(mut checksum) = checksum.get_single_mut() {
+                *checksum = result;
+            } else {
+                commands.spawn((result, ChecksumFlag::<C>::default()));
+            }
+        };
+
+        app.add_systems(SaveWorld, update.in_set(SaveWorldSet::Checksum));
+    }
+}
@@ -37,15 +37,60 @@ impl ChecksumPart {
 }
 
 /// Represents a total checksum for a given frame.
+///
+/// # Portability
+///
+/// The parts provided by this crate are keyed by [`std::any::type_name`] using
+/// [`checksum_hasher_for`](`crate::checksum_hasher_for`). Type names are not guaranteed to be
+/// stable, and may differ between compiler versions. Checksums are therefore only comparable
+/// between peers running builds of the same source made with the same toolchain. Peers mixing
+/// toolchains will report a desync even though their states are identical.
 #[derive(Resource, Default, Clone, Copy)]
 pub struct Checksum(pub u128);
 
-/// A [`Plugin`] which creates a [`Checksum`] resource which can be read after or during the
-/// [`SaveWorldSet::Snapshot`] set in the [`SaveWorld`] schedule has been run.
+/// Flags a [`Rollback`](`crate::Rollback`) entity as contributing to the [`Checksum`] while
+/// [`ChecksumContributorsOnly`] is present.
+#[derive(Component, Clone, Copy, Default, Debug, Hash, PartialEq, Eq)]
+pub struct ChecksumContributor;
+
+/// When present, only [`Rollback`](`crate::Rollback`) entities flagged as a
+/// [`ChecksumContributor`] contribute to the [`Checksum`], across all of their checksummed
+/// [`Components`](`Component`). Set this using [`GgrsApp::checksum_contributors_only`](`crate::GgrsApp::checksum_contributors_only`).
+///
+/// This is useful when only important entities, such as players and projectiles, matter for
+/// detecting desyncs, while the rest is cosmetic. It combines with the per-[`Component`] opt-in:
+/// a [`Component`] contributes only if it is checksummed, such as by
+/// [`checksum_component_with_hash`](`crate::GgrsApp::checksum_component_with_hash`), and its
+/// entity is a [`ChecksumContributor`]. The amount of rollback entities in the checksum is then
+/// also limited to contributors. [`Resources`](`Resource`) are unaffected.
+///
+/// The [`ChecksumContributor`] flag itself should be identical on every peer, so add it to
+/// entities when spawning them, or within the [`GgrsSchedule`](`crate::GgrsSchedule`).
+#[derive(Resource, Clone, Copy, Default, Debug)]
+pub struct ChecksumContributorsOnly;
+
+impl ChecksumContributorsOnly {
+    /// Returns `true` if an entity with the provided [`ChecksumContributor`] flag contributes to
+    /// the [`Checksum`].
+    pub(crate) fn includes(restricted: Option<&Self>, contributor: bool) -> bool {
+        contributor || restricted.is_none()
+    }
+}
+
+/// A [`Plugin`] which creates a [`Checksum`] resource which can be read once the
+/// [`SaveWorldSet::Checksum`] set in the [`SaveWorld`] schedule has been run.
 ///
 /// To add you own data to this [`Checksum`], create an [`Entity`] with a [`ChecksumPart`]
 /// [`Component`]. Every [`Entity`] with this [`Component`] will participate in the
-/// creation of a [`Checksum`].
+/// creation of a [`Checksum`]. Parts are combined independently of the order they were
+/// registered in, so peers registering the same types in a different order still agree.
+/// Create custom parts using [`checksum_hasher_for`](`crate::checksum_hasher_for`), so they
+/// cannot cancel out another part.
+///
+/// The parts provided by this crate are kept on a separate [`Entity`] per type, flagged with a
+/// [`ChecksumFlag`] for that type. When peers disagree on the [`Checksum`], compare the parts of
+/// each type, such as by logging `Query<&ChecksumPart, With<ChecksumFlag<Health>>>`, to narrow
+/// down which type diverged.
 ///
 /// # Examples
 /// ```rust
@@ -78,8 +123,8 @@ pub struct ChecksumPlugin;
 impl ChecksumPlugin {
     /// A [`System`] responsible for updating [`Checksum`] based on [`ChecksumParts`](`ChecksumPart`).
     pub fn update(mut checksum: ResMut<Checksum>, parts: Query<&ChecksumPart>) {
-        // TODO: Add explicit ordering to `ChecksumPart`'s to make checksum more robust to transposition
-        // XOR is commutative, ensuring order does not matter.
+        // XOR is commutative, ensuring the order parts were registered in does not matter.
+        // Parts provided by this crate are keyed by type, so equal parts do not cancel out.
         // Chosen over addition and multiplication as XOR is closed on u128
         let parts = parts.iter().fold(0, |a: u128, &ChecksumPart(b)| a ^ b);
 
@@ -91,11 +136,7 @@ impl ChecksumPlugin {
 
 impl Plugin for ChecksumPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Checksum>().add_systems(
-            SaveWorld,
-            Self::update
-                .after(SaveWorldSet::Checksum)
-                .before(SaveWorldSet::Snapshot),
-        );
+        app.init_resource::<Checksum>()
+            .add_systems(SaveWorld, Self::update.after(SaveWorldSet::Checksum));
     }
 }
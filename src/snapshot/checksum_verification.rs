@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+/// A [`Resource`] which, when present, verifies that the [`Checksum`](`crate::Checksum`) of every
+/// loaded frame matches the [`Checksum`](`crate::Checksum`) saved for that frame.
+///
+/// GGRS compares the checksums saved alongside each snapshot, but those checksums are computed
+/// from the live [`World`], not from the snapshots. If a type is checksummed but not rolled back,
+/// or rolled back in a way which does not restore everything that is hashed, the compared
+/// checksums no longer describe what is actually restored. Desyncs then surface far from their
+/// cause, or not at all.
+///
+/// After loading a frame, the [`SaveWorld`](`crate::SaveWorld`) schedule is run again to
+/// recompute its checksum from the restored state. Any difference to the checksum saved before
+/// is logged as an error and raised as a [`BevyGgrsError::ChecksumDrift`](`crate::BevyGgrsError::ChecksumDrift`).
+/// This doubles the cost of every rollback, so it is intended for debugging only. Enable it using
+/// [`GgrsApp::verify_snapshot_checksums`](`crate::GgrsApp::verify_snapshot_checksums`).
+#[derive(Resource, Debug, Default, Clone)]
+pub struct SnapshotChecksumVerification {
+    saved: BTreeMap<i32, u128>,
+    mismatches: usize,
+}
+
+impl SnapshotChecksumVerification {
+    /// The amount of loaded frames whose recomputed checksum differed from the saved one.
+    pub fn mismatches(&self) -> usize {
+        self.mismatches
+    }
+
+    /// Get the checksum saved for the provided frame, if it is still retained.
+    pub fn saved(&self, frame: i32) -> Option<u128> {
+        self.saved.get(&frame).copied()
+    }
+
+    /// Records the checksum saved for the provided frame, retaining at most `depth` frames.
+    /// Checksums of later frames are discarded, as they are about to be saved again.
+    pub(crate) fn record(&mut self, frame: i32, checksum: u128, depth: usize) {
+        self.saved.split_off(&frame);
+        self.saved.insert(frame, checksum);
+
+        while self.saved.len() > depth.max(1) {
+            self.saved.pop_first();
+        }
+    }
+
+    pub(crate) fn record_mismatch(&mut self) {
+        self.mismatches += 1;
+    }
+}
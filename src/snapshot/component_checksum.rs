@@ -3,7 +3,9 @@ use std::hash::{Hash, Hasher};
 use bevy::prelude::*;
 
 use crate::{
-    checksum_hasher, ChecksumFlag, ChecksumPart, Rollback, RollbackOrdered, SaveWorld, SaveWorldSet,
+    checksum_hasher, checksum_hasher_for, ActiveRollback, ChecksumContributor,
+    ChecksumContributorsOnly, ChecksumFlag, ChecksumPart, ChecksumSpawnFilter, NoRollback,
+    Rollback, RollbackOrdered, RollbackScope, RollbackSpawnFrame, SaveWorld, SaveWorldSet,
 };
 
 /// A [`Plugin`] which will track the [`Component`] `C` on [`Rollback Entities`](`Rollback`) and ensure a
@@ -58,19 +60,48 @@ where
 
         let update = move |mut commands: Commands,
                            rollback_ordered: Res<RollbackOrdered>,
+                           scope: Option<Res<RollbackScope>>,
+                           restricted: Option<Res<ChecksumContributorsOnly>>,
+                           spawn_filter: ChecksumSpawnFilter,
                            components: Query<
-            (&Rollback, &C),
-            (With<Rollback>, Without<ChecksumFlag<C>>),
+            (
+                &Rollback,
+                &C,
+                Has<ActiveRollback>,
+                Has<ChecksumContributor>,
+                Option<&RollbackSpawnFrame>,
+            ),
+            (
+                With<Rollback>,
+                Without<ChecksumFlag<C>>,
+                Without<NoRollback>,
+            ),
         >,
                            mut checksum: Query<
             &mut ChecksumPart,
             (Without<Rollback>, With<ChecksumFlag<C>>),
         >| {
-            let mut hasher = checksum_hasher();
+            let mut hasher = checksum_hasher_for::<C>();
 
             let mut result = 0;
 
-            for (&rollback, component) in components.iter() {
+            let scoped = scope.is_some();
+
+            for (&rollback, component, active, contributor, spawned) in components.iter() {
+                // Entities out of scope are not rolled back, and so cannot be compared
+                if scoped && !active {
+                    continue;
+                }
+
+                if !ChecksumContributorsOnly::includes(restricted.as_deref(), contributor) {
+                    continue;
+                }
+
+                // Entities spawned during prediction may not exist on every peer yet
+                if !spawn_filter.includes(spawned) {
+                    continue;
+                }
+
                 let mut hasher = hasher;
 
                 // Hashing the rollback index ensures this hash is unique and stable
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use bevy::{ecs::entity::MapEntities, prelude::*};
 
-use crate::{LoadWorld, LoadWorldSet, RollbackEntityMap};
+use crate::{EntityMappingAudit, LoadWorld, LoadWorldSet, RollbackEntityMap};
 
 /// A [`Plugin`] which updates the state of a post-rollback [`Component`] `C` using [`MapEntities`].
 ///
@@ -86,6 +86,8 @@ where
     C: Component + MapEntities,
 {
     fn build(&self, app: &mut App) {
+        EntityMappingAudit::register_mapped_in::<C>(app);
+
         app.add_systems(LoadWorld, Self::update.in_set(LoadWorldSet::Mapping));
     }
 }
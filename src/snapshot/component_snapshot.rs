@@ -1,11 +1,65 @@
 use crate::{
-    GgrsComponentSnapshot, GgrsComponentSnapshots, LoadWorld, LoadWorldSet, Rollback,
-    RollbackFrameCount, SaveWorld, SaveWorldSet, Strategy,
+    error::report_load_error, ActiveRollback, BevyGgrsError, EntityMappingAudit,
+    GgrsComponentSnapshot, GgrsComponentSnapshots, LoadWorld, LoadWorldSet, NamedSnapshots,
+    NoRollback, Rollback, RollbackExclusions, RollbackFrameCount, RollbackKey,
+    RollbackRegistrationFingerprint, RollbackScope, SaveWorld, SaveWorldSet, SnapshotMemoryUsage,
+    SnapshotStorages, Strategy,
+};
+use bevy::{
+    ecs::system::Command,
+    prelude::*,
+    tasks::{ComputeTaskPool, TaskPool},
+    utils::HashMap,
 };
-use bevy::prelude::*;
 use std::marker::PhantomData;
 
+/// When present, snapshots of a single [`Component`] type covering at least `min_entities`
+/// entities are created in parallel on the [`ComputeTaskPool`], split into one chunk per thread.
+///
+/// Snapshots of different [`Component`] types are already taken in parallel, as the [`SaveWorld`]
+/// schedule uses a multi-threaded executor. This additionally spreads the cost of storing a
+/// single type over many entities, which helps worlds with tens of thousands of entities.
+///
+/// Snapshots are still completed within the [`SaveWorld`] schedule, before the simulation is
+/// advanced again. Deferring them past that point is not supported: GGRS may request loading the
+/// saved frame within the very same step, and the next advance mutates the data being stored.
+/// Below `min_entities`, the overhead of spawning tasks outweighs the gains.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParallelSnapshots {
+    /// The minimum amount of entities to store a [`Component`] type in parallel.
+    pub min_entities: usize,
+}
+
+impl Default for ParallelSnapshots {
+    fn default() -> Self {
+        Self { min_entities: 4096 }
+    }
+}
+
+/// When present, rolling back never removes the [`Component`] `C` from an entity, even if the
+/// snapshot being loaded lacks it. Entities which do have `C` in the snapshot are still updated
+/// and inserted as usual. Registered using [`GgrsApp::keep_on_rollback`](`crate::GgrsApp::keep_on_rollback`).
+///
+/// This is intended for components which live on rollback entities but are not truly part of
+/// the simulation state, such as a render-only cache rebuilt lazily. Such components must not
+/// affect determinism, as a component kept this way may be present on one peer and missing on
+/// another.
+#[derive(Resource)]
+pub struct KeepOnRollback<C: Component> {
+    _phantom: PhantomData<fn() -> C>,
+}
+
+impl<C: Component> Default for KeepOnRollback<C> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
 /// A [`Plugin`] which manages snapshots for a [`Component`] using a provided [`Strategy`].
+/// Entities are matched to their snapshots by the [`RollbackKey`] `K`, which is [`Rollback`] by
+/// default.
 ///
 /// # Examples
 /// ```rust
@@ -24,20 +78,22 @@ use std::marker::PhantomData;
 /// app.add_plugins(ComponentSnapshotPlugin::<CloneStrategy<Transform>>::default());
 /// # }
 /// ```
-pub struct ComponentSnapshotPlugin<S>
+pub struct ComponentSnapshotPlugin<S, K = Rollback>
 where
     S: Strategy,
     S::Target: Component,
     S::Stored: Send + Sync + 'static,
+    K: RollbackKey,
 {
-    _phantom: PhantomData<S>,
+    _phantom: PhantomData<(S, K)>,
 }
 
-impl<S> Default for ComponentSnapshotPlugin<S>
+impl<S, K> Default for ComponentSnapshotPlugin<S, K>
 where
     S: Strategy,
     S::Target: Component,
     S::Stored: Send + Sync + 'static,
+    K: RollbackKey,
 {
     fn default() -> Self {
         Self {
@@ -46,80 +102,474 @@ where
     }
 }
 
-impl<S> ComponentSnapshotPlugin<S>
+impl<S, K> ComponentSnapshotPlugin<S, K>
 where
     S: Strategy,
     S::Target: Component,
     S::Stored: Send + Sync + 'static,
+    K: RollbackKey,
 {
     pub fn save(
-        mut snapshots: ResMut<GgrsComponentSnapshots<S::Target, S::Stored>>,
+        mut snapshots: ResMut<GgrsComponentSnapshots<S::Target, S::Stored, K>>,
         frame: Res<RollbackFrameCount>,
-        query: Query<(&Rollback, &S::Target)>,
+        scope: Option<Res<RollbackScope>>,
+        parallel: Option<Res<ParallelSnapshots>>,
+        query: Query<(&K, &S::Target, Has<ActiveRollback>), Without<NoRollback>>,
     ) {
-        let components = query
-            .iter()
-            .map(|(&rollback, component)| (rollback, S::store(component)));
-
-        let snapshot = GgrsComponentSnapshot::new(components);
-
-        trace!(
-            "Snapshot {} {} component(s)",
-            snapshot.iter().count(),
-            bevy::utils::get_short_name(std::any::type_name::<S::Target>())
+        save_components(
+            &mut snapshots,
+            frame.0,
+            scope.as_deref(),
+            parallel.as_deref(),
+            &query,
+            S::store,
         );
-
-        snapshots.push(frame.0, snapshot);
     }
 
     pub fn load(
         mut commands: Commands,
-        mut snapshots: ResMut<GgrsComponentSnapshots<S::Target, S::Stored>>,
+        mut snapshots: ResMut<GgrsComponentSnapshots<S::Target, S::Stored, K>>,
         frame: Res<RollbackFrameCount>,
-        mut query: Query<(Entity, &Rollback, Option<&mut S::Target>)>,
-    ) {
-        let snapshot = snapshots.rollback(frame.0).get();
+        scope: Option<Res<RollbackScope>>,
+        exclusions: Option<Res<RollbackExclusions>>,
+        keep: Option<Res<KeepOnRollback<S::Target>>>,
+        mut query: Query<(Entity, &K, Option<&mut S::Target>, Has<NoRollback>)>,
+    ) -> Result<(), BevyGgrsError>
+    where
+        S: 'static,
+    {
+        load_components(
+            &mut commands,
+            &mut snapshots,
+            frame.0,
+            scope.as_deref(),
+            exclusions.as_deref(),
+            keep.is_some(),
+            &mut query,
+            S::load,
+            S::update,
+            |commands, entity, &key, _| {
+                commands.add(LoadMissingComponent::<S, K> {
+                    entity,
+                    key,
+                    _phantom: PhantomData,
+                });
+            },
+        )
+    }
+}
 
-        for (entity, rollback, component) in query.iter_mut() {
-            let snapshot = snapshot.get(rollback);
+/// A [`Command`] inserting a [`Component`] missing from an entity, recreated from the current
+/// snapshot using [`Strategy::load_with_world`].
+struct LoadMissingComponent<S, K> {
+    entity: Entity,
+    key: K,
+    _phantom: PhantomData<fn() -> S>,
+}
 
-            match (component, snapshot) {
-                (Some(mut component), Some(snapshot)) => S::update(component.as_mut(), snapshot),
-                (Some(_), None) => {
-                    commands.entity(entity).remove::<S::Target>();
-                }
-                (None, Some(snapshot)) => {
-                    commands.entity(entity).insert(S::load(snapshot));
-                }
-                (None, None) => {}
-            }
-        }
+impl<S, K> Command for LoadMissingComponent<S, K>
+where
+    S: Strategy + 'static,
+    S::Target: Component,
+    S::Stored: Send + Sync + 'static,
+    K: RollbackKey,
+{
+    fn apply(self, world: &mut World) {
+        world.resource_scope(
+            |world, snapshots: Mut<GgrsComponentSnapshots<S::Target, S::Stored, K>>| {
+                let Some(stored) = snapshots.get().get(&self.key) else {
+                    return;
+                };
 
-        trace!(
-            "Rolled back {} {} component(s)",
-            snapshot.iter().count(),
-            bevy::utils::get_short_name(std::any::type_name::<S::Target>())
+                let component = S::load_with_world(stored, world);
+
+                if let Some(mut entity) = world.get_entity_mut(self.entity) {
+                    entity.insert(component);
+                }
+            },
         );
     }
 }
 
-impl<S> Plugin for ComponentSnapshotPlugin<S>
+impl<S, K> Plugin for ComponentSnapshotPlugin<S, K>
 where
     S: Send + Sync + 'static + Strategy,
     S::Target: Component,
     S::Stored: Send + Sync + 'static,
+    K: RollbackKey,
 {
     fn build(&self, app: &mut App) {
-        app.init_resource::<GgrsComponentSnapshots<S::Target, S::Stored>>()
+        RollbackRegistrationFingerprint::register_in::<
+            GgrsComponentSnapshots<S::Target, S::Stored, K>,
+        >(app);
+        NamedSnapshots::register_storage_in::<GgrsComponentSnapshots<S::Target, S::Stored, K>>(app);
+        SnapshotStorages::register_in::<GgrsComponentSnapshots<S::Target, S::Stored, K>>(app);
+        EntityMappingAudit::register_rolled_back_in::<S::Target>(app);
+
+        app.init_resource::<GgrsComponentSnapshots<S::Target, S::Stored, K>>()
             .add_systems(
                 SaveWorld,
                 (
-                    GgrsComponentSnapshots::<S::Target, S::Stored>::discard_old_snapshots,
+                    GgrsComponentSnapshots::<S::Target, S::Stored, K>::discard_old_snapshots,
                     Self::save,
+                    SnapshotMemoryUsage::record_component::<S::Target, S::Stored, K>
+                        .run_if(resource_exists::<SnapshotMemoryUsage>),
                 )
                     .chain()
                     .in_set(SaveWorldSet::Snapshot),
             )
-            .add_systems(LoadWorld, Self::load.in_set(LoadWorldSet::Data));
+            .add_systems(
+                LoadWorld,
+                Self::load
+                    .pipe(report_load_error)
+                    .in_set(LoadWorldSet::Data),
+            );
+
+        #[cfg(all(feature = "round-trip-check", debug_assertions))]
+        app.add_systems(
+            SaveWorld,
+            check_round_trip::<S, K>.in_set(SaveWorldSet::Snapshot),
+        );
+    }
+}
+
+/// Stores and loads the first [`Component`] found, panicking if the loaded value differs
+/// according to [`Strategy::round_trip_difference`]. Only a single value is ever checked.
+///
+/// This turns a [`Strategy`] which silently drops fields, such as a [`ReflectStrategy`](`crate::ReflectStrategy`)
+/// for a type with `#[reflect(ignore)]` fields, into a panic naming the offending field, rather
+/// than a desync once rolling back. Only available in debug builds with the `round-trip-check`
+/// feature.
+#[cfg(all(feature = "round-trip-check", debug_assertions))]
+fn check_round_trip<S, K>(world: &mut World, mut checked: Local<bool>)
+where
+    S: Strategy + 'static,
+    S::Target: Component,
+    K: Component,
+{
+    if *checked {
+        return;
+    }
+
+    let mut query = world.query_filtered::<&S::Target, With<K>>();
+
+    let Some(stored) = query.iter(world).next().map(S::store) else {
+        return;
+    };
+
+    *checked = true;
+
+    let loaded = S::load_with_world(&stored, world);
+
+    let Some(target) = query.iter(world).next() else {
+        return;
+    };
+
+    if let Some(difference) = S::round_trip_difference(target, &loaded) {
+        panic!(
+            "{} is not restored exactly when rolling back: {difference}",
+            bevy::utils::get_short_name(std::any::type_name::<S::Target>())
+        );
+    }
+}
+
+/// A [`Plugin`] which manages snapshots for a [`Component`] `C`, stored as `As` using a pair of
+/// transform functions. This is useful when the stored form of a [`Component`] should differ from
+/// its runtime form, such as quantizing a position to save memory and enforce determinism.
+///
+/// The provided functions should form a bijection between `C` and `As` for any value `C` can take
+/// during the simulation, otherwise rolling back will not restore the exact state of the snapshot.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ComponentSnapshotTransformPlugin};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// #[derive(Component, Clone, Copy)]
+/// struct Position(Vec2);
+///
+/// // Positions are stored as fixed-point integers in 1/256 units
+/// app.add_plugins(ComponentSnapshotTransformPlugin::<Position, IVec2>::new(
+///     |position| (position.0 * 256.).round().as_ivec2(),
+///     |stored| Position(stored.as_vec2() / 256.),
+/// ));
+/// # }
+/// ```
+pub struct ComponentSnapshotTransformPlugin<C, As>
+where
+    C: Component,
+    As: Send + Sync + 'static,
+{
+    /// Transforms a [`Component`] into its stored form.
+    pub store: for<'a> fn(&'a C) -> As,
+    /// Transforms a stored form back into a [`Component`].
+    pub load: for<'a> fn(&'a As) -> C,
+}
+
+impl<C, As> ComponentSnapshotTransformPlugin<C, As>
+where
+    C: Component,
+    As: Send + Sync + 'static,
+{
+    /// Create a new [`ComponentSnapshotTransformPlugin`] from a pair of transform functions.
+    pub fn new(store: for<'a> fn(&'a C) -> As, load: for<'a> fn(&'a As) -> C) -> Self {
+        Self { store, load }
+    }
+}
+
+impl<C, As> Plugin for ComponentSnapshotTransformPlugin<C, As>
+where
+    C: Component,
+    As: Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        let store = self.store;
+        let load = self.load;
+
+        let save = move |mut snapshots: ResMut<GgrsComponentSnapshots<C, As>>,
+                         frame: Res<RollbackFrameCount>,
+                         scope: Option<Res<RollbackScope>>,
+                         parallel: Option<Res<ParallelSnapshots>>,
+                         query: Query<
+            (&Rollback, &C, Has<ActiveRollback>),
+            Without<NoRollback>,
+        >| {
+            save_components(
+                &mut snapshots,
+                frame.0,
+                scope.as_deref(),
+                parallel.as_deref(),
+                &query,
+                store,
+            );
+        };
+
+        let load = move |mut commands: Commands,
+                         mut snapshots: ResMut<GgrsComponentSnapshots<C, As>>,
+                         frame: Res<RollbackFrameCount>,
+                         scope: Option<Res<RollbackScope>>,
+                         exclusions: Option<Res<RollbackExclusions>>,
+                         keep: Option<Res<KeepOnRollback<C>>>,
+                         mut query: Query<(Entity, &Rollback, Option<&mut C>, Has<NoRollback>)>| {
+            load_components(
+                &mut commands,
+                &mut snapshots,
+                frame.0,
+                scope.as_deref(),
+                exclusions.as_deref(),
+                keep.is_some(),
+                &mut query,
+                load,
+                |component: &mut C, stored: &As| *component = load(stored),
+                |commands: &mut Commands, entity, _: &Rollback, stored: &As| {
+                    commands.entity(entity).insert(load(stored));
+                },
+            )
+        };
+
+        RollbackRegistrationFingerprint::register_in::<GgrsComponentSnapshots<C, As>>(app);
+        NamedSnapshots::register_storage_in::<GgrsComponentSnapshots<C, As>>(app);
+        SnapshotStorages::register_in::<GgrsComponentSnapshots<C, As>>(app);
+        EntityMappingAudit::register_rolled_back_in::<C>(app);
+
+        app.init_resource::<GgrsComponentSnapshots<C, As>>()
+            .add_systems(
+                SaveWorld,
+                (
+                    GgrsComponentSnapshots::<C, As>::discard_old_snapshots,
+                    save,
+                    SnapshotMemoryUsage::record_component::<C, As, Rollback>
+                        .run_if(resource_exists::<SnapshotMemoryUsage>),
+                )
+                    .chain()
+                    .in_set(SaveWorldSet::Snapshot),
+            )
+            .add_systems(
+                LoadWorld,
+                load.pipe(report_load_error).in_set(LoadWorldSet::Data),
+            );
+    }
+}
+
+/// Push a snapshot of all entities with a [`RollbackKey`] `K` and a [`Component`] `C` for the
+/// provided frame, skipping entities [excluded](`NoRollback`) from rollback.
+fn save_components<C, As, K>(
+    snapshots: &mut GgrsComponentSnapshots<C, As, K>,
+    frame: i32,
+    scope: Option<&RollbackScope>,
+    parallel: Option<&ParallelSnapshots>,
+    query: &Query<(&K, &C, Has<ActiveRollback>), Without<NoRollback>>,
+    store: impl Fn(&C) -> As + Sync,
+) where
+    C: Component,
+    As: Send,
+    K: RollbackKey,
+{
+    let scoped = scope.is_some();
+
+    let components = query
+        .iter()
+        .filter(|&(key, _, active)| active || !scoped || key.as_rollback().is_none())
+        .map(|(&key, component, _)| (key, component));
+
+    let snapshot = match parallel {
+        Some(parallel) if query.iter().len() >= parallel.min_entities => {
+            let components = components.collect::<Vec<_>>();
+
+            let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+            let chunk_size = components.len().div_ceil(pool.thread_num().max(1)).max(1);
+            let store = &store;
+
+            let chunks = pool.scope(|scope| {
+                for chunk in components.chunks(chunk_size) {
+                    scope.spawn(async move {
+                        chunk
+                            .iter()
+                            .map(|&(key, component)| (key, store(component)))
+                            .collect::<Vec<_>>()
+                    });
+                }
+            });
+
+            GgrsComponentSnapshot::new(chunks.into_iter().flatten())
+        }
+        _ => GgrsComponentSnapshot::new(components.map(|(key, component)| (key, store(component)))),
+    };
+
+    trace!(
+        "Snapshot {} {} component(s)",
+        snapshot.iter().count(),
+        bevy::utils::get_short_name(std::any::type_name::<C>())
+    );
+
+    snapshots.push(frame, snapshot);
+}
+
+/// Rollback all entities with a [`RollbackKey`] `K` to match the snapshot for [`Component`] `C`
+/// at the provided frame. If `keep` is set, `C` is never removed, see [`KeepOnRollback`].
+///
+/// Nothing is changed if no snapshot is held for the provided frame.
+#[allow(clippy::too_many_arguments)]
+fn load_components<C, As, K>(
+    commands: &mut Commands,
+    snapshots: &mut GgrsComponentSnapshots<C, As, K>,
+    frame: i32,
+    scope: Option<&RollbackScope>,
+    exclusions: Option<&RollbackExclusions>,
+    keep: bool,
+    query: &mut Query<(Entity, &K, Option<&mut C>, Has<NoRollback>)>,
+    load: impl Fn(&As) -> C,
+    update: impl Fn(&mut C, &As),
+    insert: impl Fn(&mut Commands, Entity, &K, &As),
+) -> Result<(), BevyGgrsError>
+where
+    C: Component,
+    K: RollbackKey,
+{
+    // Exclusions are rare, so only consult them if any entity was excluded on this frame
+    let exclusions = exclusions.filter(|exclusions| !exclusions.is_empty(frame));
+
+    // Entities which entered the scope, or were included again, after the frame being rolled
+    // back to were static until then, so they are restored from the snapshot taken as they did.
+    let mut entered: HashMap<K, Option<C>> = if scope.is_some() || exclusions.is_some() {
+        query
+            .iter()
+            .filter_map(|(_, key, ..)| {
+                let entered_frame = entered_after(scope, exclusions, frame, key.as_rollback()?)?;
+                let stored = snapshots
+                    .peek(entered_frame)
+                    .and_then(|snapshot| snapshot.get(key));
+                Some((*key, stored.map(&load)))
+            })
+            .collect()
+    } else {
+        default()
+    };
+
+    let snapshot = snapshots
+        .try_rollback(frame)
+        .ok_or(BevyGgrsError::SnapshotMissing { frame })?
+        .get();
+
+    for (entity, key, component, excluded) in query.iter_mut() {
+        let inactive = key
+            .as_rollback()
+            .is_some_and(|rollback| was_inactive(scope, exclusions, frame, rollback));
+
+        // Excluded entities which did not exist during the frame are none of rollback's business
+        if excluded && !inactive {
+            continue;
+        }
+
+        if inactive {
+            match (component, entered.remove(key)) {
+                (Some(mut component), Some(Some(entered))) => *component = entered,
+                (Some(_), Some(None)) if !keep => {
+                    commands.entity(entity).remove::<C>();
+                }
+                (None, Some(Some(entered))) => {
+                    commands.entity(entity).insert(entered);
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let snapshot = snapshot.get(key);
+
+        match (component, snapshot) {
+            (Some(mut component), Some(snapshot)) => update(component.as_mut(), snapshot),
+            (Some(_), None) if !keep => {
+                commands.entity(entity).remove::<C>();
+            }
+            (None, Some(snapshot)) => insert(commands, entity, key, snapshot),
+            (_, None) => {}
+        }
+    }
+
+    trace!(
+        "Rolled back {} {} component(s)",
+        snapshot.iter().count(),
+        bevy::utils::get_short_name(std::any::type_name::<C>())
+    );
+
+    Ok(())
+}
+
+/// Returns `true` if the provided [`Rollback`] was out of scope or [excluded](`NoRollback`)
+/// during the provided frame.
+fn was_inactive(
+    scope: Option<&RollbackScope>,
+    exclusions: Option<&RollbackExclusions>,
+    frame: i32,
+    rollback: &Rollback,
+) -> bool {
+    scope.is_some_and(|scope| !scope.was_active(frame, rollback))
+        || exclusions.is_some_and(|exclusions| exclusions.was_excluded(frame, rollback))
+}
+
+/// If the provided [`Rollback`] was out of scope or [excluded](`NoRollback`) during the provided
+/// frame, returns the earliest retained frame after it where it was neither, if any.
+fn entered_after(
+    scope: Option<&RollbackScope>,
+    exclusions: Option<&RollbackExclusions>,
+    frame: i32,
+    rollback: &Rollback,
+) -> Option<i32> {
+    let out_of_scope = scope.is_some_and(|scope| !scope.was_active(frame, rollback));
+    let excluded = exclusions.is_some_and(|exclusions| exclusions.was_excluded(frame, rollback));
+
+    let entered = || scope?.entered_after(frame, rollback);
+    let included = || exclusions?.included_after(frame, rollback);
+
+    match (out_of_scope, excluded) {
+        (false, false) => None,
+        (true, false) => entered(),
+        (false, true) => included(),
+        (true, true) => Some(entered()?.max(included()?)),
     }
 }
@@ -0,0 +1,145 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    AdvanceWorld, AdvanceWorldSet, ComponentSnapshotPlugin, CopyStrategy, MaxPredictionWindow,
+    Rollback, RollbackFrameCount,
+};
+
+/// The frame a [`Rollback`] entity was spawned on, tracked while [`ChecksumConfirmedEntitiesOnly`]
+/// is present. This is rolled back along with the entity.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RollbackSpawnFrame(Option<i32>);
+
+impl RollbackSpawnFrame {
+    /// The frame this entity was spawned on while advancing, or [`None`] if it was spawned
+    /// outside of advancing a frame, such as before the [`Session`](`crate::Session`) started.
+    pub fn frame(&self) -> Option<i32> {
+        self.0
+    }
+}
+
+/// When present, [`Rollback`] entities only contribute to the [`Checksum`](`crate::Checksum`)
+/// once they were spawned on a frame every peer has confirmed. Set this using
+/// [`GgrsApp::checksum_confirmed_entities_only`](`crate::GgrsApp::checksum_confirmed_entities_only`).
+///
+/// Entities spawned during prediction are likely to be rolled back, and a peer which has not yet
+/// received the inputs spawning them will disagree on whether they exist at all. In games spawning
+/// many entities, such as projectiles, this is a source of transient mismatches which resolve
+/// themselves once the inputs arrive. While this is present, an entity is left out of the
+/// checksum of a frame until it was spawned at least [`MaxPredictionWindow`] frames before it,
+/// as no peer can still be predicting the frame it was spawned on by then. The age of an entity
+/// is used rather than the [`ConfirmedFrameCount`](`crate::ConfirmedFrameCount`), as the latter
+/// differs between peers saving the same frame, which would itself cause mismatches.
+///
+/// The tradeoff is that a genuine desync on a new entity, such as one spawned with the wrong
+/// state or not spawned at all, goes unnoticed until the entity has aged past the window, by
+/// which point the state which caused it may no longer be available to roll back to. Entities
+/// which are spawned and despawned within the window are never checked at all, and the total
+/// amount of rollback entities ever spawned is no longer included either. Only use this once
+/// transient mismatches are known to come from predicted spawns.
+///
+/// Entities spawned outside of advancing a frame, such as before the session started, always
+/// contribute. [`Resources`](`Resource`) are unaffected.
+#[derive(Resource, Clone, Copy, Default, Debug)]
+pub struct ChecksumConfirmedEntitiesOnly;
+
+/// Decides whether a [`Rollback`] entity contributes to the checksum of the frame being saved,
+/// based on its [`RollbackSpawnFrame`] and [`ChecksumConfirmedEntitiesOnly`].
+#[derive(SystemParam)]
+pub struct ChecksumSpawnFilter<'w> {
+    confirmed_only: Option<Res<'w, ChecksumConfirmedEntitiesOnly>>,
+    frame: Option<Res<'w, RollbackFrameCount>>,
+    max_prediction: Option<Res<'w, MaxPredictionWindow>>,
+}
+
+impl<'w> ChecksumSpawnFilter<'w> {
+    /// Returns `true` while [`ChecksumConfirmedEntitiesOnly`] is present.
+    pub fn is_enabled(&self) -> bool {
+        self.confirmed_only.is_some()
+    }
+
+    /// Returns `true` if [`ChecksumConfirmedEntitiesOnly`] was added or removed since this
+    /// system last ran.
+    pub(crate) fn is_toggled(&self, was_enabled: bool) -> bool {
+        match &self.confirmed_only {
+            Some(confirmed_only) => confirmed_only.is_added(),
+            None => was_enabled,
+        }
+    }
+
+    /// The frame being saved.
+    pub fn frame(&self) -> i32 {
+        self.frame.as_deref().map_or(0, |frame| frame.0)
+    }
+
+    /// The latest frame an entity may have been spawned on to contribute to the checksum of the
+    /// frame being saved.
+    pub fn latest_spawn_frame(&self) -> i32 {
+        let window = self
+            .max_prediction
+            .as_deref()
+            .map_or(0, |max_prediction| max_prediction.0);
+
+        self.frame().saturating_sub(window as i32)
+    }
+
+    /// Returns `true` if an entity with the provided [`RollbackSpawnFrame`] contributes to the
+    /// checksum of the frame being saved.
+    pub fn includes(&self, spawned: Option<&RollbackSpawnFrame>) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+
+        match spawned.and_then(RollbackSpawnFrame::frame) {
+            Some(spawned) => spawned <= self.latest_spawn_frame(),
+            None => true,
+        }
+    }
+}
+
+/// A [`Plugin`] which tracks the [`RollbackSpawnFrame`] of every [`Rollback`] entity, and inserts
+/// [`ChecksumConfirmedEntitiesOnly`].
+pub struct ConfirmedChecksumPlugin;
+
+impl ConfirmedChecksumPlugin {
+    /// Records entities which already existed before advancing the frame as spawned outside of it.
+    pub fn record_existing(world: &mut World) {
+        Self::record(world, None);
+    }
+
+    /// Records entities spawned while advancing the frame as spawned on the current
+    /// [`RollbackFrameCount`].
+    pub fn record_spawned(world: &mut World) {
+        let frame = world
+            .get_resource::<RollbackFrameCount>()
+            .map_or(0, |frame| frame.0);
+
+        Self::record(world, Some(frame));
+    }
+
+    fn record(world: &mut World, frame: Option<i32>) {
+        let unrecorded = world
+            .query_filtered::<Entity, (With<Rollback>, Without<RollbackSpawnFrame>)>()
+            .iter(world)
+            .collect::<Vec<_>>();
+
+        for entity in unrecorded {
+            world.entity_mut(entity).insert(RollbackSpawnFrame(frame));
+        }
+    }
+}
+
+impl Plugin for ConfirmedChecksumPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ChecksumConfirmedEntitiesOnly)
+            .add_plugins(ComponentSnapshotPlugin::<CopyStrategy<RollbackSpawnFrame>>::default())
+            .add_systems(
+                AdvanceWorld,
+                Self::record_existing.in_set(AdvanceWorldSet::First),
+            )
+            .add_systems(
+                AdvanceWorld,
+                Self::record_spawned.in_set(AdvanceWorldSet::Last),
+            );
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    error::report_load_error, BevyGgrsError, ConfirmedFrameCount, EntityMappingAudit,
+    KeepOnRollback, LoadWorld, LoadWorldSet, NamedSnapshots, NoRollback, Rollback,
+    RollbackFrameCount, RollbackRegistrationFingerprint, SaveWorld, SaveWorldSet,
+    SnapshotRetention, SnapshotStorage, SnapshotStorages, DEFAULT_FPS,
+};
+
+/// The changes to a [`Component`] `C` between two consecutive snapshots, keyed by [`Rollback`],
+/// where `None` marks a removed component.
+type Delta<C> = HashMap<Rollback, Option<C>>;
+
+/// Delta-compressed snapshots for a [`Component`] `C`, used by the [`ComponentDeltaSnapshotPlugin`].
+///
+/// A single full baseline snapshot is stored, followed by one delta per frame containing only
+/// the components which changed since the previous frame. The baseline moves forward as frames
+/// are confirmed, or once more than [`depth`](`GgrsDeltaSnapshots::depth`) frames are stored.
+#[derive(Resource)]
+pub struct GgrsDeltaSnapshots<C> {
+    /// The frame of the baseline, or `None` before the first snapshot.
+    baseline_frame: Option<i32>,
+    /// Full snapshot for the baseline frame.
+    baseline: HashMap<Rollback, C>,
+    /// Deltas after the baseline, oldest at the front.
+    deltas: VecDeque<(i32, Delta<C>)>,
+    /// Full snapshot for the newest frame, reconstructed after every rollback.
+    latest: HashMap<Rollback, C>,
+    /// Maximum amount of frames to store at any one time.
+    depth: usize,
+}
+
+impl<C: Send + Sync + 'static> SnapshotStorage for GgrsDeltaSnapshots<C> {
+    fn contains_frame(&self, frame: i32) -> bool {
+        self.contains(frame)
+    }
+}
+
+impl<C> Default for GgrsDeltaSnapshots<C> {
+    fn default() -> Self {
+        Self {
+            baseline_frame: None,
+            baseline: default(),
+            deltas: default(),
+            latest: default(),
+            depth: DEFAULT_FPS,
+        }
+    }
+}
+
+impl<C: Clone + PartialEq> GgrsDeltaSnapshots<C> {
+    /// Push a new snapshot for the provided frame, storing only the difference to the previous
+    /// frame. If the frame is not after every currently stored frame, those frames are discarded.
+    pub fn push(&mut self, frame: i32, snapshot: HashMap<Rollback, C>) -> &mut Self {
+        self.push_with_eq(frame, snapshot, |a: &C, b: &C| a == b)
+    }
+}
+
+impl<C: Clone> GgrsDeltaSnapshots<C> {
+    /// Updates the maximum amount of frames stored, including the baseline. At least one frame is
+    /// always retained, so a depth of `0` is treated as `1`.
+    pub fn set_depth(&mut self, depth: usize) -> &mut Self {
+        self.depth = depth.max(1);
+        self.enforce_depth();
+        self
+    }
+
+    /// Get the maximum amount of frames stored.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Iterate over the frames of all retained snapshots, newest first.
+    pub fn frames(&self) -> impl Iterator<Item = i32> + '_ {
+        self.deltas
+            .iter()
+            .rev()
+            .map(|&(frame, _)| frame)
+            .chain(self.baseline_frame)
+    }
+
+    /// Returns `true` if a snapshot can be reconstructed for the provided frame.
+    pub fn contains(&self, frame: i32) -> bool {
+        self.baseline_frame == Some(frame) || self.deltas.iter().any(|&(saved, _)| saved == frame)
+    }
+
+    /// The amount of changed components stored across all deltas.
+    pub fn delta_len(&self) -> usize {
+        self.deltas.iter().map(|(_, delta)| delta.len()).sum()
+    }
+
+    /// Like [`push`](`GgrsDeltaSnapshots::push`), but a component is only stored as changed if
+    /// `eq` considers it different from the value stored last, see [`ComponentDeltaSnapshotPlugin`].
+    pub fn push_with_eq(
+        &mut self,
+        frame: i32,
+        snapshot: HashMap<Rollback, C>,
+        eq: impl Fn(&C, &C) -> bool,
+    ) -> &mut Self {
+        let newest = self.frames().next();
+
+        if newest.is_some_and(|newest| newest >= frame) {
+            self.deltas.retain(|&(saved, _)| saved < frame);
+
+            if self
+                .baseline_frame
+                .is_some_and(|baseline| baseline >= frame)
+            {
+                self.baseline_frame = None;
+                self.deltas.clear();
+            }
+
+            self.latest = self.reconstruct();
+        }
+
+        if self.baseline_frame.is_none() {
+            self.baseline_frame = Some(frame);
+            self.baseline = snapshot.clone();
+            self.latest = snapshot;
+            return self;
+        }
+
+        let mut delta: Delta<C> = snapshot
+            .iter()
+            .filter(|&(rollback, component)| {
+                !self
+                    .latest
+                    .get(rollback)
+                    .is_some_and(|stored| eq(stored, component))
+            })
+            .map(|(&rollback, component)| (rollback, Some(component.clone())))
+            .collect();
+
+        delta.extend(
+            self.latest
+                .keys()
+                .filter(|rollback| !snapshot.contains_key(rollback))
+                .map(|&rollback| (rollback, None)),
+        );
+
+        // values considered equal are not stored, so later frames are compared to the stored value
+        apply_delta(&mut self.latest, &delta);
+        self.deltas.push_back((frame, delta));
+        self.enforce_depth();
+
+        self
+    }
+
+    /// Confirms a frame as being stable across clients, moving the baseline up to it.
+    pub fn confirm(&mut self, confirmed_frame: i32) -> &mut Self {
+        while self
+            .deltas
+            .front()
+            .is_some_and(|&(frame, _)| frame <= confirmed_frame)
+        {
+            self.advance_baseline();
+        }
+
+        self
+    }
+
+    /// Rolls back to the provided frame, discarding snapshots taken after it, and returns the
+    /// reconstructed snapshot. Returns `None` if the frame is not retained, leaving the stored
+    /// snapshots untouched.
+    ///
+    /// Reconstructing copies the baseline and applies every delta up to the provided frame, so
+    /// rolling back further from the newest frame is cheaper, as more deltas are discarded first.
+    pub fn try_rollback(&mut self, frame: i32) -> Option<&HashMap<Rollback, C>> {
+        if !self.contains(frame) {
+            return None;
+        }
+
+        self.deltas.retain(|&(saved, _)| saved <= frame);
+        self.latest = self.reconstruct();
+
+        Some(&self.latest)
+    }
+
+    /// A system which automatically confirms the [`ConfirmedFrameCount`], discarding older deltas.
+    /// The depth follows the [`SnapshotRetention`], if any.
+    pub fn discard_old_snapshots(
+        mut snapshots: ResMut<Self>,
+        confirmed_frame: Option<Res<ConfirmedFrameCount>>,
+        retention: Option<Res<SnapshotRetention>>,
+    ) where
+        C: Send + Sync + 'static,
+    {
+        if let Some(retention) = retention {
+            snapshots.set_depth(retention.frames());
+        }
+
+        let Some(confirmed_frame) = confirmed_frame else {
+            return;
+        };
+
+        snapshots.confirm(confirmed_frame.0);
+    }
+
+    /// Applies every stored delta to a copy of the baseline.
+    fn reconstruct(&self) -> HashMap<Rollback, C> {
+        let mut snapshot = self.baseline.clone();
+
+        for (_, delta) in &self.deltas {
+            apply_delta(&mut snapshot, delta);
+        }
+
+        snapshot
+    }
+
+    /// Folds the oldest delta into the baseline.
+    fn advance_baseline(&mut self) {
+        if let Some((frame, delta)) = self.deltas.pop_front() {
+            apply_delta(&mut self.baseline, &delta);
+            self.baseline_frame = Some(frame);
+        }
+    }
+
+    fn enforce_depth(&mut self) {
+        while self.deltas.len() + 1 > self.depth {
+            self.advance_baseline();
+        }
+    }
+}
+
+fn apply_delta<C: Clone>(snapshot: &mut HashMap<Rollback, C>, delta: &Delta<C>) {
+    for (&rollback, component) in delta {
+        match component {
+            Some(component) => {
+                snapshot.insert(rollback, component.clone());
+            }
+            None => {
+                snapshot.remove(&rollback);
+            }
+        }
+    }
+}
+
+/// A [`Plugin`] which manages delta-compressed snapshots for a [`Component`] `C`, see
+/// [`GgrsDeltaSnapshots`]. Register it using [`GgrsApp::rollback_component_with_delta`](`crate::GgrsApp::rollback_component_with_delta`).
+///
+/// Only components which differ from the previous frame are stored, which greatly reduces memory
+/// usage for components which rarely change, at the cost of CPU time. Every save compares each
+/// component against the previous frame, and every load reconstructs the whole snapshot from the
+/// baseline, applying every retained delta up to the frame being loaded. Deep rollbacks in a
+/// session with a large prediction window therefore apply many deltas, while shallow rollbacks
+/// cost about as much as a [`Clone`] based snapshot.
+///
+/// Every entity is always snapshot, as a [`RollbackScope`](`crate::RollbackScope`) is not
+/// supported.
+///
+/// # Custom Equality
+///
+/// A component is considered changed unless it [equals](`PartialEq`) the value stored last. A
+/// custom equality can be provided using [`with_eq`](`ComponentDeltaSnapshotPlugin::with_eq`),
+/// such as to ignore tiny floating point jitter. Values are always stored and restored exactly,
+/// so the equality only decides which values are stored: a component considered equal to the
+/// value stored last is restored as that value after a rollback, not as the value it had on the
+/// frame being loaded.
+///
+/// Only peers which roll back observe the restored value, so the equality must never consider
+/// two values equal unless the simulation treats them identically, such as values quantized
+/// before being used. Otherwise, peers which rolled back diverge from those which did not. The
+/// equality must also be deterministic, as it decides the state restored on every peer.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ComponentDeltaSnapshotPlugin};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// #[derive(Component, Clone, PartialEq)]
+/// struct Terrain(Vec<u8>);
+///
+/// // Terrain is large, but rarely changes
+/// app.add_plugins(ComponentDeltaSnapshotPlugin::<Terrain>::default());
+///
+/// #[derive(Component, Clone, PartialEq)]
+/// struct Height(i32);
+///
+/// // Only the height in whole metres is ever read by the simulation
+/// app.add_plugins(ComponentDeltaSnapshotPlugin::<Height>::with_eq(|a, b| {
+///     a.0.div_euclid(1000) == b.0.div_euclid(1000)
+/// }));
+/// ```
+pub struct ComponentDeltaSnapshotPlugin<C>
+where
+    C: Component + Clone + PartialEq,
+{
+    /// Decides whether a component is unchanged from the value stored last.
+    eq: fn(&C, &C) -> bool,
+}
+
+impl<C> Default for ComponentDeltaSnapshotPlugin<C>
+where
+    C: Component + Clone + PartialEq,
+{
+    fn default() -> Self {
+        Self { eq: |a, b| a == b }
+    }
+}
+
+impl<C> ComponentDeltaSnapshotPlugin<C>
+where
+    C: Component + Clone + PartialEq,
+{
+    /// Creates a plugin which considers a component unchanged from the value stored last if `eq`
+    /// returns `true`. See [Custom Equality](`ComponentDeltaSnapshotPlugin#custom-equality`) for
+    /// the requirements on `eq`.
+    pub fn with_eq(eq: fn(&C, &C) -> bool) -> Self {
+        Self { eq }
+    }
+
+    /// Push a snapshot of all entities with a [`Rollback`] and `C`, using `eq` to decide which
+    /// components changed.
+    pub fn save(
+        mut snapshots: ResMut<GgrsDeltaSnapshots<C>>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<(&Rollback, &C), Without<NoRollback>>,
+        eq: fn(&C, &C) -> bool,
+    ) {
+        let snapshot = query
+            .iter()
+            .map(|(&rollback, component)| (rollback, component.clone()))
+            .collect();
+
+        snapshots.push_with_eq(frame.0, snapshot, eq);
+
+        trace!(
+            "Snapshot {} {} component(s) as a delta",
+            snapshots.deltas.back().map_or(0, |(_, delta)| delta.len()),
+            bevy::utils::get_short_name(std::any::type_name::<C>())
+        );
+    }
+
+    pub fn load(
+        mut commands: Commands,
+        mut snapshots: ResMut<GgrsDeltaSnapshots<C>>,
+        frame: Res<RollbackFrameCount>,
+        keep: Option<Res<KeepOnRollback<C>>>,
+        mut query: Query<(Entity, &Rollback, Option<&mut C>), Without<NoRollback>>,
+    ) -> Result<(), BevyGgrsError> {
+        let snapshot = snapshots
+            .try_rollback(frame.0)
+            .ok_or(BevyGgrsError::SnapshotMissing { frame: frame.0 })?;
+
+        for (entity, rollback, component) in query.iter_mut() {
+            match (component, snapshot.get(rollback)) {
+                (Some(mut component), Some(snapshot)) => {
+                    if *component != *snapshot {
+                        *component = snapshot.clone();
+                    }
+                }
+                (Some(_), None) if keep.is_none() => {
+                    commands.entity(entity).remove::<C>();
+                }
+                (None, Some(snapshot)) => {
+                    commands.entity(entity).insert(snapshot.clone());
+                }
+                (_, None) => {}
+            }
+        }
+
+        trace!(
+            "Rolled back {} {} component(s) from a delta",
+            snapshot.len(),
+            bevy::utils::get_short_name(std::any::type_name::<C>())
+        );
+
+        Ok(())
+    }
+}
+
+impl<C> Plugin for ComponentDeltaSnapshotPlugin<C>
+where
+    C: Component + Clone + PartialEq,
+{
+    fn build(&self, app: &mut App) {
+        let eq = self.eq;
+
+        let save = move |snapshots: ResMut<GgrsDeltaSnapshots<C>>,
+                         frame: Res<RollbackFrameCount>,
+                         query: Query<(&Rollback, &C), Without<NoRollback>>| {
+            Self::save(snapshots, frame, query, eq);
+        };
+
+        RollbackRegistrationFingerprint::register_in::<GgrsDeltaSnapshots<C>>(app);
+        NamedSnapshots::register_storage_in::<GgrsDeltaSnapshots<C>>(app);
+        SnapshotStorages::register_in::<GgrsDeltaSnapshots<C>>(app);
+        EntityMappingAudit::register_rolled_back_in::<C>(app);
+
+        app.init_resource::<GgrsDeltaSnapshots<C>>()
+            .add_systems(
+                SaveWorld,
+                (GgrsDeltaSnapshots::<C>::discard_old_snapshots, save)
+                    .chain()
+                    .in_set(SaveWorldSet::Snapshot),
+            )
+            .add_systems(
+                LoadWorld,
+                Self::load
+                    .pipe(report_load_error)
+                    .in_set(LoadWorldSet::Data),
+            );
+    }
+}
@@ -1,13 +1,21 @@
 use crate::{
-    GgrsComponentSnapshot, GgrsComponentSnapshots, LoadWorld, LoadWorldSet, Rollback,
-    RollbackEntityMap, RollbackFrameCount, SaveWorld, SaveWorldSet,
+    error::report_load_error, BevyGgrsError, GgrsComponentSnapshot, GgrsComponentSnapshots,
+    LoadWorld, LoadWorldSet, NamedSnapshots, NoRollback, RetainedFrames, Rollback,
+    RollbackEntityMap, RollbackExclusions, RollbackFrameCount, RollbackRegistrationFingerprint,
+    SaveWorld, SaveWorldSet, SnapshotMemoryUsage, SnapshotStorages,
+};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
 };
-use bevy::{prelude::*, utils::HashMap};
 
 /// A [`Plugin`] which manages the rollback for [`Entities`](`Entity`). This will ensure
 /// all [`Entities`](`Entity`) match the state of the desired frame, or can be mapped using a
 /// [`RollbackEntityMap`], which this [`Plugin`] will also manage.
 ///
+/// Entities [excluded](`NoRollback`) from rollback keep their place in the snapshot, but are
+/// never despawned or respawned by a rollback.
+///
 /// # Examples
 /// ```rust
 /// # use bevy::prelude::*;
@@ -47,19 +55,29 @@ impl EntitySnapshotPlugin {
         mut snapshots: ResMut<GgrsComponentSnapshots<Entity>>,
         mut map: ResMut<RollbackEntityMap>,
         frame: Res<RollbackFrameCount>,
-        query: Query<(&Rollback, Entity)>,
-    ) {
+        exclusions: Option<Res<RollbackExclusions>>,
+        query: Query<(&Rollback, Entity, Has<NoRollback>)>,
+    ) -> Result<(), BevyGgrsError> {
         let mut entity_map = HashMap::default();
         let mut rollback_mapping = HashMap::new();
 
-        let snapshot = snapshots.rollback(frame.0).get();
+        let snapshot = snapshots
+            .try_rollback(frame.0)
+            .ok_or(BevyGgrsError::SnapshotMissing { frame: frame.0 })?
+            .get();
 
         for (&rollback, &old_entity) in snapshot.iter() {
             rollback_mapping.insert(rollback, (None, Some(old_entity)));
         }
 
-        for (&rollback, current_entity) in query.iter() {
+        let mut excluded: HashSet<Entity> = default();
+
+        for (&rollback, current_entity, is_excluded) in query.iter() {
             rollback_mapping.entry(rollback).or_insert((None, None)).0 = Some(current_entity);
+
+            if is_excluded {
+                excluded.insert(current_entity);
+            }
         }
 
         for (rollback, (current_entity, old_entity)) in rollback_mapping {
@@ -67,9 +85,14 @@ impl EntitySnapshotPlugin {
                 (Some(current_entity), Some(old_entity)) => {
                     entity_map.insert(current_entity, old_entity);
                 }
+                (Some(current_entity), None) if excluded.contains(&current_entity) => {}
                 (Some(current_entity), None) => {
                     commands.entity(current_entity).despawn();
                 }
+                (None, Some(_))
+                    if exclusions
+                        .as_ref()
+                        .is_some_and(|exclusions| exclusions.was_excluded(frame.0, &rollback)) => {}
                 (None, Some(old_entity)) => {
                     let current_entity = commands.spawn(rollback).id();
                     entity_map.insert(old_entity, current_entity);
@@ -83,22 +106,37 @@ impl EntitySnapshotPlugin {
         trace!("Rolled back {} entity(s)", snapshot.iter().count());
 
         *map = RollbackEntityMap::new(entity_map);
+
+        Ok(())
     }
 }
 
 impl Plugin for EntitySnapshotPlugin {
     fn build(&self, app: &mut App) {
+        RollbackRegistrationFingerprint::register_in::<GgrsComponentSnapshots<Entity>>(app);
+        NamedSnapshots::register_storage_in::<GgrsComponentSnapshots<Entity>>(app);
+        SnapshotStorages::register_in::<GgrsComponentSnapshots<Entity>>(app);
+
         app.init_resource::<GgrsComponentSnapshots<Entity>>()
             .init_resource::<RollbackEntityMap>()
+            .init_resource::<RetainedFrames>()
             .add_systems(
                 SaveWorld,
                 (
                     GgrsComponentSnapshots::<Entity>::discard_old_snapshots,
                     Self::save,
+                    RetainedFrames::update,
+                    SnapshotMemoryUsage::record_component::<Entity, Entity, Rollback>
+                        .run_if(resource_exists::<SnapshotMemoryUsage>),
                 )
                     .chain()
                     .in_set(SaveWorldSet::Snapshot),
             )
-            .add_systems(LoadWorld, Self::load.in_set(LoadWorldSet::Entity));
+            .add_systems(
+                LoadWorld,
+                (Self::load.pipe(report_load_error), RetainedFrames::update)
+                    .chain()
+                    .in_set(LoadWorldSet::Entity),
+            );
     }
 }
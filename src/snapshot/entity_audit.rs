@@ -0,0 +1,132 @@
+use std::any::TypeId;
+
+use bevy::{
+    prelude::*,
+    reflect::{TypeInfo, TypeRegistry, VariantInfo},
+    utils::{HashMap, HashSet},
+};
+
+/// A [`Resource`] tracking which types are rolled back, and which of those are mapped using
+/// [`MapEntities`](`bevy::ecs::entity::MapEntities`) after loading a snapshot.
+///
+/// Loading a snapshot may recreate despawned entities with a different [`Entity`] id, which will
+/// also differ between peers. Any [`Entity`] stored within rolled back data must therefore be
+/// mapped after loading, such as by a [`ComponentMapEntitiesPlugin`](`crate::ComponentMapEntitiesPlugin`),
+/// otherwise it silently refers to the wrong entity. The [`Rollback`](`crate::Rollback`) component
+/// is the stable identity of an entity across frames and peers, so prefer it over a raw [`Entity`].
+///
+/// In debug builds, every rolled back type registered for [reflection](`Reflect`) which contains
+/// an [`Entity`] but is not mapped raises an error once a [`Session`](`crate::Session`) starts.
+/// Types not registered for reflection cannot be inspected, and are never reported.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct EntityMappingAudit {
+    rolled_back: HashMap<TypeId, &'static str>,
+    mapped: HashSet<TypeId>,
+}
+
+impl EntityMappingAudit {
+    /// Records the type `T` as rolled back.
+    pub fn register_rolled_back<T: 'static>(&mut self) -> &mut Self {
+        self.rolled_back
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
+        self
+    }
+
+    /// Records the type `T` as mapped after loading a snapshot.
+    pub fn register_mapped<T: 'static>(&mut self) -> &mut Self {
+        self.mapped.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Iterate over the [`TypeId`] and name of every rolled back type, in no particular order.
+    pub fn rolled_back(&self) -> impl Iterator<Item = (TypeId, &'static str)> + '_ {
+        self.rolled_back
+            .iter()
+            .map(|(&type_id, &name)| (type_id, name))
+    }
+
+    /// The names of all rolled back types which contain an [`Entity`] according to the provided
+    /// [`TypeRegistry`], but are not mapped, in sorted order.
+    pub fn unmapped(&self, registry: &TypeRegistry) -> Vec<&'static str> {
+        let mut unmapped = self
+            .rolled_back
+            .iter()
+            .filter(|(type_id, _)| !self.mapped.contains(type_id))
+            .filter(|&(&type_id, _)| contains_entity(registry, type_id, &mut default()))
+            .map(|(_, &name)| name)
+            .collect::<Vec<_>>();
+
+        unmapped.sort_unstable();
+        unmapped
+    }
+
+    /// Records the type `T` as rolled back within the provided [`App`].
+    pub(crate) fn register_rolled_back_in<T: 'static>(app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<Self>(default)
+            .register_rolled_back::<T>();
+    }
+
+    /// Records the type `T` as mapped within the provided [`App`].
+    pub(crate) fn register_mapped_in<T: 'static>(app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<Self>(default)
+            .register_mapped::<T>();
+    }
+
+    /// Raises an error for every rolled back type with an unmapped [`Entity`] in the provided
+    /// [`World`].
+    pub(crate) fn report(world: &World) {
+        let (Some(audit), Some(registry)) = (
+            world.get_resource::<Self>(),
+            world.get_resource::<AppTypeRegistry>(),
+        ) else {
+            return;
+        };
+
+        for name in audit.unmapped(&registry.read()) {
+            error!(
+                "{name} is rolled back and contains an Entity, but is not mapped after loading. \
+                Entities recreated by a rollback may have a different id, so map it using \
+                MapEntities, or refer to entities by their Rollback component instead."
+            );
+        }
+    }
+}
+
+/// Returns `true` if the type is, or recursively contains, an [`Entity`].
+fn contains_entity(
+    registry: &TypeRegistry,
+    type_id: TypeId,
+    visited: &mut HashSet<TypeId>,
+) -> bool {
+    if type_id == TypeId::of::<Entity>() {
+        return true;
+    }
+
+    // guards against recursive types
+    if !visited.insert(type_id) {
+        return false;
+    }
+
+    let Some(info) = registry.get_type_info(type_id) else {
+        return false;
+    };
+
+    let mut contains = |type_id| contains_entity(registry, type_id, visited);
+
+    match info {
+        TypeInfo::Struct(info) => info.iter().any(|field| contains(field.type_id())),
+        TypeInfo::TupleStruct(info) => info.iter().any(|field| contains(field.type_id())),
+        TypeInfo::Tuple(info) => info.iter().any(|field| contains(field.type_id())),
+        TypeInfo::List(info) => contains(info.item_type_id()),
+        TypeInfo::Array(info) => contains(info.item_type_id()),
+        TypeInfo::Map(info) => contains(info.key_type_id()) || contains(info.value_type_id()),
+        TypeInfo::Enum(info) => info.iter().any(|variant| match variant {
+            VariantInfo::Struct(variant) => variant.iter().any(|field| contains(field.type_id())),
+            VariantInfo::Tuple(variant) => variant.iter().any(|field| contains(field.type_id())),
+            VariantInfo::Unit(_) => false,
+        }),
+        TypeInfo::Value(_) => false,
+    }
+}
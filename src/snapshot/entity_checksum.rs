@@ -3,7 +3,9 @@ use std::hash::{Hash, Hasher};
 use bevy::prelude::*;
 
 use crate::{
-    checksum_hasher, ChecksumFlag, ChecksumPart, Rollback, RollbackOrdered, SaveWorld, SaveWorldSet,
+    checksum_hasher_for, ChecksumContributor, ChecksumContributorsOnly, ChecksumFlag, ChecksumPart,
+    ChecksumSpawnFilter, NoRollback, Rollback, RollbackOrdered, RollbackSpawnFrame, SaveWorld,
+    SaveWorldSet,
 };
 
 pub struct EntityChecksumPlugin;
@@ -13,16 +15,36 @@ impl EntityChecksumPlugin {
     pub fn update(
         mut commands: Commands,
         rollback_ordered: Res<RollbackOrdered>,
-        active_entities: Query<&Rollback, (With<Rollback>, Without<ChecksumFlag<Entity>>)>,
+        restricted: Option<Res<ChecksumContributorsOnly>>,
+        spawn_filter: ChecksumSpawnFilter,
+        active_entities: Query<
+            (Has<ChecksumContributor>, Option<&RollbackSpawnFrame>),
+            (
+                With<Rollback>,
+                Without<ChecksumFlag<Entity>>,
+                Without<NoRollback>,
+            ),
+        >,
         mut checksum: Query<&mut ChecksumPart, (Without<Rollback>, With<ChecksumFlag<Entity>>)>,
     ) {
-        let mut hasher = checksum_hasher();
+        let mut hasher = checksum_hasher_for::<Entity>();
 
         // The quantity of active rollback entities must be synced.
-        (active_entities.iter().len() as u64).hash(&mut hasher);
+        let active = active_entities
+            .iter()
+            .filter(|&(contributor, spawned)| {
+                ChecksumContributorsOnly::includes(restricted.as_deref(), contributor)
+                    && spawn_filter.includes(spawned)
+            })
+            .count();
 
-        // The quantity of total spawned rollback entities must be synced.
-        (rollback_ordered.len() as u64).hash(&mut hasher);
+        (active as u64).hash(&mut hasher);
+
+        // The quantity of total spawned rollback entities must be synced, unless it may include
+        // entities spawned during prediction.
+        if !spawn_filter.is_enabled() {
+            (rollback_ordered.len() as u64).hash(&mut hasher);
+        }
 
         let result = ChecksumPart(hasher.finish() as u128);
 
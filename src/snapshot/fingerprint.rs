@@ -0,0 +1,90 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
+};
+
+use bevy::prelude::*;
+
+use crate::checksum_hasher;
+
+/// A [`Resource`] tracking the full set of snapshot storages registered for rollback, such as by
+/// [`ComponentSnapshotPlugin`](`crate::ComponentSnapshotPlugin`) and
+/// [`ResourceSnapshotPlugin`](`crate::ResourceSnapshotPlugin`).
+///
+/// Peers registering different types for rollback will silently desync. Exchange the
+/// [`fingerprint`](`RollbackRegistrationFingerprint::fingerprint`) during matchmaking, outside of
+/// GGRS, to verify all peers share the same rollback setup before starting a [`Session`](`crate::Session`).
+/// The fingerprint depends on type names, so it is only comparable between builds of the same source.
+///
+/// When the layout of a rolled back type changes between releases, attach a new
+/// [version](`RollbackRegistrationFingerprint::set_version`) to it. Versions are included in the
+/// fingerprint, so states and replays recorded by a different release can be detected and rejected
+/// instead of silently producing a desync.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, RollbackRegistrationFingerprint};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default())
+///     .rollback_component_with_clone::<Transform>();
+///
+/// let fingerprint = app.world.resource::<RollbackRegistrationFingerprint>().fingerprint();
+/// # }
+/// ```
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq)]
+pub struct RollbackRegistrationFingerprint {
+    types: BTreeSet<&'static str>,
+    versions: BTreeMap<&'static str, u16>,
+}
+
+impl RollbackRegistrationFingerprint {
+    /// Registers the snapshot storage type `T` as part of the rollback setup.
+    pub fn register<T: ?Sized>(&mut self) -> &mut Self {
+        self.types.insert(std::any::type_name::<T>());
+        self
+    }
+
+    /// Attaches a schema version to the rolled back type `T`.
+    pub fn set_version<T: ?Sized>(&mut self, version: u16) -> &mut Self {
+        self.versions.insert(std::any::type_name::<T>(), version);
+        self
+    }
+
+    /// The schema version attached to the rolled back type `T`, if any.
+    pub fn version<T: ?Sized>(&self) -> Option<u16> {
+        self.versions.get(std::any::type_name::<T>()).copied()
+    }
+
+    /// Iterate over the names of all registered snapshot storage types, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.types.iter().copied()
+    }
+
+    /// A hash of all registered snapshot storage types and versions, independent of the order
+    /// they were registered in.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = checksum_hasher();
+
+        for name in self.iter() {
+            name.hash(&mut hasher);
+        }
+
+        for version in self.versions.iter() {
+            version.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Registers the snapshot storage type `T` within the provided [`App`].
+    pub(crate) fn register_in<T: ?Sized>(app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<Self>(default)
+            .register::<T>();
+    }
+}
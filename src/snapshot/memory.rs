@@ -0,0 +1,133 @@
+use std::mem::size_of;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{GgrsComponentSnapshot, GgrsSnapshots, RollbackKey, SaveWorld, SaveWorldSet};
+
+/// A [`Resource`] estimating the memory held by each snapshot storage, in bytes.
+/// This is only updated while a [`SnapshotMemoryPlugin`] is in use.
+///
+/// Storages are identified by the type they snapshot, `For`, together with the type it is stored
+/// as, `As`, so the same type rolled back using several strategies is recorded separately.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SnapshotMemoryUsage {
+    by_type: HashMap<(&'static str, &'static str), usize>,
+}
+
+impl SnapshotMemoryUsage {
+    /// Records the estimated memory usage for snapshots of the type `For`, stored as `As`.
+    pub fn record<For, As>(&mut self, bytes: usize) -> &mut Self {
+        self.by_type.insert(Self::key::<For, As>(), bytes);
+        self
+    }
+
+    /// Get the estimated memory usage for snapshots of the type `For`, stored as `As`, if it has
+    /// been recorded.
+    pub fn get<For, As>(&self) -> Option<usize> {
+        self.by_type.get(&Self::key::<For, As>()).copied()
+    }
+
+    /// Iterate over the estimated memory usage of all recorded snapshot storages as
+    /// `(type_name, stored_type_name, bytes)`.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &'static str, usize)> + '_ {
+        self.by_type
+            .iter()
+            .map(|(&(name, stored), &bytes)| (name, stored, bytes))
+    }
+
+    /// The estimated memory usage across all recorded snapshot storages.
+    pub fn total(&self) -> usize {
+        self.by_type.values().sum()
+    }
+
+    fn key<For, As>() -> (&'static str, &'static str) {
+        (std::any::type_name::<For>(), std::any::type_name::<As>())
+    }
+
+    /// A system recording the estimated memory usage of [`GgrsComponentSnapshots`](`crate::GgrsComponentSnapshots`) for `C`.
+    pub fn record_component<C, As, K>(
+        mut usage: ResMut<Self>,
+        snapshots: Res<GgrsSnapshots<C, GgrsComponentSnapshot<C, As, K>>>,
+    ) where
+        C: Send + Sync + 'static,
+        As: Send + Sync + 'static,
+        K: RollbackKey,
+    {
+        usage.record::<C, As>(snapshots.memory_usage());
+    }
+
+    /// A system recording the estimated memory usage of [`GgrsResourceSnapshots`](`crate::GgrsResourceSnapshots`) for `R`.
+    pub fn record_resource<R, As>(
+        mut usage: ResMut<Self>,
+        snapshots: Res<GgrsSnapshots<R, Option<As>>>,
+    ) where
+        R: Send + Sync + 'static,
+        As: Send + Sync + 'static,
+    {
+        usage.record::<R, As>(snapshots.memory_usage());
+    }
+}
+
+impl<For, As, K: RollbackKey> GgrsSnapshots<For, GgrsComponentSnapshot<For, As, K>> {
+    /// Estimates the memory held by all retained snapshots, in bytes.
+    ///
+    /// Stored values are assumed to be at least as large as the type they were created from,
+    /// which approximates [`Reflect`] based snapshots. Other heap allocations owned by stored
+    /// values, and the overhead of the underlying collections, are not included.
+    pub fn memory_usage(&self) -> usize {
+        let per_entity = size_of::<K>() + size_of::<As>().max(size_of::<For>());
+
+        self.iter()
+            .map(|(_, snapshot)| size_of::<i32>() + snapshot.len() * per_entity)
+            .sum()
+    }
+}
+
+impl<For, As> GgrsSnapshots<For, Option<As>> {
+    /// Estimates the memory held by all retained snapshots, in bytes.
+    ///
+    /// Stored values are assumed to be at least as large as the type they were created from,
+    /// which approximates [`Reflect`] based snapshots. Other heap allocations owned by stored
+    /// values are not included.
+    pub fn memory_usage(&self) -> usize {
+        let per_frame = size_of::<i32>() + size_of::<Option<As>>().max(size_of::<For>());
+
+        self.iter().count() * per_frame
+    }
+}
+
+/// A [`Plugin`] which enables estimating the memory used by all snapshot storage, made available
+/// through the [`SnapshotMemoryUsage`] [`Resource`] after every [`SaveWorld`].
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, SnapshotMemoryPlugin, SnapshotMemoryUsage};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// app.add_plugins(SnapshotMemoryPlugin);
+///
+/// fn print_memory_usage(usage: Res<SnapshotMemoryUsage>) {
+///     info!("Snapshots are using {} bytes", usage.total());
+/// }
+/// # app.add_systems(Update, print_memory_usage);
+/// # }
+/// ```
+pub struct SnapshotMemoryPlugin;
+
+impl SnapshotMemoryPlugin {
+    /// A system logging the total estimated memory usage of all snapshots.
+    pub fn log(usage: Res<SnapshotMemoryUsage>) {
+        debug!("Snapshots are using an estimated {} bytes", usage.total());
+    }
+}
+
+impl Plugin for SnapshotMemoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SnapshotMemoryUsage>()
+            .add_systems(SaveWorld, Self::log.after(SaveWorldSet::Snapshot));
+    }
+}
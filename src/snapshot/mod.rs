@@ -1,45 +1,72 @@
-use crate::{ConfirmedFrameCount, Rollback, DEFAULT_FPS};
+use crate::{ConfirmedFrameCount, Rollback, RollbackKey, SnapshotRetention, DEFAULT_FPS};
 use bevy::{prelude::*, utils::HashMap};
 use seahash::SeaHasher;
-use std::{collections::VecDeque, marker::PhantomData};
+use std::{collections::VecDeque, hash::Hash, marker::PhantomData};
 
+mod cached_checksum;
 mod checksum;
+mod checksum_verification;
 mod component_checksum;
 mod component_map;
 mod component_snapshot;
+mod confirmed_checksum;
+mod delta_snapshot;
 mod entity;
+mod entity_audit;
 mod entity_checksum;
+mod fingerprint;
+mod memory;
+mod mutation_check;
+mod named_snapshot;
+mod pooled_snapshot;
 mod resource_checksum;
 mod resource_map;
 mod resource_snapshot;
+mod retained;
 mod rollback_entity_map;
+mod rollback_scope;
 mod set;
+mod state_snapshot;
 mod strategy;
 
+pub use cached_checksum::*;
 pub use checksum::*;
+pub use checksum_verification::*;
 pub use component_checksum::*;
 pub use component_map::*;
 pub use component_snapshot::*;
+pub use confirmed_checksum::*;
+pub use delta_snapshot::*;
 pub use entity::*;
+pub use entity_audit::*;
 pub use entity_checksum::*;
+pub use fingerprint::*;
+pub use memory::*;
+pub use mutation_check::*;
+pub use named_snapshot::*;
+pub use pooled_snapshot::*;
 pub use resource_checksum::*;
 pub use resource_map::*;
 pub use resource_snapshot::*;
+pub use retained::*;
 pub use rollback_entity_map::*;
+pub use rollback_scope::*;
 pub use set::*;
+pub use state_snapshot::*;
 pub use strategy::*;
 
 pub mod prelude {
-    pub use super::{Checksum, LoadWorldSet, SaveWorldSet};
+    pub use super::{Checksum, GgrsScheduleSet, LoadWorldSet, SaveWorldSet};
 }
 
 /// Typical [`Resource`] used to store snapshots for a [`Resource`] `R` as the type `As`.
 /// For most types, the default `As = R` will suffice.
 pub type GgrsResourceSnapshots<R, As = R> = GgrsSnapshots<R, Option<As>>;
 
-/// Typical [`Resource`] used to store snapshots for a [`Component`] `C` as the type `As`.
-/// For most types, the default `As = C` will suffice.
-pub type GgrsComponentSnapshots<C, As = C> = GgrsSnapshots<C, GgrsComponentSnapshot<C, As>>;
+/// Typical [`Resource`] used to store snapshots for a [`Component`] `C` as the type `As`, keyed by
+/// `K`. For most types, the defaults `As = C` and `K = Rollback` will suffice.
+pub type GgrsComponentSnapshots<C, As = C, K = Rollback> =
+    GgrsSnapshots<C, GgrsComponentSnapshot<C, As, K>>;
 
 /// Collection of snapshots for a type `For`, stored as `As`
 #[derive(Resource)]
@@ -68,8 +95,10 @@ impl<For, As> Default for GgrsSnapshots<For, As> {
 
 impl<For, As> GgrsSnapshots<For, As> {
     /// Updates the capacity of this storage to the provided depth.
+    /// At least one snapshot is always retained, even in lockstep sessions where no rollback
+    /// can occur, so a depth of `0` is treated as `1`.
     pub fn set_depth(&mut self, depth: usize) -> &mut Self {
-        self.depth = depth;
+        self.depth = depth.max(1);
 
         // Greedy allocation to avoid allocating at a more sensitive time.
         if self.snapshots.capacity() < self.depth {
@@ -93,6 +122,16 @@ impl<For, As> GgrsSnapshots<For, As> {
     /// Push a new snapshot for the provided frame. If the frame is earlier than any
     /// currently stored snapshots, those snapshots will be discarded.
     pub fn push(&mut self, frame: i32, snapshot: As) -> &mut Self {
+        self.push_discarding(frame, snapshot, drop)
+    }
+
+    /// Like [`push`](`GgrsSnapshots::push`), but hands every discarded snapshot to `discard`.
+    pub(crate) fn push_discarding(
+        &mut self,
+        frame: i32,
+        snapshot: As,
+        mut discard: impl FnMut(As),
+    ) -> &mut Self {
         debug_assert_eq!(
             self.snapshots.len(),
             self.frames.len(),
@@ -110,7 +149,7 @@ impl<For, As> GgrsSnapshots<For, As> {
             let current_after_frame_wrapped = frame >= current && wrapped;
 
             if current_after_frame || current_after_frame_wrapped {
-                self.snapshots.pop_front().unwrap();
+                discard(self.snapshots.pop_front().unwrap());
                 self.frames.pop_front().unwrap();
             } else {
                 break;
@@ -121,7 +160,7 @@ impl<For, As> GgrsSnapshots<For, As> {
         self.frames.push_front(frame);
 
         while self.snapshots.len() > self.depth {
-            self.snapshots.pop_back().unwrap();
+            discard(self.snapshots.pop_back().unwrap());
             self.frames.pop_back().unwrap();
         }
 
@@ -131,6 +170,15 @@ impl<For, As> GgrsSnapshots<For, As> {
     /// Confirms a snapshot as being stable across clients. Snapshots from before this
     /// point are discarded as no longer required.
     pub fn confirm(&mut self, confirmed_frame: i32) -> &mut Self {
+        self.confirm_discarding(confirmed_frame, drop)
+    }
+
+    /// Like [`confirm`](`GgrsSnapshots::confirm`), but hands every discarded snapshot to `discard`.
+    pub(crate) fn confirm_discarding(
+        &mut self,
+        confirmed_frame: i32,
+        mut discard: impl FnMut(As),
+    ) -> &mut Self {
         debug_assert_eq!(
             self.snapshots.len(),
             self.frames.len(),
@@ -139,7 +187,7 @@ impl<For, As> GgrsSnapshots<For, As> {
 
         while let Some(&frame) = self.frames.back() {
             if frame < confirmed_frame {
-                self.snapshots.pop_back().unwrap();
+                discard(self.snapshots.pop_back().unwrap());
                 self.frames.pop_back().unwrap();
             } else {
                 break;
@@ -150,22 +198,51 @@ impl<For, As> GgrsSnapshots<For, As> {
     }
 
     /// Rolls back to the provided frame, discarding snapshots taken after the rollback point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no snapshot is held for the provided frame. See [`try_rollback`](`GgrsSnapshots::try_rollback`)
+    /// for a non-panicking alternative.
     pub fn rollback(&mut self, frame: i32) -> &mut Self {
-        loop {
-            let Some(&current) = self.frames.front() else {
-                // TODO: A panic may not be appropriate here, but suitable for now.
-                panic!("Could not rollback to {frame}: no snapshot at that moment could be found.");
-            };
+        self.try_rollback(frame).unwrap_or_else(|| {
+            panic!("Could not rollback to {frame}: no snapshot at that moment could be found.")
+        })
+    }
 
-            if current != frame {
-                self.snapshots.pop_front().unwrap();
-                self.frames.pop_front().unwrap();
-            } else {
-                break;
-            }
-        }
+    /// Rolls back to the provided frame, discarding snapshots taken after the rollback point.
+    /// If no snapshot is held for the provided frame, `None` is returned and the stored
+    /// snapshots are left untouched.
+    pub fn try_rollback(&mut self, frame: i32) -> Option<&mut Self> {
+        self.try_rollback_discarding(frame, drop)
+    }
 
-        self
+    /// Like [`try_rollback`](`GgrsSnapshots::try_rollback`), but hands every discarded snapshot
+    /// to `discard`.
+    pub(crate) fn try_rollback_discarding(
+        &mut self,
+        frame: i32,
+        discard: impl FnMut(As),
+    ) -> Option<&mut Self> {
+        debug_assert_eq!(
+            self.snapshots.len(),
+            self.frames.len(),
+            "Snapshot and Frame queues must always be in sync"
+        );
+
+        let index = self
+            .frames
+            .iter()
+            .position(|&saved_frame| saved_frame == frame)?;
+
+        self.snapshots.drain(..index).for_each(discard);
+        self.frames.drain(..index);
+
+        Some(self)
+    }
+
+    /// Returns `true` if a snapshot is held for the provided frame.
+    pub fn contains(&self, frame: i32) -> bool {
+        self.frames.contains(&frame)
     }
 
     /// Get the current snapshot. Use `rollback(frame)` to first select a frame to rollback to.
@@ -175,22 +252,41 @@ impl<For, As> GgrsSnapshots<For, As> {
 
     /// Get a particular snapshot if it exists.
     pub fn peek(&self, frame: i32) -> Option<&As> {
-        let (index, _) = self
+        let index = self
             .frames
             .iter()
-            .enumerate()
-            .find(|(_, &saved_frame)| saved_frame == frame)?;
+            .position(|&saved_frame| saved_frame == frame)?;
         self.snapshots.get(index)
     }
 
+    /// Iterate over the frames of all retained snapshots, newest first.
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = i32> + '_ {
+        self.frames.iter().copied()
+    }
+
+    /// Iterate over all retained snapshots as `(frame, snapshot)`, newest first. Use `rev()` to
+    /// iterate oldest first, such as for a timeline.
+    ///
+    /// This only reflects retained history: snapshots older than the [`depth`](`Self::depth`), or
+    /// before the confirmed frame, have already been discarded.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (i32, &As)> + '_ {
+        self.frames.iter().copied().zip(self.snapshots.iter())
+    }
+
     /// A system which automatically confirms the [`ConfirmedFrameCount`], discarding older snapshots.
+    /// The depth follows the [`SnapshotRetention`], if any.
     pub fn discard_old_snapshots(
         mut snapshots: ResMut<Self>,
         confirmed_frame: Option<Res<ConfirmedFrameCount>>,
+        retention: Option<Res<SnapshotRetention>>,
     ) where
         For: Send + Sync + 'static,
         As: Send + Sync + 'static,
     {
+        if let Some(retention) = retention {
+            snapshots.set_depth(retention.frames());
+        }
+
         let Some(confirmed_frame) = confirmed_frame else {
             return;
         };
@@ -199,13 +295,14 @@ impl<For, As> GgrsSnapshots<For, As> {
     }
 }
 
-/// A storage type suitable for per-[`Entity`] snapshots, such as [`Component`] types.
-pub struct GgrsComponentSnapshot<For, As = For> {
-    snapshot: HashMap<Rollback, As>,
+/// A storage type suitable for per-[`Entity`] snapshots, such as [`Component`] types, keyed by
+/// a [`RollbackKey`] `K`.
+pub struct GgrsComponentSnapshot<For, As = For, K = Rollback> {
+    snapshot: HashMap<K, As>,
     _phantom: PhantomData<For>,
 }
 
-impl<For, As> Default for GgrsComponentSnapshot<For, As> {
+impl<For, As, K> Default for GgrsComponentSnapshot<For, As, K> {
     fn default() -> Self {
         Self {
             snapshot: default(),
@@ -214,33 +311,96 @@ impl<For, As> Default for GgrsComponentSnapshot<For, As> {
     }
 }
 
-impl<For, As> GgrsComponentSnapshot<For, As> {
-    /// Create a new snapshot from a list of [`Rollback`] flags and stored [`Component`] types.
-    pub fn new(components: impl IntoIterator<Item = (Rollback, As)>) -> Self {
+impl<For, As, K: RollbackKey> GgrsComponentSnapshot<For, As, K> {
+    /// Create a new snapshot from a list of [`RollbackKey`] flags and stored [`Component`] types.
+    pub fn new(components: impl IntoIterator<Item = (K, As)>) -> Self {
         Self {
             snapshot: components.into_iter().collect(),
             ..default()
         }
     }
 
-    /// Insert a single snapshot for the provided [`Rollback`].
-    pub fn insert(&mut self, entity: Rollback, snapshot: As) -> &mut Self {
+    /// Insert a single snapshot for the provided [`RollbackKey`].
+    pub fn insert(&mut self, entity: K, snapshot: As) -> &mut Self {
         self.snapshot.insert(entity, snapshot);
         self
     }
 
-    /// Get a single snapshot for the provided [`Rollback`].
-    pub fn get(&self, entity: &Rollback) -> Option<&As> {
+    /// Get a single snapshot for the provided [`RollbackKey`].
+    pub fn get(&self, entity: &K) -> Option<&As> {
         self.snapshot.get(entity)
     }
 
     /// Iterate over all stored snapshots.
-    pub fn iter(&self) -> impl Iterator<Item = (&Rollback, &As)> + '_ {
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &As)> + '_ {
         self.snapshot.iter()
     }
+
+    /// Reconstruct the value stored for the provided [`RollbackKey`] using the [`Strategy`] `S`
+    /// it was stored with, without touching the [`World`]. Returns `None` if no snapshot is
+    /// stored, or if `S` cannot [`materialize`](`Strategy::materialize`) it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, CopyStrategy, GgrsComponentSnapshots};
+    /// #
+    /// #[derive(Component, Clone, Copy, Debug)]
+    /// struct Health(u32);
+    ///
+    /// fn print_history(snapshots: Res<GgrsComponentSnapshots<Health>>, query: Query<&Rollback>) {
+    ///     for frame in snapshots.frames() {
+    ///         let snapshot = snapshots.peek(frame).unwrap();
+    ///
+    ///         for rollback in query.iter() {
+    ///             let health = snapshot.materialize::<CopyStrategy<Health>>(rollback);
+    ///             info!("{frame}: {rollback:?} had {health:?}");
+    ///         }
+    ///     }
+    /// }
+    /// # let mut app = App::new();
+    /// # app.add_systems(Update, print_history);
+    /// ```
+    pub fn materialize<S>(&self, entity: &K) -> Option<For>
+    where
+        S: Strategy<Target = For, Stored = As>,
+    {
+        S::materialize(self.get(entity)?)
+    }
+
+    /// The number of stored snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshot.len()
+    }
+
+    /// Returns `true` if no snapshots are stored.
+    pub fn is_empty(&self) -> bool {
+        self.snapshot.is_empty()
+    }
+}
+
+impl<For: FromReflect, K: RollbackKey> GgrsComponentSnapshot<For, Box<dyn Reflect>, K> {
+    /// Reconstruct the value stored by a [`ReflectStrategy`] for the provided [`RollbackKey`]
+    /// using [`FromReflect`], without creating it from a [`World`]. Returns `None` if no snapshot
+    /// is stored, or if it does not represent a `For`.
+    pub fn materialize_reflect(&self, entity: &K) -> Option<For> {
+        For::from_reflect(self.get(entity)?.as_ref())
+    }
 }
 
 /// Returns a hasher built using the `seahash` library appropriate for creating portable checksums.
 pub fn checksum_hasher() -> SeaHasher {
     SeaHasher::new()
 }
+
+/// Returns a [`checksum_hasher`] keyed by the name of the type `T`. Checksums of equal values
+/// for different types will differ, so combining them never cancels out or depends on the order
+/// in which types were registered.
+///
+/// Type names may differ between compiler versions, so the resulting hashes are only
+/// comparable between builds made with the same toolchain. See [`Checksum`] for details.
+pub fn checksum_hasher_for<T: ?Sized>() -> SeaHasher {
+    let mut hasher = checksum_hasher();
+    std::any::type_name::<T>().hash(&mut hasher);
+    hasher
+}
@@ -0,0 +1,157 @@
+use std::any::TypeId;
+
+use bevy::{
+    ecs::component::Tick,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::RollbackFrameCount;
+
+/// A [`Resource`] which, once [enabled](`UnregisteredMutationCheck::set_enabled`), warns whenever
+/// a [`Resource`] which is not rolled back is mutated while advancing a frame, such as by a system
+/// in the [`GgrsSchedule`](`crate::GgrsSchedule`).
+///
+/// Mutations of resources which are not rolled back survive a rollback, so the resimulated frames
+/// start from a different state than the original ones, which is a frequent cause of desyncs.
+/// Mutations are detected using change detection, so any [`ResMut`] dereferenced mutably counts,
+/// even if its value did not actually change. Components are not checked.
+///
+/// Checking iterates every resource after each frame advanced, so it is disabled by default.
+/// Resources mutated on purpose, such as [`Events`], can be [ignored](`UnregisteredMutationCheck::ignore`).
+/// Every type is only warned about once.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default());
+/// #[derive(Event)]
+/// struct Hit;
+///
+/// # #[cfg(debug_assertions)]
+/// app.add_event::<Hit>()
+///     .warn_on_unregistered_mutations()
+///     .ignore_unregistered_mutations::<Events<Hit>>();
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct UnregisteredMutationCheck {
+    enabled: bool,
+    rolled_back: HashSet<TypeId>,
+    ignored: HashSet<TypeId>,
+    reported: HashMap<TypeId, String>,
+}
+
+impl Default for UnregisteredMutationCheck {
+    fn default() -> Self {
+        let mut check = Self {
+            enabled: false,
+            rolled_back: default(),
+            ignored: default(),
+            reported: default(),
+        };
+
+        // updated around every frame advanced by the plugin itself
+        check.ignore::<Time>().ignore::<RollbackFrameCount>();
+
+        check
+    }
+}
+
+impl UnregisteredMutationCheck {
+    /// Enables or disables checking for mutations.
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Returns `true` if mutations are checked.
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records the [`Resource`] `R` as rolled back.
+    pub fn register_rolled_back<R: Resource>(&mut self) -> &mut Self {
+        self.rolled_back.insert(TypeId::of::<R>());
+        self
+    }
+
+    /// Never warns about mutations of the [`Resource`] `R`.
+    pub fn ignore<R: Resource>(&mut self) -> &mut Self {
+        self.ignored.insert(TypeId::of::<R>());
+        self
+    }
+
+    /// The names of all resources which were warned about, in sorted order.
+    pub fn reported(&self) -> Vec<&str> {
+        let mut reported = self
+            .reported
+            .values()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        reported.sort_unstable();
+        reported
+    }
+
+    /// Records the [`Resource`] `R` as rolled back within the provided [`App`].
+    pub(crate) fn register_rolled_back_in<R: Resource>(app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<Self>(default)
+            .register_rolled_back::<R>();
+    }
+
+    /// Ignores mutations of the [`Resource`] `R` within the provided [`App`].
+    pub(crate) fn ignore_in<R: Resource>(app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<Self>(default)
+            .ignore::<R>();
+    }
+
+    /// The current change [`Tick`] if checking is enabled, to be passed to [`report`](`UnregisteredMutationCheck::report`)
+    /// once a frame has been advanced.
+    pub(crate) fn start(world: &World) -> Option<Tick> {
+        world
+            .get_resource::<Self>()
+            .filter(|check| check.enabled)
+            .map(|_| world.read_change_tick())
+    }
+
+    /// Warns about every [`Resource`] which is not rolled back, but was mutated since `since`.
+    pub(crate) fn report(world: &mut World, since: Tick) {
+        let this_run = world.read_change_tick();
+
+        let mutated = world
+            .storages()
+            .resources
+            .iter()
+            .filter(|(_, data)| {
+                data.get_ticks()
+                    .is_some_and(|ticks| ticks.is_changed(since, this_run))
+            })
+            .filter_map(|(id, _)| world.components().get_info(id))
+            .filter_map(|info| Some((info.type_id()?, info.name().to_string())))
+            .collect::<Vec<_>>();
+
+        let mut check = world.resource_mut::<Self>();
+
+        for (type_id, name) in mutated {
+            if check.rolled_back.contains(&type_id)
+                || check.ignored.contains(&type_id)
+                || check.reported.contains_key(&type_id)
+            {
+                continue;
+            }
+
+            warn!(
+                "{name} was mutated while advancing a frame, but is not rolled back. Its changes \
+                survive a rollback, which will likely cause a desync. Register it for rollback, \
+                or ignore it if this is intended."
+            );
+
+            check.reported.insert(type_id, name);
+        }
+    }
+}
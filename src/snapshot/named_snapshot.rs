@@ -0,0 +1,255 @@
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    BevyGgrsError, GgrsComponentSnapshots, LoadWorld, RetainedFrames, RollbackFrameCount, SaveWorld,
+};
+
+/// A snapshot storage taken out of the [`World`].
+type Storage = Box<dyn Any + Send + Sync>;
+
+/// Moves a registered snapshot storage in and out of the [`World`].
+#[derive(Clone, Copy)]
+struct StorageRegistration {
+    type_id: TypeId,
+    /// Creates an empty storage.
+    empty: fn(&mut World) -> Storage,
+    /// Replaces the storage held by the [`World`], returning the previous one.
+    swap: fn(&mut World, Storage) -> Storage,
+}
+
+fn empty_storage<R: Resource + FromWorld>(world: &mut World) -> Storage {
+    Box::new(R::from_world(world))
+}
+
+fn swap_storage<R: Resource + FromWorld>(world: &mut World, storage: Storage) -> Storage {
+    let storage = storage
+        .downcast::<R>()
+        .expect("Snapshot storages are only swapped with storages of the same type");
+
+    // a storage which was removed is left out, rather than inserting it again
+    let Some(mut current) = world.get_resource_mut::<R>() else {
+        return storage;
+    };
+
+    Box::new(std::mem::replace(current.as_mut(), *storage))
+}
+
+/// A snapshot captured by [`capture_named_snapshot`].
+struct NamedSnapshot {
+    /// The [`RollbackFrameCount`] when the snapshot was captured.
+    frame: i32,
+    /// Every snapshot storage, each holding only the captured frame.
+    storages: HashMap<TypeId, Storage>,
+}
+
+/// A [`Resource`] holding snapshots captured by [`capture_named_snapshot`], keyed by name, along
+/// with the snapshot storages they are captured from.
+///
+/// Unlike the snapshots saved whenever GGRS requests it, named snapshots are never discarded by
+/// confirming or rolling back frames, so they can be restored at any later point, such as to
+/// return to the start of a round, or as a manual save point. Every storage registered by the
+/// snapshot plugins of this crate is captured. A custom snapshot storage must be registered using
+/// [`register_storage`](`NamedSnapshots::register_storage`) to be included.
+#[derive(Resource, Default)]
+pub struct NamedSnapshots {
+    registrations: Vec<StorageRegistration>,
+    snapshots: BTreeMap<String, NamedSnapshot>,
+}
+
+impl NamedSnapshots {
+    /// Registers the snapshot storage [`Resource`] `R`, which is replaced by an empty storage
+    /// while capturing and by the captured storage while restoring a named snapshot.
+    pub fn register_storage<R: Resource + FromWorld>(&mut self) -> &mut Self {
+        let type_id = TypeId::of::<R>();
+
+        if self
+            .registrations
+            .iter()
+            .all(|registration| registration.type_id != type_id)
+        {
+            self.registrations.push(StorageRegistration {
+                type_id,
+                empty: empty_storage::<R>,
+                swap: swap_storage::<R>,
+            });
+        }
+
+        self
+    }
+
+    /// Returns `true` if a snapshot was captured with the provided name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.snapshots.contains_key(name)
+    }
+
+    /// The frame the snapshot with the provided name was captured on, if any.
+    pub fn frame(&self, name: &str) -> Option<i32> {
+        self.snapshots.get(name).map(|snapshot| snapshot.frame)
+    }
+
+    /// Iterate over the names of all captured snapshots, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.snapshots.keys().map(String::as_str)
+    }
+
+    /// Discards the snapshot with the provided name, returning `true` if it was captured.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.snapshots.remove(name).is_some()
+    }
+
+    /// The amount of captured snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns `true` if no snapshots are captured.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Registers the snapshot storage `R` within the provided [`App`].
+    pub(crate) fn register_storage_in<R: Resource + FromWorld>(app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<Self>(default)
+            .register_storage::<R>();
+    }
+
+    fn registrations(world: &World) -> Vec<StorageRegistration> {
+        world
+            .get_resource::<Self>()
+            .map(|named| named.registrations.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Lists the frames of the GGRS snapshots again, after they were swapped out and back in.
+fn refresh_retained_frames(world: &mut World) {
+    if !world.contains_resource::<RetainedFrames>() {
+        return;
+    }
+
+    world.resource_scope(|world, mut retained: Mut<RetainedFrames>| {
+        if let Some(snapshots) = world.get_resource::<GgrsComponentSnapshots<Entity>>() {
+            retained.update_from(snapshots);
+        }
+    });
+}
+
+/// Captures the current state of the [`World`] as a snapshot with the provided name, replacing
+/// any snapshot previously captured with that name. See [`NamedSnapshots`] for details.
+///
+/// The [`SaveWorld`] schedule is run for the current [`RollbackFrameCount`], with every snapshot
+/// storage temporarily replaced by an empty one, which is then kept as the named snapshot. The
+/// snapshots saved for GGRS are left untouched, so they can still be rolled back to.
+///
+/// This must not be called while the [`GgrsPlugin`](`crate::GgrsPlugin`) is running its
+/// schedules, such as from within the [`GgrsSchedule`](`crate::GgrsSchedule`).
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, capture_named_snapshot, restore_named_snapshot};
+/// #
+/// fn start_round(world: &mut World) {
+///     capture_named_snapshot(world, "round start");
+/// }
+///
+/// fn restart_round(world: &mut World) {
+///     if let Err(error) = restore_named_snapshot(world, "round start") {
+///         warn!("{error}");
+///     }
+/// }
+/// ```
+pub fn capture_named_snapshot(world: &mut World, name: impl Into<String>) {
+    let name = name.into();
+    let frame = world
+        .get_resource::<RollbackFrameCount>()
+        .map_or(0, |frame| frame.0);
+
+    debug!("capturing snapshot \"{name}\" for frame {frame}");
+
+    let registrations = NamedSnapshots::registrations(world);
+
+    let present = registrations
+        .iter()
+        .map(|registration| {
+            let empty = (registration.empty)(world);
+            (registration.swap)(world, empty)
+        })
+        .collect::<Vec<_>>();
+
+    world.run_schedule(SaveWorld);
+
+    let storages = registrations
+        .iter()
+        .zip(present)
+        .map(|(registration, present)| (registration.type_id, (registration.swap)(world, present)))
+        .collect();
+
+    refresh_retained_frames(world);
+
+    world
+        .get_resource_or_insert_with::<NamedSnapshots>(default)
+        .snapshots
+        .insert(name, NamedSnapshot { frame, storages });
+}
+
+/// Restores the [`World`] to the snapshot captured with the provided name by
+/// [`capture_named_snapshot`], returning the frame it was captured on. The snapshot is kept, so
+/// it can be restored again.
+///
+/// The [`LoadWorld`] schedule is run with every snapshot storage temporarily replaced by the
+/// captured one, after which the [`RollbackFrameCount`] is the captured frame. The snapshots
+/// saved for GGRS are left untouched.
+///
+/// Restoring a snapshot changes the [`World`] outside of the inputs GGRS knows about, so while a
+/// [`Session`](`crate::Session`) is running, every peer would have to restore it identically.
+/// Restore named snapshots while no [`Session`](`crate::Session`) is running instead, such as
+/// before starting a new one using [`start_session_at_frame`](`crate::start_session_at_frame`).
+///
+/// This must not be called while the [`GgrsPlugin`](`crate::GgrsPlugin`) is running its
+/// schedules, such as from within the [`GgrsSchedule`](`crate::GgrsSchedule`).
+pub fn restore_named_snapshot(world: &mut World, name: &str) -> Result<i32, BevyGgrsError> {
+    let mut snapshot = world
+        .get_resource_mut::<NamedSnapshots>()
+        .and_then(|mut named| named.snapshots.remove(name))
+        .ok_or_else(|| BevyGgrsError::NamedSnapshotMissing {
+            name: name.to_owned(),
+        })?;
+
+    debug!("restoring snapshot \"{name}\" for frame {}", snapshot.frame);
+
+    let present = NamedSnapshots::registrations(world)
+        .into_iter()
+        .filter_map(|registration| {
+            let captured = snapshot.storages.remove(&registration.type_id)?;
+            Some((registration, (registration.swap)(world, captured)))
+        })
+        .collect::<Vec<_>>();
+
+    world
+        .get_resource_or_insert_with::<RollbackFrameCount>(default)
+        .0 = snapshot.frame;
+    world.run_schedule(LoadWorld);
+
+    for (registration, present) in present {
+        let captured = (registration.swap)(world, present);
+        snapshot.storages.insert(registration.type_id, captured);
+    }
+
+    refresh_retained_frames(world);
+
+    let frame = snapshot.frame;
+
+    world
+        .resource_mut::<NamedSnapshots>()
+        .snapshots
+        .insert(name.to_owned(), snapshot);
+
+    Ok(frame)
+}
@@ -0,0 +1,341 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{
+    error::report_load_error, BevyGgrsError, ConfirmedFrameCount, EntityMappingAudit,
+    GgrsSnapshots, KeepOnRollback, LoadWorld, LoadWorldSet, NamedSnapshots, NoRollback, Rollback,
+    RollbackFrameCount, RollbackRegistrationFingerprint, SaveWorld, SaveWorldSet,
+    SnapshotRetention, SnapshotStorage, SnapshotStorages, Strategy,
+};
+
+/// A storage type for per-[`Entity`] snapshots, backed by a [`Vec`] sorted by [`Rollback`].
+///
+/// Behaves like a [`GgrsComponentSnapshot`](`crate::GgrsComponentSnapshot`), but finds snapshots
+/// using a binary search, and keeps its allocation when refilled, see [`GgrsPooledComponentSnapshots`].
+pub struct SortedComponentSnapshot<For, As = For> {
+    snapshot: Vec<(Rollback, As)>,
+    _phantom: PhantomData<For>,
+}
+
+impl<For, As> Default for SortedComponentSnapshot<For, As> {
+    fn default() -> Self {
+        Self {
+            snapshot: default(),
+            _phantom: default(),
+        }
+    }
+}
+
+impl<For, As> SortedComponentSnapshot<For, As> {
+    /// Create a new snapshot from a list of [`Rollback`] flags and stored [`Component`] types.
+    pub fn new(components: impl IntoIterator<Item = (Rollback, As)>) -> Self {
+        let mut snapshot = Self::default();
+        snapshot.refill(components);
+        snapshot
+    }
+
+    /// Replaces all stored snapshots, reusing the existing allocation.
+    pub fn refill(&mut self, components: impl IntoIterator<Item = (Rollback, As)>) -> &mut Self {
+        self.snapshot.clear();
+        self.snapshot.extend(components);
+        self.snapshot
+            .sort_unstable_by_key(|&(rollback, _)| rollback);
+        self
+    }
+
+    /// Get a single snapshot for the provided [`Rollback`].
+    pub fn get(&self, entity: &Rollback) -> Option<&As> {
+        let index = self
+            .snapshot
+            .binary_search_by_key(entity, |&(rollback, _)| rollback)
+            .ok()?;
+
+        Some(&self.snapshot[index].1)
+    }
+
+    /// Iterate over all stored snapshots, in order of their [`Rollback`].
+    pub fn iter(&self) -> impl Iterator<Item = (&Rollback, &As)> + '_ {
+        self.snapshot
+            .iter()
+            .map(|(rollback, stored)| (rollback, stored))
+    }
+
+    /// The number of stored snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshot.len()
+    }
+
+    /// Returns `true` if no snapshots are stored.
+    pub fn is_empty(&self) -> bool {
+        self.snapshot.is_empty()
+    }
+
+    /// The number of snapshots which can be stored without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.snapshot.capacity()
+    }
+}
+
+/// Snapshots for a [`Component`] `C` stored as `As`, used by the [`PooledComponentSnapshotPlugin`].
+///
+/// Unlike [`GgrsComponentSnapshots`](`crate::GgrsComponentSnapshots`), which builds a new map for
+/// every frame, discarded snapshots are returned to a pool and refilled when a later frame is
+/// saved. Once every slot of the ring buffer has been used, saving no longer allocates unless the
+/// amount of entities grows.
+#[derive(Resource)]
+pub struct GgrsPooledComponentSnapshots<C, As = C> {
+    snapshots: GgrsSnapshots<C, SortedComponentSnapshot<C, As>>,
+    pool: Vec<SortedComponentSnapshot<C, As>>,
+}
+
+impl<C, As> Default for GgrsPooledComponentSnapshots<C, As> {
+    fn default() -> Self {
+        Self {
+            snapshots: default(),
+            pool: default(),
+        }
+    }
+}
+
+impl<C, As> SnapshotStorage for GgrsPooledComponentSnapshots<C, As>
+where
+    C: Send + Sync + 'static,
+    As: Send + Sync + 'static,
+{
+    fn contains_frame(&self, frame: i32) -> bool {
+        self.snapshots.contains(frame)
+    }
+}
+
+impl<C, As> GgrsPooledComponentSnapshots<C, As> {
+    /// Updates the capacity of this snapshot storage to the provided depth.
+    pub fn set_depth(&mut self, depth: usize) -> &mut Self {
+        self.snapshots.set_depth(depth);
+        self.pool.truncate(self.snapshots.depth());
+        self
+    }
+
+    /// Get the current capacity of this snapshot storage.
+    pub const fn depth(&self) -> usize {
+        self.snapshots.depth()
+    }
+
+    /// The amount of discarded snapshots available for reuse.
+    pub fn pooled(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Push a new snapshot for the provided frame, refilling a pooled snapshot if one is
+    /// available. If the frame is earlier than any currently stored snapshots, those snapshots
+    /// will be discarded.
+    pub fn push(
+        &mut self,
+        frame: i32,
+        components: impl ExactSizeIterator<Item = (Rollback, As)>,
+    ) -> &mut Self {
+        let mut snapshot = self.pool.pop().unwrap_or_else(|| SortedComponentSnapshot {
+            snapshot: Vec::with_capacity(components.len()),
+            _phantom: PhantomData,
+        });
+
+        snapshot.refill(components);
+
+        let pool = &mut self.pool;
+        self.snapshots
+            .push_discarding(frame, snapshot, |snapshot| pool.push(snapshot));
+
+        self
+    }
+
+    /// Confirms a snapshot as being stable across clients. Snapshots from before this
+    /// point are returned to the pool.
+    pub fn confirm(&mut self, confirmed_frame: i32) -> &mut Self {
+        let pool = &mut self.pool;
+        self.snapshots
+            .confirm_discarding(confirmed_frame, |snapshot| pool.push(snapshot));
+
+        self
+    }
+
+    /// Rolls back to the provided frame, returning snapshots taken after the rollback point to
+    /// the pool. If no snapshot is held for the provided frame, `None` is returned and the stored
+    /// snapshots are left untouched.
+    pub fn try_rollback(&mut self, frame: i32) -> Option<&SortedComponentSnapshot<C, As>> {
+        let pool = &mut self.pool;
+        let snapshots = self
+            .snapshots
+            .try_rollback_discarding(frame, |snapshot| pool.push(snapshot))?;
+
+        Some(snapshots.get())
+    }
+
+    /// Get a particular snapshot if it exists.
+    pub fn peek(&self, frame: i32) -> Option<&SortedComponentSnapshot<C, As>> {
+        self.snapshots.peek(frame)
+    }
+
+    /// Iterate over the frames of all retained snapshots, newest first.
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = i32> + '_ {
+        self.snapshots.frames()
+    }
+
+    /// A system which automatically confirms the [`ConfirmedFrameCount`], returning older
+    /// snapshots to the pool. The depth follows the [`SnapshotRetention`], if any.
+    pub fn discard_old_snapshots(
+        mut snapshots: ResMut<Self>,
+        confirmed_frame: Option<Res<ConfirmedFrameCount>>,
+        retention: Option<Res<SnapshotRetention>>,
+    ) where
+        C: Send + Sync + 'static,
+        As: Send + Sync + 'static,
+    {
+        if let Some(retention) = retention {
+            snapshots.set_depth(retention.frames());
+        }
+
+        let Some(confirmed_frame) = confirmed_frame else {
+            return;
+        };
+
+        snapshots.confirm(confirmed_frame.0);
+    }
+}
+
+/// A [`Plugin`] which manages snapshots for a [`Component`] using a provided [`Strategy`], stored
+/// in [`GgrsPooledComponentSnapshots`].
+///
+/// Rolling back behaves identically to a [`ComponentSnapshotPlugin`](`crate::ComponentSnapshotPlugin`),
+/// but saving reuses the allocations of discarded snapshots, which helps small components which
+/// change every frame and are present on thousands of entities. Every entity is always snapshot,
+/// as a [`RollbackScope`](`crate::RollbackScope`) is not supported.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, CopyStrategy, PooledComponentSnapshotPlugin};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// #[derive(Component, Clone, Copy)]
+/// struct Velocity(Vec2);
+///
+/// // Thousands of particles move every frame
+/// app.add_plugins(PooledComponentSnapshotPlugin::<CopyStrategy<Velocity>>::default());
+/// ```
+pub struct PooledComponentSnapshotPlugin<S>
+where
+    S: Strategy,
+    S::Target: Component,
+    S::Stored: Send + Sync + 'static,
+{
+    _phantom: PhantomData<S>,
+}
+
+impl<S> Default for PooledComponentSnapshotPlugin<S>
+where
+    S: Strategy,
+    S::Target: Component,
+    S::Stored: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            _phantom: default(),
+        }
+    }
+}
+
+impl<S> PooledComponentSnapshotPlugin<S>
+where
+    S: Strategy,
+    S::Target: Component,
+    S::Stored: Send + Sync + 'static,
+{
+    pub fn save(
+        mut snapshots: ResMut<GgrsPooledComponentSnapshots<S::Target, S::Stored>>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<(&Rollback, &S::Target), Without<NoRollback>>,
+    ) {
+        let components = query
+            .iter()
+            .map(|(&rollback, component)| (rollback, S::store(component)));
+
+        snapshots.push(frame.0, components);
+
+        trace!(
+            "Snapshot {} {} component(s) into a pooled snapshot",
+            query.iter().len(),
+            bevy::utils::get_short_name(std::any::type_name::<S::Target>())
+        );
+    }
+
+    pub fn load(
+        mut commands: Commands,
+        mut snapshots: ResMut<GgrsPooledComponentSnapshots<S::Target, S::Stored>>,
+        frame: Res<RollbackFrameCount>,
+        keep: Option<Res<KeepOnRollback<S::Target>>>,
+        mut query: Query<(Entity, &Rollback, Option<&mut S::Target>), Without<NoRollback>>,
+    ) -> Result<(), BevyGgrsError> {
+        let snapshot = snapshots
+            .try_rollback(frame.0)
+            .ok_or(BevyGgrsError::SnapshotMissing { frame: frame.0 })?;
+
+        for (entity, rollback, component) in query.iter_mut() {
+            match (component, snapshot.get(rollback)) {
+                (Some(mut component), Some(snapshot)) => S::update(component.as_mut(), snapshot),
+                (Some(_), None) if keep.is_none() => {
+                    commands.entity(entity).remove::<S::Target>();
+                }
+                (None, Some(snapshot)) => {
+                    commands.entity(entity).insert(S::load(snapshot));
+                }
+                (_, None) => {}
+            }
+        }
+
+        trace!(
+            "Rolled back {} {} component(s) from a pooled snapshot",
+            snapshot.len(),
+            bevy::utils::get_short_name(std::any::type_name::<S::Target>())
+        );
+
+        Ok(())
+    }
+}
+
+impl<S> Plugin for PooledComponentSnapshotPlugin<S>
+where
+    S: Send + Sync + 'static + Strategy,
+    S::Target: Component,
+    S::Stored: Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        RollbackRegistrationFingerprint::register_in::<
+            GgrsPooledComponentSnapshots<S::Target, S::Stored>,
+        >(app);
+        NamedSnapshots::register_storage_in::<GgrsPooledComponentSnapshots<S::Target, S::Stored>>(
+            app,
+        );
+        SnapshotStorages::register_in::<GgrsPooledComponentSnapshots<S::Target, S::Stored>>(app);
+        EntityMappingAudit::register_rolled_back_in::<S::Target>(app);
+
+        app.init_resource::<GgrsPooledComponentSnapshots<S::Target, S::Stored>>()
+            .add_systems(
+                SaveWorld,
+                (
+                    GgrsPooledComponentSnapshots::<S::Target, S::Stored>::discard_old_snapshots,
+                    Self::save,
+                )
+                    .chain()
+                    .in_set(SaveWorldSet::Snapshot),
+            )
+            .add_systems(
+                LoadWorld,
+                Self::load
+                    .pipe(report_load_error)
+                    .in_set(LoadWorldSet::Data),
+            );
+    }
+}
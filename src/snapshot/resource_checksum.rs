@@ -2,7 +2,10 @@ use std::hash::{Hash, Hasher};
 
 use bevy::prelude::*;
 
-use crate::{checksum_hasher, ChecksumFlag, ChecksumPart, Rollback, SaveWorld, SaveWorldSet};
+use crate::{
+    checksum_hasher, checksum_hasher_for, ChecksumFlag, ChecksumPart, Rollback, SaveWorld,
+    SaveWorldSet,
+};
 
 /// Plugin which will track the [`Resource`] `R` and ensure a [`ChecksumPart`] is
 /// available and updated. This can be used to generate a [`Checksum`](`crate::Checksum`).
@@ -60,7 +63,10 @@ where
             &mut ChecksumPart,
             (Without<Rollback>, With<ChecksumFlag<R>>),
         >| {
-            let result = ChecksumPart(custom_hasher(resource.as_ref()) as u128);
+            let mut hasher = checksum_hasher_for::<R>();
+            custom_hasher(resource.as_ref()).hash(&mut hasher);
+
+            let result = ChecksumPart(hasher.finish() as u128);
 
             trace!(
                 "Resource {} has checksum {:X}",
@@ -1,8 +1,12 @@
 use std::marker::PhantomData;
 
-use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::ReflectMut,
+};
 
-use crate::{LoadWorld, LoadWorldSet, RollbackEntityMap};
+use crate::{EntityMappingAudit, LoadWorld, LoadWorldSet, RollbackEntityMap};
 
 /// A [`Plugin`] which updates the state of a post-rollback [`Resource`] `R` using [`MapEntities`].
 ///
@@ -84,6 +88,141 @@ where
     R: Resource + MapEntities,
 {
     fn build(&self, app: &mut App) {
+        EntityMappingAudit::register_mapped_in::<R>(app);
+
         app.add_systems(LoadWorld, Self::update.in_set(LoadWorldSet::Mapping));
     }
 }
+
+/// A [`Plugin`] which updates the state of a post-rollback [`Resource`] `R` using [`Reflect`],
+/// mapping every [`Entity`] found within it. This is useful for resources from third-party crates,
+/// such as relation graphs, which cannot implement [`MapEntities`] themselves.
+///
+/// Entities are found within structs, tuples, lists, arrays, enums and the values of maps. The
+/// keys of maps, and collections only reflected as values such as [`HashSet`](`bevy::utils::HashSet`),
+/// cannot be mutated through [`Reflect`], so they are not mapped.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ResourceReflectMapEntitiesPlugin};
+/// #
+/// # let mut app = App::new();
+/// #[derive(Resource, Reflect, Default)]
+/// #[reflect(Resource)]
+/// struct Edges(Vec<(Entity, Entity)>);
+///
+/// // Mapped resources must be snapshot using any supported method
+/// app.rollback_resource_with_reflect::<Edges>();
+///
+/// // This will map every Entity within Edges on each rollback
+/// app.add_plugins(ResourceReflectMapEntitiesPlugin::<Edges>::default());
+/// ```
+pub struct ResourceReflectMapEntitiesPlugin<R>
+where
+    R: Resource + Reflect,
+{
+    _phantom: PhantomData<R>,
+}
+
+impl<R> Default for ResourceReflectMapEntitiesPlugin<R>
+where
+    R: Resource + Reflect,
+{
+    fn default() -> Self {
+        Self {
+            _phantom: default(),
+        }
+    }
+}
+
+impl<R> ResourceReflectMapEntitiesPlugin<R>
+where
+    R: Resource + Reflect,
+{
+    /// Exclusive system which will apply a [`RollbackEntityMap`] to every [`Entity`] within the [`Resource`] `R`.
+    pub fn update(world: &mut World) {
+        world.resource_scope(|world: &mut World, map: Mut<RollbackEntityMap>| {
+            if let Some(mut resource) = world.get_resource_mut::<R>() {
+                map_entities_reflect(resource.as_reflect_mut(), &mut map.as_ref());
+            }
+
+            trace!(
+                "Mapped {}",
+                bevy::utils::get_short_name(std::any::type_name::<R>())
+            );
+        });
+    }
+}
+
+impl<R> Plugin for ResourceReflectMapEntitiesPlugin<R>
+where
+    R: Resource + Reflect,
+{
+    fn build(&self, app: &mut App) {
+        EntityMappingAudit::register_mapped_in::<R>(app);
+
+        app.add_systems(LoadWorld, Self::update.in_set(LoadWorldSet::Mapping));
+    }
+}
+
+/// Maps every [`Entity`] within the provided value using [`Reflect`]. See
+/// [`ResourceReflectMapEntitiesPlugin`] for which entities can be mapped.
+pub fn map_entities_reflect<M: EntityMapper>(value: &mut dyn Reflect, entity_mapper: &mut M) {
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_entities_reflect(field, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_entities_reflect(field, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_entities_reflect(field, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_entities_reflect(item, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_entities_reflect(item, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::Map(value) => {
+            for index in 0..value.len() {
+                if let Some((_, item)) = value.get_at_mut(index) {
+                    map_entities_reflect(item, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_entities_reflect(field, entity_mapper);
+                }
+            }
+        }
+        ReflectMut::Value(value) => {
+            if let Some(entity) = value.downcast_mut::<Entity>() {
+                *entity = entity_mapper.map_entity(*entity);
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
-    GgrsResourceSnapshots, LoadWorld, LoadWorldSet, RollbackFrameCount, SaveWorld, SaveWorldSet,
-    Strategy,
+    error::report_load_error, BevyGgrsError, EntityMappingAudit, GgrsResourceSnapshots, LoadWorld,
+    LoadWorldSet, NamedSnapshots, RollbackFrameCount, RollbackRegistrationFingerprint, SaveWorld,
+    SaveWorldSet, SnapshotMemoryUsage, SnapshotStorages, Strategy, UnregisteredMutationCheck,
 };
 use bevy::prelude::*;
 use std::marker::PhantomData;
@@ -73,13 +74,19 @@ where
         mut snapshots: ResMut<GgrsResourceSnapshots<S::Target, S::Stored>>,
         frame: Res<RollbackFrameCount>,
         resource: Option<ResMut<S::Target>>,
-    ) {
-        let snapshot = snapshots.rollback(frame.0).get();
+    ) -> Result<(), BevyGgrsError>
+    where
+        S: 'static,
+    {
+        let snapshot = snapshots
+            .try_rollback(frame.0)
+            .ok_or(BevyGgrsError::SnapshotMissing { frame: frame.0 })?
+            .get();
 
         match (resource, snapshot) {
             (Some(mut resource), Some(snapshot)) => S::update(resource.as_mut(), snapshot),
             (Some(_), None) => commands.remove_resource::<S::Target>(),
-            (None, Some(snapshot)) => commands.insert_resource(S::load(snapshot)),
+            (None, Some(_)) => commands.add(Self::load_missing),
             (None, None) => {}
         }
 
@@ -87,6 +94,28 @@ where
             "Rolled back {}",
             bevy::utils::get_short_name(std::any::type_name::<S::Target>())
         );
+
+        Ok(())
+    }
+}
+
+impl<S> ResourceSnapshotPlugin<S>
+where
+    S: Strategy + 'static,
+    S::Target: Resource,
+    S::Stored: Send + Sync + 'static,
+{
+    /// Inserts a missing [`Resource`], recreated from the current snapshot using
+    /// [`Strategy::load_with_world`].
+    fn load_missing(world: &mut World) {
+        world.resource_scope(
+            |world, snapshots: Mut<GgrsResourceSnapshots<S::Target, S::Stored>>| {
+                if let Some(stored) = snapshots.get() {
+                    let resource = S::load_with_world(stored, world);
+                    world.insert_resource(resource);
+                }
+            },
+        );
     }
 }
 
@@ -97,16 +126,31 @@ where
     S::Stored: Send + Sync + 'static,
 {
     fn build(&self, app: &mut App) {
+        RollbackRegistrationFingerprint::register_in::<GgrsResourceSnapshots<S::Target, S::Stored>>(
+            app,
+        );
+        NamedSnapshots::register_storage_in::<GgrsResourceSnapshots<S::Target, S::Stored>>(app);
+        SnapshotStorages::register_in::<GgrsResourceSnapshots<S::Target, S::Stored>>(app);
+        EntityMappingAudit::register_rolled_back_in::<S::Target>(app);
+        UnregisteredMutationCheck::register_rolled_back_in::<S::Target>(app);
+
         app.init_resource::<GgrsResourceSnapshots<S::Target, S::Stored>>()
             .add_systems(
                 SaveWorld,
                 (
                     GgrsResourceSnapshots::<S::Target, S::Stored>::discard_old_snapshots,
                     Self::save,
+                    SnapshotMemoryUsage::record_resource::<S::Target, S::Stored>
+                        .run_if(resource_exists::<SnapshotMemoryUsage>),
                 )
                     .chain()
                     .in_set(SaveWorldSet::Snapshot),
             )
-            .add_systems(LoadWorld, Self::load.in_set(LoadWorldSet::Data));
+            .add_systems(
+                LoadWorld,
+                Self::load
+                    .pipe(report_load_error)
+                    .in_set(LoadWorldSet::Data),
+            );
     }
 }
@@ -0,0 +1,125 @@
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+
+use crate::{GgrsComponentSnapshots, GgrsSnapshots};
+
+/// A [`Resource`] listing the frames which can currently be rolled back to.
+///
+/// Every snapshot storage saves, confirms and rolls back the same frames, so these are read from
+/// the [`Entity`] snapshots managed by the [`EntitySnapshotPlugin`](`crate::EntitySnapshotPlugin`)
+/// after every [`SaveWorld`](`crate::SaveWorld`) and [`LoadWorld`](`crate::LoadWorld`).
+/// When a [`SnapshotInterval`](`crate::SnapshotInterval`) is in use, only snapshot frames are listed,
+/// although any frame after the oldest one can be reached by fast-forwarding.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, RetainedFrames};
+/// #
+/// fn print_rewind_range(retained: Res<RetainedFrames>) {
+///     if let Some(range) = retained.range() {
+///         info!("Can rewind to frames {range:?}");
+///     }
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RetainedFrames {
+    /// Retained frames, oldest first.
+    frames: Vec<i32>,
+}
+
+impl RetainedFrames {
+    /// Replaces the retained frames with those held by the provided snapshot storage.
+    pub fn update_from<For, As>(&mut self, snapshots: &GgrsSnapshots<For, As>) -> &mut Self {
+        self.frames.clear();
+        self.frames.extend(snapshots.frames().rev());
+        self
+    }
+
+    /// Iterate over all retained frames, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = i32> + '_ {
+        self.frames.iter().copied()
+    }
+
+    /// Returns `true` if a snapshot is held for the provided frame.
+    pub fn contains(&self, frame: i32) -> bool {
+        self.frames.contains(&frame)
+    }
+
+    /// The oldest retained frame, if any.
+    pub fn oldest(&self) -> Option<i32> {
+        self.frames.first().copied()
+    }
+
+    /// The newest retained frame, if any.
+    pub fn newest(&self) -> Option<i32> {
+        self.frames.last().copied()
+    }
+
+    /// The range from the oldest to the newest retained frame, if any.
+    pub fn range(&self) -> Option<RangeInclusive<i32>> {
+        Some(self.oldest()?..=self.newest()?)
+    }
+
+    /// The amount of retained frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frames are retained.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// A system updating the retained frames from the [`Entity`] snapshots.
+    pub fn update(mut retained: ResMut<Self>, snapshots: Res<GgrsComponentSnapshots<Entity>>) {
+        retained.update_from(&snapshots);
+    }
+}
+
+/// A snapshot storage which can tell whether it holds a snapshot for a frame.
+pub(crate) trait SnapshotStorage: Resource {
+    /// Returns `true` if a snapshot is held for the provided frame.
+    fn contains_frame(&self, frame: i32) -> bool;
+}
+
+impl<For, As> SnapshotStorage for GgrsSnapshots<For, As>
+where
+    For: Send + Sync + 'static,
+    As: Send + Sync + 'static,
+{
+    fn contains_frame(&self, frame: i32) -> bool {
+        self.contains(frame)
+    }
+}
+
+/// Every snapshot storage registered by a snapshot plugin, checked before rolling back so a frame
+/// is either restored by every storage or by none.
+#[derive(Resource, Default)]
+pub(crate) struct SnapshotStorages {
+    storages: Vec<fn(&World, i32) -> bool>,
+}
+
+impl SnapshotStorages {
+    pub(crate) fn register_in<R: SnapshotStorage>(app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<Self>(default)
+            .storages
+            .push(|world, frame| {
+                world
+                    .get_resource::<R>()
+                    .map_or(true, |storage| storage.contains_frame(frame))
+            });
+    }
+
+    /// Returns `true` if every registered storage holds a snapshot for the provided frame.
+    pub(crate) fn contain(world: &World, frame: i32) -> bool {
+        world.get_resource::<Self>().map_or(true, |registered| {
+            registered
+                .storages
+                .iter()
+                .all(|contains| contains(world, frame))
+        })
+    }
+}
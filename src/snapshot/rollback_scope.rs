@@ -0,0 +1,397 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    error::report_load_error, BevyGgrsError, ConfirmedFrameCount, EntitySnapshotPlugin,
+    GgrsComponentSnapshots, GgrsSnapshots, LoadWorld, LoadWorldSet, NamedSnapshots, Rollback,
+    RollbackFrameCount, SaveWorld, SaveWorldSet, SnapshotRetention, SnapshotStorage,
+    SnapshotStorages,
+};
+
+/// Flags a [`Rollback`] entity as being in scope for rollback while a [`RollbackScope`] is in use.
+/// See [`RollbackScopePlugin`] for details.
+#[derive(Component, Clone, Copy, Default, Debug, Hash, PartialEq, Eq)]
+pub struct ActiveRollback;
+
+/// A [`Resource`] recording which [`Rollback`] entities were [in scope](`ActiveRollback`)
+/// during each retained frame. Its presence enables scoped rollback for all snapshot and
+/// checksum plugins provided by this crate.
+#[derive(Resource, Default)]
+pub struct RollbackScope {
+    snapshots: GgrsSnapshots<ActiveRollback, HashSet<Rollback>>,
+}
+
+impl SnapshotStorage for RollbackScope {
+    fn contains_frame(&self, frame: i32) -> bool {
+        self.snapshots.contains(frame)
+    }
+}
+
+impl RollbackScope {
+    /// Returns `true` if the provided [`Rollback`] was in scope during the provided frame.
+    /// Frames which are not retained are treated as having every entity in scope.
+    pub fn was_active(&self, frame: i32, rollback: &Rollback) -> bool {
+        self.snapshots
+            .peek(frame)
+            .map_or(true, |active| active.contains(rollback))
+    }
+
+    /// If the provided [`Rollback`] was out of scope during the provided frame, returns the
+    /// earliest retained frame after it where it was in scope, if any.
+    pub fn entered_after(&self, frame: i32, rollback: &Rollback) -> Option<i32> {
+        if self.was_active(frame, rollback) {
+            return None;
+        }
+
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|&(saved_frame, active)| saved_frame > frame && active.contains(rollback))
+            .map(|(saved_frame, _)| saved_frame)
+    }
+}
+
+/// A [`Plugin`] which limits rollback to [`Rollback`] entities which also have an [`ActiveRollback`]
+/// marker. Entities without the marker are excluded from [`Component`] snapshots and checksums,
+/// which can greatly reduce the cost of rollback in large worlds where most entities are static.
+///
+/// The [`Entity`] graph itself is still rolled back for all [`Rollback`] entities, and the
+/// [`ActiveRollback`] marker is rolled back alongside it.
+///
+/// # Transitions
+///
+/// Entities out of scope are assumed to be static: simulation systems must not modify them.
+/// When rolling back to a frame where an entity was out of scope:
+/// - If it is still out of scope, it is left untouched.
+/// - If it has since entered the scope, it is restored from the earliest retained snapshot
+///   in which it was in scope.
+///
+/// For the latter to be correct, an entity must not be modified on the frame it enters the
+/// scope, as the first snapshot it is in scope for is only taken once that frame is complete.
+/// Add [`ActiveRollback`] through [`Commands`] within the [`GgrsSchedule`](`crate::GgrsSchedule`),
+/// and do not order any system modifying in-scope entities after the inserting system: Bevy
+/// applies the [`Commands`] before running such systems, which would then modify the entity on
+/// the frame it enters the scope.
+///
+/// An entity leaving the scope is restored as usual when rolling back to a frame where it was
+/// still in scope, and left untouched otherwise.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ActiveRollback, RollbackScopePlugin};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # fn start(session: Session<GgrsConfig<MyInputType>>) {
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// // Only entities with ActiveRollback will be snapshot from now on
+/// app.add_plugins(RollbackScopePlugin);
+///
+/// fn spawn_player(mut commands: Commands) {
+///     commands.spawn(ActiveRollback).add_rollback();
+/// }
+/// # }
+/// ```
+pub struct RollbackScopePlugin;
+
+impl RollbackScopePlugin {
+    /// Records which [`Rollback`] entities are in scope during the current frame.
+    pub fn save(
+        mut scope: ResMut<RollbackScope>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<&Rollback, With<ActiveRollback>>,
+    ) {
+        let active: HashSet<Rollback> = query.iter().copied().collect();
+
+        trace!("Snapshot {} active rollback entity(s)", active.len());
+
+        scope.snapshots.push(frame.0, active);
+    }
+
+    /// Restores the [`ActiveRollback`] marker of every [`Rollback`] entity to match the frame
+    /// being rolled back to, discarding the scopes recorded after it.
+    pub fn load(
+        mut commands: Commands,
+        mut scope: ResMut<RollbackScope>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<(Entity, &Rollback, Has<ActiveRollback>)>,
+    ) -> Result<(), BevyGgrsError> {
+        let active = scope
+            .snapshots
+            .try_rollback(frame.0)
+            .ok_or(BevyGgrsError::SnapshotMissing { frame: frame.0 })?
+            .get();
+
+        for (entity, rollback, is_active) in query.iter() {
+            match (is_active, active.contains(rollback)) {
+                (true, false) => {
+                    commands.entity(entity).remove::<ActiveRollback>();
+                }
+                (false, true) => {
+                    commands.entity(entity).insert(ActiveRollback);
+                }
+                _ => {}
+            }
+        }
+
+        trace!("Rolled back {} active rollback entity(s)", active.len());
+
+        Ok(())
+    }
+
+    /// Discards the scopes of frames which can no longer be rolled back to, and applies any
+    /// [`SnapshotRetention`].
+    pub fn discard_old_snapshots(
+        mut scope: ResMut<RollbackScope>,
+        confirmed_frame: Option<Res<ConfirmedFrameCount>>,
+        retention: Option<Res<SnapshotRetention>>,
+    ) {
+        if let Some(retention) = retention {
+            scope.snapshots.set_depth(retention.frames());
+        }
+
+        let Some(confirmed_frame) = confirmed_frame else {
+            return;
+        };
+
+        scope.snapshots.confirm(confirmed_frame.0);
+    }
+}
+
+impl Plugin for RollbackScopePlugin {
+    fn build(&self, app: &mut App) {
+        NamedSnapshots::register_storage_in::<RollbackScope>(app);
+        SnapshotStorages::register_in::<RollbackScope>(app);
+
+        app.init_resource::<RollbackScope>()
+            .add_systems(
+                SaveWorld,
+                (Self::discard_old_snapshots, Self::save)
+                    .chain()
+                    .in_set(SaveWorldSet::Snapshot),
+            )
+            // Component loads require the scope of frames after the one being rolled back to,
+            // so the scope itself must only be rolled back once they are complete.
+            .add_systems(
+                LoadWorld,
+                Self::load
+                    .pipe(report_load_error)
+                    .after(LoadWorldSet::Data)
+                    .before(LoadWorldSet::DataFlush),
+            );
+    }
+}
+
+/// Excludes a [`Rollback`] entity from rollback while keeping its [`Rollback`] id, such as a
+/// body which is only animated locally once its player has been defeated. Its
+/// [`Components`](`Component`) are neither snapshot, restored nor included in checksums, and it
+/// is never despawned or respawned by a rollback.
+///
+/// Changes made to an excluded entity are never undone, so simulation systems should skip them,
+/// for example using a `Without<NoRollback>` filter. As excluded entities are never despawned by
+/// a rollback, avoid excluding entities spawned within the [`GgrsSchedule`](`crate::GgrsSchedule`),
+/// which would be spawned again when that frame is simulated again.
+///
+/// # Transitions
+///
+/// Like [`ActiveRollback`], this marker is rolled back alongside the [`Entity`] graph, for any
+/// entity which existed during the frame being rolled back to. Entities spawned since keep it.
+/// When rolling back to a frame where an entity was excluded:
+/// - If it is still excluded, it is left untouched.
+/// - If it has since been included again, it is restored from the earliest retained snapshot in
+///   which it was included, or left untouched if there is none.
+///
+/// For the latter to be correct, an entity must not be modified on the frame it is included
+/// again, as the first snapshot it is included in is only taken once that frame is complete.
+/// Remove [`NoRollback`] through [`Commands`] within the [`GgrsSchedule`](`crate::GgrsSchedule`),
+/// and do not order any system modifying included entities after the removing system: Bevy
+/// applies the [`Commands`] before running such systems, which would then modify the entity on
+/// the frame it is included again.
+/// Entities excluded within the [`GgrsSchedule`](`crate::GgrsSchedule`) are restored as usual when
+/// rolling back to a frame before their exclusion. Adding or removing [`NoRollback`] outside of
+/// the [`GgrsSchedule`](`crate::GgrsSchedule`) is undone by such rollbacks.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, NoRollback};
+/// #
+/// #[derive(Component, Clone, Copy)]
+/// struct Health(u32);
+///
+/// fn ragdoll_on_death(
+///     mut commands: Commands,
+///     query: Query<(Entity, &Health), Without<NoRollback>>,
+/// ) {
+///     for (entity, health) in query.iter() {
+///         if health.0 == 0 {
+///             // only animated locally from now on
+///             commands.entity(entity).insert(NoRollback);
+///         }
+///     }
+/// }
+/// #
+/// # let mut app = App::new();
+/// # app.add_systems(GgrsSchedule, ragdoll_on_death);
+/// ```
+#[derive(Component, Clone, Copy, Default, Debug, Hash, PartialEq, Eq)]
+pub struct NoRollback;
+
+/// A [`Resource`] recording which [`Rollback`] entities were [excluded](`NoRollback`) from
+/// rollback during each retained frame, maintained by the [`NoRollbackPlugin`].
+#[derive(Resource, Default)]
+pub struct RollbackExclusions {
+    snapshots: GgrsSnapshots<NoRollback, HashSet<Rollback>>,
+}
+
+impl SnapshotStorage for RollbackExclusions {
+    fn contains_frame(&self, frame: i32) -> bool {
+        self.snapshots.contains(frame)
+    }
+}
+
+impl RollbackExclusions {
+    /// Returns `true` if the provided [`Rollback`] was excluded during the provided frame.
+    /// Frames which are not retained are treated as having no entity excluded.
+    pub fn was_excluded(&self, frame: i32, rollback: &Rollback) -> bool {
+        self.snapshots
+            .peek(frame)
+            .is_some_and(|excluded| excluded.contains(rollback))
+    }
+
+    /// If the provided [`Rollback`] was excluded during the provided frame, returns the earliest
+    /// retained frame after it where it was included, if any.
+    pub fn included_after(&self, frame: i32, rollback: &Rollback) -> Option<i32> {
+        if !self.was_excluded(frame, rollback) {
+            return None;
+        }
+
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|&(saved_frame, excluded)| saved_frame > frame && !excluded.contains(rollback))
+            .map(|(saved_frame, _)| saved_frame)
+    }
+
+    /// Returns `true` if no entity was excluded during the provided frame.
+    pub(crate) fn is_empty(&self, frame: i32) -> bool {
+        self.snapshots
+            .peek(frame)
+            .map_or(true, |excluded| excluded.is_empty())
+    }
+}
+
+/// A [`Plugin`] which records the [`RollbackExclusions`] and rolls back the [`NoRollback`]
+/// marker. This is added by the [`GgrsPlugin`](`crate::GgrsPlugin`).
+pub struct NoRollbackPlugin;
+
+impl NoRollbackPlugin {
+    /// Records which [`Rollback`] entities are excluded during the current frame.
+    pub fn save(
+        mut exclusions: ResMut<RollbackExclusions>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<&Rollback, With<NoRollback>>,
+    ) {
+        let excluded: HashSet<Rollback> = query.iter().copied().collect();
+
+        trace!("Snapshot {} excluded rollback entity(s)", excluded.len());
+
+        exclusions.snapshots.push(frame.0, excluded);
+    }
+
+    /// Restores the [`NoRollback`] marker of every entity which existed during the frame being
+    /// rolled back to.
+    pub fn load_markers(
+        mut commands: Commands,
+        exclusions: Res<RollbackExclusions>,
+        entities: Res<GgrsComponentSnapshots<Entity>>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<(Entity, &Rollback, Has<NoRollback>)>,
+    ) -> Result<(), BevyGgrsError> {
+        let existing = entities
+            .peek(frame.0)
+            .ok_or(BevyGgrsError::SnapshotMissing { frame: frame.0 })?;
+
+        for (entity, rollback, is_excluded) in query.iter() {
+            if existing.get(rollback).is_none() {
+                continue;
+            }
+
+            match (is_excluded, exclusions.was_excluded(frame.0, rollback)) {
+                (true, false) => {
+                    commands.entity(entity).remove::<NoRollback>();
+                }
+                (false, true) => {
+                    commands.entity(entity).insert(NoRollback);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Discards the exclusions recorded after the frame being rolled back to.
+    pub fn load(
+        mut exclusions: ResMut<RollbackExclusions>,
+        frame: Res<RollbackFrameCount>,
+    ) -> Result<(), BevyGgrsError> {
+        exclusions
+            .snapshots
+            .try_rollback(frame.0)
+            .ok_or(BevyGgrsError::SnapshotMissing { frame: frame.0 })?;
+
+        Ok(())
+    }
+
+    /// Discards the exclusions of frames which can no longer be rolled back to, and applies any
+    /// [`SnapshotRetention`].
+    pub fn discard_old_snapshots(
+        mut exclusions: ResMut<RollbackExclusions>,
+        confirmed_frame: Option<Res<ConfirmedFrameCount>>,
+        retention: Option<Res<SnapshotRetention>>,
+    ) {
+        if let Some(retention) = retention {
+            exclusions.snapshots.set_depth(retention.frames());
+        }
+
+        let Some(confirmed_frame) = confirmed_frame else {
+            return;
+        };
+
+        exclusions.snapshots.confirm(confirmed_frame.0);
+    }
+}
+
+impl Plugin for NoRollbackPlugin {
+    fn build(&self, app: &mut App) {
+        NamedSnapshots::register_storage_in::<RollbackExclusions>(app);
+        SnapshotStorages::register_in::<RollbackExclusions>(app);
+
+        app.init_resource::<RollbackExclusions>()
+            .add_systems(
+                SaveWorld,
+                (Self::discard_old_snapshots, Self::save)
+                    .chain()
+                    .in_set(SaveWorldSet::Snapshot),
+            )
+            // Markers are restored before any data, so excluded entities are skipped
+            .add_systems(
+                LoadWorld,
+                Self::load_markers
+                    .pipe(report_load_error)
+                    .after(EntitySnapshotPlugin::load)
+                    .in_set(LoadWorldSet::Entity),
+            )
+            // Component loads require the exclusions of frames after the one being rolled back
+            // to, so the exclusions themselves must only be rolled back once they are complete.
+            .add_systems(
+                LoadWorld,
+                Self::load
+                    .pipe(report_load_error)
+                    .after(LoadWorldSet::Data)
+                    .before(LoadWorldSet::DataFlush),
+            );
+    }
+}
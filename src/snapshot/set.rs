@@ -29,21 +29,84 @@ pub enum LoadWorldSet {
     /// which had to be recreated could not use the same ID, so any data referring to that ID is now invalid.
     /// Once this set completes, all data should now be coherent with the [`World`].
     Mapping,
+    /// Flush any deferred operations
+    MappingFlush,
+    /// Runs once all rolled back data has been loaded and mapped. Use this to rebuild any state
+    /// derived from rolled back data which is not itself rolled back, such as a spatial index.
+    ///
+    /// NOTE: While a [`SnapshotInterval`](`crate::SnapshotInterval`) is in use, the loaded snapshot
+    /// may precede the requested frame, which is then fast-forwarded to after this set.
+    PostLoad,
 }
 
+/// Set for ordering systems during the [`SaveWorld`] schedule. The [`Snapshot`](`SaveWorldSet::Snapshot`)
+/// set always runs before the [`Checksum`](`SaveWorldSet::Checksum`) set.
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
 pub enum SaveWorldSet {
-    /// Generate checksums for any tracked data.
+    /// Saves a snapshot of the [`World`] in this state for future possible rollback.
+    ///
+    /// Custom snapshot systems belong in this set. As it completes before any checksum is
+    /// generated, a custom snapshot system may also update a [`ChecksumPart`](`crate::ChecksumPart`)
+    /// from the data it saved, which is then included in the checksum of the same frame.
+    Snapshot,
+    /// Generate checksums for any tracked data, strictly after every [`Snapshot`](`SaveWorldSet::Snapshot`)
+    /// system has saved the frame.
     ///
     /// Within this set, it is expected that all data which will participate in the
     /// total checksum recorded for this frame will have updated/created a single [`Entity`]
     /// with a [`ChecksumPart`](`crate::ChecksumPart`) component, containing its contribution.
     ///
-    /// The final [`Checksum`](`crate::Checksum`) for the frame will be produced after this set, but before
-    /// the [`Snapshot`](`SaveWorldSet::Snapshot`) set.
+    /// The final [`Checksum`](`crate::Checksum`) for the frame will be produced after this set.
     Checksum,
-    /// Saves a snapshot of the [`World`] in this state for future possible rollback.
-    Snapshot,
+}
+
+/// Set for ordering systems during the [`GgrsSchedule`], with deterministic points at which
+/// deferred operations are applied.
+///
+/// The sets run in order, and [`apply_deferred`] runs in each of the flush sets. Entities spawned
+/// or despawned and components inserted or removed by [`Commands`] in one set are therefore
+/// visible to every system in the following sets, identically on every peer. This holds
+/// regardless of the executor, of [`auto_insert_apply_deferred`](`bevy::ecs::schedule::ScheduleBuildSettings::auto_insert_apply_deferred`),
+/// and of the order in which systems within a set happen to run.
+///
+/// Systems outside of these sets are not ordered relative to the flush points, so whether they
+/// observe commands of other systems may differ between runs. Any commands still pending once
+/// the [`GgrsSchedule`] completes are applied before the frame is saved.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsScheduleSet};
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default());
+/// #[derive(Component)]
+/// struct Bullet;
+///
+/// fn fire(mut commands: Commands) {
+///     commands.spawn(Bullet).add_rollback();
+/// }
+///
+/// // Always sees the bullets fired during the same frame
+/// fn hit(bullets: Query<&Bullet>) {
+///     # let _ = bullets;
+/// }
+///
+/// app.add_systems(GgrsSchedule, fire.in_set(GgrsScheduleSet::First))
+///     .add_systems(GgrsSchedule, hit.in_set(GgrsScheduleSet::Main));
+/// ```
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub enum GgrsScheduleSet {
+    /// Runs first, such as to spawn entities or react to inputs.
+    First,
+    /// Flush any deferred operations
+    FirstFlush,
+    /// The main simulation.
+    Main,
+    /// Flush any deferred operations
+    MainFlush,
+    /// Runs last, such as to clean up entities despawned during the frame.
+    Last,
 }
 
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
@@ -53,7 +116,7 @@ pub enum AdvanceWorldSet {
     Last,
 }
 
-/// Sets up the [`LoadWorldSet`] and [`SaveWorldSet`] sets, allowing for explicit ordering of
+/// Sets up the [`LoadWorldSet`], [`SaveWorldSet`] and [`GgrsScheduleSet`] sets, allowing for explicit ordering of
 /// rollback systems across plugins.
 pub struct SnapshotSetPlugin;
 
@@ -67,12 +130,14 @@ impl Plugin for SnapshotSetPlugin {
                 LoadWorldSet::Data,
                 LoadWorldSet::DataFlush,
                 LoadWorldSet::Mapping,
+                LoadWorldSet::MappingFlush,
+                LoadWorldSet::PostLoad,
             )
                 .chain(),
         )
         .configure_sets(
             SaveWorld,
-            (SaveWorldSet::Checksum, SaveWorldSet::Snapshot).chain(),
+            (SaveWorldSet::Snapshot, SaveWorldSet::Checksum).chain(),
         )
         .configure_sets(
             AdvanceWorld,
@@ -83,8 +148,33 @@ impl Plugin for SnapshotSetPlugin {
             )
                 .chain(),
         )
+        .configure_sets(
+            GgrsSchedule,
+            (
+                GgrsScheduleSet::First,
+                GgrsScheduleSet::FirstFlush,
+                GgrsScheduleSet::Main,
+                GgrsScheduleSet::MainFlush,
+                GgrsScheduleSet::Last,
+            )
+                .chain(),
+        )
         .add_systems(LoadWorld, apply_deferred.in_set(LoadWorldSet::EntityFlush))
         .add_systems(LoadWorld, apply_deferred.in_set(LoadWorldSet::DataFlush))
+        .add_systems(LoadWorld, apply_deferred.in_set(LoadWorldSet::MappingFlush))
+        // systems outside of the sets are deliberately left unordered relative to the flushes
+        .add_systems(
+            GgrsSchedule,
+            apply_deferred
+                .ambiguous_with_all()
+                .in_set(GgrsScheduleSet::FirstFlush),
+        )
+        .add_systems(
+            GgrsSchedule,
+            apply_deferred
+                .ambiguous_with_all()
+                .in_set(GgrsScheduleSet::MainFlush),
+        )
         .add_systems(
             AdvanceWorld,
             apply_deferred
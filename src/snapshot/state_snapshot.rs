@@ -0,0 +1,102 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{ResourceSnapshotPlugin, Strategy};
+
+/// A [`Strategy`] storing the value of a [`State`], rather than the [`State`] itself.
+pub struct StateStrategy<S: States>(PhantomData<S>);
+
+impl<S: States> Strategy for StateStrategy<S> {
+    type Target = State<S>;
+
+    type Stored = S;
+
+    #[inline(always)]
+    fn store(target: &Self::Target) -> Self::Stored {
+        target.get().clone()
+    }
+
+    #[inline(always)]
+    fn load(stored: &Self::Stored) -> Self::Target {
+        State::new(stored.clone())
+    }
+}
+
+/// A [`Strategy`] storing the pending value of a [`NextState`].
+pub struct NextStateStrategy<S: States>(PhantomData<S>);
+
+impl<S: States> Strategy for NextStateStrategy<S> {
+    type Target = NextState<S>;
+
+    type Stored = Option<S>;
+
+    #[inline(always)]
+    fn store(target: &Self::Target) -> Self::Stored {
+        target.0.clone()
+    }
+
+    #[inline(always)]
+    fn load(stored: &Self::Stored) -> Self::Target {
+        NextState(stored.clone())
+    }
+}
+
+/// A [`Plugin`] which rolls back the [`State`] and [`NextState`] of the [`States`] `S`, so state
+/// transitions on frames which are rolled back are undone as well.
+///
+/// Loading a snapshot restores the value of the [`State`] directly, so no [`OnEnter`] or
+/// [`OnExit`] schedules are run. Any state owned by those schedules, such as entities spawned
+/// when entering a state, must be rolled back on its own.
+///
+/// # Transitions
+///
+/// Bevy applies transitions in the [`StateTransition`] schedule, once per update and outside of
+/// the [`GgrsSchedule`](`crate::GgrsSchedule`). A transition requested during a frame would
+/// therefore only be applied after all frames of that update have advanced, which differs between
+/// peers. To apply transitions deterministically, add [`apply_state_transition`] to the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`) after the systems setting the [`NextState`]. The
+/// transition is then applied within the same frame on every peer, and its [`OnEnter`],
+/// [`OnExit`] and [`OnTransition`] schedules are run as part of advancing that frame.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, StateSnapshotPlugin};
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # let mut app = App::new();
+/// # app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::default());
+/// #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+/// enum RoundPhase {
+///     #[default]
+///     Countdown,
+///     Fight,
+/// }
+///
+/// fn start_fight(mut next: ResMut<NextState<RoundPhase>>) {
+///     next.set(RoundPhase::Fight);
+/// }
+///
+/// app.init_state::<RoundPhase>()
+///     .add_plugins(StateSnapshotPlugin::<RoundPhase>::default())
+///     .add_systems(
+///         GgrsSchedule,
+///         (start_fight, apply_state_transition::<RoundPhase>).chain(),
+///     );
+/// ```
+pub struct StateSnapshotPlugin<S: States>(PhantomData<S>);
+
+impl<S: States> Default for StateSnapshotPlugin<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: States> Plugin for StateSnapshotPlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ResourceSnapshotPlugin::<StateStrategy<S>>::default())
+            .add_plugins(ResourceSnapshotPlugin::<NextStateStrategy<S>>::default());
+    }
+}
@@ -1,8 +1,13 @@
-use std::marker::PhantomData;
+use std::{
+    any::{Any, TypeId},
+    fmt::Write,
+    marker::PhantomData,
+};
 
 use bevy::{
+    log::error,
     prelude::{FromWorld, World},
-    reflect::Reflect,
+    reflect::{Reflect, ReflectRef},
 };
 
 /// Describes how to efficiently transform a [`Target`](`Strategy::Target`) into a
@@ -21,11 +26,35 @@ pub trait Strategy {
     /// Create a [`Target`](`Strategy::Target`) version of the provided [`Stored`](`Strategy::Stored`) reference.
     fn load(stored: &Self::Stored) -> Self::Target;
 
+    /// Create a [`Target`](`Strategy::Target`) version of the provided [`Stored`](`Strategy::Stored`)
+    /// reference, with full access to the [`World`] it is about to be inserted into. This is used
+    /// when rolling back re-creates a missing [`Target`](`Strategy::Target`), and defaults to
+    /// [`load`](`Strategy::load`).
+    fn load_with_world(stored: &Self::Stored, world: &mut World) -> Self::Target {
+        let _ = world;
+        Self::load(stored)
+    }
+
     /// Directly update a mutable reference to an existing [`Target`](`Strategy::Target`)
     /// with the data from a provided [`Stored`](`Strategy::Stored`).
     fn update(target: &mut Self::Target, stored: &Self::Stored) {
         *target = Self::load(stored);
     }
+
+    /// Create a [`Target`](`Strategy::Target`) version of the provided [`Stored`](`Strategy::Stored`)
+    /// reference outside of any [`World`], such as for tools inspecting historical snapshots.
+    /// Returns `None` if this is not possible, and defaults to [`load`](`Strategy::load`).
+    fn materialize(stored: &Self::Stored) -> Option<Self::Target> {
+        Some(Self::load(stored))
+    }
+
+    /// Describes the first difference between a [`Target`](`Strategy::Target`) and the value
+    /// loaded after storing it, if any. Used to validate that rolling back restores every value
+    /// exactly. Defaults to `None`, as most strategies cannot compare values.
+    fn round_trip_difference(target: &Self::Target, loaded: &Self::Target) -> Option<String> {
+        let _ = (target, loaded);
+        None
+    }
 }
 
 /// A [`Strategy`] based on [`Copy`]
@@ -72,6 +101,10 @@ impl<T: Clone> Strategy for CloneStrategy<T> {
 }
 
 /// A [`Strategy`] based on [`Reflect`] and [`FromWorld`]
+///
+/// When rolling back re-creates a missing value, it is first created using [`FromWorld`] with
+/// full access to the [`World`], so it may read assets and other resources. The only exception is
+/// the snapshot storage of the type being rolled back, which is unavailable while it is loaded.
 pub struct ReflectStrategy<T: Reflect + FromWorld>(PhantomData<T>);
 
 impl<T: Reflect + FromWorld> Strategy for ReflectStrategy<T> {
@@ -84,16 +117,277 @@ impl<T: Reflect + FromWorld> Strategy for ReflectStrategy<T> {
         target.as_reflect().clone_value()
     }
 
-    #[inline(always)]
+    /// Applies the stored data onto the target. The stored data is validated first, as its
+    /// schema may differ from the target (e.g. after hot reloading). If it represents a
+    /// different type, or any of its fields has a different kind or type than the matching field
+    /// of the target, an error is logged and the target is left untouched.
     fn update(target: &mut Self::Target, stored: &Self::Stored) {
+        if let Some(chain) = reflect_mismatch(target.as_reflect(), stored.as_ref()) {
+            let name = bevy::utils::get_short_name(std::any::type_name::<T>());
+            error!(
+                "Could not rollback {}: snapshot does not match `{}`",
+                std::any::type_name::<T>(),
+                field_path(stored.as_ref(), &chain, &name)
+            );
+            return;
+        }
+
         target.apply(stored.as_ref());
     }
 
+    /// Loads the stored data onto a value created using [`FromWorld`] from an empty [`World`].
+    /// Prefer [`load_with_world`](`Strategy::load_with_world`) where a [`World`] is available.
     #[inline(always)]
     fn load(stored: &Self::Stored) -> Self::Target {
         let mut world: World = Default::default();
-        let mut target = Self::Target::from_world(&mut world);
+        Self::load_with_world(stored, &mut world)
+    }
+
+    #[inline(always)]
+    fn load_with_world(stored: &Self::Stored, world: &mut World) -> Self::Target {
+        let mut target = Self::Target::from_world(world);
         Self::update(&mut target, stored);
         target
     }
+
+    /// Loads the stored data onto a value created using [`FromWorld`] from an empty [`World`].
+    /// Returns `None` if the stored data represents a different type, or any of its fields does
+    /// not match the layout of the created value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`FromWorld`] implementation requires resources, as the [`World`] is empty.
+    /// Types implementing [`FromReflect`](`bevy::reflect::FromReflect`) can instead be
+    /// materialized without a [`World`] using
+    /// [`GgrsComponentSnapshot::materialize_reflect`](`crate::GgrsComponentSnapshot::materialize_reflect`).
+    fn materialize(stored: &Self::Stored) -> Option<Self::Target> {
+        let info = stored.get_represented_type_info()?;
+
+        if info.type_id() != TypeId::of::<T>() {
+            return None;
+        }
+
+        let mut target = Self::Target::from_world(&mut World::default());
+
+        if reflect_mismatch(target.as_reflect(), stored.as_ref()).is_some() {
+            return None;
+        }
+
+        target.apply(stored.as_ref());
+        Some(target)
+    }
+
+    /// Compares every reflected field, so fields which [`apply`](`Reflect::apply`) silently
+    /// drops are found by name. Fields which are not reflected at all, such as those marked
+    /// `#[reflect(ignore)]`, are only found if the type reflects its [`PartialEq`] using
+    /// `#[reflect(PartialEq)]`.
+    fn round_trip_difference(target: &Self::Target, loaded: &Self::Target) -> Option<String> {
+        let name = bevy::utils::get_short_name(std::any::type_name::<T>());
+
+        if let Some(path) = reflect_difference(target.as_reflect(), loaded.as_reflect(), name) {
+            return Some(format!("`{path}` differs"));
+        }
+
+        match target.reflect_partial_eq(loaded.as_reflect()) {
+            Some(false) => Some(
+                "a field which is not reflected differs, such as one marked #[reflect(ignore)]"
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the path of the first reflected field which differs between `a` and `b`.
+fn reflect_difference(a: &dyn Reflect, b: &dyn Reflect, path: String) -> Option<String> {
+    match (a.reflect_ref(), b.reflect_ref()) {
+        (ReflectRef::Struct(a), ReflectRef::Struct(b)) => (0..a.field_len()).find_map(|index| {
+            let name = a.name_at(index)?;
+            let path = format!("{path}.{name}");
+            match (a.field_at(index), b.field(name)) {
+                (Some(a), Some(b)) => reflect_difference(a, b, path),
+                _ => Some(path),
+            }
+        }),
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b)) => {
+            (0..a.field_len()).find_map(|index| {
+                let path = format!("{path}.{index}");
+                match (a.field(index), b.field(index)) {
+                    (Some(a), Some(b)) => reflect_difference(a, b, path),
+                    _ => Some(path),
+                }
+            })
+        }
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) => (0..a.field_len()).find_map(|index| {
+            let path = format!("{path}.{index}");
+            match (a.field(index), b.field(index)) {
+                (Some(a), Some(b)) => reflect_difference(a, b, path),
+                _ => Some(path),
+            }
+        }),
+        (ReflectRef::List(a), ReflectRef::List(b)) if a.len() == b.len() => {
+            (0..a.len()).find_map(|index| {
+                reflect_difference(a.get(index)?, b.get(index)?, format!("{path}[{index}]"))
+            })
+        }
+        (ReflectRef::Array(a), ReflectRef::Array(b)) if a.len() == b.len() => (0..a.len())
+            .find_map(|index| {
+                reflect_difference(a.get(index)?, b.get(index)?, format!("{path}[{index}]"))
+            }),
+        (ReflectRef::Map(a), ReflectRef::Map(b)) if a.len() == b.len() => {
+            a.iter().find_map(|(key, a)| match b.get(key) {
+                Some(b) => reflect_difference(a, b, format!("{path}[{key:?}]")),
+                None => Some(format!("{path}[{key:?}]")),
+            })
+        }
+        (ReflectRef::Enum(a), ReflectRef::Enum(b)) if a.variant_name() == b.variant_name() => {
+            (0..a.field_len()).find_map(|index| {
+                let path = match a.name_at(index) {
+                    Some(name) => format!("{path}.{name}"),
+                    None => format!("{path}.{index}"),
+                };
+                match (a.field_at(index), b.field_at(index)) {
+                    (Some(a), Some(b)) => reflect_difference(a, b, path),
+                    _ => Some(path),
+                }
+            })
+        }
+        (ReflectRef::Value(a), _) => match a.reflect_partial_eq(b) {
+            Some(false) => Some(path),
+            _ => None,
+        },
+        _ => Some(path),
+    }
+}
+
+/// The kind of a reflected value, for comparing the layout of two values.
+fn reflect_kind(value: &dyn Reflect) -> &'static str {
+    match value.reflect_ref() {
+        ReflectRef::Struct(_) => "struct",
+        ReflectRef::TupleStruct(_) => "tuple struct",
+        ReflectRef::Tuple(_) => "tuple",
+        ReflectRef::List(_) => "list",
+        ReflectRef::Array(_) => "array",
+        ReflectRef::Map(_) => "map",
+        ReflectRef::Enum(_) => "enum",
+        ReflectRef::Value(_) => "value",
+    }
+}
+
+/// Returns the indices leading to the first field of `stored` which cannot be
+/// [applied](`Reflect::apply`) onto the matching field of `target`, as it represents a different
+/// type or kind, innermost first. Runs on every load, so nothing is allocated unless a mismatch
+/// is found; see [`field_path`] to name the field.
+fn reflect_mismatch(target: &dyn Reflect, stored: &dyn Reflect) -> Option<Vec<usize>> {
+    let represented =
+        |value: &dyn Reflect| value.get_represented_type_info().map(|info| info.type_id());
+
+    if let (Some(target), Some(stored)) = (represented(target), represented(stored)) {
+        if target != stored {
+            return Some(Vec::new());
+        }
+    }
+
+    if reflect_kind(target) != reflect_kind(stored) {
+        return Some(Vec::new());
+    }
+
+    // appends the index of the field a nested mismatch was found in
+    let nested = |index: usize, target: &dyn Reflect, stored: &dyn Reflect| {
+        let mut chain = reflect_mismatch(target, stored)?;
+        chain.push(index);
+        Some(chain)
+    };
+
+    match (target.reflect_ref(), stored.reflect_ref()) {
+        (ReflectRef::Struct(target), ReflectRef::Struct(stored)) => (0..stored.field_len())
+            .find_map(|index| {
+                let name = stored.name_at(index)?;
+                nested(index, target.field(name)?, stored.field_at(index)?)
+            }),
+        (ReflectRef::TupleStruct(target), ReflectRef::TupleStruct(stored)) => (0..stored
+            .field_len())
+            .find_map(|index| nested(index, target.field(index)?, stored.field(index)?)),
+        (ReflectRef::Tuple(target), ReflectRef::Tuple(stored)) => (0..stored.field_len())
+            .find_map(|index| nested(index, target.field(index)?, stored.field(index)?)),
+        (ReflectRef::List(target), ReflectRef::List(stored)) => (0..stored.len())
+            .find_map(|index| nested(index, target.get(index)?, stored.get(index)?)),
+        // arrays can only be applied onto arrays of the same length
+        (ReflectRef::Array(target), ReflectRef::Array(stored)) if target.len() != stored.len() => {
+            Some(Vec::new())
+        }
+        (ReflectRef::Array(target), ReflectRef::Array(stored)) => (0..stored.len())
+            .find_map(|index| nested(index, target.get(index)?, stored.get(index)?)),
+        (ReflectRef::Map(target), ReflectRef::Map(stored)) => stored
+            .iter()
+            .enumerate()
+            .find_map(|(index, (key, stored))| nested(index, target.get(key)?, stored)),
+        (ReflectRef::Enum(target), ReflectRef::Enum(stored))
+            if target.variant_name() == stored.variant_name() =>
+        {
+            (0..stored.field_len()).find_map(|index| {
+                let target = match stored.name_at(index) {
+                    Some(name) => target.field(name)?,
+                    None => target.field_at(index)?,
+                };
+                nested(index, target, stored.field_at(index)?)
+            })
+        }
+        // values are applied by downcasting, so they must be of the same concrete type
+        (ReflectRef::Value(target), ReflectRef::Value(stored))
+            if Any::type_id(target.as_any()) != Any::type_id(stored.as_any()) =>
+        {
+            Some(Vec::new())
+        }
+        _ => None,
+    }
+}
+
+/// Formats the path to the field of `stored` found by [`reflect_mismatch`], starting at `name`.
+fn field_path(stored: &dyn Reflect, chain: &[usize], name: &str) -> String {
+    let mut path = name.to_string();
+    let mut value = Some(stored);
+
+    for &index in chain.iter().rev() {
+        let Some(current) = value else {
+            break;
+        };
+
+        value = match current.reflect_ref() {
+            ReflectRef::Struct(current) => {
+                let _ = write!(path, ".{}", current.name_at(index).unwrap_or_default());
+                current.field_at(index)
+            }
+            ReflectRef::TupleStruct(current) => {
+                let _ = write!(path, ".{index}");
+                current.field(index)
+            }
+            ReflectRef::Tuple(current) => {
+                let _ = write!(path, ".{index}");
+                current.field(index)
+            }
+            ReflectRef::List(current) => {
+                let _ = write!(path, "[{index}]");
+                current.get(index)
+            }
+            ReflectRef::Array(current) => {
+                let _ = write!(path, "[{index}]");
+                current.get(index)
+            }
+            ReflectRef::Map(current) => current.get_at(index).map(|(key, value)| {
+                let _ = write!(path, "[{key:?}]");
+                value
+            }),
+            ReflectRef::Enum(current) => {
+                let _ = match current.name_at(index) {
+                    Some(name) => write!(path, ".{name}"),
+                    None => write!(path, ".{index}"),
+                };
+                current.field_at(index)
+            }
+            ReflectRef::Value(_) => None,
+        };
+    }
+
+    path
 }
@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    utils::{Duration, HashMap},
+};
+use ggrs::{Config, PlayerHandle, SessionState};
+
+use crate::{PredictionDepth, Session, SessionType};
+
+/// A summary of the health of the current [`Session`], updated once per update by the
+/// [`GgrsPlugin`](`crate::GgrsPlugin`). Use this for a single netcode status overlay, or for
+/// logging, rather than reading every individual diagnostic.
+///
+/// This is composed from the other diagnostics, such as the [`PredictionDepth`], and is reset
+/// once the [`Session`] ends.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsStatus};
+/// #
+/// fn netcode_overlay(status: Res<GgrsStatus>) {
+///     for (handle, ping) in status.pings() {
+///         info!("Player {handle}: {}ms", ping.as_millis());
+///     }
+///
+///     info!(
+///         "{} frames predicted, {:.1} rollbacks/s",
+///         status.prediction_depth(),
+///         status.rollbacks_per_second()
+///     );
+///
+///     if let Some(frame) = status.last_desync_frame() {
+///         warn!("Desynced on frame {frame}");
+///     }
+/// }
+/// # let mut app = App::new();
+/// # app.add_systems(Update, netcode_overlay);
+/// ```
+#[derive(Resource, Debug, Default, Clone)]
+pub struct GgrsStatus {
+    session_type: SessionType,
+    state: Option<SessionState>,
+    pings: HashMap<PlayerHandle, Duration>,
+    frames_ahead: i32,
+    prediction_depth: usize,
+    rollbacks_per_second: f32,
+    last_desync_frame: Option<i32>,
+    /// Rollbacks since the last update.
+    pending_rollbacks: u32,
+    /// Duration and rollbacks of each recent update, spanning up to [`Self::ROLLBACK_WINDOW`].
+    recent_rollbacks: VecDeque<(Duration, u32)>,
+}
+
+impl GgrsStatus {
+    /// The span of time the [`rollbacks_per_second`](`GgrsStatus::rollbacks_per_second`) are
+    /// averaged over.
+    pub const ROLLBACK_WINDOW: Duration = Duration::from_secs(1);
+
+    /// The type of the current [`Session`].
+    pub fn session_type(&self) -> SessionType {
+        self.session_type
+    }
+
+    /// The state of the current [`Session`], or `None` if there is none. A
+    /// [`SyncTestSession`](`ggrs::SyncTestSession`) is always running.
+    pub fn state(&self) -> Option<SessionState> {
+        self.state
+    }
+
+    /// The round-trip time to every remote player of a [`P2PSession`](`ggrs::P2PSession`), once
+    /// it is known.
+    pub fn pings(&self) -> impl Iterator<Item = (PlayerHandle, Duration)> + '_ {
+        self.pings.iter().map(|(&handle, &ping)| (handle, ping))
+    }
+
+    /// The round-trip time to the remote player with the provided handle, if known.
+    pub fn ping(&self, handle: PlayerHandle) -> Option<Duration> {
+        self.pings.get(&handle).copied()
+    }
+
+    /// How many frames a [`P2PSession`](`ggrs::P2PSession`) is ahead of its peers. A positive value
+    /// makes the local client run slow until it has caught up.
+    pub fn frames_ahead(&self) -> i32 {
+        self.frames_ahead
+    }
+
+    /// The current [`PredictionDepth`].
+    pub fn prediction_depth(&self) -> usize {
+        self.prediction_depth
+    }
+
+    /// How many rollbacks occurred per second, averaged over the
+    /// [`ROLLBACK_WINDOW`](`GgrsStatus::ROLLBACK_WINDOW`). A
+    /// [`SyncTestSession`](`ggrs::SyncTestSession`) rolls back every frame.
+    pub fn rollbacks_per_second(&self) -> f32 {
+        self.rollbacks_per_second
+    }
+
+    /// The most recent frame a desync was detected on during the current [`Session`], if any.
+    pub fn last_desync_frame(&self) -> Option<i32> {
+        self.last_desync_frame
+    }
+
+    /// Records a rollback of the current update.
+    pub(crate) fn record_rollback(&mut self) {
+        self.pending_rollbacks += 1;
+    }
+
+    /// Records a desync detected on the provided frame.
+    pub(crate) fn record_desync(&mut self, frame: i32) {
+        self.last_desync_frame = Some(self.last_desync_frame.map_or(frame, |last| last.max(frame)));
+    }
+
+    /// Moves the rollbacks of the current update into the window, which now spans `delta` more.
+    fn update_rollbacks(&mut self, delta: Duration) {
+        self.recent_rollbacks
+            .push_back((delta, std::mem::take(&mut self.pending_rollbacks)));
+
+        let mut window: Duration = self.recent_rollbacks.iter().map(|&(delta, _)| delta).sum();
+
+        while window > Self::ROLLBACK_WINDOW && self.recent_rollbacks.len() > 1 {
+            if let Some((delta, _)) = self.recent_rollbacks.pop_front() {
+                window -= delta;
+            }
+        }
+
+        let rollbacks: u32 = self.recent_rollbacks.iter().map(|&(_, count)| count).sum();
+
+        self.rollbacks_per_second = match window.as_secs_f32() {
+            secs if secs > 0. => rollbacks as f32 / secs,
+            _ => 0.,
+        };
+    }
+}
+
+/// Updates the [`GgrsStatus`] from the current [`Session`], after `delta` has passed.
+pub(crate) fn update_status<T: Config>(world: &mut World, delta: Duration) {
+    let prediction_depth = world
+        .get_resource::<PredictionDepth>()
+        .map_or(0, |depth| depth.0);
+
+    let (state, pings, frames_ahead) = match world.get_resource::<Session<T>>() {
+        Some(Session::P2P(session)) => {
+            let pings = session
+                .remote_player_handles()
+                .into_iter()
+                .filter_map(|handle| {
+                    let stats = session.network_stats(handle).ok()?;
+                    Some((handle, Duration::from_millis(stats.ping as u64)))
+                })
+                .collect();
+
+            (Some(session.current_state()), pings, session.frames_ahead())
+        }
+        #[cfg(feature = "spectator")]
+        Some(Session::Spectator(session)) => (Some(session.current_state()), default(), 0),
+        #[cfg(feature = "synctest")]
+        Some(Session::SyncTest(_)) => (Some(SessionState::Running), default(), 0),
+        Some(Session::Replay(_)) => (Some(SessionState::Running), default(), 0),
+        None => (None, default(), 0),
+    };
+
+    let session_type = world
+        .get_resource::<SessionType>()
+        .copied()
+        .unwrap_or_default();
+
+    let mut status = world.get_resource_or_insert_with::<GgrsStatus>(default);
+
+    status.session_type = session_type;
+    status.state = state;
+    status.pings = pings;
+    status.frames_ahead = frames_ahead;
+    status.prediction_depth = prediction_depth;
+    status.update_rollbacks(delta);
+}
@@ -0,0 +1,165 @@
+use std::mem::size_of;
+
+use bytemuck::{Pod, Zeroable};
+
+/// A payload which can be encoded into a tagged input, such as a [`TaggedInput8`], identified by
+/// a unique [`TAG`](`InputScheme::TAG`).
+///
+/// Every control scheme a player may switch between during a match, such as keyboard and
+/// gamepad, should have its own payload type with its own tag. Tags must be identical across
+/// peers, so never derive them from the order schemes were registered in.
+///
+/// Encoding differently laid out payloads into the same bits makes them indistinguishable, so
+/// a peer may interpret a keyboard input as a gamepad input and desync. A tagged input stores
+/// the tag as its first byte, followed by a fixed amount of payload bytes. Unused payload bytes
+/// are always zero, so the same payload always encodes to the same bits, and the layout is
+/// identical on every peer. Payloads are stored as bytes, so pick the smallest tagged input which
+/// fits the largest payload: [`TaggedInput4`], [`TaggedInput8`], [`TaggedInput16`] or
+/// [`TaggedInput32`].
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, InputScheme, TaggedInput4};
+/// # use bytemuck::{Pod, Zeroable};
+/// #
+/// #[repr(C)]
+/// #[derive(Clone, Copy, PartialEq, Pod, Zeroable)]
+/// struct Keyboard {
+///     keys: u8,
+/// }
+///
+/// impl InputScheme for Keyboard {
+///     const TAG: u8 = 1;
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, PartialEq, Pod, Zeroable)]
+/// struct Gamepad {
+///     stick: [i8; 2],
+///     buttons: u8,
+/// }
+///
+/// impl InputScheme for Gamepad {
+///     const TAG: u8 = 2;
+/// }
+///
+/// type MyConfig = GgrsConfig<TaggedInput4>;
+///
+/// fn movement(inputs: Res<PlayerInputs<MyConfig>>) {
+///     let (input, _) = inputs[0];
+///
+///     if let Some(keyboard) = input.decode::<Keyboard>() {
+///         // ...
+///     } else if let Some(gamepad) = input.decode::<Gamepad>() {
+///         // ...
+///     }
+/// }
+/// # let mut app = App::new();
+/// # app.add_systems(GgrsSchedule, movement);
+/// ```
+pub trait InputScheme: Pod {
+    /// The tag identifying this scheme. `0` is reserved for a tagged input without a payload,
+    /// such as the [zeroed](`Zeroable`) input of a disconnected player.
+    const TAG: u8;
+}
+
+/// Declares a tagged input with a payload of a fixed size. Each size is its own type, so
+/// [`Pod`] and [`Zeroable`] can be derived rather than implemented unsafely.
+macro_rules! tagged_input {
+    ($($name:ident: $size:literal),* $(,)?) => {
+        $(
+            #[doc = concat!(
+                "An input for a [`Config`](`ggrs::Config`) which holds one of several ",
+                "[`InputScheme`] payloads of up to ",
+                stringify!($size),
+                " bytes, together with the tag of the scheme it was encoded with.\n\n",
+                "See [`InputScheme`] for details."
+            )]
+            #[repr(C)]
+            #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Pod, Zeroable)]
+            pub struct $name {
+                tag: u8,
+                payload: [u8; $size],
+            }
+
+            impl Default for $name {
+                fn default() -> Self {
+                    Self::EMPTY
+                }
+            }
+
+            impl $name {
+                /// An input without a payload.
+                pub const EMPTY: Self = Self {
+                    tag: 0,
+                    payload: [0; $size],
+                };
+
+                /// The largest payload this input can hold, in bytes.
+                pub const PAYLOAD_SIZE: usize = $size;
+
+                /// Encodes the provided payload, tagged with its [`InputScheme::TAG`].
+                ///
+                /// # Panics
+                ///
+                #[doc = concat!(
+                    "Panics if the payload is larger than ",
+                    stringify!($size),
+                    " bytes, or its tag is the reserved tag `0`."
+                )]
+                pub fn encode<S: InputScheme>(payload: S) -> Self {
+                    let bytes = bytemuck::bytes_of(&payload);
+
+                    assert!(
+                        bytes.len() <= $size,
+                        "{} is {} bytes, which does not fit a {}",
+                        std::any::type_name::<S>(),
+                        bytes.len(),
+                        stringify!($name)
+                    );
+                    assert_ne!(S::TAG, 0, "the InputScheme tag 0 is reserved");
+
+                    let mut input = Self::EMPTY;
+                    input.tag = S::TAG;
+                    input.payload[..bytes.len()].copy_from_slice(bytes);
+                    input
+                }
+
+                /// Decodes the payload, if it was encoded with the provided [`InputScheme`].
+                pub fn decode<S: InputScheme>(&self) -> Option<S> {
+                    if self.tag != S::TAG || self.tag == 0 || size_of::<S>() > $size {
+                        return None;
+                    }
+
+                    Some(bytemuck::pod_read_unaligned(
+                        &self.payload[..size_of::<S>()],
+                    ))
+                }
+
+                /// The tag of the [`InputScheme`] this input was encoded with, or `0` if it has
+                /// no payload.
+                pub const fn tag(&self) -> u8 {
+                    self.tag
+                }
+
+                /// Returns `true` if this input has no payload.
+                pub const fn is_empty(&self) -> bool {
+                    self.tag == 0
+                }
+
+                /// Returns `true` if this input was encoded with the provided [`InputScheme`].
+                pub const fn is<S: InputScheme>(&self) -> bool {
+                    self.tag != 0 && self.tag == S::TAG
+                }
+            }
+        )*
+    };
+}
+
+tagged_input!(
+    TaggedInput4: 4,
+    TaggedInput8: 8,
+    TaggedInput16: 16,
+    TaggedInput32: 32,
+);
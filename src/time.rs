@@ -1,13 +1,20 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use ggrs::{Config, GgrsError, SessionBuilder};
 
 use crate::{
-    AdvanceWorld, AdvanceWorldSet, CloneStrategy, ResourceSnapshotPlugin, RollbackFrameCount,
-    DEFAULT_FPS,
+    AdvanceWorld, AdvanceWorldSet, CloneStrategy, FixedTimestepData, ResourceSnapshotPlugin,
+    RollbackFrameCount, SessionConfig, DEFAULT_FPS,
 };
 
 /// [`Resource`] describing the rate at which the [`AdvanceWorld`] will run.
+///
+/// GGRS also uses a frame rate for time synchronization and wait recommendations, which is
+/// fixed when a [`Session`](`crate::Session`) is built. Use [`configure`](`RollbackFrameRate::configure`)
+/// to keep both in agreement, and [`set_fps`] to change it. All peers must use the same frame rate,
+/// so it should only be changed before a [`Session`](`crate::Session`) starts. The [`GgrsPlugin`](`crate::GgrsPlugin`)
+/// warns whenever it differs from the frame rate the running session was built with.
 #[derive(Resource, Clone, Copy, Debug, Hash, Deref)]
 pub struct RollbackFrameRate(pub(crate) usize);
 
@@ -17,6 +24,44 @@ impl Default for RollbackFrameRate {
     }
 }
 
+impl RollbackFrameRate {
+    /// Applies this frame rate to the provided [`SessionBuilder`], so GGRS time synchronization
+    /// matches the rate at which the [`AdvanceWorld`] schedule is run.
+    pub fn configure<T: Config>(
+        &self,
+        builder: SessionBuilder<T>,
+    ) -> Result<SessionBuilder<T>, GgrsError> {
+        builder.with_fps(self.0)
+    }
+}
+
+/// Sets the [`RollbackFrameRate`], together with the [`fps`](`SessionConfig::fps`) of the
+/// [`SessionConfig`] if present, so the rate frames are advanced at and the rate the next
+/// [`Session`](`crate::Session`) is built with stay in agreement. Time already accumulated towards
+/// the next frame is kept, up to a single frame at the new rate.
+///
+/// GGRS fixes the frame rate of a [`Session`](`crate::Session`) once it is built, and cannot change
+/// it afterwards. All peers must change their frame rate identically, so only call this before a
+/// session starts, or when rebuilding the session on a frame all peers agreed upon, using
+/// [`RollbackFrameRate::configure`] or [`SessionConfig::apply`]. While the frame rate differs from
+/// the one the running session was built with, see [`FixedTimestepData::session_frame_rate`],
+/// the [`GgrsPlugin`](`crate::GgrsPlugin`) logs a warning.
+pub fn set_fps(world: &mut World, fps: usize) {
+    world.insert_resource(RollbackFrameRate(fps));
+
+    if let Some(mut config) = world.get_resource_mut::<SessionConfig>() {
+        config.fps = fps;
+    }
+
+    if let (Some(mut time_data), Some(frame)) = (
+        world.get_resource_mut::<FixedTimestepData>(),
+        Duration::from_secs(1).checked_div(fps as u32),
+    ) {
+        let accumulator = time_data.accumulator().min(frame);
+        time_data.set_accumulator(accumulator);
+    }
+}
+
 /// A [`Time`] type for use with GGRS. This time is guaranteed to be in-sync with
 /// all peers, and reflect that exactly [`RollbackFrameCount`] frames have passed at
 /// the [`RollbackFrameRate`] rate. Note that in the [`GgrsSchedule`](`crate::GgrsSchedule`),
@@ -0,0 +1,80 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ggrs::prelude::*;
+
+use common::{self, input_system, TestConfig};
+
+/// Not rolled back, so only the first frame is inspected.
+#[derive(Resource, Default)]
+struct Log(Vec<u8>);
+
+fn first(mut log: ResMut<Log>) {
+    log.0.push(1);
+}
+
+fn second(mut log: ResMut<Log>) {
+    log.0.push(2);
+}
+
+fn create_app<M>(seed: u64, systems: impl IntoSystemConfigs<M>) -> App {
+    let mut app = common::create_app();
+
+    app.set_chaos_schedule_seed(seed)
+        .init_resource::<Log>()
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsSchedule, systems);
+
+    let session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .with_check_distance(2)
+        .add_player(PlayerType::Local, 0)
+        .unwrap()
+        .start_synctest_session()
+        .unwrap();
+
+    app.insert_resource(Session::SyncTest(session));
+
+    app
+}
+
+fn first_frame_order<M>(seed: u64, systems: impl IntoSystemConfigs<M>) -> Vec<u8> {
+    let mut app = create_app(seed, systems);
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    app.world.resource::<Log>().0[..2].to_vec()
+}
+
+/// This test makes sure the same seed always produces the same order.
+#[test]
+fn it_orders_systems_reproducibly() {
+    for seed in 0..8 {
+        assert_eq!(
+            first_frame_order(seed, (first, second.ambiguous_with(first))),
+            first_frame_order(seed, (first, second.ambiguous_with(first)))
+        );
+    }
+}
+
+/// This test makes sure independent systems run in different orders for different seeds.
+#[test]
+fn it_reorders_independent_systems() {
+    let orders = (0..32)
+        .map(|seed| first_frame_order(seed, (first, second.ambiguous_with(first))))
+        .collect::<Vec<_>>();
+
+    assert!(orders.contains(&vec![1, 2]));
+    assert!(orders.contains(&vec![2, 1]));
+}
+
+/// This test makes sure existing ordering constraints are never violated.
+#[test]
+fn it_respects_existing_ordering() {
+    for seed in 0..32 {
+        assert_eq!(first_frame_order(seed, (first, second).chain()), vec![1, 2]);
+        assert_eq!(first_frame_order(seed, (second, first).chain()), vec![2, 1]);
+    }
+}
@@ -0,0 +1,823 @@
+mod common;
+
+mod cached_checksum {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, Checksum, RollbackFrameCount};
+
+    use crate::common::{self, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy, Default, Debug)]
+    struct Moving;
+
+    fn setup_system(mut commands: Commands) {
+        for health in 0..20 {
+            commands.spawn(Health(health)).add_rollback();
+        }
+
+        commands.spawn((Health(100), Moving)).add_rollback();
+        commands.spawn((Health(200), Moving)).add_rollback();
+    }
+
+    fn simulate(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        mut moving: Query<&mut Health, With<Moving>>,
+    ) {
+        for mut health in moving.iter_mut() {
+            health.0 += 1;
+        }
+
+        if frame.0 % 5 == 0 {
+            commands.spawn(Health(frame.0 as u32)).add_rollback();
+        }
+    }
+
+    fn despawn_system(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        query: Query<(Entity, &Health), Without<Moving>>,
+    ) {
+        for (entity, health) in query.iter() {
+            if frame.0 % 7 == 0 && health.0 % 3 == 0 {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    fn create_app(cached: bool) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(synctest_session(2))
+            .rollback_component_with_copy::<Health>()
+            .rollback_component_with_copy::<Moving>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, (simulate, despawn_system).chain());
+
+        if cached {
+            app.checksum_component_with_hash_cached::<Health>();
+        } else {
+            app.checksum_component_with_hash::<Health>();
+        }
+
+        app
+    }
+
+    /// This test makes sure caching the checksum of a component produces the same [`Checksum`] as
+    /// hashing every component each frame, while components change, spawn, despawn and roll back.
+    #[test]
+    fn it_matches_the_uncached_checksum() {
+        let mut uncached = create_app(false);
+        let mut cached = create_app(true);
+
+        for _ in 0..60 {
+            uncached.update();
+            cached.update();
+
+            assert_eq!(
+                uncached.world.resource::<RollbackFrameCount>(),
+                cached.world.resource::<RollbackFrameCount>()
+            );
+            assert_eq!(
+                uncached.world.resource::<Checksum>().0,
+                cached.world.resource::<Checksum>().0
+            );
+        }
+    }
+}
+
+mod checksum_after_snapshot {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, Checksum, ChecksumFlag, ChecksumPart, ChecksumPlugin, LoadWorld,
+        RollbackFrameCount, SaveWorld,
+    };
+    use std::collections::BTreeMap;
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    /// Rolled back by a custom snapshot system, which also provides its [`ChecksumPart`].
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct Score(u32);
+
+    #[derive(Resource, Default)]
+    struct ScoreSnapshots(BTreeMap<i32, Score>);
+
+    /// The frame and [`Checksum`] of every save, alongside the [`Score`] it should include.
+    #[derive(Resource, Default)]
+    struct Saves(Vec<(i32, u128, u128)>);
+
+    fn setup_system(mut commands: Commands) {
+        commands.spawn((ChecksumPart::default(), ChecksumFlag::<Score>::default()));
+    }
+
+    fn score_system(mut score: ResMut<Score>) {
+        score.0 += 3;
+    }
+
+    fn save_score(
+        frame: Res<RollbackFrameCount>,
+        score: Res<Score>,
+        mut snapshots: ResMut<ScoreSnapshots>,
+        mut part: Query<&mut ChecksumPart, With<ChecksumFlag<Score>>>,
+    ) {
+        snapshots.0.insert(frame.0, *score);
+
+        // derived from the saved data, rather than the world
+        part.single_mut().0 = snapshots.0[&frame.0].0 as u128;
+    }
+
+    fn load_score(
+        frame: Res<RollbackFrameCount>,
+        snapshots: Res<ScoreSnapshots>,
+        mut score: ResMut<Score>,
+    ) {
+        *score = snapshots.0[&frame.0];
+    }
+
+    fn record_save(
+        frame: Res<RollbackFrameCount>,
+        checksum: Res<Checksum>,
+        parts: Query<&ChecksumPart>,
+        mut saves: ResMut<Saves>,
+    ) {
+        let parts = parts.iter().fold(0, |a, part| a ^ part.0);
+
+        saves.0.push((frame.0, checksum.0, parts));
+    }
+
+    /// This test makes sure the [`Checksum`] of every frame includes the [`ChecksumPart`] written by
+    /// a custom system in [`SaveWorldSet::Snapshot`] for that same frame.
+    #[test]
+    fn it_includes_custom_snapshots_in_the_checksum() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .init_resource::<Score>()
+            .init_resource::<ScoreSnapshots>()
+            .init_resource::<Saves>()
+            .add_systems(Startup, setup_system)
+            .add_systems(SaveWorld, save_score.in_set(SaveWorldSet::Snapshot))
+            .add_systems(LoadWorld, load_score.in_set(LoadWorldSet::Data))
+            .add_systems(
+                SaveWorld,
+                record_save
+                    .after(SaveWorldSet::Checksum)
+                    .after(ChecksumPlugin::update),
+            )
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, score_system);
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let saves = &app.world.resource::<Saves>().0;
+
+        assert!(saves.len() > 10);
+
+        for &(frame, checksum, parts) in saves {
+            assert_eq!(checksum, parts, "frame {frame}");
+        }
+    }
+}
+
+mod checksum_contributor {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, Checksum, ChecksumContributor};
+
+    use crate::common::{self, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Health(u32);
+
+    #[derive(Resource, Clone, Copy)]
+    struct Initial {
+        contributor: u32,
+        cosmetic: u32,
+    }
+
+    fn setup_system(mut commands: Commands, initial: Res<Initial>) {
+        commands
+            .spawn((Health(initial.contributor), ChecksumContributor))
+            .add_rollback();
+        commands.spawn(Health(initial.cosmetic)).add_rollback();
+    }
+
+    fn heal(mut query: Query<&mut Health>) {
+        for mut health in query.iter_mut() {
+            health.0 += 1;
+        }
+    }
+
+    fn create_app(initial: Initial, cached: bool) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(synctest_session(2))
+            .insert_resource(initial)
+            .rollback_component_with_copy::<Health>()
+            .checksum_contributors_only()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, heal);
+
+        if cached {
+            app.checksum_component_with_hash_cached::<Health>();
+        } else {
+            app.checksum_component_with_hash::<Health>();
+        }
+
+        app
+    }
+
+    fn checksums(initial: Initial, cached: bool) -> Vec<u128> {
+        let mut app = create_app(initial, cached);
+
+        (0..30)
+            .map(|_| {
+                app.update();
+                app.world.resource::<Checksum>().0
+            })
+            .collect()
+    }
+
+    /// This test makes sure only entities flagged as a [`ChecksumContributor`] affect the
+    /// [`Checksum`], with both cached and uncached component checksums.
+    #[test]
+    fn it_only_checksums_contributors() {
+        for cached in [false, true] {
+            let expected = checksums(
+                Initial {
+                    contributor: 0,
+                    cosmetic: 0,
+                },
+                cached,
+            );
+
+            let cosmetic_changed = checksums(
+                Initial {
+                    contributor: 0,
+                    cosmetic: 50,
+                },
+                cached,
+            );
+            assert_eq!(expected, cosmetic_changed);
+
+            let contributor_changed = checksums(
+                Initial {
+                    contributor: 50,
+                    cosmetic: 0,
+                },
+                cached,
+            );
+            assert_ne!(expected.last(), contributor_changed.last());
+        }
+    }
+}
+
+mod checksum_order {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, Checksum, Replay, ReplayError, ReplayRecorder, RollbackRegistrationFingerprint,
+    };
+
+    use crate::common::{self, input_system, synctest_session, TestConfig};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Armor(u32);
+
+    #[derive(Resource, Clone, Copy, Default, Debug, Hash)]
+    struct Score(u32);
+
+    fn setup_system(mut commands: Commands) {
+        commands.spawn((Health(3), Armor(3))).add_rollback();
+        commands.spawn((Health(7), Armor(2))).add_rollback();
+    }
+
+    fn simulate(mut score: ResMut<Score>, mut query: Query<(&mut Health, &mut Armor)>) {
+        score.0 += 1;
+
+        for (mut health, mut armor) in query.iter_mut() {
+            health.0 += 1;
+            armor.0 += 1;
+        }
+    }
+
+    fn create_app(health_first: bool) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(synctest_session(2))
+            .init_resource::<Score>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, simulate);
+
+        if health_first {
+            app.rollback_component_with_copy::<Health>()
+                .checksum_component_with_hash::<Health>()
+                .rollback_resource_with_copy::<Score>()
+                .checksum_resource_with_hash::<Score>()
+                .rollback_component_with_copy::<Armor>()
+                .checksum_component_with_hash::<Armor>();
+        } else {
+            app.rollback_component_with_copy::<Armor>()
+                .checksum_component_with_hash::<Armor>()
+                .rollback_resource_with_copy::<Score>()
+                .checksum_resource_with_hash::<Score>()
+                .rollback_component_with_copy::<Health>()
+                .checksum_component_with_hash::<Health>();
+        }
+
+        app
+    }
+
+    /// This test makes sure the [`Checksum`] does not depend on the order in which types were
+    /// registered, so peers adding plugins in a different order still agree on identical state.
+    #[test]
+    fn checksum_is_independent_of_registration_order() {
+        let mut app1 = create_app(true);
+        let mut app2 = create_app(false);
+
+        for _ in 0..20 {
+            app1.update();
+            app2.update();
+        }
+
+        let checksum1 = app1.world.resource::<Checksum>().0;
+        let checksum2 = app2.world.resource::<Checksum>().0;
+
+        assert_ne!(checksum1, 0, "Checksum was not computed");
+        assert_eq!(
+            checksum1, checksum2,
+            "Checksum depends on registration order"
+        );
+    }
+
+    /// This test makes sure the [`RollbackRegistrationFingerprint`] identifies the set of rolled back
+    /// types, independent of the order in which they were registered.
+    #[test]
+    fn fingerprint_is_independent_of_registration_order() {
+        let app1 = create_app(true);
+        let app2 = create_app(false);
+
+        let fingerprint1 = app1.world.resource::<RollbackRegistrationFingerprint>();
+        let fingerprint2 = app2.world.resource::<RollbackRegistrationFingerprint>();
+
+        assert_eq!(fingerprint1.fingerprint(), fingerprint2.fingerprint());
+
+        let mut app3 = create_app(true);
+        app3.rollback_component_with_copy::<Transform>();
+
+        let fingerprint3 = app3.world.resource::<RollbackRegistrationFingerprint>();
+
+        assert_ne!(fingerprint1.fingerprint(), fingerprint3.fingerprint());
+
+        let mut app4 = create_app(true);
+        app4.set_rollback_version::<Health>(2);
+
+        let fingerprint4 = app4.world.resource::<RollbackRegistrationFingerprint>();
+
+        assert_eq!(fingerprint4.version::<Health>(), Some(2));
+        assert_ne!(fingerprint1.fingerprint(), fingerprint4.fingerprint());
+    }
+
+    /// This test makes sure a [`Replay`] recorded with a different rollback setup, such as before
+    /// changing the version of a rolled back type, is rejected when read back.
+    #[test]
+    fn replays_are_rejected_after_a_version_change() {
+        let mut app1 = create_app(true);
+        app1.insert_resource(ReplayRecorder::<TestConfig>::default());
+
+        for _ in 0..20 {
+            app1.update();
+        }
+
+        let bytes = app1
+            .world
+            .resource::<ReplayRecorder<TestConfig>>()
+            .replay()
+            .to_bytes();
+        let registration1 = app1.world.resource::<RollbackRegistrationFingerprint>();
+
+        assert!(Replay::<TestConfig>::from_bytes(&bytes, registration1).is_ok());
+
+        let mut app2 = create_app(true);
+        app2.set_rollback_version::<Health>(2);
+
+        let registration2 = app2.world.resource::<RollbackRegistrationFingerprint>();
+
+        assert_eq!(
+            Replay::<TestConfig>::from_bytes(&bytes, registration2).err(),
+            Some(ReplayError::RegistrationMismatch {
+                expected: registration2.fingerprint(),
+                found: registration1.fingerprint(),
+            })
+        );
+    }
+}
+
+mod checksum_parts {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, Checksum, ChecksumFlag, ChecksumPart};
+
+    use crate::common::{self, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Armor(u32);
+
+    /// The [`Armor`] every entity is spawned with.
+    #[derive(Resource, Clone, Copy)]
+    struct InitialArmor(u32);
+
+    fn setup_system(mut commands: Commands, armor: Res<InitialArmor>) {
+        commands.spawn((Health(3), Armor(armor.0))).add_rollback();
+        commands.spawn((Health(7), Armor(armor.0))).add_rollback();
+    }
+
+    fn simulate(mut query: Query<(&mut Health, &mut Armor)>) {
+        for (mut health, mut armor) in query.iter_mut() {
+            health.0 += 1;
+            armor.0 += 2;
+        }
+    }
+
+    fn create_app(armor: u32) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(synctest_session(2))
+            .insert_resource(InitialArmor(armor))
+            .rollback_component_with_copy::<Health>()
+            .checksum_component_with_hash::<Health>()
+            .rollback_component_with_copy::<Armor>()
+            .checksum_component_with_hash::<Armor>()
+            .add_systems(GgrsInitSchedule, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, simulate);
+
+        app
+    }
+
+    fn checksum(app: &App) -> u128 {
+        app.world.resource::<Checksum>().0
+    }
+
+    fn part<T: Component>(app: &mut App) -> u128 {
+        app.world
+            .query_filtered::<&ChecksumPart, With<ChecksumFlag<T>>>()
+            .single(&app.world)
+            .0
+    }
+
+    /// This test makes sure identical worlds produce identical checksums on every frame.
+    #[test]
+    fn it_produces_identical_checksums_for_identical_worlds() {
+        let mut app1 = create_app(5);
+        let mut app2 = create_app(5);
+
+        for _ in 0..20 {
+            app1.update();
+            app2.update();
+
+            assert_eq!(checksum(&app1), checksum(&app2));
+        }
+
+        assert_ne!(checksum(&app1), 0, "Checksum was not computed");
+    }
+
+    /// This test makes sure the part of every type can be inspected, so a diverging type can be
+    /// identified from the parts alone.
+    #[test]
+    fn it_identifies_the_diverging_type() {
+        let mut app1 = create_app(5);
+        let mut app2 = create_app(6);
+
+        for _ in 0..20 {
+            app1.update();
+            app2.update();
+        }
+
+        assert_ne!(checksum(&app1), checksum(&app2));
+        assert_eq!(part::<Health>(&mut app1), part::<Health>(&mut app2));
+        assert_ne!(part::<Armor>(&mut app1), part::<Armor>(&mut app2));
+        assert_eq!(
+            checksum(&app1),
+            part::<Health>(&mut app1) ^ part::<Armor>(&mut app1) ^ other_parts(&mut app1)
+        );
+    }
+
+    /// The parts of every type other than [`Health`] and [`Armor`], such as the entities themselves.
+    fn other_parts(app: &mut App) -> u128 {
+        app.world
+            .query_filtered::<&ChecksumPart, (Without<ChecksumFlag<Health>>, Without<ChecksumFlag<Armor>>)>()
+            .iter(&app.world)
+            .fold(0, |a, part| a ^ part.0)
+    }
+}
+
+mod checksum_verification {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, BevyGgrsError, SessionError, SnapshotChecksumVerification};
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Counter(u32);
+
+    #[derive(Resource, Default)]
+    struct Drifts(Vec<i32>);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Counter::default()).add_rollback();
+    }
+
+    fn count(mut query: Query<&mut Counter>) {
+        for mut counter in query.iter_mut() {
+            counter.0 += 1;
+        }
+    }
+
+    fn record_drifts(mut errors: EventReader<SessionError>, mut drifts: ResMut<Drifts>) {
+        for SessionError(error) in errors.read() {
+            if let BevyGgrsError::ChecksumDrift { frame, .. } = error {
+                drifts.0.push(*frame);
+            }
+        }
+    }
+
+    fn run_counter(register: impl FnOnce(&mut App)) -> App {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .verify_snapshot_checksums()
+            .checksum_component_with_hash::<Counter>()
+            .init_resource::<Drifts>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count)
+            .add_systems(PostUpdate, record_drifts);
+
+        register(&mut app);
+
+        for _ in 0..30 {
+            app.update();
+        }
+
+        app
+    }
+
+    /// This test makes sure the checksum of a loaded frame matches the saved one, as long as every
+    /// checksummed type is rolled back.
+    #[test]
+    fn it_accepts_matching_checksums() {
+        let app = run_counter(|app| {
+            app.rollback_component_with_copy::<Counter>();
+        });
+
+        assert!(app.world.resource::<Drifts>().0.is_empty());
+        assert_eq!(
+            app.world
+                .resource::<SnapshotChecksumVerification>()
+                .mismatches(),
+            0
+        );
+    }
+
+    /// This test makes sure a checksummed type which is not rolled back is reported, as loading a
+    /// frame does not restore the state its checksum was computed from.
+    #[test]
+    fn it_reports_checksums_drifting_from_snapshots() {
+        let app = run_counter(|_| {});
+
+        assert!(!app.world.resource::<Drifts>().0.is_empty());
+        assert!(
+            app.world
+                .resource::<SnapshotChecksumVerification>()
+                .mismatches()
+                > 0
+        );
+    }
+}
+
+mod confirmed_checksum {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, Checksum, RollbackFrameCount, RollbackSpawnFrame, SessionError};
+
+    use crate::common::{self, input_system, TestConfig};
+
+    const WINDOW: usize = 4;
+    const SPAWNED_ON: i32 = 5;
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Health(u32);
+
+    /// Whether an additional entity is spawned on [`SPAWNED_ON`].
+    #[derive(Resource, Clone, Copy)]
+    struct SpawnAdditional(bool);
+
+    #[derive(Resource, Default)]
+    struct Errors(usize);
+
+    fn setup_system(mut commands: Commands) {
+        commands.spawn(Health(0)).add_rollback();
+    }
+
+    fn spawn_system(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        additional: Res<SpawnAdditional>,
+    ) {
+        if additional.0 && frame.0 == SPAWNED_ON {
+            commands.spawn(Health(50)).add_rollback();
+        }
+    }
+
+    fn heal(mut query: Query<&mut Health>) {
+        for mut health in query.iter_mut() {
+            health.0 += 1;
+        }
+    }
+
+    fn record_errors(mut events: EventReader<SessionError>, mut errors: ResMut<Errors>) {
+        errors.0 += events.read().count();
+    }
+
+    fn create_app(additional: bool, cached: bool) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(Session::SyncTest(
+            SessionBuilder::<TestConfig>::new()
+                .with_num_players(1)
+                .with_check_distance(2)
+                .with_max_prediction_window(WINDOW)
+                .unwrap()
+                .add_player(PlayerType::Local, 0)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap(),
+        ))
+        .insert_resource(SpawnAdditional(additional))
+        .init_resource::<Errors>()
+        .rollback_component_with_copy::<Health>()
+        .checksum_confirmed_entities_only()
+        .add_systems(Startup, setup_system)
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsSchedule, (spawn_system, heal).chain())
+        .add_systems(Update, record_errors);
+
+        if cached {
+            app.checksum_component_with_hash_cached::<Health>();
+        } else {
+            app.checksum_component_with_hash::<Health>();
+        }
+
+        app
+    }
+
+    /// The checksum of every frame, along with the app it was produced by.
+    fn checksums(additional: bool, cached: bool) -> (App, Vec<(i32, u128)>) {
+        let mut app = create_app(additional, cached);
+
+        let checksums = (0..30)
+            .map(|_| {
+                app.update();
+                (
+                    app.world.resource::<RollbackFrameCount>().0,
+                    app.world.resource::<Checksum>().0,
+                )
+            })
+            .collect();
+
+        (app, checksums)
+    }
+
+    /// This test makes sure entities only contribute to the [`Checksum`] once they were spawned at
+    /// least the maximum prediction window ago, with both cached and uncached component checksums.
+    #[test]
+    fn it_only_checksums_confirmed_entities() {
+        for cached in [false, true] {
+            let (_, expected) = checksums(false, cached);
+            let (mut app, spawned) = checksums(true, cached);
+
+            assert_eq!(app.world.resource::<Errors>().0, 0);
+
+            let mut frames = app
+                .world
+                .query::<&RollbackSpawnFrame>()
+                .iter(&app.world)
+                .map(RollbackSpawnFrame::frame)
+                .collect::<Vec<_>>();
+            frames.sort();
+            assert_eq!(frames, vec![None, Some(SPAWNED_ON)]);
+
+            assert!(expected.last().unwrap().0 > SPAWNED_ON + WINDOW as i32);
+
+            for (&(frame, expected), &(spawned_frame, spawned)) in expected.iter().zip(&spawned) {
+                assert_eq!(frame, spawned_frame);
+
+                if frame < SPAWNED_ON + WINDOW as i32 {
+                    assert_eq!(expected, spawned, "frame {frame}");
+                } else {
+                    assert_ne!(expected, spawned, "frame {frame}");
+                }
+            }
+        }
+    }
+}
+
+mod mutation_check {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, UnregisteredMutationCheck};
+
+    use crate::common::{self, input_system, TestConfig};
+
+    #[derive(Resource, Clone, Copy, Default)]
+    struct RolledBack(u32);
+
+    #[derive(Resource, Clone, Copy, Default)]
+    struct NotRolledBack(u32);
+
+    #[derive(Resource, Clone, Copy, Default)]
+    struct Ignored(u32);
+
+    #[derive(Resource, Clone, Copy, Default)]
+    struct OnlyRead(u32);
+
+    fn simulate(
+        mut rolled_back: ResMut<RolledBack>,
+        mut not_rolled_back: ResMut<NotRolledBack>,
+        mut ignored: ResMut<Ignored>,
+        only_read: Res<OnlyRead>,
+    ) {
+        rolled_back.0 += 1;
+        not_rolled_back.0 += 1;
+        ignored.0 += only_read.0;
+    }
+
+    fn create_app(enabled: bool) -> App {
+        let mut app = common::create_app();
+
+        app.init_resource::<RolledBack>()
+            .init_resource::<NotRolledBack>()
+            .init_resource::<Ignored>()
+            .init_resource::<OnlyRead>()
+            .rollback_resource_with_copy::<RolledBack>()
+            .ignore_unregistered_mutations::<Ignored>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, simulate)
+            .insert_resource(Session::SyncTest(
+                SessionBuilder::<TestConfig>::new()
+                    .with_num_players(1)
+                    .add_player(PlayerType::Local, 0)
+                    .unwrap()
+                    .with_check_distance(2)
+                    .start_synctest_session()
+                    .unwrap(),
+            ));
+
+        if enabled {
+            app.warn_on_unregistered_mutations();
+        }
+
+        app
+    }
+
+    /// This test makes sure only resources which are mutated while advancing a frame, but are neither
+    /// rolled back nor ignored, are reported.
+    #[test]
+    fn it_reports_unregistered_mutations() {
+        let mut app = create_app(true);
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let check = app.world.resource::<UnregisteredMutationCheck>();
+
+        assert_eq!(check.reported().len(), 1);
+        assert!(check.reported()[0].ends_with("NotRolledBack"));
+    }
+
+    /// This test makes sure nothing is reported unless the check is enabled.
+    #[test]
+    fn it_is_disabled_by_default() {
+        let mut app = create_app(false);
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let check = app.world.resource::<UnregisteredMutationCheck>();
+
+        assert!(!check.is_enabled());
+        assert!(check.reported().is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+use bevy::{
+    prelude::*,
+    time::TimeUpdateStrategy,
+    utils::{Duration, HashMap},
+};
+use bevy_ggrs::{prelude::*, LocalInputs};
+
+pub type TestConfig = GgrsConfig<u8, usize>;
+
+/// Provides the input `0` for the single local player every frame.
+pub fn input_system(mut commands: Commands) {
+    commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, 0)])));
+}
+
+/// Creates an [`App`] running the [`GgrsPlugin`] at 60 fps, where every update advances the
+/// time by exactly one frame.
+pub fn create_app() -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .set_rollback_schedule_fps(60);
+
+    app
+}
+
+/// Starts a [`Session::SyncTest`] for a single local player, rolling back `check_distance`
+/// frames every frame.
+pub fn synctest_session(check_distance: usize) -> Session<TestConfig> {
+    Session::SyncTest(
+        SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(check_distance)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap(),
+    )
+}
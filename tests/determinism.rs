@@ -0,0 +1,217 @@
+mod common;
+
+mod deterministic_map {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, DeterministicMap, RollbackFrameCount};
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    #[derive(Resource, Reflect, Clone, Default, Debug, PartialEq, Eq, Hash)]
+    struct Scores(DeterministicMap<u32, i32>);
+
+    /// Grows and shrinks the map, so rolling back crosses both insertions and removals.
+    fn score(map: &mut DeterministicMap<u32, i32>, frame: i32) {
+        map.insert(frame as u32 % 10, frame);
+
+        if frame % 3 == 0 {
+            map.remove(&((frame as u32 + 4) % 10));
+            map.remove(&((frame as u32 + 7) % 10));
+        }
+    }
+
+    fn score_system(frame: Res<RollbackFrameCount>, mut scores: ResMut<Scores>) {
+        score(&mut scores.0, frame.0);
+    }
+
+    fn hash(map: &DeterministicMap<u32, i32>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        map.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// This test makes sure maps holding the same entries iterate, compare and hash identically,
+    /// regardless of the order they were inserted in.
+    #[test]
+    fn it_iterates_in_key_order() {
+        let forwards = (0..20)
+            .map(|key| (key, key as i32))
+            .collect::<DeterministicMap<_, _>>();
+        let mut backwards = (0..25)
+            .rev()
+            .map(|key| (key, key as i32))
+            .collect::<DeterministicMap<_, _>>();
+
+        backwards.retain(|&key, _| key < 20);
+
+        assert_eq!(forwards, backwards);
+        assert_eq!(hash(&forwards), hash(&backwards));
+        assert!(backwards.keys().copied().eq(0..20));
+    }
+
+    /// This test makes sure applying a reflected map leaves no entries of the previous value behind.
+    #[test]
+    fn it_applies_shorter_maps() {
+        let mut map = (0..10)
+            .map(|key| (key, 0))
+            .collect::<DeterministicMap<u32, i32>>();
+        let shorter = [(3, 1), (5, 2)]
+            .into_iter()
+            .collect::<DeterministicMap<u32, i32>>();
+
+        map.apply(shorter.as_reflect());
+
+        assert_eq!(map, shorter);
+        assert_eq!(hash(&map), hash(&shorter));
+
+        map.insert(4, 3);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(&3, &1), (&4, &3), (&5, &2)]
+        );
+    }
+
+    fn run_scores(register: impl FnOnce(&mut App)) {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(7))
+            .init_resource::<Scores>()
+            .checksum_resource_with_hash::<Scores>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, score_system);
+
+        register(&mut app);
+
+        for _ in 0..60 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+
+            let mut expected = DeterministicMap::new();
+            for frame in 1..=frame {
+                score(&mut expected, frame);
+            }
+
+            assert_eq!(app.world.resource::<Scores>().0, expected, "frame {frame}");
+        }
+    }
+
+    /// This test makes sure a map rolled back using reflection matches the map without rollback.
+    #[test]
+    fn it_rolls_back_with_reflect() {
+        run_scores(|app| {
+            app.rollback_resource_with_reflect::<Scores>();
+        });
+    }
+
+    /// This test makes sure a map rolled back using [`Clone`] matches the map without rollback.
+    #[test]
+    fn it_rolls_back_with_clone() {
+        run_scores(|app| {
+            app.rollback_resource_with_clone::<Scores>();
+        });
+    }
+}
+
+mod deterministic_sort {
+    use bevy_ggrs::{deterministic_sort, TotalOrder};
+
+    /// This test makes sure elements with equal keys keep the order they started in.
+    #[test]
+    fn it_sorts_stably() {
+        let mut elements = (0..20).map(|id| (id % 3, id)).collect::<Vec<_>>();
+
+        deterministic_sort(&mut elements, |&(key, _)| key);
+
+        let expected = (0..3)
+            .flat_map(|key| {
+                (0..20)
+                    .filter(move |id| id % 3 == key)
+                    .map(move |id| (key, id))
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(elements, expected);
+    }
+
+    /// This test makes sure floating point keys sort identically regardless of the order they
+    /// started in, including signed zeroes and `NaN`.
+    #[test]
+    fn it_sorts_floats_in_total_order() {
+        let floats = [3.5, -0.0, f32::NAN, 0.0, -f32::NAN, -2.0, f32::INFINITY];
+
+        let mut forwards = floats.to_vec();
+        let mut backwards = floats.iter().rev().copied().collect::<Vec<_>>();
+
+        deterministic_sort(&mut forwards, |&float| TotalOrder(float));
+        deterministic_sort(&mut backwards, |&float| TotalOrder(float));
+
+        let bits = |floats: &[f32]| {
+            floats
+                .iter()
+                .map(|float| float.to_bits())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(bits(&forwards), bits(&backwards));
+        assert_eq!(
+            bits(&forwards),
+            bits(&[-f32::NAN, -2.0, -0.0, 0.0, 3.5, f32::INFINITY, f32::NAN])
+        );
+    }
+}
+
+mod ordered_iteration {
+    use bevy::{ecs::system::EntityCommand, prelude::*};
+    use bevy_ggrs::{AddRollbackCommand, Rollback, RollbackOrdered};
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+    struct Id(u32);
+
+    fn spawn(world: &mut World, ids: impl IntoIterator<Item = u32>) {
+        for id in ids {
+            let entity = world.spawn(Id(id)).id();
+            AddRollbackCommand.apply(entity, world);
+        }
+    }
+
+    /// This test makes sure entities are visited in the order their [`Rollback`] was created in,
+    /// regardless of the order a [`Query`] yields them in.
+    #[test]
+    fn it_iterates_pairs_in_rollback_order() {
+        let mut world = World::new();
+
+        spawn(&mut world, [0, 1]);
+
+        // despawning and respawning changes the archetype order, but not the rollback order
+        let first = world
+            .query::<(Entity, &Id)>()
+            .iter(&world)
+            .find(|(_, id)| id.0 == 0)
+            .map(|(entity, _)| entity)
+            .unwrap();
+        world.entity_mut(first).remove::<Id>().insert(Id(0));
+
+        spawn(&mut world, [2, 3]);
+
+        let order = world.resource::<RollbackOrdered>().clone();
+        let mut query = world.query::<(&Rollback, &Id)>();
+
+        let sorted = order
+            .sorted(query.iter(&world))
+            .into_iter()
+            .map(|(_, id)| id.0)
+            .collect::<Vec<_>>();
+
+        assert_eq!(sorted, vec![0, 1, 2, 3]);
+
+        let mut pairs = Vec::new();
+
+        order.for_each_pair(query.iter(&world), |(_, a), (_, b)| pairs.push((a.0, b.0)));
+
+        assert_eq!(pairs, vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    }
+}
@@ -0,0 +1,791 @@
+mod common;
+
+mod deferred_reactions {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, GgrsComponentSnapshots, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    /// A [`Hit`] occurs on every frame which is a multiple of this period.
+    const HIT_PERIOD: i32 = 5;
+
+    /// Rollbacks span further than the hit period, so every rollback crosses a hit.
+    const CHECK_DISTANCE: usize = 7;
+
+    #[derive(Event)]
+    struct Hit(Entity);
+
+    /// Inserted in reaction to a [`Hit`], holding the frame of the most recent hit.
+    #[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    struct Stunned(i32);
+
+    #[derive(Component)]
+    struct Player;
+
+    fn setup_system(mut commands: Commands) {
+        commands.spawn(Player).add_rollback();
+    }
+
+    fn hit(
+        frame: Res<RollbackFrameCount>,
+        players: Query<Entity, With<Player>>,
+        mut hits: EventWriter<Hit>,
+    ) {
+        if frame.0 % HIT_PERIOD == 0 {
+            hits.send_batch(players.iter().map(Hit));
+        }
+    }
+
+    fn react(mut commands: Commands, frame: Res<RollbackFrameCount>, mut hits: EventReader<Hit>) {
+        for &Hit(entity) in hits.read() {
+            commands.entity(entity).insert(Stunned(frame.0));
+        }
+    }
+
+    fn expected_stun(frame: i32) -> Option<Stunned> {
+        (frame >= HIT_PERIOD).then(|| Stunned(frame - frame % HIT_PERIOD))
+    }
+
+    /// This test makes sure components inserted by commands queued in reaction to an [`Event`] sent
+    /// within the [`GgrsSchedule`] are applied before the frame is saved, and are removed again when
+    /// rolling back to a frame before the reaction.
+    #[test]
+    fn it_snapshots_components_inserted_in_reaction() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(CHECK_DISTANCE))
+            .add_event::<Hit>()
+            .rollback_component_with_copy::<Stunned>()
+            .checksum_component_with_hash::<Stunned>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, (hit, react).chain());
+
+        for _ in 0..60 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+
+            let (&rollback, stunned) = app
+                .world
+                .query_filtered::<(&Rollback, Option<&Stunned>), With<Player>>()
+                .single(&app.world);
+
+            assert_eq!(stunned.copied(), expected_stun(frame), "frame {frame}");
+
+            let snapshots = app.world.resource::<GgrsComponentSnapshots<Stunned>>();
+
+            for frame in snapshots.frames() {
+                let snapshot = snapshots.peek(frame).unwrap();
+                assert_eq!(
+                    snapshot.get(&rollback).copied(),
+                    expected_stun(frame),
+                    "snapshot of frame {frame}"
+                );
+            }
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+        assert!(frame > HIT_PERIOD * 4, "Not enough frames advanced");
+    }
+}
+
+mod deferred_spawns {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{prelude::*, DeferredSpawnPlugin, DeferredSpawns, LocalInputs};
+
+    use crate::common::create_app;
+
+    type TestConfig = GgrsConfig<u32, usize>;
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+    struct Spawned(u32);
+
+    /// The amount of spawns each of the two players claims to be ready for.
+    #[derive(Resource, Default)]
+    struct Ready([u32; 2]);
+
+    fn input_system(mut commands: Commands, ready: Res<Ready>) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([
+            (0, ready.0[0]),
+            (1, ready.0[1]),
+        ])));
+    }
+
+    fn queue_spawns(mut spawns: ResMut<DeferredSpawns>) {
+        for id in 0..2 {
+            spawns.push([], move |commands| {
+                commands.spawn(Spawned(id)).add_rollback();
+            });
+        }
+    }
+
+    fn spawned(app: &mut App) -> Vec<u32> {
+        let mut spawned = app
+            .world
+            .query::<&Spawned>()
+            .iter(&app.world)
+            .map(|spawned| spawned.0)
+            .collect::<Vec<_>>();
+        spawned.sort();
+        spawned
+    }
+
+    /// This test makes sure deferred spawns are only performed once every player is ready for them,
+    /// and exactly once despite rollbacks.
+    #[test]
+    fn it_spawns_once_every_player_is_ready() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.add_plugins(DeferredSpawnPlugin::<TestConfig>::new(|&ready| ready))
+            .rollback_component_with_copy::<Spawned>()
+            .init_resource::<Ready>()
+            .add_systems(Startup, queue_spawns)
+            .add_systems(ReadInputs, input_system);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(2)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .add_player(PlayerType::Local, 1)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        app.world.resource_mut::<Ready>().0 = [2, 0];
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(spawned(&mut app), Vec::<u32>::new());
+
+        app.world.resource_mut::<Ready>().0 = [2, 1];
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(spawned(&mut app), vec![0]);
+
+        app.world.resource_mut::<Ready>().0 = [2, 2];
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(spawned(&mut app), vec![0, 1]);
+
+        Ok(())
+    }
+}
+
+mod entity_audit {
+    use bevy::{
+        ecs::entity::{EntityMapper, MapEntities},
+        prelude::*,
+    };
+    use bevy_ggrs::{prelude::*, EntityMappingAudit};
+
+    use crate::common::TestConfig;
+
+    #[derive(Component, Reflect, Clone, Copy)]
+    struct Target(Entity);
+
+    #[derive(Component, Reflect, Clone, Copy)]
+    struct MappedTarget(Entity);
+
+    impl MapEntities for MappedTarget {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    #[derive(Resource, Reflect, Clone, Default)]
+    struct Targets(Vec<Option<Entity>>);
+
+    #[derive(Component, Reflect, Clone, Copy)]
+    struct Health(u32);
+
+    /// This test makes sure rolled back types containing an unmapped [`Entity`] are reported.
+    #[test]
+    fn it_reports_unmapped_entities() {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .register_type::<Target>()
+            .register_type::<MappedTarget>()
+            .register_type::<Targets>()
+            .register_type::<Health>()
+            .register_type::<Vec<Option<Entity>>>()
+            .register_type::<Option<Entity>>()
+            .rollback_component_with_copy::<Target>()
+            .rollback_component_with_copy::<MappedTarget>()
+            .update_component_with_map_entities::<MappedTarget>()
+            .rollback_resource_with_clone::<Targets>()
+            .rollback_component_with_copy::<Health>();
+
+        let registry = app.world.resource::<AppTypeRegistry>().read();
+        let unmapped = app
+            .world
+            .resource::<EntityMappingAudit>()
+            .unmapped(&registry);
+
+        assert_eq!(
+            unmapped,
+            vec![
+                std::any::type_name::<Target>(),
+                std::any::type_name::<Targets>()
+            ]
+        );
+    }
+}
+
+mod no_rollback {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, GgrsInitSchedule, NoRollback, RollbackFrameCount};
+
+    use crate::common::{self, input_system, TestConfig};
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+    struct Counter(i32);
+
+    #[derive(Component, Clone, Copy)]
+    struct Excluded;
+
+    fn count(mut query: Query<&mut Counter, Without<NoRollback>>) {
+        for mut counter in query.iter_mut() {
+            counter.0 += 1;
+        }
+    }
+
+    fn create_app<M>(spawn: impl IntoSystemConfigs<M>) -> App {
+        let mut app = common::create_app();
+
+        app.rollback_component_with_copy::<Counter>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        app.insert_resource(Session::SyncTest(session));
+
+        app
+    }
+
+    fn counter<F: bevy::ecs::query::QueryFilter>(app: &mut App) -> Counter {
+        *app.world.query_filtered::<&Counter, F>().single(&app.world)
+    }
+
+    /// This test makes sure excluded entities are never restored or despawned by a rollback, while
+    /// keeping their [`Rollback`] id.
+    #[test]
+    fn it_excludes_entities_from_rollback() {
+        let mut app = create_app(|mut commands: Commands| {
+            commands.spawn(Counter(0)).add_rollback();
+            commands
+                .spawn((Counter(0), Excluded, NoRollback))
+                .add_rollback();
+        });
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        // changed outside of the simulation, which is never undone
+        app.world
+            .query_filtered::<&mut Counter, With<Excluded>>()
+            .single_mut(&mut app.world)
+            .0 = 100;
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+
+        assert_eq!(counter::<Without<NoRollback>>(&mut app), Counter(frame));
+        assert_eq!(counter::<With<Excluded>>(&mut app), Counter(100));
+
+        // never despawned, and still identified by its rollback id
+        app.world
+            .query_filtered::<&Rollback, With<Excluded>>()
+            .single(&app.world);
+    }
+
+    fn toggle(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        query: Query<Entity, With<Counter>>,
+    ) {
+        for entity in query.iter() {
+            match frame.0 {
+                5 => {
+                    commands.entity(entity).insert(NoRollback);
+                }
+                10 => {
+                    commands.entity(entity).remove::<NoRollback>();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// This test makes sure entities are rolled back exactly while transitioning in and out of
+    /// rollback, even when rolling back across the transition.
+    #[test]
+    fn it_transitions_entities_in_and_out_of_rollback() {
+        let mut app = create_app(|mut commands: Commands| {
+            commands.spawn(Counter(0)).add_rollback();
+        });
+
+        // the toggled marker must not be seen by systems modifying the entity on the same frame
+        app.add_systems(GgrsSchedule, toggle.after(count));
+
+        for _ in 0..20 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+            let Counter(counter) = counter::<()>(&mut app);
+
+            // counting until the exclusion on frame 5, and again after the inclusion on frame 10
+            assert_eq!(counter, frame.min(5) + (frame - 10).max(0), "frame {frame}");
+        }
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > 12);
+    }
+}
+
+mod resource_entity_mapping {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    #[derive(Component, Clone, Copy, Default)]
+    struct Target;
+
+    /// Stands in for a relation graph from a third-party crate, which cannot implement `MapEntities`.
+    #[derive(Resource, Reflect, Clone, Default, Debug)]
+    #[reflect(Resource)]
+    struct Targets(Vec<Entity>);
+
+    fn setup_system(mut commands: Commands) {
+        let targets = (0..3)
+            .map(|_| commands.spawn(Target).add_rollback().id())
+            .collect();
+
+        commands.insert_resource(Targets(targets));
+    }
+
+    /// Periodically replaces the first target with a new entity.
+    fn replace_target(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        mut targets: ResMut<Targets>,
+    ) {
+        if frame.0 % 4 != 0 {
+            return;
+        }
+
+        commands.entity(targets.0[0]).despawn();
+        targets.0[0] = commands.spawn(Target).add_rollback().id();
+    }
+
+    /// This test makes sure every [`Entity`] within a resource is remapped after a rollback recreates
+    /// it, using [`Reflect`] to find them.
+    #[test]
+    fn it_maps_entities_within_resources() {
+        let mut app = create_app();
+
+        app.register_type::<Targets>()
+            .rollback_component_with_copy::<Target>()
+            .rollback_resource_with_clone::<Targets>()
+            .update_resource_with_reflect_map_entities::<Targets>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, replace_target)
+            .insert_resource(Session::SyncTest(
+                SessionBuilder::<TestConfig>::new()
+                    .with_num_players(1)
+                    .add_player(PlayerType::Local, 0)
+                    .unwrap()
+                    .with_check_distance(2)
+                    .start_synctest_session()
+                    .unwrap(),
+            ));
+
+        for _ in 0..20 {
+            app.update();
+
+            let targets = app.world.resource::<Targets>().0.clone();
+            assert_eq!(targets.len(), 3);
+
+            for target in targets {
+                assert!(
+                    app.world.get::<Target>(target).is_some(),
+                    "{target:?} is not a live target"
+                );
+            }
+        }
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > 8);
+    }
+}
+
+mod rollback_commands {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, GgrsComponentSnapshots, RollbackCommands, RollbackFrameCount, RollbackOrdered,
+        SessionError,
+    };
+
+    use crate::common::{self, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    struct Bullet {
+        fired: i32,
+    }
+
+    const LIFETIME: i32 = 4;
+
+    #[derive(Resource, Default)]
+    struct Errors(usize);
+
+    /// The amount of bullets seen by the system firing them, after every frame.
+    #[derive(Resource, Default)]
+    struct Seen(Vec<(i32, usize)>);
+
+    fn fire(
+        mut commands: RollbackCommands,
+        frame: Res<RollbackFrameCount>,
+        bullets: Query<&Bullet>,
+        mut seen: ResMut<Seen>,
+    ) {
+        seen.0.retain(|&(recorded, _)| recorded < frame.0);
+        seen.0.push((frame.0, bullets.iter().count()));
+
+        commands.spawn(Bullet { fired: frame.0 });
+    }
+
+    fn expire(
+        mut commands: RollbackCommands,
+        frame: Res<RollbackFrameCount>,
+        bullets: Query<(Entity, &Bullet)>,
+    ) {
+        for (entity, bullet) in &bullets {
+            if frame.0 - bullet.fired >= LIFETIME {
+                commands.despawn(entity);
+            }
+        }
+    }
+
+    /// The frames of the bullets alive once the provided frame has advanced.
+    fn alive_after(frame: i32) -> Vec<i32> {
+        ((frame - LIFETIME + 1).max(1)..=frame).collect()
+    }
+
+    fn record_errors(mut events: EventReader<SessionError>, mut errors: ResMut<Errors>) {
+        errors.0 += events.read().count();
+    }
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.rollback_component_with_copy::<Bullet>()
+            .checksum_component_with_hash::<Bullet>()
+            .init_resource::<Errors>()
+            .init_resource::<Seen>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, (fire, expire).chain())
+            .add_systems(Update, record_errors)
+            .insert_resource(synctest_session(2));
+
+        app
+    }
+
+    /// This test makes sure entities spawned and despawned through [`RollbackCommands`] are applied
+    /// after the frame advanced, are included in its snapshot, and are rolled back consistently.
+    #[test]
+    fn it_applies_rollback_commands_before_saving() {
+        let mut app = create_app();
+
+        for _ in 0..30 {
+            app.update();
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+
+        assert!(frame > 20);
+        assert_eq!(app.world.resource::<Errors>().0, 0);
+
+        // Bullets are only visible from the frame after they were fired, so the firing system never
+        // sees its own bullet, and always sees exactly the bullets of the previous frames.
+        let seen = &app.world.resource::<Seen>().0;
+        assert!(seen.len() > 20);
+        for &(frame, count) in seen {
+            assert_eq!(
+                count,
+                (frame - 1).clamp(0, LIFETIME) as usize,
+                "frame {frame}"
+            );
+        }
+
+        // Every snapshot already holds the bullet fired during the frame it was saved on.
+        let snapshots = app.world.resource::<GgrsComponentSnapshots<Bullet>>();
+        assert!(snapshots.iter().count() > 0);
+        for (saved, snapshot) in snapshots.iter() {
+            let mut fired = snapshot
+                .iter()
+                .map(|(_, bullet)| bullet.fired)
+                .collect::<Vec<_>>();
+            fired.sort();
+
+            assert_eq!(fired, alive_after(saved), "frame {saved}");
+        }
+
+        let mut fired = app
+            .world
+            .query::<(&Rollback, &Bullet)>()
+            .iter(&app.world)
+            .map(|(&rollback, bullet)| (rollback, bullet.fired))
+            .collect::<Vec<_>>();
+        fired.sort_by_key(|&(_, fired)| fired);
+
+        assert_eq!(
+            fired.iter().map(|&(_, fired)| fired).collect::<Vec<_>>(),
+            alive_after(frame)
+        );
+
+        // Rollback ids are handed out in the order bullets were fired, including re-simulated ones.
+        let ordered = app.world.resource::<RollbackOrdered>();
+        for pair in fired.windows(2) {
+            assert!(ordered.order(pair[0].0) < ordered.order(pair[1].0));
+        }
+    }
+}
+
+mod rollback_entities {
+    use bevy::prelude::*;
+    use bevy_ggrs::prelude::*;
+
+    use crate::common::TestConfig;
+
+    #[derive(Component, Clone, Copy)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy)]
+    struct Velocity(f32);
+
+    /// Not rolled back, so never listed.
+    #[derive(Component)]
+    struct Decoration;
+
+    /// Every rollback entity, with the rolled back components it holds, as listed by
+    /// [`RollbackEntities`].
+    #[derive(Resource, Default)]
+    struct Listed(Vec<(Entity, Rollback, Vec<&'static str>)>);
+
+    /// The rollback entities spawned, in order.
+    #[derive(Resource, Default)]
+    struct Spawned(Vec<Entity>);
+
+    fn spawn_entities(mut commands: Commands) {
+        let spawned = vec![
+            commands
+                .spawn((Health(10), Velocity(1.), Decoration))
+                .add_rollback()
+                .id(),
+            commands.spawn(Health(20)).add_rollback().id(),
+            commands.spawn(Decoration).add_rollback().id(),
+        ];
+
+        commands.spawn((Health(30), Decoration));
+        commands.insert_resource(Spawned(spawned));
+    }
+
+    fn list_entities(entities: RollbackEntities, mut listed: ResMut<Listed>) {
+        listed.0 = entities
+            .iter()
+            .map(|(entity, rollback)| (entity, rollback, entities.components(entity)))
+            .collect();
+    }
+
+    /// This test makes sure [`RollbackEntities`] lists exactly the rollback entities, in the order of
+    /// their [`Rollback`], along with their rolled back components.
+    #[test]
+    fn it_lists_rollback_entities() {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .rollback_component_with_copy::<Health>()
+            .rollback_component_with_copy::<Velocity>()
+            .init_resource::<Listed>()
+            .add_systems(Startup, spawn_entities)
+            .add_systems(Update, list_entities);
+
+        app.update();
+
+        let listed = &app.world.resource::<Listed>().0;
+        let entities = listed
+            .iter()
+            .map(|&(entity, _, _)| entity)
+            .collect::<Vec<_>>();
+
+        assert_eq!(entities, app.world.resource::<Spawned>().0);
+
+        for (entity, rollback, _) in listed {
+            assert_eq!(app.world.get::<Rollback>(*entity), Some(rollback));
+        }
+
+        let health = std::any::type_name::<Health>();
+        let velocity = std::any::type_name::<Velocity>();
+
+        let mut expected = vec![health, velocity];
+        expected.sort_unstable();
+
+        assert_eq!(listed[0].2, expected);
+        assert_eq!(listed[1].2, vec![health]);
+        assert!(listed[2].2.is_empty());
+    }
+}
+
+mod rollback_scope {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, ActiveRollback, RollbackFrameCount, RollbackScopePlugin};
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    /// Frame on which the [`Late`] entity enters the rollback scope.
+    const ACTIVATION_FRAME: i32 = 10;
+
+    /// Frame on which the [`Leaving`] entity leaves the rollback scope.
+    const DEACTIVATION_FRAME: i32 = 20;
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq)]
+    struct Counter(u32);
+
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct Frames(u32);
+
+    /// Always in scope.
+    #[derive(Component)]
+    struct Early;
+
+    /// Never in scope.
+    #[derive(Component)]
+    struct Static;
+
+    /// Enters the scope on [`ACTIVATION_FRAME`].
+    #[derive(Component)]
+    struct Late;
+
+    /// Leaves the scope on [`DEACTIVATION_FRAME`].
+    #[derive(Component)]
+    struct Leaving;
+
+    fn setup_system(mut commands: Commands) {
+        commands
+            .spawn((Early, ActiveRollback, Counter::default()))
+            .add_rollback();
+        commands.spawn((Static, Counter::default())).add_rollback();
+        commands.spawn((Late, Counter::default())).add_rollback();
+        commands
+            .spawn((Leaving, ActiveRollback, Counter::default()))
+            .add_rollback();
+    }
+
+    fn count_frames(mut frames: ResMut<Frames>) {
+        frames.0 += 1;
+    }
+
+    fn increment_active(mut counters: Query<&mut Counter, With<ActiveRollback>>) {
+        for mut counter in counters.iter_mut() {
+            counter.0 += 1;
+        }
+    }
+
+    fn transition_scope(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        late: Query<Entity, (With<Late>, Without<ActiveRollback>)>,
+        leaving: Query<Entity, (With<Leaving>, With<ActiveRollback>)>,
+    ) {
+        if frame.0 == ACTIVATION_FRAME {
+            for entity in late.iter() {
+                commands.entity(entity).insert(ActiveRollback);
+            }
+        }
+
+        if frame.0 == DEACTIVATION_FRAME {
+            for entity in leaving.iter() {
+                commands.entity(entity).remove::<ActiveRollback>();
+            }
+        }
+    }
+
+    /// This test makes sure entities entering or leaving the rollback scope are restored correctly
+    /// when rolling back across the transition, and that entities out of scope are left untouched.
+    #[test]
+    fn rollback_scope_transitions() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .add_plugins(RollbackScopePlugin)
+            .init_resource::<Frames>()
+            .rollback_resource_with_copy::<Frames>()
+            .rollback_component_with_copy::<Counter>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(
+                GgrsSchedule,
+                (count_frames, increment_active, transition_scope).chain(),
+            );
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let frames = app.world.resource::<Frames>().0;
+        assert!(
+            frames > DEACTIVATION_FRAME as u32 + 5,
+            "Not enough frames advanced"
+        );
+
+        let mut counters = app.world.query::<(
+            &Counter,
+            Option<&Early>,
+            Option<&Static>,
+            Option<&Late>,
+            Option<&Leaving>,
+        )>();
+
+        for (counter, early, stat, late, leaving) in counters.iter(&app.world) {
+            if early.is_some() {
+                assert_eq!(counter.0, frames, "Active entity was not rolled back");
+            }
+            if stat.is_some() {
+                assert_eq!(counter.0, 0, "Static entity was modified");
+            }
+            if late.is_some() {
+                // The marker is applied at the end of the activation frame, so counting starts
+                // on the following frame.
+                assert_eq!(
+                    counter.0,
+                    frames - ACTIVATION_FRAME as u32,
+                    "Entity entering the scope was not rolled back"
+                );
+            }
+            if leaving.is_some() {
+                // The marker is removed at the end of the deactivation frame, after counting it.
+                assert_eq!(
+                    counter.0, DEACTIVATION_FRAME as u32,
+                    "Entity leaving the scope was not rolled back, or modified since"
+                );
+            }
+        }
+    }
+}
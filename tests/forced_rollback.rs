@@ -0,0 +1,102 @@
+mod common;
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_ggrs::{
+    force_rollback, prelude::*, BevyGgrsError, GgrsInitSchedule, LocalInputs, RollbackFrameCount,
+};
+
+use common::{self, TestConfig};
+
+#[derive(Component, Clone, Copy, Hash, Debug)]
+struct Value(u32);
+
+#[derive(Resource, Default)]
+struct Calls(u32);
+
+fn input_system(mut commands: Commands) {
+    commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, 3)])));
+}
+
+fn spawn(mut commands: Commands) {
+    commands.spawn(Value(0)).add_rollback();
+}
+
+fn deterministic(inputs: Res<PlayerInputs<TestConfig>>, mut query: Query<&mut Value>) {
+    for mut value in query.iter_mut() {
+        value.0 += inputs[0].0 as u32;
+    }
+}
+
+fn nondeterministic(mut calls: ResMut<Calls>, mut query: Query<&mut Value>) {
+    // Calls is not rolled back, so re-simulating produces different values
+    calls.0 += 1;
+
+    for mut value in query.iter_mut() {
+        value.0 = calls.0;
+    }
+}
+
+fn create_app<M>(system: impl IntoSystemConfigs<M>) -> App {
+    let mut app = common::create_app();
+
+    app.init_resource::<Calls>()
+        .rollback_component_with_copy::<Value>()
+        .checksum_component_with_hash::<Value>()
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsInitSchedule, spawn)
+        .add_systems(GgrsSchedule, system);
+
+    let session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .with_check_distance(2)
+        .add_player(PlayerType::Local, 0)
+        .unwrap()
+        .start_synctest_session()
+        .unwrap();
+
+    app.insert_resource(Session::SyncTest(session));
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    app
+}
+
+/// This test makes sure a forced rollback of deterministic systems reproduces the same checksum.
+#[test]
+fn it_reproduces_deterministic_frames() {
+    let mut app = create_app(deterministic);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let forced = force_rollback::<TestConfig>(&mut app.world, frame - 2).unwrap();
+
+    assert_eq!(forced.resimulated, 2);
+    assert!(forced.checksum_before.is_some());
+    assert!(forced.is_deterministic());
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, frame);
+}
+
+/// This test makes sure a forced rollback reveals systems which are not rollback safe.
+#[test]
+fn it_detects_nondeterministic_frames() {
+    let mut app = create_app(nondeterministic);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let forced = force_rollback::<TestConfig>(&mut app.world, frame - 2).unwrap();
+
+    assert!(!forced.is_deterministic());
+}
+
+/// This test makes sure frames which are not retained cannot be rolled back to.
+#[test]
+fn it_rejects_frames_which_are_not_retained() {
+    let mut app = create_app(deterministic);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+
+    assert_eq!(
+        force_rollback::<TestConfig>(&mut app.world, frame + 1),
+        Err(BevyGgrsError::SnapshotMissing { frame: frame + 1 })
+    );
+}
@@ -0,0 +1,690 @@
+mod common;
+
+mod input_checksum {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{
+        prelude::*, InputChecksumPlugin, InputChecksums, LocalInputs, RollbackFrameCount,
+    };
+
+    use crate::common::{self, synctest_session, TestConfig};
+
+    #[derive(Resource, Clone, Copy)]
+    struct TestInput(u8);
+
+    fn input_system(mut commands: Commands, input: Res<TestInput>) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, input.0)])));
+    }
+
+    fn create_app(input: u8) -> App {
+        let mut app = common::create_app();
+
+        app.add_plugins(InputChecksumPlugin::<TestConfig>::default())
+            .insert_resource(TestInput(input))
+            .add_systems(ReadInputs, input_system)
+            .insert_resource(synctest_session(2));
+
+        app
+    }
+
+    /// This test makes sure input checksums are recorded for each frame, and are identical for
+    /// identical inputs even while rolling back.
+    #[test]
+    fn it_records_input_checksums() {
+        let mut first = create_app(1);
+        let mut second = create_app(1);
+
+        for _ in 0..20 {
+            first.update();
+            second.update();
+        }
+
+        let frame = first.world.resource::<RollbackFrameCount>().0;
+        assert!(frame > 0);
+
+        let checksum = first.world.resource::<InputChecksums>().get(frame);
+        assert!(checksum.is_some());
+        assert_eq!(
+            checksum,
+            second.world.resource::<InputChecksums>().get(frame)
+        );
+    }
+
+    /// This test makes sure a single differing input changes the input checksum of every later frame.
+    #[test]
+    fn it_folds_differing_inputs_into_later_frames() {
+        let mut first = create_app(1);
+        let mut second = create_app(1);
+
+        for _ in 0..10 {
+            first.update();
+            second.update();
+        }
+
+        second.insert_resource(TestInput(2));
+        second.update();
+        second.insert_resource(TestInput(1));
+        first.update();
+
+        for _ in 0..10 {
+            first.update();
+            second.update();
+        }
+
+        let frame = first.world.resource::<RollbackFrameCount>().0;
+
+        assert_ne!(
+            first.world.resource::<InputChecksums>().get(frame),
+            second.world.resource::<InputChecksums>().get(frame)
+        );
+    }
+}
+
+mod input_delay {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{prelude::*, LocalInputDelay, LocalInputs, RollbackFrameCount, SessionError};
+
+    use crate::common::{create_app, TestConfig};
+
+    /// How many inputs have been read so far, which is used as the input itself.
+    #[derive(Resource, Default)]
+    struct Reads(u8);
+
+    /// The input of every advanced frame, overwritten when a frame is re-simulated.
+    #[derive(Resource, Default)]
+    struct Advanced(Vec<(i32, u8)>);
+
+    #[derive(Resource, Default)]
+    struct Errors(usize);
+
+    const DELAYED_FROM: u8 = 10;
+    const RESTORED_FROM: u8 = 20;
+    const DELAY: u8 = 2;
+
+    /// Reads the amount of reads so far as the input, delaying it for a while in between.
+    fn input_system(
+        mut commands: Commands,
+        mut reads: ResMut<Reads>,
+        mut delay: ResMut<LocalInputDelay<TestConfig>>,
+    ) {
+        reads.0 += 1;
+
+        if reads.0 == DELAYED_FROM {
+            delay.set_delay(0, DELAY as usize);
+        } else if reads.0 == RESTORED_FROM {
+            delay.set_delay(0, 0);
+        }
+
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, reads.0)])));
+    }
+
+    fn record_input(
+        frame: Res<RollbackFrameCount>,
+        inputs: Res<PlayerInputs<TestConfig>>,
+        mut advanced: ResMut<Advanced>,
+    ) {
+        advanced.0.retain(|&(recorded, _)| recorded < frame.0);
+        advanced.0.push((frame.0, inputs[0].0));
+    }
+
+    fn record_errors(mut events: EventReader<SessionError>, mut errors: ResMut<Errors>) {
+        errors.0 += events.read().count();
+    }
+
+    /// The input added on the provided read: increasing the delay repeats the oldest input, and
+    /// decreasing it skips the inputs which were still queued.
+    fn expected_input(read: u8) -> u8 {
+        match read {
+            read if read < DELAYED_FROM => read,
+            read if read < DELAYED_FROM + DELAY => DELAYED_FROM,
+            read if read < RESTORED_FROM => read - DELAY,
+            read => read,
+        }
+    }
+
+    /// This test makes sure changing the local input delay mid-session neither duplicates nor drops
+    /// frames, so the confirmed inputs remain one consistent input per frame.
+    #[test]
+    fn it_changes_local_input_delay_at_runtime() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.init_resource::<LocalInputDelay<TestConfig>>()
+            .init_resource::<Reads>()
+            .init_resource::<Advanced>()
+            .init_resource::<Errors>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, record_input)
+            .add_systems(Update, record_errors);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let reads = app.world.resource::<Reads>().0;
+        let advanced = &app.world.resource::<Advanced>().0;
+
+        assert!(reads > RESTORED_FROM + 5);
+        assert_eq!(app.world.resource::<Errors>().0, 0);
+        assert_eq!(
+            app.world
+                .resource::<LocalInputDelay<TestConfig>>()
+                .queued(0),
+            0
+        );
+
+        // every read advances exactly one frame, with exactly one input
+        assert_eq!(advanced.len(), reads as usize);
+        for (read, &(frame, input)) in (1..=reads).zip(advanced) {
+            assert_eq!(frame, read as i32);
+            assert_eq!(input, expected_input(read), "frame {frame}");
+        }
+
+        Ok(())
+    }
+}
+
+mod input_history {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{
+        prelude::*, GgrsInputHistory, InputHistoryPlugin, LocalInputs, RollbackFrameCount,
+    };
+
+    use crate::common::{self, synctest_session, TestConfig};
+
+    const DEPTH: usize = 8;
+
+    /// Sends the inputs `0, 1, 2, ...`, one per frame.
+    fn input_system(mut commands: Commands, mut next: Local<u8>) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, *next)])));
+        *next = next.wrapping_add(1);
+    }
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.add_plugins(InputHistoryPlugin::<TestConfig>::new(DEPTH))
+            .add_systems(ReadInputs, input_system)
+            .insert_resource(synctest_session(4));
+
+        app
+    }
+
+    /// This test makes sure the input history holds exactly the inputs leading to the current frame,
+    /// even while rolling back and re-advancing frames.
+    #[test]
+    fn it_records_inputs_across_rollbacks() {
+        let mut app = create_app();
+
+        for _ in 0..20 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+            let history = app
+                .world
+                .resource::<GgrsInputHistory<TestConfig>>()
+                .iter(0)
+                .copied()
+                .collect::<Vec<_>>();
+
+            assert_eq!(history.len(), (frame.max(0) as usize).min(DEPTH));
+
+            for (frames_ago, &input) in history.iter().enumerate() {
+                assert_eq!(input as i32, frame - 1 - frames_ago as i32);
+            }
+        }
+    }
+
+    /// This test makes sure presses are only detected where an input starts to hold.
+    #[test]
+    fn it_detects_presses_within_a_window() {
+        let mut history = GgrsInputHistory::<TestConfig>::new(DEPTH);
+
+        // oldest first
+        for input in [1, 1, 0, 0, 1, 0, 0] {
+            history.push([input]);
+        }
+
+        let pressed = |input: &u8| *input == 1;
+
+        assert!(!history.any_within(0, 2, pressed));
+        assert!(history.any_within(0, 3, pressed));
+        assert!(!history.pressed_within(0, 2, pressed));
+        assert!(history.pressed_within(0, 3, pressed));
+        assert_eq!(history.get(0, 2), Some(&1));
+        assert_eq!(history.get(0, DEPTH), None);
+        assert_eq!(history.get(1, 0), None);
+
+        history.clear();
+        assert!(!history.any_within(0, DEPTH, pressed));
+    }
+}
+
+mod input_prediction {
+    use bevy::{
+        prelude::*,
+        time::TimeUpdateStrategy,
+        utils::{Duration, HashMap},
+    };
+    use bevy_ggrs::{
+        prelude::*, GgrsInitSchedule, InterpolationAlpha, InterpolationSet,
+        LocalInputPredictionPlugin, LocalInputs, LocalPrediction, Predicted, VisualInputs,
+    };
+
+    use crate::common::{self, TestConfig};
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq)]
+    struct Position(f32);
+
+    fn predict(position: &Position, input: &u8, alpha: f32) -> Position {
+        Position(position.0 + *input as f32 * alpha)
+    }
+
+    fn input_system(mut commands: Commands) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, 1)])));
+    }
+
+    fn sample_inputs(mut inputs: ResMut<VisualInputs<TestConfig>>) {
+        inputs.0.insert(0, 1);
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands
+            .spawn((Position(0.), LocalPrediction { handle: 0 }))
+            .add_rollback();
+    }
+
+    fn movement(inputs: Res<PlayerInputs<TestConfig>>, mut query: Query<&mut Position>) {
+        for mut position in query.iter_mut() {
+            position.0 += inputs[0].0 as f32;
+        }
+    }
+
+    fn create_app(visual_inputs: bool) -> Result<App, Box<dyn std::error::Error>> {
+        let mut app = common::create_app();
+
+        // rendering four times per rollback frame
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 240.0,
+        )))
+        .rollback_component_with_copy::<Position>()
+        .add_plugins(LocalInputPredictionPlugin::<Position, TestConfig>::new(
+            predict,
+        ))
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsInitSchedule, spawn)
+        .add_systems(GgrsSchedule, movement);
+
+        if visual_inputs {
+            app.add_systems(PreUpdate, sample_inputs.before(InterpolationSet));
+        }
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        Ok(app)
+    }
+
+    /// This test makes sure the pending input is applied to [`Predicted`] values by the
+    /// [`InterpolationAlpha`], without affecting the simulated [`Component`].
+    #[test]
+    fn it_predicts_pending_input() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app(true)?;
+        let mut predicted_updates = 0;
+
+        for _ in 0..40 {
+            app.update();
+
+            let alpha = **app.world.resource::<InterpolationAlpha>();
+            let (position, predicted) = app
+                .world
+                .query::<(&Position, Option<&Predicted<Position>>)>()
+                .single(&app.world);
+
+            let Some(predicted) = predicted else {
+                continue;
+            };
+
+            // the simulation only ever advances by whole frames
+            assert_eq!(position.0.fract(), 0.);
+            assert_eq!(predicted.0 .0, position.0 + alpha);
+
+            predicted_updates += 1;
+        }
+
+        assert!(predicted_updates > 0);
+
+        Ok(())
+    }
+
+    /// This test makes sure players without [`VisualInputs`] fall back to their last
+    /// [`LocalInputs`], and predictions are removed along with the [`LocalPrediction`].
+    #[test]
+    fn it_falls_back_to_local_inputs() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app(false)?;
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let alpha = **app.world.resource::<InterpolationAlpha>();
+        let (entity, position, predicted) = app
+            .world
+            .query::<(Entity, &Position, &Predicted<Position>)>()
+            .single(&app.world);
+
+        assert_eq!(predicted.0 .0, position.0 + alpha);
+
+        app.world.entity_mut(entity).remove::<LocalPrediction>();
+        app.update();
+
+        assert!(app.world.get::<Predicted<Position>>(entity).is_none());
+
+        Ok(())
+    }
+}
+
+mod input_source {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{prelude::*, InputSource, InputSourceMode, LocalInputs, RollbackFrameCount};
+
+    use crate::common::{create_app, synctest_session, TestConfig};
+
+    /// The sum of all inputs advanced with, rolled back.
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct Sum(u32);
+
+    /// How often the [`ReadInputs`] schedule ran.
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct ReadInputsRuns(u32);
+
+    fn input_system(mut commands: Commands, mut runs: ResMut<ReadInputsRuns>) {
+        runs.0 += 1;
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, 100)])));
+    }
+
+    fn sum(inputs: Res<PlayerInputs<TestConfig>>, mut sum: ResMut<Sum>) {
+        sum.0 += inputs[0].0 as u32;
+    }
+
+    /// A scripted input derived from the frame about to be advanced from.
+    fn scripted_input(frame: i32) -> u8 {
+        (frame % 5 + 1) as u8
+    }
+
+    fn run_with_source(mode: InputSourceMode) -> App {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .init_resource::<Sum>()
+            .init_resource::<ReadInputsRuns>()
+            .rollback_resource_with_copy::<Sum>()
+            .insert_resource(
+                InputSource::<TestConfig>::new(|world| {
+                    let frame = world.resource::<RollbackFrameCount>().0;
+                    HashMap::from([(0, scripted_input(frame))])
+                })
+                .with_mode(mode),
+            )
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, sum);
+
+        for _ in 0..30 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+            let expected = (0..frame).map(|frame| scripted_input(frame) as u32).sum();
+
+            assert_eq!(app.world.resource::<Sum>().0, expected, "frame {frame}");
+        }
+
+        app
+    }
+
+    /// This test makes sure a replacing [`InputSource`] supplies every input, without running the
+    /// [`ReadInputs`] schedule.
+    #[test]
+    fn it_replaces_read_inputs() {
+        let app = run_with_source(InputSourceMode::Replace);
+
+        assert_eq!(app.world.resource::<ReadInputsRuns>().0, 0);
+    }
+
+    /// This test makes sure a merging [`InputSource`] takes precedence over inputs read by the
+    /// [`ReadInputs`] schedule, which still runs.
+    #[test]
+    fn it_merges_with_read_inputs() {
+        let app = run_with_source(InputSourceMode::Merge);
+
+        assert!(app.world.resource::<ReadInputsRuns>().0 > 0);
+    }
+}
+
+mod local_input_transform {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{prelude::*, LocalInputTransform, LocalInputs, RollbackFrameCount};
+
+    use crate::common::{create_app, TestConfig};
+
+    /// The sum of all inputs advanced with, rolled back.
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct Sum(u32);
+
+    fn input_system(mut commands: Commands) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, 200), (1, 2)])));
+    }
+
+    fn sum(inputs: Res<PlayerInputs<TestConfig>>, mut sum: ResMut<Sum>) {
+        sum.0 += inputs.iter().map(|(input, _)| *input as u32).sum::<u32>();
+    }
+
+    /// This test makes sure every local input is transformed before it is added to the session.
+    #[test]
+    fn it_transforms_local_inputs() {
+        let mut app = create_app();
+
+        app.insert_resource(Session::SyncTest(
+            SessionBuilder::<TestConfig>::new()
+                .with_num_players(2)
+                .with_check_distance(2)
+                .add_player(PlayerType::Local, 0)
+                .unwrap()
+                .add_player(PlayerType::Local, 1)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap(),
+        ))
+        .init_resource::<Sum>()
+        .rollback_resource_with_copy::<Sum>()
+        .insert_resource(LocalInputTransform::<TestConfig>::new(|handle, input| {
+            // only the first player is clamped
+            if handle == 0 {
+                *input = (*input).min(10);
+            }
+        }))
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsSchedule, sum);
+
+        for _ in 0..30 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+
+            assert_eq!(
+                app.world.resource::<Sum>().0,
+                frame as u32 * 12,
+                "frame {frame}"
+            );
+        }
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > 0);
+    }
+}
+
+mod partial_inputs {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{
+        prelude::*, LocalInputs, PartialInputsPlugin, PartialLocalInputs, ReadInputsSet,
+    };
+
+    use crate::common::TestConfig;
+
+    fn read_keyboard(mut inputs: ResMut<PartialLocalInputs<TestConfig>>) {
+        inputs.insert(0, 0, 1);
+    }
+
+    fn read_gamepad(mut inputs: ResMut<PartialLocalInputs<TestConfig>>) {
+        inputs.insert(0, 1, 2);
+        inputs.insert(1, 1, 5);
+    }
+
+    fn read_virtual(mut inputs: ResMut<PartialLocalInputs<TestConfig>>) {
+        inputs.insert(0, 2, 3);
+    }
+
+    fn read_direct(mut commands: Commands) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(1, 9), (2, 7)])));
+    }
+
+    fn read_inputs(systems: impl IntoSystemConfigs<()>) -> HashMap<usize, u8> {
+        let mut app = App::new();
+
+        app.add_plugins(GgrsPlugin::<TestConfig>::default())
+            .add_plugins(PartialInputsPlugin::<TestConfig>::new(|merged, input| {
+                *merged = *merged * 10 + input
+            }))
+            .add_systems(ReadInputs, read_direct.before(ReadInputsSet::Collect))
+            .add_systems(ReadInputs, systems.in_set(ReadInputsSet::Collect));
+
+        app.world.run_schedule(ReadInputs);
+
+        let inputs = app
+            .world
+            .remove_resource::<LocalInputs<TestConfig>>()
+            .unwrap()
+            .0;
+
+        assert!(app
+            .world
+            .resource::<PartialLocalInputs<TestConfig>>()
+            .is_empty());
+
+        inputs
+    }
+
+    /// This test makes sure partial inputs are merged by their order, not by system order.
+    #[test]
+    fn it_merges_partial_inputs_in_order() {
+        let forwards = read_inputs((read_keyboard, read_gamepad, read_virtual).chain());
+        let backwards = read_inputs((read_virtual, read_gamepad, read_keyboard).chain());
+
+        assert_eq!(forwards, backwards);
+        assert_eq!(forwards, HashMap::from([(0, 123), (1, 5), (2, 7)]));
+    }
+}
+
+mod tagged_input {
+    use bevy_ggrs::{InputScheme, TaggedInput4};
+    use bytemuck::{Pod, Zeroable};
+
+    #[repr(C)]
+    #[derive(Clone, Copy, PartialEq, Debug, Pod, Zeroable)]
+    struct Keyboard {
+        keys: u8,
+    }
+
+    impl InputScheme for Keyboard {
+        const TAG: u8 = 1;
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, PartialEq, Debug, Pod, Zeroable)]
+    struct Gamepad {
+        stick: [i8; 2],
+        buttons: u8,
+    }
+
+    impl InputScheme for Gamepad {
+        const TAG: u8 = 2;
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, PartialEq, Debug, Pod, Zeroable)]
+    struct Wheel {
+        axes: [i8; 5],
+    }
+
+    impl InputScheme for Wheel {
+        const TAG: u8 = 3;
+    }
+
+    /// This test makes sure payloads are only decoded using the scheme they were encoded with.
+    #[test]
+    fn it_decodes_the_encoded_scheme() {
+        let keyboard = TaggedInput4::encode(Keyboard { keys: 0b101 });
+        let gamepad = TaggedInput4::encode(Gamepad {
+            stick: [-3, 7],
+            buttons: 0b11,
+        });
+
+        assert_eq!(keyboard.tag(), Keyboard::TAG);
+        assert!(keyboard.is::<Keyboard>());
+        assert_eq!(
+            keyboard.decode::<Keyboard>(),
+            Some(Keyboard { keys: 0b101 })
+        );
+        assert_eq!(keyboard.decode::<Gamepad>(), None);
+
+        assert_eq!(
+            gamepad.decode::<Gamepad>(),
+            Some(Gamepad {
+                stick: [-3, 7],
+                buttons: 0b11,
+            })
+        );
+        assert_eq!(gamepad.decode::<Keyboard>(), None);
+    }
+
+    /// This test makes sure payloads with the same bits but different schemes are never equal, and
+    /// unused payload bytes are zeroed.
+    #[test]
+    fn it_encodes_a_stable_layout() {
+        let keyboard = TaggedInput4::encode(Keyboard { keys: 5 });
+        let gamepad = TaggedInput4::encode(Gamepad {
+            stick: [5, 0],
+            buttons: 0,
+        });
+
+        assert_ne!(keyboard, gamepad);
+        assert_eq!(bytemuck::bytes_of(&keyboard), &[Keyboard::TAG, 5, 0, 0, 0]);
+        assert_eq!(bytemuck::bytes_of(&gamepad), &[Gamepad::TAG, 5, 0, 0, 0]);
+    }
+
+    /// This test makes sure a zeroed input, as provided for disconnected players, has no payload.
+    #[test]
+    fn it_treats_zeroed_inputs_as_empty() {
+        let input = TaggedInput4::zeroed();
+
+        assert!(input.is_empty());
+        assert_eq!(input, TaggedInput4::default());
+        assert_eq!(input.decode::<Keyboard>(), None);
+    }
+
+    /// This test makes sure payloads which do not fit are rejected.
+    #[test]
+    #[should_panic]
+    fn it_rejects_oversized_payloads() {
+        TaggedInput4::encode(Wheel { axes: [0; 5] });
+    }
+}
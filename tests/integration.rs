@@ -9,13 +9,23 @@ use bevy::{
     MinimalPlugins,
 };
 use bevy_ggrs::{
-    AddRollbackCommandExtension, GgrsConfig, GgrsPlugin, GgrsSchedule, LocalInputs, LocalPlayers,
-    PlayerInputs, ReadInputs, Rollback, Session,
+    close_session, promote_spectator, AddRollbackCommandExtension, FixedTimestepData,
+    FrameConfirmed, GgrsApp, GgrsConfig, GgrsEffectPlugin, GgrsEffectQueue, GgrsPlugin,
+    GgrsSchedule, GgrsStatus, LoadWorld, LocalInputs, LocalPlayers, LockstepStall,
+    NetworkInterruption, NetworkInterruptions, NetworkSimulation, PlayerInputs, PlayerKind,
+    PlayerRoster, PredictionThresholdBehavior, ReadInputs, Replay, ReplayError, ReplayRecorder,
+    ReplaySession, Rollback, RollbackFrameCount, RollbackRegistrationFingerprint, Session,
+    SessionError, SessionStats, SessionType, SpectatorCatchup, SpectatorLag, WaitRecommendation,
 };
 use bytemuck::{Pod, Zeroable};
 use ggrs::{Config, P2PSession, PlayerHandle, PlayerType, SessionBuilder, UdpNonBlockingSocket};
 use serial_test::serial;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    thread,
+};
 
 #[test]
 #[serial]
@@ -45,6 +55,9 @@ fn it_runs_advance_frame_schedule_systems() -> Result<(), Box<dyn std::error::Er
     assert!(frame_count1.frame > 25);
     assert!(frame_count2.frame > 25);
 
+    assert_eq!(*app1.world.resource::<SessionType>(), SessionType::P2P);
+    assert_eq!(*app2.world.resource::<SessionType>(), SessionType::P2P);
+
     Ok(())
 }
 
@@ -72,7 +85,719 @@ fn it_syncs_rollback_components() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn it_lists_players_with_their_addresses() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    let roster = app1.world.resource::<PlayerRoster<TestConfig>>();
+
+    let local = roster.get(player1.handle).unwrap();
+    assert_eq!(local.kind, PlayerKind::Local);
+    assert_eq!(local.addr, None);
+
+    let remote = roster.get(player2.handle).unwrap();
+    assert_eq!(remote.kind, PlayerKind::Remote);
+    assert_eq!(remote.addr, Some(player2.address));
+
+    close_session::<TestConfig>(&mut app1.world);
+    app1.update();
+
+    assert!(!app1.world.contains_resource::<PlayerRoster<TestConfig>>());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_runs_without_prediction() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session_with_prediction(&player1, &player2, 0)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session_with_prediction(&player2, &player1, 0)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    let frame_count1 = app1.world.get_resource::<FrameCount>().unwrap();
+    let frame_count2 = app2.world.get_resource::<FrameCount>().unwrap();
+
+    // In lockstep, frames only advance once inputs from all players are confirmed, so we
+    // only make sure that it started running
+    assert!(frame_count1.frame > 0);
+    assert!(frame_count2.frame > 0);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_reports_lockstep_stalls() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session_with_prediction(&player1, &player2, 0)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session_with_prediction(&player2, &player1, 0)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    // without the inputs of the second peer, the first peer stalls within its input delay
+    for _ in 0..20 {
+        app1.update();
+    }
+
+    let stall = app1.world.resource::<LockstepStall>().clone();
+    assert_eq!(stall.remote_players, vec![player2.handle]);
+    assert!(stall.steps > 1);
+
+    let stalled = app1.world.resource::<FrameCount>().frame;
+
+    app1.update();
+
+    // the same frame keeps waiting, counting every step it could not be advanced
+    let still = app1.world.resource::<LockstepStall>();
+    assert_eq!(still.frame, stall.frame);
+    assert!(still.steps > stall.steps);
+    assert_eq!(app1.world.resource::<FrameCount>().frame, stalled);
+
+    // the stall ends once the second peer provides its inputs again
+    let mut resumed = false;
+
+    for _ in 0..50 {
+        app2.update();
+        app1.update();
+
+        if !app1.world.contains_resource::<LockstepStall>()
+            && app1.world.resource::<FrameCount>().frame > stalled
+        {
+            resumed = true;
+            break;
+        }
+    }
+
+    assert!(resumed, "the first peer never left the stall");
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_follows_wait_recommendations() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    let recommendation = |app: &App| app.world.resource::<WaitRecommendation>().skip_frames();
+
+    // the first peer runs ahead until GGRS recommends it to wait
+    let mut recommended = false;
+
+    for _ in 0..300 {
+        app1.update();
+        app1.update();
+        app2.update();
+
+        if recommendation(&app1).is_some() {
+            recommended = true;
+            break;
+        }
+    }
+
+    assert!(recommended, "GGRS never recommended waiting");
+
+    // the recommendation is cleared once the first peer is no longer ahead
+    let mut cleared = false;
+
+    for _ in 0..300 {
+        app1.update();
+        app2.update();
+        app2.update();
+
+        if recommendation(&app1).is_none() {
+            cleared = true;
+            break;
+        }
+    }
+
+    assert!(cleared, "the wait recommendation was never cleared");
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_retries_steps_skipped_at_the_prediction_threshold() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (player1, player2) = create_players();
+    let session1 = start_session_with_prediction(&player1, &player2, 4)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    app1.insert_resource(PredictionThresholdBehavior::Retry);
+    let session2 = start_session_with_prediction(&player2, &player1, 4)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    let frame = |app: &App| app.world.resource::<RollbackFrameCount>().0;
+    let accumulator = |app: &App| app.world.resource::<FixedTimestepData>().accumulator();
+    let skips = |app: &App| {
+        app.world
+            .get_resource::<SessionStats>()
+            .map_or(0, |stats| stats.prediction_threshold_skips)
+    };
+
+    let skips_before = skips(&app1);
+
+    // without the inputs of the second peer, the first peer reaches its prediction threshold
+    for _ in 0..30 {
+        app1.update();
+    }
+
+    let stalled = frame(&app1);
+    assert!(skips(&app1) > skips_before);
+
+    // waiting for remote inputs is not an error
+    assert!(app1.world.resource::<Events<SessionError>>().is_empty());
+
+    // the time of every skipped step is kept to be retried, rather than dropped
+    app1.update();
+    assert_eq!(frame(&app1), stalled);
+    assert!(accumulator(&app1) > Duration::from_secs_f64(10. / 60.));
+
+    // once the inputs arrive, the retried steps are advanced several at a time
+    let mut caught_up = false;
+
+    for _ in 0..10 {
+        let before = frame(&app1);
+        app2.update();
+        app1.update();
+
+        if frame(&app1) - before > 1 {
+            caught_up = true;
+            break;
+        }
+    }
+
+    assert!(caught_up, "the retried steps were never advanced");
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_runs_slow_beyond_the_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    let set_threshold = |app: &mut App, frames: i32| {
+        app.world
+            .resource_mut::<FixedTimestepData>()
+            .set_run_slow_threshold(frames);
+    };
+    let running_slow = |app: &App| app.world.resource::<FixedTimestepData>().is_running_slow();
+    let frames_ahead = |app: &App| app.world.resource::<GgrsStatus>().frames_ahead();
+
+    // the first peer runs ahead, but never further than tolerated
+    set_threshold(&mut app1, 100);
+
+    let mut max_ahead = 0;
+
+    for _ in 0..100 {
+        app1.update();
+        app1.update();
+        app2.update();
+
+        max_ahead = max_ahead.max(frames_ahead(&app1));
+        assert!(!running_slow(&app1));
+    }
+
+    assert!(max_ahead > 0, "the first peer never ran ahead");
+
+    // once the threshold is lowered below the frames it is ahead, it slows down
+    set_threshold(&mut app1, 0);
+
+    let mut slowed = false;
+
+    for _ in 0..100 {
+        app1.update();
+        app1.update();
+        app2.update();
+
+        if running_slow(&app1) {
+            slowed = true;
+            break;
+        }
+    }
+
+    assert!(slowed, "the first peer never ran slow");
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_reports_the_prediction_depth() -> Result<(), Box<dyn std::error::Error>> {
+    const MAX_PREDICTION: usize = 4;
+
+    let (player1, player2) = create_players();
+    let session1 = start_session_with_prediction(&player1, &player2, MAX_PREDICTION)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    app1.init_resource::<Depths>()
+        .add_systems(GgrsSchedule, record_depth);
+    let session2 = start_session_with_prediction(&player2, &player1, MAX_PREDICTION)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    app1.world.resource_mut::<Depths>().0.clear();
+
+    // without the inputs of the second peer, the first peer predicts until its threshold
+    for _ in 0..20 {
+        app1.update();
+    }
+
+    let depths = &app1.world.resource::<Depths>().0;
+
+    assert!(!depths.is_empty());
+
+    // every frame advanced without confirmed inputs is being predicted
+    for &(frame, depth, confirmed) in depths {
+        assert!(
+            depth <= MAX_PREDICTION,
+            "frame {frame} predicted {depth} frames"
+        );
+        if !confirmed {
+            assert!(
+                depth > 0,
+                "frame {frame} was not confirmed, but not predicted"
+            );
+        }
+    }
+
+    let depth = **app1.world.resource::<PredictionDepth>();
+    assert!(depth > 0 && depth <= MAX_PREDICTION);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_catches_up_spectators_behind_the_host() -> Result<(), Box<dyn std::error::Error>> {
+    const HOST_PORT: u16 = 8083;
+    const SPECTATOR_PORT: u16 = 8084;
+    let host_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), HOST_PORT);
+    let spectator_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), SPECTATOR_PORT);
+
+    let host_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .add_player(PlayerType::Local, 0)?
+        .add_player(PlayerType::Spectator(spectator_addr), 1)?
+        .start_p2p_session(UdpNonBlockingSocket::bind_to_port(HOST_PORT)?)?;
+    let mut host = create_app::<TestConfig>(host_session);
+
+    let spectator_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .start_spectator_session(
+            host_addr,
+            UdpNonBlockingSocket::bind_to_port(SPECTATOR_PORT)?,
+        );
+    let mut spectator = create_session_app::<TestConfig>(Session::Spectator(spectator_session));
+    spectator.insert_resource(SpectatorCatchup {
+        max_catchup_frames: 4,
+    });
+
+    for _ in 0..50 {
+        host.update();
+        spectator.update();
+    }
+
+    // simulate the spectator being suspended, building up a backlog of frames
+    for _ in 0..40 {
+        host.update();
+    }
+
+    let behind = |host: &App, spectator: &App| {
+        host.world.resource::<FrameCount>().frame - spectator.world.resource::<FrameCount>().frame
+    };
+    assert!(behind(&host, &spectator) >= 40);
+
+    for _ in 0..30 {
+        host.update();
+        spectator.update();
+    }
+
+    assert!(
+        behind(&host, &spectator) <= 2,
+        "Spectator caught up with the host"
+    );
+    assert!(**spectator.world.resource::<SpectatorLag>() <= 2);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_promotes_spectators_to_players() -> Result<(), Box<dyn std::error::Error>> {
+    const HOST_PORT: u16 = 8085;
+    const SPECTATOR_PORT: u16 = 8086;
+    let host_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), HOST_PORT);
+    let spectator_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), SPECTATOR_PORT);
+
+    let host_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(2)
+        .add_player(PlayerType::Local, 0)?
+        .add_player(PlayerType::Local, 1)?
+        .add_player(PlayerType::Spectator(spectator_addr), 2)?
+        .start_p2p_session(UdpNonBlockingSocket::bind_to_port(HOST_PORT)?)?;
+    let mut host = create_app::<TestConfig>(host_session);
+
+    let spectator_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(2)
+        .start_spectator_session(
+            host_addr,
+            UdpNonBlockingSocket::bind_to_port(SPECTATOR_PORT)?,
+        );
+    let mut spectator = create_session_app::<TestConfig>(Session::Spectator(spectator_session));
+
+    for _ in 0..50 {
+        host.update();
+        spectator.update();
+    }
+
+    let frame = spectator.world.resource::<FrameCount>().frame;
+    assert!(frame > 0);
+
+    // both peers restart with the spectator taking over the second player
+    close_session::<TestConfig>(&mut host.world);
+
+    let host_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(2)
+        .add_player(PlayerType::Local, 0)?
+        .add_player(PlayerType::Remote(spectator_addr), 1)?
+        .start_p2p_session(UdpNonBlockingSocket::bind_to_port(HOST_PORT)?)?;
+    host.insert_resource(Session::P2P(host_session));
+
+    let player_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(2)
+        .add_player(PlayerType::Remote(host_addr), 0)?
+        .add_player(PlayerType::Local, 1)?
+        .start_p2p_session(UdpNonBlockingSocket::bind_to_port(SPECTATOR_PORT)?)?;
+    promote_spectator::<TestConfig>(&mut spectator.world, player_session);
+
+    assert_eq!(
+        *spectator.world.resource::<RollbackFrameCount>(),
+        RollbackFrameCount(0)
+    );
+
+    for _ in 0..50 {
+        host.update();
+        spectator.update();
+    }
+
+    assert_eq!(*spectator.world.resource::<SessionType>(), SessionType::P2P);
+    assert_eq!(spectator.world.resource::<LocalPlayers>().0, vec![1]);
+    assert!(
+        spectator.world.resource::<FrameCount>().frame > frame,
+        "World state is preserved and keeps advancing"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_reports_network_interruptions() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session_with_notify_delay(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session_with_notify_delay(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+    app1.init_resource::<Interruptions>()
+        .add_systems(Update, record_interruptions);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    // the second peer stops responding for longer than the disconnect notify delay
+    for _ in 0..20 {
+        thread::sleep(Duration::from_millis(15));
+        app1.update();
+    }
+
+    let remaining = app1
+        .world
+        .resource::<NetworkInterruptions<TestConfig>>()
+        .get(&player2.address);
+    assert!(remaining.is_some_and(|remaining| remaining < Duration::from_secs(10)));
+
+    for _ in 0..20 {
+        app1.update();
+        app2.update();
+    }
+
+    assert!(app1
+        .world
+        .resource::<NetworkInterruptions<TestConfig>>()
+        .is_empty());
+
+    let interruptions = &app1.world.resource::<Interruptions>().0;
+    let [NetworkInterruption::Interrupted {
+        addr,
+        disconnect_timeout,
+    }, NetworkInterruption::Resumed { addr: resumed }] = interruptions.as_slice()
+    else {
+        panic!("Unexpected interruptions {interruptions:?}");
+    };
+
+    assert_eq!(*addr, player2.address);
+    assert_eq!(*resumed, player2.address);
+    assert!(*disconnect_timeout <= Duration::from_secs(10));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_emits_effects_once_confirmed() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+    app2.add_plugins(GgrsEffectPlugin::<Spark>::default())
+        .init_resource::<Sparks>()
+        .add_systems(GgrsSchedule, enqueue_sparks)
+        .add_systems(Update, record_sparks);
+
+    for i in 0..100 {
+        // changing inputs cause the second peer to roll back
+        if i % 10 < 5 {
+            press_key(&mut app1, KeyCode::KeyW);
+        }
+        app1.update();
+        app2.update();
+    }
+
+    let sparks = &app2.world.resource::<Sparks>().0;
+    let current = app2.world.resource::<RollbackFrameCount>().0;
+
+    assert!(!sparks.is_empty());
+    assert!(sparks.iter().all(|&Spark(frame)| frame <= current));
+
+    // every simulated frame is emitted exactly once, in order
+    for (&Spark(frame), &Spark(next)) in sparks.iter().zip(sparks.iter().skip(1)) {
+        assert_eq!(next, frame + 1);
+    }
+
+    let queue = app2.world.resource::<GgrsEffectQueue<Spark>>();
+    let last = sparks.last().unwrap().0;
+
+    assert!(queue
+        .iter()
+        .all(|(frame, &Spark(spark))| frame == spark && frame > last));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_replays_recorded_matches() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+    app1.rollback_component_with_clone::<Transform>()
+        .rollback_component_with_clone::<Velocity>()
+        .init_resource::<StateChecksums>()
+        .insert_resource(ReplayRecorder::<TestConfig>::default())
+        .add_systems(GgrsSchedule, record_state.after(move_player_system));
+    app2.rollback_component_with_clone::<Transform>()
+        .rollback_component_with_clone::<Velocity>();
+
+    for i in 0..100 {
+        // changing inputs cause the peers to roll back
+        if i % 10 < 5 {
+            press_key(&mut app1, KeyCode::KeyW);
+        }
+        if i % 7 < 3 {
+            press_key(&mut app2, KeyCode::KeyW);
+        }
+        app1.update();
+        app2.update();
+    }
+
+    let replay = app1.world.resource::<ReplayRecorder<TestConfig>>().replay();
+    let registration = app1.world.resource::<RollbackRegistrationFingerprint>();
+    let replay = Replay::<TestConfig>::from_bytes(&replay.to_bytes(), registration)?;
+
+    assert_eq!(replay.num_players(), 2);
+    assert!(replay.len() > 25);
+
+    let mut app3 = create_session_app::<TestConfig>(Session::Replay(ReplaySession::new(replay)));
+    app3.init_resource::<StateChecksums>()
+        .add_systems(GgrsSchedule, record_state.after(move_player_system));
+
+    for _ in 0..200 {
+        app3.update();
+    }
+
+    let Some(Session::Replay(session)) = app3.world.get_resource::<Session<TestConfig>>() else {
+        panic!("Replay session was removed");
+    };
+    assert!(session.is_finished());
+
+    let recorded = &app1.world.resource::<StateChecksums>().0;
+    let replayed = &app3.world.resource::<StateChecksums>().0;
+    let len = session.replay().len() as i32;
+
+    assert_eq!(replayed.len(), len as usize);
+    for frame in 1..=len {
+        assert_eq!(recorded[&frame], replayed[&frame], "frame {frame}");
+    }
+
+    Ok(())
+}
+
+/// This test makes sure replays claiming more frames than they hold are rejected, rather than
+/// allocating every claimed frame.
+#[test]
+fn it_rejects_malformed_replays() {
+    let registration = RollbackRegistrationFingerprint::default();
+
+    let replay = |num_players: u32, len: u32, body: &[u8]| {
+        let mut bytes = Vec::new();
+        bytes.extend(registration.fingerprint().to_le_bytes());
+        bytes.extend(0i32.to_le_bytes());
+        bytes.extend(num_players.to_le_bytes());
+        bytes.extend(len.to_le_bytes());
+        bytes.extend_from_slice(body);
+
+        Replay::<TestConfig>::from_bytes(&bytes, &registration).err()
+    };
+
+    assert_eq!(replay(0, u32::MAX, &[]), Some(ReplayError::Malformed));
+    assert_eq!(replay(2, u32::MAX, &[0; 4]), Some(ReplayError::Malformed));
+    assert_eq!(replay(2, 2, &[0; 4]), Some(ReplayError::Malformed));
+    assert_eq!(replay(2, 1, &[0; 4]), None);
+    assert_eq!(replay(0, 0, &[]), None);
+}
+
+#[test]
+#[serial]
+fn it_rolls_back_under_simulated_latency() -> Result<(), Box<dyn std::error::Error>> {
+    let simulation = NetworkSimulation {
+        latency: Duration::from_millis(40),
+        jitter: Duration::from_millis(10),
+        ..default()
+    };
+    let (player1, player2) = create_players();
+    let session1 = start_session_with_simulation(&player1, &player2, simulation)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session_with_simulation(&player2, &player1, simulation)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+    app2.init_resource::<Loads>()
+        .add_systems(LoadWorld, count_loads);
+
+    for i in 0..100 {
+        // inputs only arrive after the simulated latency, so the second peer has to predict them
+        if i % 10 < 5 {
+            press_key(&mut app1, KeyCode::KeyW);
+        }
+        app1.update();
+        app2.update();
+        thread::sleep(Duration::from_millis(16));
+    }
+
+    assert!(app1.world.resource::<FrameCount>().frame > 25);
+    assert!(app2.world.resource::<FrameCount>().frame > 25);
+    assert!(app2.world.resource::<Loads>().0 > 0);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_reports_whether_frames_are_confirmed() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+    app2.init_resource::<Confirmations>()
+        .add_systems(GgrsSchedule, record_confirmation);
+
+    for i in 0..100 {
+        // changing inputs cause the second peer to roll back
+        if i % 10 < 5 {
+            press_key(&mut app1, KeyCode::KeyW);
+        }
+        app1.update();
+        app2.update();
+    }
+
+    let confirmations = &app2.world.resource::<Confirmations>().0;
+
+    assert!(confirmations.iter().any(|&(_, confirmed)| confirmed));
+    assert!(confirmations.iter().any(|&(_, confirmed)| !confirmed));
+
+    // a frame advanced with confirmed inputs is never advanced again
+    for (index, &(frame, confirmed)) in confirmations.iter().enumerate() {
+        if confirmed {
+            assert!(
+                confirmations[index + 1..]
+                    .iter()
+                    .all(|&(later, _)| later != frame),
+                "frame {frame} was rolled back after being confirmed"
+            );
+        }
+    }
+
+    Ok(())
+}
+
 fn create_app<T: Config>(session: P2PSession<T>) -> App {
+    create_session_app(Session::P2P(session))
+}
+
+fn create_session_app<T: Config>(session: Session<T>) -> App {
     let mut app = App::new();
     app.add_plugins(MinimalPlugins)
         .add_plugins(InputPlugin)
@@ -80,7 +805,7 @@ fn create_app<T: Config>(session: P2PSession<T>) -> App {
         .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
             1.0 / 60.0,
         )))
-        .insert_resource(Session::P2P(session))
+        .insert_resource(session)
         .insert_resource(FrameCount { frame: 0 })
         .add_systems(GgrsSchedule, (move_player_system, increase_frame_system))
         .add_systems(ReadInputs, read_local_inputs)
@@ -121,11 +846,18 @@ fn create_players() -> (TestPlayer, TestPlayer) {
 fn start_session(
     local_player: &TestPlayer,
     remote_player: &TestPlayer,
+) -> Result<P2PSession<TestConfig>, Box<dyn std::error::Error>> {
+    start_session_with_prediction(local_player, remote_player, 12)
+}
+
+fn start_session_with_prediction(
+    local_player: &TestPlayer,
+    remote_player: &TestPlayer,
+    max_prediction: usize,
 ) -> Result<P2PSession<TestConfig>, Box<dyn std::error::Error>> {
     let mut session_builder = SessionBuilder::<TestConfig>::new()
         .with_num_players(2)
-        .with_max_prediction_window(12)
-        .expect("prediction window can't be 0") // (optional) set max prediction window
+        .with_max_prediction_window(max_prediction)? // (optional) set max prediction window
         .with_input_delay(2); // (optional) set input delay for the local player
     session_builder = session_builder.add_player(PlayerType::Local, local_player.handle)?;
     session_builder = session_builder.add_player(
@@ -137,6 +869,43 @@ fn start_session(
     Ok(session)
 }
 
+fn start_session_with_notify_delay(
+    local_player: &TestPlayer,
+    remote_player: &TestPlayer,
+) -> Result<P2PSession<TestConfig>, Box<dyn std::error::Error>> {
+    let session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(2)
+        .with_disconnect_notify_delay(Duration::from_millis(100))
+        .with_disconnect_timeout(Duration::from_secs(10))
+        .add_player(PlayerType::Local, local_player.handle)?
+        .add_player(
+            PlayerType::Remote(remote_player.address),
+            remote_player.handle,
+        )?
+        .start_p2p_session(UdpNonBlockingSocket::bind_to_port(
+            local_player.address.port(),
+        )?)?;
+    Ok(session)
+}
+
+fn start_session_with_simulation(
+    local_player: &TestPlayer,
+    remote_player: &TestPlayer,
+    simulation: NetworkSimulation,
+) -> Result<P2PSession<TestConfig>, Box<dyn std::error::Error>> {
+    let session_builder = SessionBuilder::<TestConfig>::new()
+        .with_num_players(2)
+        .with_input_delay(2)
+        .add_player(PlayerType::Local, local_player.handle)?
+        .add_player(
+            PlayerType::Remote(remote_player.address),
+            remote_player.handle,
+        )?;
+    let socket = UdpNonBlockingSocket::bind_to_port(local_player.address.port())?;
+    let session = simulation.start_p2p_session(session_builder, socket)?;
+    Ok(session)
+}
+
 const INPUT_UP: u8 = 1 << 0;
 
 pub fn read_local_inputs(
@@ -157,6 +926,87 @@ pub fn read_local_inputs(
     commands.insert_resource(LocalInputs::<TestConfig>(local_inputs));
 }
 
+#[derive(Resource, Default)]
+struct Interruptions(Vec<NetworkInterruption<SocketAddr>>);
+
+fn record_interruptions(
+    mut interruptions: ResMut<Interruptions>,
+    mut events: EventReader<NetworkInterruption<SocketAddr>>,
+) {
+    interruptions.0.extend(events.read().cloned());
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+struct Spark(i32);
+
+#[derive(Resource, Default)]
+struct Sparks(Vec<Spark>);
+
+/// Whether the inputs of each advanced frame were confirmed, in the order frames were advanced.
+#[derive(Resource, Default)]
+struct Confirmations(Vec<(i32, bool)>);
+
+/// The prediction depth of each advanced frame, in the order frames were advanced.
+#[derive(Resource, Default)]
+struct Depths(Vec<(i32, usize, bool)>);
+
+/// How often a snapshot was loaded.
+#[derive(Resource, Default)]
+struct Loads(usize);
+
+/// A checksum of the players after every frame, overwritten when a frame is re-simulated.
+#[derive(Resource, Default)]
+struct StateChecksums(BTreeMap<i32, u64>);
+
+fn record_state(
+    frame: Res<RollbackFrameCount>,
+    query: Query<(&Transform, &Velocity, &PlayerComponent)>,
+    mut checksums: ResMut<StateChecksums>,
+) {
+    let mut players = query.iter().collect::<Vec<_>>();
+    players.sort_by_key(|(_, _, player)| player.handle);
+
+    let mut hasher = DefaultHasher::new();
+    for (transform, velocity, player) in players {
+        player.handle.hash(&mut hasher);
+        transform.translation.z.to_bits().hash(&mut hasher);
+        velocity.z.to_bits().hash(&mut hasher);
+    }
+
+    checksums.0.insert(frame.0, hasher.finish());
+}
+
+fn enqueue_sparks(frame: Res<RollbackFrameCount>, mut queue: ResMut<GgrsEffectQueue<Spark>>) {
+    queue.push(frame.0, Spark(frame.0));
+}
+
+fn record_confirmation(
+    mut confirmations: ResMut<Confirmations>,
+    frame: Res<RollbackFrameCount>,
+    inputs: Res<PlayerInputs<TestConfig>>,
+    confirmed: Res<FrameConfirmed>,
+) {
+    assert_eq!(inputs.all_confirmed(), **confirmed);
+    confirmations.0.push((frame.0, **confirmed));
+}
+
+fn record_depth(
+    mut depths: ResMut<Depths>,
+    frame: Res<RollbackFrameCount>,
+    depth: Res<PredictionDepth>,
+    confirmed: Res<FrameConfirmed>,
+) {
+    depths.0.push((frame.0, **depth, **confirmed));
+}
+
+fn count_loads(mut loads: ResMut<Loads>) {
+    loads.0 += 1;
+}
+
+fn record_sparks(mut sparks: ResMut<Sparks>, mut events: EventReader<Spark>) {
+    sparks.0.extend(events.read().copied());
+}
+
 pub fn increase_frame_system(mut frame_count: ResMut<FrameCount>) {
     frame_count.frame += 1;
 }
@@ -182,7 +1032,7 @@ pub struct FrameCount {
 }
 
 // Components that should be saved/loaded need to implement the `Reflect` trait
-#[derive(Default, Reflect, Component)]
+#[derive(Default, Reflect, Component, Clone)]
 pub struct Velocity {
     pub x: f32,
     pub y: f32,
@@ -204,11 +1054,7 @@ pub fn move_player_system(
 }
 
 pub fn spawn_players(mut commands: Commands, session: Res<Session<TestConfig>>) {
-    let num_players = match &*session {
-        Session::SyncTest(s) => s.num_players(),
-        Session::P2P(s) => s.num_players(),
-        Session::Spectator(s) => s.num_players(),
-    };
+    let num_players = session.num_players();
 
     for handle in 0..num_players {
         commands
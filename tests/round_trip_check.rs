@@ -0,0 +1,71 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ggrs::{prelude::*, GgrsInitSchedule};
+
+use common::{create_app, input_system, TestConfig};
+
+#[derive(Component, Reflect, Default, PartialEq)]
+#[reflect(PartialEq)]
+struct Complete {
+    health: u32,
+    position: Vec2,
+}
+
+#[derive(Component, Reflect, Default, PartialEq)]
+#[reflect(PartialEq)]
+struct Incomplete {
+    health: u32,
+    #[reflect(ignore)]
+    cooldown: u32,
+}
+
+fn spawn(mut commands: Commands) {
+    commands
+        .spawn((
+            Complete {
+                health: 3,
+                position: Vec2::new(1., 2.),
+            },
+            Incomplete {
+                health: 3,
+                cooldown: 5,
+            },
+        ))
+        .add_rollback();
+}
+
+fn run<Type: Component + Reflect + FromWorld>() {
+    let mut app = create_app();
+
+    app.rollback_component_with_reflect::<Type>()
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsInitSchedule, spawn);
+
+    let session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .with_check_distance(2)
+        .add_player(PlayerType::Local, 0)
+        .unwrap()
+        .start_synctest_session()
+        .unwrap();
+
+    app.insert_resource(Session::SyncTest(session));
+
+    for _ in 0..5 {
+        app.update();
+    }
+}
+
+/// This test makes sure components which are restored exactly pass the check.
+#[test]
+fn it_accepts_complete_components() {
+    run::<Complete>();
+}
+
+/// This test makes sure fields dropped by reflection are reported when first snapshot.
+#[test]
+#[should_panic(expected = "Incomplete is not restored exactly when rolling back")]
+fn it_rejects_components_with_ignored_fields() {
+    run::<Incomplete>();
+}
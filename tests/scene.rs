@@ -0,0 +1,89 @@
+mod common;
+
+use bevy::{prelude::*, scene::DynamicEntity};
+use bevy_ggrs::{prelude::*, RollbackFrameCount, SpawnRollbackScene};
+
+use common::{create_app, input_system, synctest_session};
+
+/// Frame on which the scene is spawned.
+const SPAWN_FRAME: i32 = 5;
+
+#[derive(Component, Reflect, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[reflect(Component)]
+struct Counter(u32);
+
+#[derive(Resource, Clone, Copy, Default, Debug)]
+struct Frames(u32);
+
+#[derive(Resource)]
+struct Prefab(Handle<DynamicScene>);
+
+fn setup_system(mut commands: Commands, mut scenes: ResMut<Assets<DynamicScene>>) {
+    let scene = DynamicScene {
+        resources: Vec::new(),
+        entities: vec![DynamicEntity {
+            entity: Entity::from_raw(0),
+            components: vec![Box::new(Counter::default())],
+        }],
+    };
+
+    commands.insert_resource(Prefab(scenes.add(scene)));
+}
+
+fn count_frames(mut frames: ResMut<Frames>) {
+    frames.0 += 1;
+}
+
+fn increment(mut counters: Query<&mut Counter>) {
+    for mut counter in counters.iter_mut() {
+        counter.0 += 1;
+    }
+}
+
+fn spawn_scene(mut commands: Commands, frame: Res<RollbackFrameCount>, prefab: Res<Prefab>) {
+    if frame.0 == SPAWN_FRAME {
+        commands.add(SpawnRollbackScene(prefab.0.clone()));
+    }
+}
+
+/// This test makes sure entities spawned from a scene within the [`GgrsSchedule`] are rolled
+/// back, rather than duplicated when rolling back across the frame they were spawned on.
+#[test]
+fn it_rolls_back_scene_instances() {
+    let mut app = create_app();
+
+    app.add_plugins(AssetPlugin::default())
+        .init_asset::<DynamicScene>()
+        .register_type::<Counter>()
+        .insert_resource(synctest_session(2))
+        .init_resource::<Frames>()
+        .rollback_resource_with_copy::<Frames>()
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(Startup, setup_system)
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsSchedule, (count_frames, increment, spawn_scene).chain());
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frames = app.world.resource::<Frames>().0;
+    assert!(
+        frames > SPAWN_FRAME as u32 + 5,
+        "Not enough frames advanced"
+    );
+
+    let counters: Vec<Counter> = app
+        .world
+        .query_filtered::<&Counter, With<Rollback>>()
+        .iter(&app.world)
+        .copied()
+        .collect();
+
+    assert_eq!(counters.len(), 1, "Scene instance was duplicated");
+    assert_eq!(
+        counters[0].0,
+        frames - SPAWN_FRAME as u32,
+        "Scene instance was not rolled back"
+    );
+}
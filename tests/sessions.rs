@@ -0,0 +1,1242 @@
+mod common;
+
+mod ggrs_active {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{
+        prelude::*, FixedTimestepData, GgrsActive, GgrsResumeBehavior, RollbackFrameCount,
+    };
+
+    use crate::common::{self, input_system, synctest_session};
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 150.0,
+        )))
+        .add_systems(ReadInputs, input_system)
+        .insert_resource(synctest_session(2));
+
+        app
+    }
+
+    fn frame(app: &App) -> i32 {
+        app.world.resource::<RollbackFrameCount>().0
+    }
+
+    fn accumulator(app: &App) -> Duration {
+        app.world.resource::<FixedTimestepData>().accumulator()
+    }
+
+    /// This test makes sure no frames are advanced while inactive, and the accumulated time is kept
+    /// for when advancing resumes.
+    #[test]
+    fn it_resumes_with_the_accumulator_preserved() {
+        let mut app = create_app();
+
+        for _ in 0..22 {
+            app.update();
+        }
+
+        let frame_before = frame(&app);
+        let accumulator_before = accumulator(&app);
+
+        assert!(frame_before > 0);
+        assert!(accumulator_before > Duration::ZERO);
+
+        app.insert_resource(GgrsActive(false));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert_eq!(frame(&app), frame_before);
+        assert_eq!(accumulator(&app), accumulator_before);
+
+        app.insert_resource(GgrsActive(true));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(frame(&app) > frame_before);
+    }
+
+    /// This test makes sure the accumulated time is dropped while inactive when configured to.
+    #[test]
+    fn it_resumes_with_the_accumulator_reset() {
+        let mut app = create_app();
+        app.insert_resource(GgrsResumeBehavior::Reset);
+
+        for _ in 0..22 {
+            app.update();
+        }
+
+        let frame_before = frame(&app);
+
+        assert!(accumulator(&app) > Duration::ZERO);
+
+        app.insert_resource(GgrsActive(false));
+        app.update();
+
+        assert_eq!(frame(&app), frame_before);
+        assert_eq!(accumulator(&app), Duration::ZERO);
+
+        app.insert_resource(GgrsActive(true));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(frame(&app) > frame_before);
+    }
+}
+
+mod request_schedules {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, LoadWorld, RollbackFrameCount, SaveWorld, SessionRequest, SessionRequests,
+    };
+
+    use crate::common::{self, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Position(i32);
+
+    /// Every schedule run on behalf of a request, with the [`RollbackFrameCount`] it saw.
+    #[derive(Resource, Default)]
+    struct ScheduleRuns(Vec<SessionRequest>);
+
+    /// Every request issued by the session.
+    #[derive(Resource, Default)]
+    struct Requests(Vec<SessionRequest>);
+
+    /// The requests issued by the session, for every step.
+    #[derive(Resource, Default)]
+    struct Steps(Vec<Vec<SessionRequest>>);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Position(0)).add_rollback();
+    }
+
+    fn move_system(mut query: Query<&mut Position>) {
+        for mut position in query.iter_mut() {
+            position.0 += 1;
+        }
+    }
+
+    fn record_save(frame: Res<RollbackFrameCount>, mut runs: ResMut<ScheduleRuns>) {
+        runs.0
+            .push(SessionRequest::SaveGameState { frame: frame.0 });
+    }
+
+    fn record_load(frame: Res<RollbackFrameCount>, mut runs: ResMut<ScheduleRuns>) {
+        runs.0
+            .push(SessionRequest::LoadGameState { frame: frame.0 });
+    }
+
+    fn record_advance(frame: Res<RollbackFrameCount>, mut runs: ResMut<ScheduleRuns>) {
+        runs.0.push(SessionRequest::AdvanceFrame { frame: frame.0 });
+    }
+
+    fn record_steps(mut events: EventReader<SessionRequests>, mut steps: ResMut<Steps>) {
+        for SessionRequests(issued) in events.read() {
+            steps.0.push(issued.clone());
+        }
+    }
+
+    fn record_requests(mut events: EventReader<SessionRequests>, mut requests: ResMut<Requests>) {
+        for SessionRequests(issued) in events.read() {
+            requests.0.extend(issued);
+        }
+    }
+
+    const CHECK_DISTANCE: usize = 3;
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(synctest_session(CHECK_DISTANCE))
+            .rollback_component_with_copy::<Position>()
+            .checksum_component_with_hash::<Position>()
+            .add_event::<SessionRequests>()
+            .init_resource::<ScheduleRuns>()
+            .init_resource::<Requests>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, (move_system, record_advance))
+            .add_systems(SaveWorld, record_save.after(SaveWorldSet::Snapshot))
+            .add_systems(LoadWorld, record_load.after(LoadWorldSet::Data));
+
+        app
+    }
+
+    /// This test makes sure every request issued by the session runs the matching schedule, with the
+    /// [`RollbackFrameCount`] set to the requested frame beforehand.
+    #[test]
+    fn it_runs_a_schedule_for_every_request() {
+        let mut app = create_app();
+        app.add_systems(PostUpdate, record_requests);
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let runs = &app.world.resource::<ScheduleRuns>().0;
+        let requests = &app.world.resource::<Requests>().0;
+
+        assert!(requests
+            .iter()
+            .any(|request| matches!(request, SessionRequest::LoadGameState { .. })));
+
+        // the initial state is saved once before the session issues any requests
+        assert_eq!(runs[0], SessionRequest::SaveGameState { frame: 0 });
+        assert_eq!(&runs[1..], &requests[..]);
+    }
+
+    /// This test makes sure a single [`SessionRequests`] event is sent for every step, holding the
+    /// requests GGRS returned for advancing that step, in order.
+    #[test]
+    fn it_sends_the_requests_of_every_step() {
+        let mut app = create_app();
+        app.init_resource::<Steps>()
+            .add_systems(PostUpdate, record_steps);
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let steps = &app.world.resource::<Steps>().0;
+        let newest = app.world.resource::<RollbackFrameCount>().0;
+
+        assert_eq!(steps.len(), newest as usize);
+
+        for (step, requests) in steps.iter().enumerate() {
+            let previous = step as i32;
+            let mut frame = previous;
+
+            for &request in requests {
+                match request {
+                    SessionRequest::SaveGameState { frame: saved } => assert_eq!(saved, frame),
+                    SessionRequest::LoadGameState { frame: loaded } => {
+                        assert!(loaded < previous && loaded >= previous - CHECK_DISTANCE as i32);
+                        frame = loaded;
+                    }
+                    SessionRequest::AdvanceFrame { frame: advanced } => {
+                        assert_eq!(advanced, frame + 1);
+                        frame = advanced;
+                    }
+                }
+            }
+
+            // every step ends on the next frame, after rolling back and resimulating if needed
+            assert_eq!(frame, previous + 1, "step {step}: {requests:?}");
+        }
+
+        assert!(steps
+            .iter()
+            .skip(CHECK_DISTANCE + 1)
+            .all(|requests| requests
+                .iter()
+                .any(|request| matches!(request, SessionRequest::LoadGameState { .. }))));
+    }
+}
+
+mod rewind_preview {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{
+        end_preview, prelude::*, preview_frame, BevyGgrsError, LocalInputs, RewindPreview,
+        RollbackFrameCount, SessionError,
+    };
+
+    use crate::common::{self, TestConfig};
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+    struct Counter(u32);
+
+    #[derive(Resource, Default)]
+    struct Errors(Vec<SessionError>);
+
+    fn input_system(mut commands: Commands, mut step: Local<u8>) {
+        *step = step.wrapping_add(1);
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, *step % 7)])));
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Counter::default()).add_rollback();
+    }
+
+    /// Folds every input into the counter, so any deviating input changes the result.
+    fn count(inputs: Res<PlayerInputs<TestConfig>>, mut query: Query<&mut Counter>) {
+        for mut counter in query.iter_mut() {
+            counter.0 = counter
+                .0
+                .wrapping_mul(31)
+                .wrapping_add(inputs[0].0 as u32 + 1);
+        }
+    }
+
+    fn record_errors(mut events: EventReader<SessionError>, mut errors: ResMut<Errors>) {
+        errors.0.extend(events.read().cloned());
+    }
+
+    fn counter(app: &mut App) -> Counter {
+        *app.world.query::<&Counter>().single(&app.world)
+    }
+
+    fn frame(app: &App) -> i32 {
+        app.world.resource::<RollbackFrameCount>().0
+    }
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.enable_seeking()
+            .set_snapshot_interval(4)
+            .rollback_component_with_copy::<Counter>()
+            .checksum_component_with_hash::<Counter>()
+            .init_resource::<Errors>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count)
+            .add_systems(Update, record_errors);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        app.insert_resource(Session::SyncTest(session));
+
+        app
+    }
+
+    /// This test makes sure a previewed frame matches the state the world had on that frame, and the
+    /// session continues from the present frame once the preview ends.
+    #[test]
+    fn it_previews_past_frames() {
+        let mut app = create_app();
+        let mut reference = create_app();
+        let mut history = HashMap::new();
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let present = frame(&app);
+        let before = counter(&mut app);
+        assert!(present > 30, "Not enough frames advanced");
+
+        for _ in 0..80 {
+            reference.update();
+            history.insert(frame(&reference), counter(&mut reference));
+        }
+
+        assert_eq!(history.get(&present), Some(&before));
+
+        for target in [present - 10, present - 3] {
+            preview_frame::<TestConfig>(&mut app.world, target).unwrap();
+
+            assert_eq!(frame(&app), target);
+            assert_eq!(
+                Some(&counter(&mut app)),
+                history.get(&target),
+                "frame {target}"
+            );
+
+            // the simulation does not advance while previewing
+            for _ in 0..5 {
+                app.update();
+            }
+
+            assert_eq!(frame(&app), target);
+            assert_eq!(app.world.resource::<RewindPreview>().present(), present);
+        }
+
+        end_preview::<TestConfig>(&mut app.world).unwrap();
+
+        assert!(!app.world.contains_resource::<RewindPreview>());
+        assert_eq!(frame(&app), present);
+        assert_eq!(counter(&mut app), before);
+
+        for _ in 0..20 {
+            app.update();
+
+            let frame = frame(&app);
+            assert_eq!(
+                Some(&counter(&mut app)),
+                history.get(&frame),
+                "frame {frame}"
+            );
+        }
+
+        assert!(frame(&app) > present);
+        assert!(app.world.resource::<Errors>().0.is_empty());
+    }
+
+    /// This test makes sure frames which have not been advanced yet are rejected, both before and
+    /// during a preview, leaving the world untouched.
+    #[test]
+    fn it_rejects_future_frames() {
+        let mut app = create_app();
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let present = frame(&app);
+        let before = counter(&mut app);
+
+        assert_eq!(
+            preview_frame::<TestConfig>(&mut app.world, present + 5),
+            Err(BevyGgrsError::FrameInFuture { frame: present + 5 })
+        );
+        assert!(!app.world.contains_resource::<RewindPreview>());
+        assert_eq!(frame(&app), present);
+        assert_eq!(counter(&mut app), before);
+
+        preview_frame::<TestConfig>(&mut app.world, present - 10).unwrap();
+
+        // the present frame is still the limit while previewing an earlier frame
+        assert_eq!(
+            preview_frame::<TestConfig>(&mut app.world, present + 1),
+            Err(BevyGgrsError::FrameInFuture { frame: present + 1 })
+        );
+        assert_eq!(frame(&app), present - 10);
+
+        end_preview::<TestConfig>(&mut app.world).unwrap();
+
+        assert_eq!(frame(&app), present);
+        assert_eq!(counter(&mut app), before);
+    }
+}
+
+mod schedule_flush {
+    use bevy::{
+        ecs::schedule::{LogLevel, ScheduleBuildSettings},
+        prelude::*,
+    };
+    use bevy_ggrs::{prelude::*, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Bullet;
+
+    /// The total amount of bullets seen by [`count_bullets`] on every frame.
+    #[derive(Resource, Clone, Copy, Default, Debug, Hash)]
+    struct Seen(u32);
+
+    fn fire(mut commands: Commands) {
+        commands.spawn(Bullet).add_rollback();
+    }
+
+    fn count_bullets(bullets: Query<&Bullet>, mut seen: ResMut<Seen>) {
+        seen.0 += bullets.iter().count() as u32;
+    }
+
+    /// This test makes sure entities spawned in one [`GgrsScheduleSet`] are visible to the following
+    /// sets within the same frame, including when re-simulating frames after a rollback.
+    #[test]
+    fn it_flushes_commands_between_sets() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .rollback_component_with_copy::<Bullet>()
+            .rollback_resource_with_copy::<Seen>()
+            .checksum_resource_with_hash::<Seen>()
+            .init_resource::<Seen>()
+            .add_systems(ReadInputs, input_system)
+            // registered in reverse, so only the sets order them
+            .add_systems(GgrsSchedule, count_bullets.in_set(GgrsScheduleSet::Main))
+            .add_systems(GgrsSchedule, fire.in_set(GgrsScheduleSet::First));
+
+        // disable automatic sync points, so only the flush sets apply commands within a frame
+        app.edit_schedule(GgrsSchedule, |schedule| {
+            schedule.set_build_settings(ScheduleBuildSettings {
+                auto_insert_apply_deferred: false,
+                ambiguity_detection: LogLevel::Error,
+                ..default()
+            });
+        });
+
+        for _ in 0..30 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0 as u32;
+            // every frame sees the bullets of all frames up to and including itself
+            let expected = (1..=frame).sum::<u32>();
+
+            assert_eq!(app.world.resource::<Seen>().0, expected, "frame {frame}");
+        }
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > 20);
+    }
+}
+
+mod seek {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{
+        prelude::*, seek_to_frame, BevyGgrsError, GgrsComponentSnapshots, LocalInputs,
+        RollbackFrameCount,
+    };
+
+    use crate::common::{self, TestConfig};
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+    struct Counter(u32);
+
+    fn input_system(mut commands: Commands, mut step: Local<u8>) {
+        *step = step.wrapping_add(1);
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, *step % 7)])));
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Counter::default()).add_rollback();
+    }
+
+    /// Folds every input into the counter, so any deviating input changes the result.
+    fn count(inputs: Res<PlayerInputs<TestConfig>>, mut query: Query<&mut Counter>) {
+        for mut counter in query.iter_mut() {
+            counter.0 = counter
+                .0
+                .wrapping_mul(31)
+                .wrapping_add(inputs[0].0 as u32 + 1);
+        }
+    }
+
+    fn counter(app: &mut App) -> Counter {
+        *app.world.query::<&Counter>().single(&app.world)
+    }
+
+    fn frame(app: &App) -> i32 {
+        app.world.resource::<RollbackFrameCount>().0
+    }
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.enable_seeking()
+            // frames between snapshots are reached by fast-forwarding
+            .set_snapshot_interval(4)
+            .rollback_component_with_copy::<Counter>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        app.insert_resource(Session::SyncTest(session));
+
+        app
+    }
+
+    /// This test makes sure seeking lands exactly on the requested frame, matching the state the
+    /// world had on that frame, both backwards and forwards again.
+    #[test]
+    fn it_seeks_to_exact_frames() {
+        let mut app = create_app();
+        let mut history = HashMap::new();
+
+        for _ in 0..40 {
+            app.update();
+            history.insert(frame(&app), counter(&mut app));
+        }
+
+        let newest = frame(&app);
+        assert!(newest > 30, "Not enough frames advanced");
+
+        for target in [newest - 20, newest - 3, newest - 17, newest] {
+            seek_to_frame::<TestConfig>(&mut app.world, target).unwrap();
+
+            assert_eq!(frame(&app), target);
+            assert_eq!(
+                Some(&counter(&mut app)),
+                history.get(&target),
+                "frame {target}"
+            );
+        }
+    }
+
+    /// This test makes sure seeking back discards the snapshots saved after the one it loaded, and
+    /// seeking forward again fast-forwards from that snapshot using the recorded inputs.
+    #[test]
+    fn it_seeks_back_and_forward_again() {
+        let mut app = create_app();
+        let mut history = HashMap::new();
+
+        for _ in 0..40 {
+            app.update();
+            history.insert(frame(&app), counter(&mut app));
+        }
+
+        let newest = frame(&app);
+        let snapshot_frames = |app: &App| {
+            app.world
+                .resource::<GgrsComponentSnapshots<Entity>>()
+                .frames()
+                .collect::<Vec<_>>()
+        };
+
+        assert!(snapshot_frames(&app)
+            .iter()
+            .any(|&saved| saved > newest - 20));
+
+        let loaded = seek_to_frame::<TestConfig>(&mut app.world, newest - 20).unwrap();
+
+        assert!(loaded <= newest - 20);
+        assert!(snapshot_frames(&app).iter().all(|&saved| saved <= loaded));
+
+        assert_eq!(
+            seek_to_frame::<TestConfig>(&mut app.world, newest),
+            Ok(loaded)
+        );
+        assert_eq!(frame(&app), newest);
+        assert_eq!(Some(&counter(&mut app)), history.get(&newest));
+    }
+
+    /// This test makes sure frames which can not be reached exactly are rejected, leaving the world
+    /// untouched.
+    #[test]
+    fn it_rejects_unreachable_frames() {
+        let mut app = create_app();
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let newest = frame(&app);
+        let before = counter(&mut app);
+
+        // no inputs are recorded beyond the current frame
+        assert!(matches!(
+            seek_to_frame::<TestConfig>(&mut app.world, newest + 5),
+            Err(BevyGgrsError::InputsMissing { .. })
+        ));
+
+        // no snapshot is retained this far back
+        assert_eq!(
+            seek_to_frame::<TestConfig>(&mut app.world, -10),
+            Err(BevyGgrsError::SnapshotMissing { frame: -10 })
+        );
+
+        assert_eq!(frame(&app), newest);
+        assert_eq!(counter(&mut app), before);
+    }
+}
+
+mod session_config {
+    use bevy::{
+        prelude::*,
+        time::TimeUpdateStrategy,
+        utils::{Duration, HashMap},
+    };
+    use bevy_ggrs::{
+        prelude::*, LocalInputs, RollbackFrameCount, SessionConfig, SessionConfigError,
+    };
+
+    use crate::common::TestConfig;
+
+    fn input_system(mut commands: Commands) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, 0), (1, 0)])));
+    }
+
+    /// This test makes sure a [`SessionConfig`] is applied to the session it builds, and can be read
+    /// back from both the configuration and the [`Session`].
+    #[test]
+    fn it_exposes_the_session_configuration() {
+        let config = SessionConfig {
+            num_players: 2,
+            fps: 30,
+            max_prediction: 6,
+            input_delay: 1,
+            sparse_saving: false,
+        };
+
+        let session = config
+            .apply(SessionBuilder::<TestConfig>::new())
+            .unwrap()
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .add_player(PlayerType::Local, 1)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        let session = Session::SyncTest(session);
+
+        assert_eq!(session.num_players(), 2);
+        assert_eq!(session.max_prediction(), Some(6));
+
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / 30.0,
+            )))
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .set_rollback_schedule_fps(config.fps)
+            .add_systems(ReadInputs, input_system)
+            .insert_resource(config)
+            .insert_resource(session);
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > 0);
+        assert_eq!(*app.world.resource::<SessionConfig>(), config);
+    }
+
+    /// This test makes sure a [`SessionConfig`] builds a session with the players it was validated
+    /// against.
+    #[test]
+    fn it_builds_validated_sessions() {
+        let config = SessionConfig {
+            num_players: 2,
+            ..default()
+        };
+
+        let session = config
+            .builder::<TestConfig>([(PlayerType::Local, 0), (PlayerType::Local, 1)])
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        assert_eq!(session.num_players(), 2);
+    }
+
+    /// This test makes sure misconfigured sessions are reported before GGRS builds them.
+    #[test]
+    fn it_rejects_misconfigured_sessions() {
+        let config = SessionConfig {
+            num_players: 2,
+            ..default()
+        };
+
+        let error = config
+            .builder::<TestConfig>([
+                (PlayerType::Local, 0),
+                (PlayerType::Remote(1), 1),
+                (PlayerType::Remote(2), 2),
+            ])
+            .err();
+        assert_eq!(
+            error,
+            Some(SessionConfigError::PlayerCount {
+                declared: 2,
+                added: 3
+            })
+        );
+
+        let error = config
+            .builder::<TestConfig>([(PlayerType::Local, 0), (PlayerType::Local, 2)])
+            .err();
+        assert_eq!(
+            error,
+            Some(SessionConfigError::InvalidHandle {
+                handle: 2,
+                num_players: 2,
+                spectator: false
+            })
+        );
+
+        let error = config
+            .builder::<TestConfig>([
+                (PlayerType::Local, 0),
+                (PlayerType::Remote(1), 1),
+                (PlayerType::Spectator(2), 1),
+            ])
+            .err();
+        assert_eq!(
+            error,
+            Some(SessionConfigError::InvalidHandle {
+                handle: 1,
+                num_players: 2,
+                spectator: true
+            })
+        );
+
+        let error = SessionConfig { fps: 0, ..config }
+            .builder::<TestConfig>([(PlayerType::Local, 0), (PlayerType::Local, 1)])
+            .err();
+        assert_eq!(error, Some(SessionConfigError::ZeroFps));
+        assert_eq!(error.unwrap().to_string(), "The session fps must not be 0.");
+    }
+}
+
+mod session_errors {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, BevyGgrsError, RollbackFrameCount, SessionError};
+
+    use crate::common::{create_app, TestConfig};
+
+    /// This test makes sure missing [`LocalInputs`](`bevy_ggrs::LocalInputs`) are raised as a
+    /// [`SessionError`] instead of advancing the [`Session`].
+    #[test]
+    fn it_reports_missing_local_inputs() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let events = app.world.resource::<Events<SessionError>>();
+        let errors = events
+            .get_reader()
+            .read(events)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert!(!errors.is_empty());
+        assert!(errors
+            .iter()
+            .all(|SessionError(error)| *error == BevyGgrsError::MissingLocalInputs));
+        assert_eq!(
+            *app.world.resource::<RollbackFrameCount>(),
+            RollbackFrameCount(0)
+        );
+
+        Ok(())
+    }
+}
+
+mod session_lifecycle {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        close_session, in_session_type, prelude::*, GgrsInitSchedule, InitialChecksum,
+        LocalPlayersChanged, RollbackFrameCount, SaveWorld, SessionReplaced,
+    };
+
+    use crate::common::{self, input_system, TestConfig};
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.add_systems(ReadInputs, input_system);
+
+        app
+    }
+
+    fn start_synctest_session() -> Session<TestConfig> {
+        Session::SyncTest(
+            SessionBuilder::<TestConfig>::new()
+                .with_num_players(1)
+                .add_player(PlayerType::Local, 0)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap(),
+        )
+    }
+
+    /// This test makes sure state prepared before a [`Session`] starts is not reset while waiting.
+    #[test]
+    fn it_keeps_state_before_a_session_starts() {
+        let mut app = create_app();
+
+        app.insert_resource(RollbackFrameCount(5));
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_eq!(
+            *app.world.resource::<RollbackFrameCount>(),
+            RollbackFrameCount(5)
+        );
+    }
+
+    /// This test makes sure state is reset once a [`Session`] ends.
+    #[test]
+    fn it_resets_state_when_a_session_ends() {
+        let mut app = create_app();
+
+        app.insert_resource(start_synctest_session());
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_ne!(
+            *app.world.resource::<RollbackFrameCount>(),
+            RollbackFrameCount(0)
+        );
+
+        app.world.remove_resource::<Session<TestConfig>>();
+        app.update();
+
+        assert_eq!(
+            *app.world.resource::<RollbackFrameCount>(),
+            RollbackFrameCount(0)
+        );
+    }
+
+    #[derive(Component, Clone, Copy, Hash)]
+    struct Health(u32);
+
+    #[derive(Resource, Default)]
+    struct InitRuns(usize);
+
+    fn spawn_players(mut commands: Commands, mut runs: ResMut<InitRuns>) {
+        runs.0 += 1;
+        commands.spawn(Health(100)).add_rollback();
+    }
+
+    /// This test makes sure the [`GgrsInitSchedule`] runs exactly once per [`Session`].
+    #[test]
+    fn it_runs_the_init_schedule_once_per_session() {
+        let mut app = create_app();
+
+        app.init_resource::<InitRuns>()
+            .rollback_component_with_copy::<Health>()
+            .checksum_component_with_hash::<Health>()
+            .add_systems(GgrsInitSchedule, spawn_players);
+
+        app.update();
+
+        assert_eq!(app.world.resource::<InitRuns>().0, 0);
+        assert_eq!(
+            *app.world.resource::<InitialChecksum>(),
+            InitialChecksum(None)
+        );
+
+        app.insert_resource(start_synctest_session());
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let initial = *app.world.resource::<InitialChecksum>();
+
+        assert_eq!(app.world.resource::<InitRuns>().0, 1);
+        assert!(initial.0.is_some());
+
+        // a second session starting from the same state yields the same initial checksum
+        app.world.remove_resource::<Session<TestConfig>>();
+        app.update();
+
+        assert_eq!(
+            *app.world.resource::<InitialChecksum>(),
+            InitialChecksum(None)
+        );
+
+        let mut other = create_app();
+
+        other
+            .init_resource::<InitRuns>()
+            .rollback_component_with_copy::<Health>()
+            .checksum_component_with_hash::<Health>()
+            .add_systems(GgrsInitSchedule, spawn_players)
+            .insert_resource(start_synctest_session());
+
+        other.update();
+
+        assert_eq!(*other.world.resource::<InitialChecksum>(), initial);
+    }
+
+    /// This test makes sure [`LocalPlayersChanged`] is only sent when the local players change
+    /// during a [`Session`].
+    #[test]
+    fn it_notifies_when_local_players_change() {
+        let mut app = create_app();
+
+        app.insert_resource(start_synctest_session());
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let events = app.world.resource::<Events<LocalPlayersChanged>>();
+        assert!(events.is_empty());
+
+        app.insert_resource(Session::SyncTest(
+            SessionBuilder::<TestConfig>::new()
+                .with_num_players(2)
+                .add_player(PlayerType::Local, 0)
+                .unwrap()
+                .add_player(PlayerType::Local, 1)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap(),
+        ));
+        app.update();
+
+        let events = app.world.resource::<Events<LocalPlayersChanged>>();
+        let changes = events
+            .get_reader()
+            .read(events)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            vec![LocalPlayersChanged {
+                previous: vec![0],
+                current: vec![0, 1],
+            }]
+        );
+    }
+
+    /// This test makes sure [`SessionReplaced`] is sent whenever the kind of [`Session`] changes.
+    #[test]
+    fn it_notifies_when_the_session_type_changes() {
+        let mut app = create_app();
+
+        let replaced = |app: &mut App| {
+            app.world
+                .resource_mut::<Events<SessionReplaced>>()
+                .drain()
+                .collect::<Vec<_>>()
+        };
+
+        app.update();
+        assert_eq!(replaced(&mut app), vec![]);
+
+        app.insert_resource(start_synctest_session());
+        app.update();
+        assert_eq!(
+            replaced(&mut app),
+            vec![SessionReplaced {
+                old: SessionType::None,
+                new: SessionType::SyncTest,
+            }]
+        );
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(replaced(&mut app), vec![]);
+
+        app.world.remove_resource::<Session<TestConfig>>();
+        app.update();
+        assert_eq!(
+            replaced(&mut app),
+            vec![SessionReplaced {
+                old: SessionType::SyncTest,
+                new: SessionType::None,
+            }]
+        );
+    }
+
+    /// How often the save systems gated on each kind of [`Session`] ran.
+    #[derive(Resource, Default)]
+    struct GatedSaves {
+        sync_test: usize,
+        p2p: usize,
+    }
+
+    /// This test makes sure [`in_session_type`] only runs systems during the matching kind of
+    /// [`Session`].
+    #[test]
+    fn it_gates_systems_on_the_session_type() {
+        let mut app = create_app();
+
+        app.init_resource::<GatedSaves>().add_systems(
+            SaveWorld,
+            (
+                (|mut saves: ResMut<GatedSaves>| saves.sync_test += 1)
+                    .run_if(in_session_type(SessionType::SyncTest)),
+                (|mut saves: ResMut<GatedSaves>| saves.p2p += 1)
+                    .run_if(in_session_type(SessionType::P2P)),
+            ),
+        );
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        assert_eq!(app.world.resource::<GatedSaves>().sync_test, 0);
+        assert_eq!(app.world.resource::<GatedSaves>().p2p, 0);
+
+        app.insert_resource(start_synctest_session());
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let sync_test = app.world.resource::<GatedSaves>().sync_test;
+
+        assert!(sync_test > 0);
+        assert_eq!(app.world.resource::<GatedSaves>().p2p, 0);
+
+        app.world.remove_resource::<Session<TestConfig>>();
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        assert_eq!(app.world.resource::<GatedSaves>().sync_test, sync_test);
+        assert_eq!(app.world.resource::<GatedSaves>().p2p, 0);
+    }
+
+    /// This test makes sure [`close_session`] removes the [`Session`], ending it.
+    #[test]
+    fn it_closes_sessions() {
+        let mut app = create_app();
+
+        app.insert_resource(start_synctest_session());
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        close_session::<TestConfig>(&mut app.world);
+        app.update();
+
+        assert!(app.world.get_resource::<Session<TestConfig>>().is_none());
+        assert_eq!(*app.world.resource::<SessionType>(), SessionType::None);
+        assert_eq!(
+            *app.world.resource::<RollbackFrameCount>(),
+            RollbackFrameCount(0)
+        );
+    }
+}
+
+mod session_start_frame {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, start_session_at_frame, RollbackFrameCount, SessionError, SessionStartFrame,
+    };
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    /// The sum of all frames advanced to, rolled back.
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct FrameSum(i64);
+
+    #[derive(Resource, Default)]
+    struct Errors(Vec<String>);
+
+    fn sum_frames(frame: Res<RollbackFrameCount>, mut sum: ResMut<FrameSum>) {
+        sum.0 += frame.0 as i64;
+    }
+
+    fn collect_errors(mut events: EventReader<SessionError>, mut errors: ResMut<Errors>) {
+        errors
+            .0
+            .extend(events.read().map(|error| error.0.to_string()));
+    }
+
+    fn run_from(start: i32, interval: Option<usize>) {
+        let mut app = create_app();
+
+        app.init_resource::<FrameSum>()
+            .init_resource::<Errors>()
+            .rollback_resource_with_copy::<FrameSum>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, sum_frames)
+            .add_systems(Update, collect_errors);
+
+        if let Some(interval) = interval {
+            app.set_snapshot_interval(interval);
+        }
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(5)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        start_session_at_frame::<TestConfig>(&mut app.world, Session::SyncTest(session), start);
+
+        for _ in 0..30 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+            let expected = (start + 1..=frame).map(|frame| frame as i64).sum::<i64>();
+
+            assert_eq!(
+                app.world.resource::<FrameSum>().0,
+                expected,
+                "frame {frame}"
+            );
+        }
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > start + 20);
+        assert_eq!(app.world.resource::<SessionStartFrame>().0, start);
+        assert!(
+            app.world.resource::<Errors>().0.is_empty(),
+            "{:?}",
+            app.world.resource::<Errors>().0
+        );
+    }
+
+    /// This test makes sure a session started at a later frame keeps counting from that frame, while
+    /// rolling back without errors.
+    #[test]
+    fn it_starts_at_the_provided_frame() {
+        run_from(100, None);
+    }
+
+    /// This test makes sure rolling back to the first frame of a session works, even if it lies
+    /// between two frames of the snapshot interval.
+    #[test]
+    fn it_starts_between_snapshot_intervals() {
+        run_from(101, Some(4));
+    }
+}
+
+mod status {
+    use bevy::prelude::*;
+    use bevy_ggrs::{ggrs::SessionState, prelude::*, GgrsStatus, SessionType};
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    /// This test makes sure the [`GgrsStatus`] summarizes a running session, and is reset once it ends.
+    #[test]
+    fn it_summarizes_the_session() {
+        let mut app = create_app();
+
+        app.add_systems(ReadInputs, input_system);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        let status = app.world.resource::<GgrsStatus>();
+        assert_eq!(status.session_type(), SessionType::SyncTest);
+        assert_eq!(status.state(), Some(SessionState::Running));
+        assert_eq!(status.pings().count(), 0);
+        assert_eq!(status.last_desync_frame(), None);
+
+        // a synctest session rolls back every frame
+        let rollbacks = status.rollbacks_per_second();
+        assert!(rollbacks > 50. && rollbacks < 70., "{rollbacks}");
+
+        app.world.remove_resource::<Session<TestConfig>>();
+        app.update();
+
+        let status = app.world.resource::<GgrsStatus>();
+        assert_eq!(status.session_type(), SessionType::None);
+        assert_eq!(status.state(), None);
+    }
+}
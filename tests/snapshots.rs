@@ -0,0 +1,1911 @@
+mod common;
+
+mod component_churn {
+    use bevy::prelude::*;
+    use bevy_ggrs::prelude::*;
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    /// The [`Shield`] is toggled on every frame which is a multiple of this period.
+    const TOGGLE_PERIOD: u32 = 5;
+
+    /// Rollbacks span further than the toggle period, so every rollback crosses a toggle.
+    const CHECK_DISTANCE: usize = 7;
+
+    #[derive(Component, Reflect, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+    struct Shield(u32);
+
+    #[derive(Component)]
+    struct Player;
+
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct Frames(u32);
+
+    fn setup_system(mut commands: Commands) {
+        commands.spawn(Player).add_rollback();
+    }
+
+    fn count_frames(mut frames: ResMut<Frames>) {
+        frames.0 += 1;
+    }
+
+    fn tick_shield(mut shields: Query<&mut Shield>) {
+        for mut shield in shields.iter_mut() {
+            shield.0 += 1;
+        }
+    }
+
+    fn toggle_shield(
+        mut commands: Commands,
+        frames: Res<Frames>,
+        players: Query<(Entity, Has<Shield>), With<Player>>,
+    ) {
+        if frames.0 % TOGGLE_PERIOD != 0 {
+            return;
+        }
+
+        for (entity, shielded) in players.iter() {
+            if shielded {
+                commands.entity(entity).remove::<Shield>();
+            } else {
+                commands.entity(entity).insert(Shield::default());
+            }
+        }
+    }
+
+    /// Computes the expected [`Shield`] after the provided amount of frames without any rollback.
+    fn expected_shield(frames: u32) -> Option<Shield> {
+        let mut shield = None;
+
+        for frame in 1..=frames {
+            if let Some(Shield(ticks)) = &mut shield {
+                *ticks += 1;
+            }
+
+            if frame % TOGGLE_PERIOD == 0 {
+                shield = match shield {
+                    Some(_) => None,
+                    None => Some(Shield::default()),
+                };
+            }
+        }
+
+        shield
+    }
+
+    fn run_churn(register: impl FnOnce(&mut App)) {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(CHECK_DISTANCE))
+            .init_resource::<Frames>()
+            .rollback_resource_with_copy::<Frames>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(
+                GgrsSchedule,
+                (count_frames, tick_shield, toggle_shield).chain(),
+            );
+
+        register(&mut app);
+
+        for _ in 0..60 {
+            app.update();
+
+            let frames = app.world.resource::<Frames>().0;
+
+            let shield = app
+                .world
+                .query_filtered::<Option<&Shield>, With<Player>>()
+                .single(&app.world)
+                .copied();
+
+            assert_eq!(
+                shield,
+                expected_shield(frames),
+                "Shield diverged after {frames} frames"
+            );
+        }
+
+        let frames = app.world.resource::<Frames>().0;
+        assert!(frames > TOGGLE_PERIOD * 4, "Not enough frames advanced");
+    }
+
+    /// This test makes sure a component repeatedly added and removed is restored correctly when
+    /// rolling back across both its insertion and removal, using reflection.
+    #[test]
+    fn it_rolls_back_component_churn_with_reflect() {
+        run_churn(|app| {
+            app.rollback_component_with_reflect::<Shield>();
+        });
+    }
+
+    /// This test makes sure a component repeatedly added and removed is restored correctly when
+    /// rolling back across both its insertion and removal, using copies.
+    #[test]
+    fn it_rolls_back_component_churn_with_copy() {
+        run_churn(|app| {
+            app.rollback_component_with_copy::<Shield>();
+        });
+    }
+}
+
+mod component_transform {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, GgrsComponentSnapshots, GgrsInitSchedule, LoadWorld, RollbackFrameCount,
+    };
+
+    use crate::common::{create_app, input_system, synctest_session, TestConfig};
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq)]
+    struct Position(f32);
+
+    /// Stores positions as fixed-point integers in 1/256 units.
+    fn store(position: &Position) -> i32 {
+        (position.0 * 256.).round() as i32
+    }
+
+    fn load(stored: &i32) -> Position {
+        Position(*stored as f32 / 256.)
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Position(0.)).add_rollback();
+    }
+
+    /// Moves by a distance which is not a multiple of 1/256.
+    fn step(mut query: Query<&mut Position>) {
+        for mut position in query.iter_mut() {
+            position.0 += 0.3;
+        }
+    }
+
+    fn position(app: &mut App) -> Position {
+        *app.world.query::<&Position>().single(&app.world)
+    }
+
+    /// This test makes sure components are saved using the store transform, and restored using the
+    /// load transform when rolling back.
+    #[test]
+    fn it_saves_and_loads_through_the_transform() {
+        let mut app = create_app();
+
+        app.rollback_component_with_transform::<Position, i32>(store, load)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, step)
+            .insert_resource(synctest_session(2));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        app.world.remove_resource::<Session<TestConfig>>();
+
+        let current = position(&mut app);
+
+        let snapshots = app
+            .world
+            .resource::<GgrsComponentSnapshots<Position, i32>>();
+        let frame = snapshots.frames().next().unwrap();
+        let stored = *snapshots
+            .get()
+            .iter()
+            .map(|(_, stored)| stored)
+            .next()
+            .unwrap();
+
+        // the snapshot holds the fixed-point form of the position, not the position itself
+        assert!(stored > 256);
+
+        app.world
+            .query::<&mut Position>()
+            .single_mut(&mut app.world)
+            .0 = -100.;
+        app.world.resource_mut::<RollbackFrameCount>().0 = frame;
+        app.world.run_schedule(LoadWorld);
+
+        // the restored position went through the load transform, so it is quantized
+        let restored = position(&mut app);
+        assert_eq!(restored, load(&stored));
+        assert_eq!(restored.0 * 256., (restored.0 * 256.).round());
+        assert!((restored.0 - current.0).abs() <= 0.3 + 1. / 256.);
+    }
+}
+
+mod custom_snapshot {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, LoadWorld, RollbackFrameCount, SaveWorld, SnapshotChecksumVerification,
+    };
+    use std::collections::BTreeMap;
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    /// Rolled back by hand, rather than by one of the provided snapshot plugins.
+    #[derive(Resource, Clone, Copy, Default, Debug, Hash)]
+    struct Score(u32);
+
+    /// Snapshots of [`Score`], keyed by frame.
+    #[derive(Resource, Default)]
+    struct ScoreSnapshots(BTreeMap<i32, Score>);
+
+    fn score_system(frame: Res<RollbackFrameCount>, mut score: ResMut<Score>) {
+        score.0 += frame.0 as u32;
+    }
+
+    fn save_score(
+        frame: Res<RollbackFrameCount>,
+        score: Res<Score>,
+        mut snapshots: ResMut<ScoreSnapshots>,
+    ) {
+        snapshots.0.insert(frame.0, *score);
+    }
+
+    fn load_score(
+        frame: Res<RollbackFrameCount>,
+        snapshots: Res<ScoreSnapshots>,
+        mut score: ResMut<Score>,
+    ) {
+        *score = snapshots.0[&frame.0];
+    }
+
+    /// This test makes sure state saved and loaded by custom systems in the [`SaveWorld`] and
+    /// [`LoadWorld`] schedules is rolled back, and matches the checksum GGRS compares.
+    #[test]
+    fn it_rolls_back_custom_snapshots() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(4))
+            .verify_snapshot_checksums()
+            .init_resource::<Score>()
+            .init_resource::<ScoreSnapshots>()
+            .checksum_resource_with_hash::<Score>()
+            .add_systems(SaveWorld, save_score.in_set(SaveWorldSet::Snapshot))
+            .add_systems(LoadWorld, load_score.in_set(LoadWorldSet::Data))
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, score_system);
+
+        for _ in 0..30 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+            let expected = (1..=frame).map(|frame| frame as u32).sum::<u32>();
+
+            assert_eq!(app.world.resource::<Score>().0, expected, "frame {frame}");
+        }
+
+        assert!(app.world.resource::<ScoreSnapshots>().0.len() > 1);
+        assert_eq!(
+            app.world
+                .resource::<SnapshotChecksumVerification>()
+                .mismatches(),
+            0
+        );
+    }
+}
+
+mod delta_snapshots {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, Checksum, GgrsDeltaSnapshots, LoadWorld, RollbackFrameCount, SaveWorld,
+    };
+
+    use crate::common::{self, input_system, synctest_session, TestConfig};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy, Default, Debug)]
+    struct Moving;
+
+    fn setup_system(mut commands: Commands) {
+        for health in 0..20 {
+            commands.spawn(Health(health)).add_rollback();
+        }
+
+        commands.spawn((Health(100), Moving)).add_rollback();
+        commands.spawn((Health(200), Moving)).add_rollback();
+    }
+
+    fn simulate(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        mut moving: Query<&mut Health, With<Moving>>,
+    ) {
+        for mut health in moving.iter_mut() {
+            health.0 += 1;
+        }
+
+        if frame.0 % 5 == 0 {
+            commands.spawn(Health(frame.0 as u32)).add_rollback();
+        }
+    }
+
+    fn despawn_system(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        query: Query<(Entity, &Health), Without<Moving>>,
+    ) {
+        for (entity, health) in query.iter() {
+            if frame.0 % 7 == 0 && health.0 % 3 == 0 {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    fn create_app(delta: bool) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(synctest_session(4))
+            .rollback_component_with_copy::<Moving>()
+            .checksum_component_with_hash::<Health>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, (simulate, despawn_system).chain());
+
+        if delta {
+            app.rollback_component_with_delta::<Health>();
+        } else {
+            app.rollback_component_with_copy::<Health>();
+        }
+
+        app
+    }
+
+    fn sorted_health(world: &mut World) -> Vec<u32> {
+        let mut health = world
+            .query::<&Health>()
+            .iter(world)
+            .map(|health| health.0)
+            .collect::<Vec<_>>();
+
+        health.sort_unstable();
+        health
+    }
+
+    /// This test makes sure delta snapshots roll back to the same state as [`Copy`] based snapshots,
+    /// while components change, spawn, despawn and roll back.
+    #[test]
+    fn it_matches_copy_snapshots() {
+        let mut copy = create_app(false);
+        let mut delta = create_app(true);
+
+        for _ in 0..60 {
+            copy.update();
+            delta.update();
+
+            assert_eq!(
+                copy.world.resource::<RollbackFrameCount>(),
+                delta.world.resource::<RollbackFrameCount>()
+            );
+            assert_eq!(
+                copy.world.resource::<Checksum>().0,
+                delta.world.resource::<Checksum>().0
+            );
+            assert_eq!(
+                sorted_health(&mut copy.world),
+                sorted_health(&mut delta.world)
+            );
+        }
+    }
+
+    /// This test makes sure only changed components are stored in each delta.
+    #[test]
+    fn it_only_stores_changed_components() {
+        let mut app = create_app(true);
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        let entities = app.world.query::<&Health>().iter(&app.world).count();
+        let snapshots = app.world.resource::<GgrsDeltaSnapshots<Health>>();
+        let deltas = snapshots.frames().count() - 1;
+
+        assert!(deltas > 0);
+        assert!(snapshots.delta_len() < deltas * entities / 2);
+    }
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+    struct Jitter(u32);
+
+    fn jitter_app(eq: Option<fn(&Jitter, &Jitter) -> bool>) -> App {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(GgrsPlugin::<TestConfig>::default());
+
+        match eq {
+            Some(eq) => app.rollback_component_with_delta_eq::<Jitter>(eq),
+            None => app.rollback_component_with_delta::<Jitter>(),
+        };
+
+        app.add_systems(Startup, |mut commands: Commands| {
+            for _ in 0..10 {
+                commands.spawn(Jitter(0)).add_rollback();
+            }
+        })
+        .update();
+
+        app
+    }
+
+    /// Saves 30 frames, where every [`Jitter`] increases by one each frame.
+    fn save_jittering_frames(app: &mut App) {
+        let mut query = app.world.query::<&mut Jitter>();
+
+        for frame in 1..=30 {
+            for mut jitter in query.iter_mut(&mut app.world) {
+                jitter.0 += 1;
+            }
+
+            app.world.resource_mut::<RollbackFrameCount>().0 = frame;
+            app.world.run_schedule(SaveWorld);
+        }
+    }
+
+    /// This test makes sure a custom equality only stores values it considers changed, and that the
+    /// values it does store are restored exactly.
+    #[test]
+    fn it_stores_changes_according_to_a_custom_equality() {
+        let mut exact = jitter_app(None);
+        let mut coarse = jitter_app(Some(|a, b| a.0 / 10 == b.0 / 10));
+
+        save_jittering_frames(&mut exact);
+        save_jittering_frames(&mut coarse);
+
+        let exact_len = exact
+            .world
+            .resource::<GgrsDeltaSnapshots<Jitter>>()
+            .delta_len();
+        let coarse_len = coarse
+            .world
+            .resource::<GgrsDeltaSnapshots<Jitter>>()
+            .delta_len();
+
+        // every entity changes on all 29 deltas exactly, but only on 3 of them coarsely
+        assert_eq!(exact_len, 29 * 10);
+        assert_eq!(coarse_len, 3 * 10);
+
+        // frame 25 is considered equal to frame 20, which was stored exactly
+        coarse.world.resource_mut::<RollbackFrameCount>().0 = 25;
+        coarse.world.run_schedule(LoadWorld);
+
+        let mut query = coarse.world.query::<&Jitter>();
+        assert!(query.iter(&coarse.world).all(|jitter| jitter.0 == 20));
+    }
+}
+
+mod disable_snapshots {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, DisableSnapshots, GgrsComponentSnapshots, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    #[derive(Resource, Default)]
+    struct Advances(u32);
+
+    fn count_advances(mut advances: ResMut<Advances>) {
+        advances.0 += 1;
+    }
+
+    /// This test makes sure [`DisableSnapshots`] stops all saving while still advancing the session.
+    #[test]
+    fn it_advances_without_snapshots() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.insert_resource(DisableSnapshots)
+            .init_resource::<Advances>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, count_advances);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(0)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+
+        assert!(frame > 0);
+        assert_eq!(app.world.resource::<Advances>().0, frame as u32);
+        assert_eq!(
+            app.world
+                .resource::<GgrsComponentSnapshots<Entity>>()
+                .frames()
+                .count(),
+            0
+        );
+
+        Ok(())
+    }
+}
+
+mod from_world_assets {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, GgrsInitSchedule, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    const REMOVED_FROM: i32 = 10;
+
+    #[derive(Asset, TypePath)]
+    struct Palette {
+        colors: Vec<u32>,
+    }
+
+    #[derive(Resource)]
+    struct DefaultPalette(Handle<Palette>);
+
+    /// A component whose reconstruction requires reading an asset.
+    #[derive(Component, Reflect)]
+    struct Tint {
+        color: u32,
+        /// Not part of the snapshot, so must be initialized by [`FromWorld`].
+        #[reflect(ignore)]
+        palette_size: usize,
+    }
+
+    impl FromWorld for Tint {
+        fn from_world(world: &mut World) -> Self {
+            let handle = &world.resource::<DefaultPalette>().0;
+            let palette = world.resource::<Assets<Palette>>().get(handle).unwrap();
+
+            Self {
+                color: palette.colors[0],
+                palette_size: palette.colors.len(),
+            }
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct SeenPaletteSizes(Vec<usize>);
+
+    fn spawn(mut commands: Commands) {
+        for color in 0..3 {
+            commands
+                .spawn(Tint {
+                    color,
+                    palette_size: 3,
+                })
+                .add_rollback();
+        }
+    }
+
+    fn remove_tints(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        query: Query<Entity, With<Tint>>,
+    ) {
+        if frame.0 >= REMOVED_FROM {
+            for entity in query.iter() {
+                commands.entity(entity).remove::<Tint>();
+            }
+        }
+    }
+
+    fn record_tints(mut seen: ResMut<SeenPaletteSizes>, query: Query<&Tint>) {
+        seen.0.extend(query.iter().map(|tint| tint.palette_size));
+    }
+
+    /// This test makes sure components recreated on rollback are created with full [`World`] access,
+    /// so [`FromWorld`] can read assets, even when several are recreated in a single load.
+    #[test]
+    fn it_recreates_components_from_world_with_assets() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<Palette>()
+            .init_resource::<SeenPaletteSizes>()
+            .rollback_component_with_reflect::<Tint>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, (record_tints, remove_tints).chain());
+
+        let handle = app.world.resource_mut::<Assets<Palette>>().add(Palette {
+            colors: vec![0xFF0000, 0x00FF00, 0x0000FF],
+        });
+        app.insert_resource(DefaultPalette(handle));
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > REMOVED_FROM + 2);
+
+        let seen = &app.world.resource::<SeenPaletteSizes>().0;
+
+        // three tints for every frame before their removal, plus any re-simulated frames
+        assert!(seen.len() > 3 * REMOVED_FROM as usize);
+        assert!(seen.iter().all(|&size| size == 3));
+
+        Ok(())
+    }
+}
+
+mod keep_on_rollback {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, GgrsInitSchedule};
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+    struct Cache(u32);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty().add_rollback();
+    }
+
+    fn app() -> App {
+        let mut app = create_app();
+
+        app.rollback_component_with_copy::<Cache>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn);
+
+        app
+    }
+
+    fn run_with_cache_inserted_outside_the_simulation(
+        mut app: App,
+    ) -> Result<Option<Cache>, Box<dyn std::error::Error>> {
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let entity = app
+            .world
+            .query_filtered::<Entity, With<Rollback>>()
+            .single(&app.world);
+
+        app.world.entity_mut(entity).insert(Cache(7));
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        Ok(app.world.get::<Cache>(entity).copied())
+    }
+
+    /// This test makes sure rolling back to a snapshot lacking a component removes it by default.
+    #[test]
+    fn it_removes_components_missing_from_the_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+        let cache = run_with_cache_inserted_outside_the_simulation(app())?;
+
+        assert_eq!(cache, None);
+
+        Ok(())
+    }
+
+    /// This test makes sure components registered with `keep_on_rollback` are never removed.
+    #[test]
+    fn it_keeps_components_registered_to_be_kept() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = app();
+        app.keep_on_rollback::<Cache>();
+
+        let cache = run_with_cache_inserted_outside_the_simulation(app)?;
+
+        assert_eq!(cache, Some(Cache(7)));
+
+        Ok(())
+    }
+}
+
+mod materialize_snapshots {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, CloneStrategy, CopyStrategy, GgrsComponentSnapshots, GgrsInitSchedule,
+        ReflectStrategy,
+    };
+
+    use crate::common::{self, input_system, TestConfig};
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+    struct Counter(i32);
+
+    #[derive(Component, Clone, PartialEq, Eq, Debug)]
+    struct Tag(String);
+
+    #[derive(Component, Reflect, Default, PartialEq, Eq, Debug)]
+    struct ReflectCounter(i32);
+
+    fn spawn(mut commands: Commands) {
+        commands
+            .spawn((Counter(0), Tag("counter".into()), ReflectCounter(0)))
+            .add_rollback();
+        commands.spawn_empty().add_rollback();
+    }
+
+    fn count(mut query: Query<(&mut Counter, &mut ReflectCounter)>) {
+        for (mut counter, mut reflect_counter) in query.iter_mut() {
+            counter.0 += 1;
+            reflect_counter.0 += 1;
+        }
+    }
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.rollback_component_with_copy::<Counter>()
+            .rollback_component_with_clone::<Tag>()
+            .rollback_component_with_reflect::<ReflectCounter>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        app
+    }
+
+    fn rollbacks(app: &mut App) -> (Rollback, Rollback) {
+        let counted = *app
+            .world
+            .query_filtered::<&Rollback, With<Counter>>()
+            .single(&app.world);
+        let empty = *app
+            .world
+            .query_filtered::<&Rollback, Without<Counter>>()
+            .single(&app.world);
+
+        (counted, empty)
+    }
+
+    /// This test makes sure every retained frame can be materialized using the strategy it was
+    /// stored with, matching the value the component had on that frame.
+    #[test]
+    fn it_materializes_historical_values() {
+        let mut app = create_app();
+        let (counted, empty) = rollbacks(&mut app);
+
+        let counters = app.world.resource::<GgrsComponentSnapshots<Counter>>();
+        let tags = app.world.resource::<GgrsComponentSnapshots<Tag>>();
+        let reflect_counters = app
+            .world
+            .resource::<GgrsComponentSnapshots<ReflectCounter, Box<dyn Reflect>>>();
+
+        assert!(counters.frames().count() > 1);
+
+        for frame in counters.frames() {
+            let snapshot = counters.peek(frame).unwrap();
+            assert_eq!(
+                snapshot.materialize::<CopyStrategy<Counter>>(&counted),
+                Some(Counter(frame))
+            );
+            assert_eq!(snapshot.materialize::<CopyStrategy<Counter>>(&empty), None);
+
+            let snapshot = tags.peek(frame).unwrap();
+            assert_eq!(
+                snapshot.materialize::<CloneStrategy<Tag>>(&counted),
+                Some(Tag("counter".into()))
+            );
+
+            let snapshot = reflect_counters.peek(frame).unwrap();
+            assert_eq!(
+                snapshot.materialize::<ReflectStrategy<ReflectCounter>>(&counted),
+                Some(ReflectCounter(frame))
+            );
+            assert_eq!(
+                snapshot.materialize_reflect(&counted),
+                Some(ReflectCounter(frame))
+            );
+            assert_eq!(snapshot.materialize_reflect(&empty), None);
+        }
+    }
+
+    /// This test makes sure retained snapshots can be iterated in order, for example to graph the
+    /// history of a component.
+    #[test]
+    fn it_iterates_retained_history() {
+        let mut app = create_app();
+        let (counted, _) = rollbacks(&mut app);
+
+        let counters = app.world.resource::<GgrsComponentSnapshots<Counter>>();
+        let history = counters
+            .iter()
+            .rev()
+            .map(|(frame, snapshot)| (frame, snapshot.get(&counted).copied()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(history.len(), counters.frames().count());
+        assert!(history.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        for (frame, counter) in history {
+            assert_eq!(counter, Some(Counter(frame)));
+        }
+    }
+}
+
+mod named_snapshots {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        capture_named_snapshot, prelude::*, restore_named_snapshot, BevyGgrsError,
+        GgrsComponentSnapshots, NamedSnapshots, RetainedFrames, RollbackFrameCount, SessionError,
+    };
+
+    use crate::common::{self, input_system, synctest_session, TestConfig};
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+    struct Health(u32);
+
+    #[derive(Resource, Clone, Copy, Default, Debug, PartialEq, Eq)]
+    struct Counter(u32);
+
+    #[derive(Resource, Default)]
+    struct Errors(usize);
+
+    /// Counts every frame, spawning a new entity every few frames.
+    fn simulate(
+        mut commands: Commands,
+        mut counter: ResMut<Counter>,
+        mut query: Query<&mut Health>,
+    ) {
+        counter.0 += 1;
+
+        for mut health in query.iter_mut() {
+            health.0 += 1;
+        }
+
+        if counter.0 % 4 == 0 {
+            commands.spawn(Health(0)).add_rollback();
+        }
+    }
+
+    fn record_errors(mut events: EventReader<SessionError>, mut errors: ResMut<Errors>) {
+        errors.0 += events.read().count();
+    }
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.rollback_component_with_copy::<Health>()
+            .rollback_resource_with_copy::<Counter>()
+            .init_resource::<Counter>()
+            .init_resource::<Errors>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, simulate)
+            .add_systems(Update, record_errors)
+            .insert_resource(synctest_session(2));
+
+        app
+    }
+
+    /// The state being rolled back, with the healths sorted.
+    fn state(app: &mut App) -> (u32, Vec<u32>) {
+        let mut healths = app
+            .world
+            .query::<&Health>()
+            .iter(&app.world)
+            .map(|health| health.0)
+            .collect::<Vec<_>>();
+        healths.sort();
+
+        (app.world.resource::<Counter>().0, healths)
+    }
+
+    /// The frames held by the snapshots saved for GGRS.
+    fn retained(app: &App) -> (Vec<i32>, Vec<i32>) {
+        (
+            app.world
+                .resource::<GgrsComponentSnapshots<Health>>()
+                .frames()
+                .collect(),
+            app.world.resource::<RetainedFrames>().iter().collect(),
+        )
+    }
+
+    /// This test makes sure a named snapshot can be restored long after the frame it was captured on
+    /// has been discarded, without changing the snapshots saved for GGRS.
+    #[test]
+    fn it_captures_and_restores_named_snapshots() {
+        let mut app = create_app();
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let captured_frame = app.world.resource::<RollbackFrameCount>().0;
+        let captured = state(&mut app);
+        let before = retained(&app);
+
+        capture_named_snapshot(&mut app.world, "round start");
+
+        assert_eq!(retained(&app), before);
+        assert_eq!(
+            app.world.resource::<NamedSnapshots>().frame("round start"),
+            Some(captured_frame)
+        );
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        assert_eq!(app.world.resource::<Errors>().0, 0);
+        assert!(!app
+            .world
+            .resource::<GgrsComponentSnapshots<Health>>()
+            .contains(captured_frame));
+        assert_ne!(state(&mut app), captured);
+
+        app.world.remove_resource::<Session<TestConfig>>();
+
+        let before = retained(&app);
+
+        for _ in 0..2 {
+            assert_eq!(
+                restore_named_snapshot(&mut app.world, "round start"),
+                Ok(captured_frame)
+            );
+            assert_eq!(state(&mut app), captured);
+            assert_eq!(app.world.resource::<RollbackFrameCount>().0, captured_frame);
+            assert_eq!(retained(&app), before);
+        }
+
+        assert_eq!(
+            restore_named_snapshot(&mut app.world, "round end"),
+            Err(BevyGgrsError::NamedSnapshotMissing {
+                name: "round end".to_owned()
+            })
+        );
+    }
+}
+
+mod parallel_snapshots {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, GgrsInitSchedule, ParallelSnapshots, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    const ENTITIES: i32 = 1000;
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+    struct Counter(i32);
+
+    fn spawn(mut commands: Commands) {
+        for offset in 0..ENTITIES {
+            commands.spawn(Counter(offset)).add_rollback();
+        }
+    }
+
+    fn count(mut query: Query<&mut Counter>) {
+        for mut counter in query.iter_mut() {
+            counter.0 += 1;
+        }
+    }
+
+    /// This test makes sure components stored in parallel are restored to the right entities.
+    #[test]
+    fn it_rolls_back_components_stored_in_parallel() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.rollback_component_with_copy::<Counter>()
+            .insert_resource(ParallelSnapshots { min_entities: 1 })
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+        assert!(frame > 2);
+
+        let mut counters = app
+            .world
+            .query::<&Counter>()
+            .iter(&app.world)
+            .map(|counter| counter.0)
+            .collect::<Vec<_>>();
+        counters.sort();
+
+        // every entity must have been restored from its own snapshot on each rollback
+        let expected = (0..ENTITIES)
+            .map(|offset| offset + frame)
+            .collect::<Vec<_>>();
+        assert_eq!(counters, expected);
+
+        Ok(())
+    }
+}
+
+mod pooled_snapshots {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, Checksum, CopyStrategy, GgrsPooledComponentSnapshots,
+        PooledComponentSnapshotPlugin, RollbackFrameCount,
+    };
+
+    use crate::common::{self, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash, PartialEq)]
+    struct Health(u32);
+
+    fn setup_system(mut commands: Commands) {
+        for health in 0..20 {
+            commands.spawn(Health(health)).add_rollback();
+        }
+    }
+
+    fn simulate(
+        mut commands: Commands,
+        frame: Res<RollbackFrameCount>,
+        mut query: Query<(Entity, &mut Health)>,
+    ) {
+        for (entity, mut health) in query.iter_mut() {
+            health.0 += 1;
+
+            if frame.0 % 7 == 0 && health.0 % 3 == 0 {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        if frame.0 % 5 == 0 {
+            commands.spawn(Health(frame.0 as u32)).add_rollback();
+        }
+    }
+
+    fn create_app(pooled: bool) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(synctest_session(4))
+            .checksum_component_with_hash::<Health>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, simulate);
+
+        if pooled {
+            app.add_plugins(PooledComponentSnapshotPlugin::<CopyStrategy<Health>>::default());
+        } else {
+            app.rollback_component_with_copy::<Health>();
+        }
+
+        app
+    }
+
+    /// This test makes sure pooled snapshots roll back to the same state as map based snapshots,
+    /// while components change, spawn, despawn and roll back.
+    #[test]
+    fn it_matches_map_snapshots() {
+        let mut map = create_app(false);
+        let mut pooled = create_app(true);
+
+        for _ in 0..60 {
+            map.update();
+            pooled.update();
+
+            assert_eq!(
+                map.world.resource::<RollbackFrameCount>(),
+                pooled.world.resource::<RollbackFrameCount>()
+            );
+            assert_eq!(
+                map.world.resource::<Checksum>().0,
+                pooled.world.resource::<Checksum>().0
+            );
+        }
+    }
+
+    /// This test makes sure discarded snapshots are reused, rather than allocated for every frame.
+    #[test]
+    fn it_reuses_discarded_snapshots() {
+        let mut app = create_app(true);
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        let snapshots = app
+            .world
+            .resource::<GgrsPooledComponentSnapshots<Health, Health>>();
+
+        // a snapshot is only allocated while the pool is empty, so at most one more than retained
+        assert!(snapshots.frames().count() > 0);
+        assert!(snapshots.pooled() + snapshots.frames().count() <= snapshots.depth() + 1);
+    }
+}
+
+mod post_load {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, LoadWorld, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy, Default, Debug, Hash)]
+    struct Position(i32);
+
+    /// Derived from every [`Position`], but not rolled back itself.
+    #[derive(Resource, Default, Debug)]
+    struct PositionIndex {
+        total: i32,
+        rebuilds: usize,
+    }
+
+    fn setup_system(mut commands: Commands) {
+        commands.spawn(Position(0)).add_rollback();
+        commands.spawn(Position(0)).add_rollback();
+    }
+
+    fn move_system(mut query: Query<&mut Position>) {
+        for mut position in query.iter_mut() {
+            position.0 += 1;
+        }
+    }
+
+    fn rebuild_index(
+        mut index: ResMut<PositionIndex>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<&Position>,
+    ) {
+        index.total = query.iter().map(|position| position.0).sum();
+        index.rebuilds += 1;
+
+        // every position has moved once per frame, so the loaded data must match the frame
+        assert_eq!(index.total, 2 * frame.0);
+    }
+
+    /// This test makes sure systems in [`LoadWorldSet::PostLoad`] run once per load, after all
+    /// rolled back data has been applied.
+    #[test]
+    fn it_runs_post_load_systems_after_loading() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .rollback_component_with_copy::<Position>()
+            .init_resource::<PositionIndex>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, move_system)
+            .add_systems(LoadWorld, rebuild_index.in_set(LoadWorldSet::PostLoad));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(app.world.resource::<PositionIndex>().rebuilds > 0);
+    }
+}
+
+mod reflect_strategy {
+    use bevy::{
+        prelude::*,
+        reflect::{DynamicStruct, DynamicTupleStruct},
+    };
+    use bevy_ggrs::{
+        prelude::*, GgrsComponentSnapshot, GgrsComponentSnapshots, GgrsInitSchedule, LoadWorld,
+        ReflectStrategy, RollbackFrameCount, Strategy,
+    };
+
+    use crate::common::{create_app, input_system, synctest_session, TestConfig};
+
+    #[derive(Component, Reflect, Default, Clone, PartialEq, Eq, Debug)]
+    struct Counter(i32);
+
+    #[derive(Reflect, Default, Clone, PartialEq, Eq, Debug)]
+    struct Score {
+        points: i32,
+        streak: i32,
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Counter(0)).add_rollback();
+    }
+
+    fn count(mut query: Query<&mut Counter>) {
+        for mut counter in query.iter_mut() {
+            counter.0 += 1;
+        }
+    }
+
+    /// This test makes sure snapshots which do not match the layout of the target are skipped,
+    /// rather than panicking or partially applying them.
+    #[test]
+    fn it_skips_mismatched_snapshots() {
+        let mut score = Score {
+            points: 3,
+            streak: 1,
+        };
+
+        // a different type
+        ReflectStrategy::<Score>::update(&mut score, &Counter(7).clone_value());
+
+        // a different kind
+        let mut tuple = DynamicTupleStruct::default();
+        tuple.insert(5);
+        ReflectStrategy::<Score>::update(&mut score, &(Box::new(tuple) as Box<dyn Reflect>));
+
+        // a field of a different type, after a field which would have been applied
+        let mut fields = DynamicStruct::default();
+        fields.insert("points", 5);
+        fields.insert("streak", String::from("five"));
+        ReflectStrategy::<Score>::update(&mut score, &(Box::new(fields) as Box<dyn Reflect>));
+
+        assert_eq!(
+            score,
+            Score {
+                points: 3,
+                streak: 1,
+            }
+        );
+
+        // a matching snapshot, missing a field, is still applied
+        let mut fields = DynamicStruct::default();
+        fields.insert("points", 5);
+        ReflectStrategy::<Score>::update(&mut score, &(Box::new(fields) as Box<dyn Reflect>));
+
+        assert_eq!(
+            score,
+            Score {
+                points: 5,
+                streak: 1,
+            }
+        );
+    }
+
+    /// This test makes sure loading a snapshot of a different type leaves the component untouched.
+    #[test]
+    fn it_loads_mismatched_snapshots_without_panicking() {
+        let mut app = create_app();
+
+        app.rollback_component_with_reflect::<Counter>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count)
+            .insert_resource(synctest_session(2));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        app.world.remove_resource::<Session<TestConfig>>();
+
+        let (rollback, counter) = app
+            .world
+            .query::<(&Rollback, &Counter)>()
+            .single(&app.world);
+        let (rollback, counter) = (*rollback, counter.clone());
+
+        let mut snapshots = app
+            .world
+            .resource_mut::<GgrsComponentSnapshots<Counter, Box<dyn Reflect>>>();
+        let frame = snapshots.frames().next().unwrap();
+
+        snapshots.push(
+            frame,
+            GgrsComponentSnapshot::new([(
+                rollback,
+                Box::new(String::from("not a counter")) as Box<dyn Reflect>,
+            )]),
+        );
+
+        app.world.resource_mut::<RollbackFrameCount>().0 = frame;
+        app.world.run_schedule(LoadWorld);
+
+        assert_eq!(app.world.query::<&Counter>().single(&app.world), &counter);
+    }
+}
+
+mod retained_frames {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, BevyGgrsError, GgrsComponentSnapshots, GgrsInitSchedule, LoadWorld,
+        RetainedFrames, RollbackFrameCount, SessionError,
+    };
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    #[derive(Resource, Default)]
+    struct Errors(Vec<BevyGgrsError>);
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+    struct Counter(u32);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Counter(0)).add_rollback();
+    }
+
+    fn count(mut query: Query<&mut Counter>) {
+        for mut counter in query.iter_mut() {
+            counter.0 += 1;
+        }
+    }
+
+    fn record_errors(mut errors: EventReader<SessionError>, mut recorded: ResMut<Errors>) {
+        recorded
+            .0
+            .extend(errors.read().map(|SessionError(error)| error.clone()));
+    }
+
+    /// This test makes sure [`RetainedFrames`] lists exactly the frames held by the snapshot stores.
+    #[test]
+    fn it_lists_retained_frames() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.add_systems(ReadInputs, input_system);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        assert!(app.world.resource::<RetainedFrames>().is_empty());
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let retained = app.world.resource::<RetainedFrames>();
+        let snapshots = app.world.resource::<GgrsComponentSnapshots<Entity>>();
+        let current = app.world.resource::<RollbackFrameCount>().0;
+
+        assert!(!retained.is_empty());
+        assert_eq!(
+            retained.iter().collect::<Vec<_>>(),
+            snapshots.frames().rev().collect::<Vec<_>>()
+        );
+
+        let range = retained.range().unwrap();
+
+        assert!(*range.end() <= current);
+        assert_eq!(retained.len(), range.clone().count());
+
+        for frame in range {
+            assert!(retained.contains(frame));
+        }
+
+        Ok(())
+    }
+
+    /// This test makes sure a rollback spanning further than the retained snapshots is reported,
+    /// rather than restoring another frame.
+    #[test]
+    fn it_reports_rollbacks_beyond_retention() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.init_resource::<Errors>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(Update, record_errors);
+
+        // only the two most recent frames are retained, while every rollback spans four
+        app.world
+            .resource_mut::<GgrsComponentSnapshots<Entity>>()
+            .set_depth(2);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(4)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let errors = &app.world.resource::<Errors>().0;
+
+        assert!(errors
+            .iter()
+            .any(|error| matches!(error, BevyGgrsError::SnapshotMissing { .. })));
+
+        Ok(())
+    }
+
+    /// This test makes sure loading a frame which is not retained raises an error from the load
+    /// systems, leaving the world untouched rather than panicking.
+    #[test]
+    fn it_reports_loading_frames_which_are_not_retained() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut app = create_app();
+
+        app.rollback_component_with_copy::<Counter>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        app.world.remove_resource::<Session<TestConfig>>();
+        app.world.resource_mut::<Events<SessionError>>().clear();
+
+        let counter = *app.world.query::<&Counter>().single(&app.world);
+        let frames: Vec<_> = app.world.resource::<RetainedFrames>().iter().collect();
+        let missing = app.world.resource::<RollbackFrameCount>().0 + 100;
+
+        app.world.resource_mut::<RollbackFrameCount>().0 = missing;
+        app.world.run_schedule(LoadWorld);
+
+        let errors: Vec<_> = app
+            .world
+            .resource_mut::<Events<SessionError>>()
+            .drain()
+            .collect();
+
+        assert!(!errors.is_empty());
+        assert!(
+            errors
+                .iter()
+                .all(|SessionError(error)| *error
+                    == BevyGgrsError::SnapshotMissing { frame: missing })
+        );
+
+        // nothing was rolled back or discarded
+        assert_eq!(*app.world.query::<&Counter>().single(&app.world), counter);
+        assert_eq!(
+            app.world
+                .resource::<RetainedFrames>()
+                .iter()
+                .collect::<Vec<_>>(),
+            frames
+        );
+
+        Ok(())
+    }
+
+    /// This test makes sure nothing is advanced once any snapshot storage misses the frame to roll
+    /// back to, rather than simulating on top of the current, un-rolled-back world.
+    #[test]
+    fn it_stops_advancing_once_a_snapshot_is_missing() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.rollback_component_with_copy::<Counter>()
+            .init_resource::<Errors>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count)
+            .add_systems(Update, record_errors);
+
+        // only the component snapshots are too shallow for the rollbacks of the session
+        app.world
+            .resource_mut::<GgrsComponentSnapshots<Counter>>()
+            .set_depth(2);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(4)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        let mut missed = None;
+
+        for _ in 0..20 {
+            app.update();
+
+            let frame = app.world.resource::<RollbackFrameCount>().0;
+            let counter = *app.world.query::<&Counter>().single(&app.world);
+
+            match missed {
+                // neither the frame nor the simulation moved on since
+                Some(state) => assert_eq!((frame, counter), state),
+                None if !app.world.resource::<Errors>().0.is_empty() => {
+                    missed = Some((frame, counter))
+                }
+                None => {}
+            }
+        }
+
+        let (frame, _) = missed.expect("the rollback should have missed a snapshot");
+
+        assert!(frame <= 5);
+        assert!(app
+            .world
+            .resource::<Errors>()
+            .0
+            .iter()
+            .any(|error| matches!(error, BevyGgrsError::SnapshotMissing { .. })));
+
+        Ok(())
+    }
+}
+
+mod rollback_key {
+    use bevy::prelude::*;
+    use bevy_ggrs::{
+        prelude::*, ComponentSnapshotPlugin, CopyStrategy, GgrsInitSchedule, RollbackFrameCount,
+        RollbackKey,
+    };
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    struct NetworkId(u32);
+
+    impl RollbackKey for NetworkId {}
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+    struct Counter(i32);
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn((NetworkId(7), Counter(0)));
+    }
+
+    fn count(mut query: Query<&mut Counter>) {
+        for mut counter in query.iter_mut() {
+            counter.0 += 1;
+        }
+    }
+
+    /// This test makes sure components can be rolled back keyed by a custom [`RollbackKey`], on
+    /// entities without a [`Rollback`] component.
+    #[test]
+    fn it_rolls_back_components_keyed_by_a_custom_key() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        app.add_plugins(ComponentSnapshotPlugin::<CopyStrategy<Counter>, NetworkId>::default())
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .add_systems(GgrsSchedule, count);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .with_check_distance(2)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+        let counter = *app
+            .world
+            .query_filtered::<&Counter, Without<Rollback>>()
+            .single(&app.world);
+
+        // every rollback re-simulates frames, which would overcount without restoring the snapshot
+        assert!(frame > 2);
+        assert_eq!(counter, Counter(frame));
+
+        Ok(())
+    }
+}
+
+mod snapshot_interval {
+    use bevy::{prelude::*, utils::HashMap};
+    use bevy_ggrs::{prelude::*, LocalInputs};
+
+    use crate::common::{create_app, synctest_session, TestConfig};
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+    struct Counter(u32);
+
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct Frames(u32);
+
+    fn input_system(mut commands: Commands) {
+        commands.insert_resource(LocalInputs::<TestConfig>(HashMap::from([(0, 1)])));
+    }
+
+    fn setup_system(mut commands: Commands) {
+        commands.spawn(Counter::default()).add_rollback();
+    }
+
+    fn count_frames(mut frames: ResMut<Frames>) {
+        frames.0 += 1;
+    }
+
+    fn increment(inputs: Res<PlayerInputs<TestConfig>>, mut counters: Query<&mut Counter>) {
+        for mut counter in counters.iter_mut() {
+            counter.0 += inputs[0].0 as u32;
+        }
+    }
+
+    /// This test makes sure rolling back to frames without a snapshot fast-forwards from the
+    /// nearest prior snapshot, producing the same state as if every frame was saved.
+    #[test]
+    fn it_fast_forwards_between_snapshots() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .set_snapshot_interval(4)
+            .init_resource::<Frames>()
+            .rollback_resource_with_copy::<Frames>()
+            .rollback_component_with_copy::<Counter>()
+            .checksum_component_with_hash::<Counter>()
+            .add_systems(Startup, setup_system)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, (count_frames, increment).chain());
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let frames = app.world.resource::<Frames>().0;
+        assert!(frames > 10, "Not enough frames advanced");
+
+        let counter = *app.world.query::<&Counter>().single(&app.world);
+        assert_eq!(counter.0, frames, "Fast-forwarded state did not match");
+    }
+}
+
+mod snapshot_memory {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, GgrsInitSchedule, SnapshotMemoryPlugin, SnapshotMemoryUsage};
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    #[derive(Component, Clone, Copy)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy)]
+    struct Position(f32);
+
+    fn store(position: &Position) -> i16 {
+        (position.0 * 16.) as i16
+    }
+
+    fn load(stored: &i16) -> Position {
+        Position(*stored as f32 / 16.)
+    }
+
+    fn spawn(mut commands: Commands) {
+        for _ in 0..3 {
+            commands.spawn((Health(100), Position(0.))).add_rollback();
+        }
+    }
+
+    /// This test makes sure the memory usage of every snapshot storage is estimated after saving,
+    /// identified by both the snapshotted type and the type it is stored as.
+    #[test]
+    fn it_records_memory_usage_after_saving() {
+        let mut app = create_app();
+
+        app.add_plugins(SnapshotMemoryPlugin)
+            .rollback_component_with_copy::<Health>()
+            .rollback_component_with_transform::<Position, i16>(store, load)
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsInitSchedule, spawn)
+            .insert_resource(synctest_session(2));
+
+        assert_eq!(app.world.resource::<SnapshotMemoryUsage>().total(), 0);
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let usage = app.world.resource::<SnapshotMemoryUsage>();
+
+        assert!(usage.get::<Health, Health>().unwrap() > 0);
+        assert!(usage.get::<Position, i16>().unwrap() > 0);
+        assert!(usage.get::<Entity, Entity>().unwrap() > 0);
+        assert_eq!(usage.get::<Position, Position>(), None);
+
+        assert_eq!(
+            usage.total(),
+            usage.iter().map(|(_, _, bytes)| bytes).sum::<usize>()
+        );
+    }
+}
+
+mod snapshot_retention {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{prelude::*, GgrsComponentSnapshots, GgrsResourceSnapshots, SnapshotRetention};
+
+    use crate::common::{input_system, synctest_session, TestConfig};
+
+    #[derive(Resource, Default, Clone, Copy)]
+    struct FrameCounter(u32);
+
+    fn increase_counter(mut counter: ResMut<FrameCounter>) {
+        counter.0 += 1;
+    }
+
+    fn create_app(fps: usize, seconds: f32) -> App {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / fps as f64,
+            )))
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .set_rollback_schedule_fps(fps)
+            .set_snapshot_retention(seconds)
+            .init_resource::<FrameCounter>()
+            .rollback_resource_with_copy::<FrameCounter>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, increase_counter)
+            .insert_resource(synctest_session(2));
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        app
+    }
+
+    fn entity_depth(app: &App) -> usize {
+        app.world
+            .resource::<GgrsComponentSnapshots<Entity>>()
+            .depth()
+    }
+
+    fn resource_depth(app: &App) -> usize {
+        app.world
+            .resource::<GgrsResourceSnapshots<FrameCounter>>()
+            .depth()
+    }
+
+    /// This test makes sure a retention of one second retains at least a second worth of frames in
+    /// every snapshot storage.
+    #[test]
+    fn it_retains_a_second_of_frames() {
+        let app = create_app(60, 1.0);
+
+        assert_eq!(app.world.resource::<SnapshotRetention>().frames(), 60);
+        assert!(entity_depth(&app) >= 60);
+        assert!(resource_depth(&app) >= 60);
+    }
+
+    /// This test makes sure the retained frames follow changes to the frame rate.
+    #[test]
+    fn it_follows_the_frame_rate() {
+        let mut app = create_app(60, 2.0);
+
+        assert_eq!(entity_depth(&app), 120);
+
+        app.set_rollback_schedule_fps(30);
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_eq!(app.world.resource::<SnapshotRetention>().frames(), 60);
+        assert_eq!(entity_depth(&app), 60);
+        assert_eq!(resource_depth(&app), 60);
+    }
+
+    /// This test makes sure a short retention never drops frames which may still be rolled back to.
+    #[test]
+    fn it_retains_at_least_the_prediction_window() {
+        let app = create_app(60, 0.01);
+
+        let max_prediction = 8;
+
+        assert!(app.world.resource::<SnapshotRetention>().frames() >= max_prediction);
+        assert!(entity_depth(&app) >= max_prediction);
+    }
+}
+
+mod state_rollback {
+    use bevy::prelude::*;
+    use bevy_ggrs::{prelude::*, RollbackFrameCount};
+
+    use crate::common::{create_app, input_system, synctest_session};
+
+    #[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Phase {
+        #[default]
+        Countdown,
+        Fight,
+    }
+
+    /// The [`Phase`] seen after every advanced frame, overwritten when a frame is re-simulated.
+    #[derive(Resource, Default)]
+    struct Phases(Vec<(i32, Phase)>);
+
+    /// Toggles the [`Phase`] every third frame.
+    fn toggle_phase(
+        frame: Res<RollbackFrameCount>,
+        phase: Res<State<Phase>>,
+        mut next: ResMut<NextState<Phase>>,
+    ) {
+        if frame.0 % 3 == 0 {
+            next.set(match phase.get() {
+                Phase::Countdown => Phase::Fight,
+                Phase::Fight => Phase::Countdown,
+            });
+        }
+    }
+
+    fn record_phase(
+        frame: Res<RollbackFrameCount>,
+        phase: Res<State<Phase>>,
+        mut phases: ResMut<Phases>,
+    ) {
+        phases.0.retain(|&(recorded, _)| recorded < frame.0);
+        phases.0.push((frame.0, *phase.get()));
+    }
+
+    fn expected_phase(frame: i32) -> Phase {
+        if (frame / 3) % 2 == 0 {
+            Phase::Countdown
+        } else {
+            Phase::Fight
+        }
+    }
+
+    /// This test makes sure state transitions applied within the [`GgrsSchedule`] are rolled back, so
+    /// re-simulating a frame transitions from the same state again.
+    #[test]
+    fn it_rolls_back_state_transitions() {
+        let mut app = create_app();
+
+        app.insert_resource(synctest_session(2))
+            .init_state::<Phase>()
+            .rollback_state::<Phase>()
+            .init_resource::<Phases>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(
+                GgrsSchedule,
+                (toggle_phase, apply_state_transition::<Phase>, record_phase).chain(),
+            );
+
+        for _ in 0..30 {
+            app.update();
+        }
+
+        let phases = &app.world.resource::<Phases>().0;
+
+        assert!(phases.len() > 20);
+
+        for &(frame, phase) in phases {
+            assert_eq!(phase, expected_phase(frame), "frame {frame}");
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+        assert_eq!(
+            *app.world.resource::<State<Phase>>().get(),
+            expected_phase(frame)
+        );
+    }
+}
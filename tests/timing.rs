@@ -0,0 +1,898 @@
+mod common;
+
+mod fixed {
+    use bevy_ggrs::fixed::{Fixed, FixedVec2};
+
+    /// Largest error accepted for approximated functions.
+    const TOLERANCE: Fixed = Fixed::from_bits(1 << 12);
+
+    fn assert_close(actual: Fixed, expected: Fixed) {
+        assert!(
+            (actual - expected).abs() <= TOLERANCE,
+            "{actual} is not close to {expected}"
+        );
+    }
+
+    #[test]
+    fn it_performs_exact_arithmetic() {
+        let a = Fixed::from_int(6);
+        let b = Fixed::from_ratio(3, 2);
+
+        assert_eq!(a + b, Fixed::from_ratio(15, 2));
+        assert_eq!(a - b, Fixed::from_ratio(9, 2));
+        assert_eq!(a * b, Fixed::from_int(9));
+        assert_eq!(a / b, Fixed::from_int(4));
+        assert_eq!(-b, Fixed::from_ratio(-3, 2));
+
+        assert_eq!(b.floor(), Fixed::ONE);
+        assert_eq!(b.ceil(), Fixed::from_int(2));
+        assert_eq!((-b).floor(), Fixed::from_int(-2));
+        assert_eq!((-b).round(), Fixed::from_int(-1));
+        assert_eq!(b.fract(), Fixed::HALF);
+    }
+
+    #[test]
+    fn it_computes_square_roots() {
+        assert_eq!(Fixed::from_int(4).sqrt(), Fixed::from_int(2));
+        assert_eq!(Fixed::from_int(144).sqrt(), Fixed::from_int(12));
+        assert_eq!(Fixed::from_ratio(1, 4).sqrt(), Fixed::HALF);
+        assert_eq!(Fixed::from_int(-1).sqrt(), Fixed::ZERO);
+
+        assert_eq!(
+            FixedVec2::new(Fixed::from_int(3), Fixed::from_int(4)).length(),
+            Fixed::from_int(5)
+        );
+    }
+
+    #[test]
+    fn it_computes_trigonometry() {
+        assert_eq!(Fixed::ZERO.sin(), Fixed::ZERO);
+        assert_close(Fixed::FRAC_PI_2.sin(), Fixed::ONE);
+        assert_close(Fixed::PI.sin(), Fixed::ZERO);
+        assert_close((-Fixed::FRAC_PI_2).sin(), -Fixed::ONE);
+        assert_close(Fixed::ZERO.cos(), Fixed::ONE);
+        assert_close(Fixed::PI.cos(), -Fixed::ONE);
+
+        // Angles outside of a single turn are reduced first
+        assert_close(
+            (Fixed::TAU * Fixed::from_int(3) + Fixed::FRAC_PI_2).sin(),
+            Fixed::ONE,
+        );
+
+        // Compare against floats, which are accurate enough for this purpose
+        for step in -64..=64 {
+            let angle = Fixed::from_ratio(step, 8);
+            let expected = Fixed::from_f32(angle.to_f64().sin() as f32);
+
+            assert!(
+                (angle.sin() - expected).abs() < Fixed::from_ratio(1, 100_000),
+                "sin({angle}) was {}, expected {expected}",
+                angle.sin()
+            );
+        }
+    }
+}
+
+mod frame_budget {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{prelude::*, FixedTimestepData, RollbackFrameCount};
+    use std::thread;
+
+    use crate::common::{self, input_system, TestConfig};
+
+    const FRAME: Duration = Duration::from_millis(2);
+
+    /// Artificially slows down advancing every frame.
+    fn slow_system() {
+        thread::sleep(FRAME);
+    }
+
+    fn create_app(budget: Option<Duration>) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            10. / 60.,
+        )))
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsSchedule, slow_system)
+        .insert_resource(Session::SyncTest(
+            SessionBuilder::<TestConfig>::new()
+                .with_num_players(1)
+                .add_player(PlayerType::Local, 0)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap(),
+        ));
+
+        app.world
+            .resource_mut::<FixedTimestepData>()
+            .set_frame_budget(budget);
+
+        app
+    }
+
+    fn frame(app: &App) -> i32 {
+        app.world.resource::<RollbackFrameCount>().0
+    }
+
+    /// This test makes sure no further frames are advanced once an update exceeds its frame budget,
+    /// and that the time left over is dropped.
+    #[test]
+    fn it_stops_advancing_once_over_budget() {
+        let mut app = create_app(Some(FRAME / 2));
+
+        // the first update has no elapsed time
+        app.update();
+
+        for _ in 0..5 {
+            let before = frame(&app);
+            app.update();
+
+            // every frame exceeds the budget, but at least one is always advanced
+            assert_eq!(frame(&app) - before, 1);
+            assert_eq!(
+                app.world.resource::<FixedTimestepData>().accumulator(),
+                Duration::from_secs_f64(1. / 60.)
+            );
+        }
+    }
+
+    /// This test makes sure the slow schedule alone does not limit the frames advanced per update.
+    #[test]
+    fn it_advances_every_due_frame_without_budget() {
+        let mut app = create_app(None);
+
+        app.update();
+
+        let before = frame(&app);
+        app.update();
+
+        assert!(frame(&app) - before >= 9);
+    }
+}
+
+mod frame_pacing {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{prelude::*, InterpolationAlpha, RollbackFrameCount};
+
+    use crate::common::{input_system, TestConfig};
+
+    const FPS: usize = 60;
+
+    fn create_app(smoothing: Option<f64>) -> App {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .set_rollback_schedule_fps(FPS)
+            .add_systems(ReadInputs, input_system);
+
+        if let Some(factor) = smoothing {
+            app.set_frame_pacing_smoothing(factor);
+        }
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .add_player(PlayerType::Local, 0)
+            .unwrap()
+            .start_synctest_session()
+            .unwrap();
+
+        app.insert_resource(Session::SyncTest(session));
+
+        app
+    }
+
+    /// Runs the app with a frame time jittering around the rollback frame period, returning the
+    /// total variation of the [`InterpolationAlpha`] over time.
+    fn run_jittery(app: &mut App, updates: usize) -> f32 {
+        let period = 1.0 / FPS as f64;
+        let mut previous = None;
+        let mut variation = 0.;
+
+        for update in 0..updates {
+            let jitter = if update % 2 == 0 { 0.6 } else { 1.4 };
+
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                period * jitter,
+            )));
+            app.update();
+
+            let alpha = **app.world.resource::<InterpolationAlpha>();
+
+            assert!((0.0..=1.0).contains(&alpha));
+
+            if let Some(previous) = previous {
+                variation += (alpha - previous).abs();
+            }
+
+            previous = Some(alpha);
+        }
+
+        variation
+    }
+
+    /// This test makes sure frame pacing smoothing only affects the [`InterpolationAlpha`], not the
+    /// amount of frames advanced over time.
+    #[test]
+    fn it_advances_identically_with_smoothing() {
+        let mut unsmoothed = create_app(None);
+        let mut smoothed = create_app(Some(0.1));
+
+        run_jittery(&mut unsmoothed, 120);
+        run_jittery(&mut smoothed, 120);
+
+        assert_eq!(
+            *unsmoothed.world.resource::<RollbackFrameCount>(),
+            *smoothed.world.resource::<RollbackFrameCount>()
+        );
+    }
+
+    /// This test makes sure frame pacing smoothing reduces how much the [`InterpolationAlpha`]
+    /// oscillates with a jittery frame time.
+    #[test]
+    fn it_smooths_the_interpolation_alpha() {
+        let mut unsmoothed = create_app(None);
+        let mut smoothed = create_app(Some(0.1));
+
+        let unsmoothed_variation = run_jittery(&mut unsmoothed, 120);
+        let smoothed_variation = run_jittery(&mut smoothed, 120);
+
+        assert!(smoothed_variation < unsmoothed_variation);
+    }
+}
+
+mod frame_rate {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{
+        prelude::*, set_fps, FixedTimestepData, LocalInputs, LocalPlayers, RollbackFrameRate,
+        SessionConfig,
+    };
+    use ggrs::{P2PSession, UdpNonBlockingSocket};
+    use serial_test::serial;
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    use crate::common::TestConfig;
+
+    const FPS: usize = 30;
+
+    const PORTS: [u16; 2] = [8091, 8092];
+
+    #[derive(Resource, Clone, Copy, Default, Debug)]
+    struct Frames(u32);
+
+    fn input_system(mut commands: Commands, local_players: Res<LocalPlayers>) {
+        let inputs = local_players.0.iter().map(|&handle| (handle, 0)).collect();
+        commands.insert_resource(LocalInputs::<TestConfig>(inputs));
+    }
+
+    fn count_frames(mut frames: ResMut<Frames>) {
+        frames.0 += 1;
+    }
+
+    /// This test makes sure a [`Session`] configured from the [`RollbackFrameRate`] advances in
+    /// lockstep with it, at exactly one frame per rollback frame period.
+    #[test]
+    fn it_advances_at_the_rollback_frame_rate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / FPS as f64,
+            )))
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .set_rollback_schedule_fps(FPS)
+            .init_resource::<Frames>()
+            .rollback_resource_with_copy::<Frames>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, count_frames);
+
+        let builder = app
+            .world
+            .resource::<RollbackFrameRate>()
+            .configure(SessionBuilder::<TestConfig>::new())?;
+
+        let session = builder
+            .with_num_players(1)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        assert_eq!(**app.world.resource::<RollbackFrameRate>(), FPS);
+
+        let updates = 20;
+        for _ in 0..updates {
+            app.update();
+        }
+
+        // Accumulated time may lag behind by a frame due to rounding
+        let frames = app.world.resource::<Frames>().0;
+        assert!(
+            (updates - 2..=updates).contains(&frames),
+            "Advanced {frames} frames in {updates} updates"
+        );
+
+        Ok(())
+    }
+
+    /// This test makes sure the [`RollbackFrameRate`] and the frame rate a [`P2PSession`] was built
+    /// with stay in lockstep when changed using [`set_fps`], and that a divergence is tracked.
+    #[test]
+    #[serial]
+    fn it_keeps_the_frame_rate_in_lockstep_with_a_p2p_session(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let config = SessionConfig {
+            fps: FPS,
+            ..default()
+        };
+
+        let mut apps = [
+            create_p2p_app(config, FPS, start_p2p_session(config, 0)?),
+            create_p2p_app(config, FPS, start_p2p_session(config, 1)?),
+        ];
+
+        for _ in 0..50 {
+            for app in &mut apps {
+                app.update();
+            }
+        }
+
+        for app in &apps {
+            assert!(app.world.resource::<Frames>().0 > 0);
+            assert_eq!(**app.world.resource::<RollbackFrameRate>(), FPS);
+            assert_eq!(
+                app.world
+                    .resource::<FixedTimestepData>()
+                    .session_frame_rate(),
+                Some(FPS)
+            );
+        }
+
+        for app in &mut apps {
+            set_fps(&mut app.world, FPS * 2);
+            app.update();
+        }
+
+        // the session config follows, but GGRS keeps the frame rate the session was built with
+        for app in &apps {
+            assert_eq!(**app.world.resource::<RollbackFrameRate>(), FPS * 2);
+            assert_eq!(app.world.resource::<SessionConfig>().fps, FPS * 2);
+            assert_eq!(
+                app.world
+                    .resource::<FixedTimestepData>()
+                    .session_frame_rate(),
+                Some(FPS)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// This test makes sure a [`P2PSession`] built with a frame rate differing from the
+    /// [`RollbackFrameRate`] is detected as soon as it starts.
+    #[test]
+    #[serial]
+    fn it_detects_a_p2p_session_built_with_another_frame_rate(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let config = SessionConfig {
+            fps: FPS * 2,
+            ..default()
+        };
+
+        let mut app = create_p2p_app(config, FPS, start_p2p_session(config, 0)?);
+
+        app.update();
+
+        assert_eq!(**app.world.resource::<RollbackFrameRate>(), FPS);
+        assert_eq!(
+            app.world
+                .resource::<FixedTimestepData>()
+                .session_frame_rate(),
+            Some(FPS * 2)
+        );
+
+        Ok(())
+    }
+
+    fn create_p2p_app(config: SessionConfig, fps: usize, session: P2PSession<TestConfig>) -> App {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / fps as f64,
+            )))
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .set_rollback_schedule_fps(fps)
+            .init_resource::<Frames>()
+            .rollback_resource_with_copy::<Frames>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, count_frames)
+            .insert_resource(config)
+            .insert_resource(Session::P2P(session));
+
+        app
+    }
+
+    fn start_p2p_session(
+        config: SessionConfig,
+        handle: usize,
+    ) -> Result<P2PSession<TestConfig>, Box<dyn std::error::Error>> {
+        let address = |handle: usize| SocketAddr::from((Ipv4Addr::LOCALHOST, PORTS[handle]));
+
+        let session = config
+            .builder::<TestConfig>([
+                (PlayerType::Local, handle),
+                (PlayerType::Remote(address(1 - handle)), 1 - handle),
+            ])?
+            .start_p2p_session(UdpNonBlockingSocket::bind_to_port(PORTS[handle])?)?;
+
+        Ok(session)
+    }
+}
+
+mod frame_stepping {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{advance_frame, prelude::*, FixedTimestepData, RollbackFrameCount};
+
+    use crate::common::{self, input_system, TestConfig};
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 600.0,
+        )))
+        .add_systems(ReadInputs, input_system)
+        .insert_resource(Session::SyncTest(
+            SessionBuilder::<TestConfig>::new()
+                .with_num_players(1)
+                .add_player(PlayerType::Local, 0)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap(),
+        ));
+
+        app
+    }
+
+    /// This test makes sure [`advance_frame`] advances exactly one frame and clears the accumulator.
+    #[test]
+    fn it_advances_exactly_one_frame() {
+        let mut app = create_app();
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+        assert!(app.world.resource::<FixedTimestepData>().accumulator() > Duration::ZERO);
+
+        assert!(advance_frame::<TestConfig>(&mut app.world));
+
+        assert_eq!(app.world.resource::<RollbackFrameCount>().0, frame + 1);
+        assert_eq!(
+            app.world.resource::<FixedTimestepData>().accumulator(),
+            Duration::ZERO
+        );
+    }
+
+    /// This test makes sure writing the accumulator controls when frames are advanced.
+    #[test]
+    fn it_advances_frames_from_the_accumulator() {
+        let mut app = create_app();
+
+        app.update();
+
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+
+        app.world
+            .resource_mut::<FixedTimestepData>()
+            .set_accumulator(Duration::from_secs_f64(2.5 / 60.0));
+        app.update();
+
+        assert_eq!(app.world.resource::<RollbackFrameCount>().0, frame + 2);
+
+        app.world
+            .resource_mut::<FixedTimestepData>()
+            .clear_accumulator();
+        app.update();
+
+        assert_eq!(app.world.resource::<RollbackFrameCount>().0, frame + 2);
+    }
+}
+
+mod interpolation {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{
+        prelude::*, ComponentInterpolationPlugin, GgrsInitSchedule, Interpolated,
+        InterpolationAlpha, Lerp,
+    };
+
+    use crate::common::{create_app, input_system, TestConfig};
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq)]
+    struct Zoom(f32);
+
+    impl Lerp for Zoom {
+        fn lerp(&self, other: &Self, t: f32) -> Self {
+            Zoom(Lerp::lerp(&self.0, &other.0, t))
+        }
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn(Zoom(0.)).add_rollback();
+    }
+
+    fn zoom_in(mut query: Query<&mut Zoom>) {
+        for mut zoom in query.iter_mut() {
+            zoom.0 += 1.;
+        }
+    }
+
+    /// This test makes sure [`Interpolated`] values lie between the previous and the current frame,
+    /// without affecting the simulated [`Component`].
+    #[test]
+    fn it_interpolates_between_frames() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = create_app();
+
+        // rendering twice per rollback frame
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 120.0,
+        )))
+        .rollback_component_with_copy::<Zoom>()
+        .add_plugins(ComponentInterpolationPlugin::<Zoom>::default())
+        .add_systems(ReadInputs, input_system)
+        .add_systems(GgrsInitSchedule, spawn)
+        .add_systems(GgrsSchedule, zoom_in);
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(1)
+            .add_player(PlayerType::Local, 0)?
+            .start_synctest_session()?;
+
+        app.insert_resource(Session::SyncTest(session));
+
+        for _ in 0..20 {
+            app.update();
+
+            let alpha = **app.world.resource::<InterpolationAlpha>();
+            let (zoom, interpolated) = app
+                .world
+                .query::<(&Zoom, Option<&Interpolated<Zoom>>)>()
+                .single(&app.world);
+
+            let Some(interpolated) = interpolated else {
+                continue;
+            };
+
+            // the simulation is never altered, and visuals trail it by up to a frame
+            assert!(interpolated.0 .0 <= zoom.0);
+            assert!(interpolated.0 .0 >= zoom.0 - 1. - f32::EPSILON);
+            assert!((0.0..=1.0).contains(&alpha));
+        }
+
+        let zoom = *app.world.query::<&Zoom>().single(&app.world);
+
+        assert!(zoom.0 > 0.);
+
+        Ok(())
+    }
+}
+
+mod poll_cadence {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{prelude::*, NetworkPollCadence};
+    use ggrs::{Message, NonBlockingSocket};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::common::TestConfig;
+
+    /// A socket which never receives anything, counting how often it is polled.
+    struct CountingSocket(Arc<AtomicUsize>);
+
+    impl NonBlockingSocket<usize> for CountingSocket {
+        fn send_to(&mut self, _msg: &Message, _addr: &usize) {}
+
+        fn receive_all_messages(&mut self) -> Vec<(usize, Message)> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Vec::new()
+        }
+    }
+
+    /// Counts polls over 64 updates at 240 fps, while the session waits to synchronize.
+    fn count_polls(
+        cadence: Option<NetworkPollCadence>,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / 240.0,
+            )))
+            .add_plugins(GgrsPlugin::<TestConfig>::default())
+            .set_rollback_schedule_fps(30);
+
+        if let Some(cadence) = cadence {
+            app.set_network_poll_cadence(cadence);
+        }
+
+        let polls = Arc::new(AtomicUsize::new(0));
+
+        let session = SessionBuilder::<TestConfig>::new()
+            .with_num_players(2)
+            .add_player(PlayerType::Local, 0)?
+            .add_player(PlayerType::Remote(1), 1)?
+            .start_p2p_session(CountingSocket(polls.clone()))?;
+
+        app.insert_resource(Session::P2P(session));
+
+        let before = polls.load(Ordering::Relaxed);
+
+        for _ in 0..64 {
+            app.update();
+        }
+
+        Ok(polls.load(Ordering::Relaxed) - before)
+    }
+
+    /// This test makes sure remote clients are polled every update by default.
+    #[test]
+    fn it_polls_every_update_by_default() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(count_polls(None)?, 64);
+
+        Ok(())
+    }
+
+    /// This test makes sure polls are skipped within the interval, unless a frame is due.
+    #[test]
+    fn it_polls_at_the_configured_cadence() -> Result<(), Box<dyn std::error::Error>> {
+        // every 3rd update at 240 fps
+        let polls = count_polls(Some(NetworkPollCadence {
+            interval: Duration::from_secs_f64(2.5 / 240.0),
+        }))?;
+
+        assert!(polls >= 64 / 3, "{polls}");
+        assert!(polls < 64 / 2, "{polls}");
+
+        // only when a frame of the 30 fps rollback schedule is due
+        let polls = count_polls(Some(NetworkPollCadence {
+            interval: Duration::from_secs(1),
+        }))?;
+
+        assert!(polls > 0, "{polls}");
+        assert!(polls <= 64 / 8 + 1, "{polls}");
+
+        Ok(())
+    }
+}
+
+mod rollback_timings {
+    use bevy::{prelude::*, utils::Duration};
+    use bevy_ggrs::{prelude::*, RollbackTimings, ScheduleTiming, SessionRequest, SessionRequests};
+    use std::thread;
+
+    use crate::common::{self, input_system, synctest_session};
+
+    const FRAME: Duration = Duration::from_millis(1);
+
+    /// How many requests of every kind the session issued.
+    #[derive(Resource, Default)]
+    struct RequestCounts {
+        save: u64,
+        load: u64,
+        advance: u64,
+    }
+
+    /// Artificially slows down advancing every frame.
+    fn slow_system() {
+        thread::sleep(FRAME);
+    }
+
+    fn count_requests(mut events: EventReader<SessionRequests>, mut counts: ResMut<RequestCounts>) {
+        for SessionRequests(requests) in events.read() {
+            for request in requests {
+                match request {
+                    SessionRequest::SaveGameState { .. } => counts.save += 1,
+                    SessionRequest::LoadGameState { .. } => counts.load += 1,
+                    SessionRequest::AdvanceFrame { .. } => counts.advance += 1,
+                }
+            }
+        }
+    }
+
+    fn create_app() -> App {
+        let mut app = common::create_app();
+
+        app.add_event::<SessionRequests>()
+            .init_resource::<RequestCounts>()
+            .add_systems(ReadInputs, input_system)
+            .add_systems(GgrsSchedule, slow_system)
+            .add_systems(PostUpdate, count_requests)
+            .insert_resource(synctest_session(2));
+
+        app
+    }
+
+    /// This test makes sure every schedule run for a request of the session is timed, including
+    /// the loads and advances of a rollback.
+    #[test]
+    fn it_times_every_schedule_run() {
+        let mut app = create_app();
+        app.init_resource::<RollbackTimings>();
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let timings = *app.world.resource::<RollbackTimings>();
+        let counts = app.world.resource::<RequestCounts>();
+
+        // the synctest rolls back on every frame past the check distance
+        assert!(counts.load > 0);
+        assert_eq!(timings.save.count, counts.save);
+        assert_eq!(timings.load.count, counts.load);
+        assert_eq!(timings.advance.count, counts.advance);
+
+        // resimulating advances more frames than were loaded
+        assert!(timings.advance.count > timings.load.count);
+        assert!(timings.advance.last >= FRAME);
+        assert!(timings.advance.average >= FRAME);
+    }
+
+    /// This test makes sure nothing is timed unless [`RollbackTimings`] is present.
+    #[test]
+    fn it_only_times_when_requested() {
+        let mut app = create_app();
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert!(app.world.resource::<RequestCounts>().load > 0);
+        assert!(!app.world.contains_resource::<RollbackTimings>());
+    }
+
+    /// This test makes sure the average starts at the first run, then moves towards later runs.
+    #[test]
+    fn it_averages_runs() {
+        let mut timing = ScheduleTiming::default();
+
+        timing.record(Duration::from_millis(10));
+        assert_eq!(timing.average, Duration::from_millis(10));
+
+        timing.record(Duration::from_millis(20));
+        assert_eq!(timing.last, Duration::from_millis(20));
+        assert_eq!(timing.count, 2);
+        assert!(timing.average > Duration::from_millis(10));
+        assert!(timing.average < Duration::from_millis(20));
+    }
+}
+
+mod simulation_pacing {
+    use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+    use bevy_ggrs::{prelude::*, InterpolationAlpha, RollbackFrameCount, SimulationPacing};
+
+    use crate::common::{self, input_system, TestConfig};
+
+    fn create_app(update_secs: f64, pacing: SimulationPacing) -> App {
+        let mut app = common::create_app();
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            update_secs,
+        )))
+        .set_simulation_pacing(pacing)
+        .add_systems(ReadInputs, input_system)
+        .insert_resource(Session::SyncTest(
+            SessionBuilder::<TestConfig>::new()
+                .with_num_players(1)
+                .add_player(PlayerType::Local, 0)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap(),
+        ));
+
+        app
+    }
+
+    fn frame(app: &App) -> i32 {
+        app.world.resource::<RollbackFrameCount>().0
+    }
+
+    /// This test makes sure no more than the maximum amount of catch-up frames are advanced in a
+    /// single update, and that the time left over is dropped.
+    #[test]
+    fn it_limits_catchup_frames() {
+        let mut app = create_app(
+            10. / 60.,
+            SimulationPacing {
+                max_catchup_frames: Some(4),
+                ..default()
+            },
+        );
+
+        // the first update has no elapsed time
+        app.update();
+
+        for _ in 0..5 {
+            let before = frame(&app);
+            app.update();
+            assert_eq!(frame(&app) - before, 4);
+        }
+    }
+
+    /// This test makes sure capping the catch-up frames keeps the smoothed [`InterpolationAlpha`]
+    /// within a single frame.
+    #[test]
+    fn it_limits_catchup_frames_with_smoothing() {
+        let mut app = create_app(
+            10. / 60.,
+            SimulationPacing {
+                max_catchup_frames: Some(4),
+                ..default()
+            },
+        );
+        app.set_frame_pacing_smoothing(0.1);
+
+        app.update();
+
+        for _ in 0..5 {
+            let before = frame(&app);
+            app.update();
+            assert_eq!(frame(&app) - before, 4);
+
+            let alpha = **app.world.resource::<InterpolationAlpha>();
+            assert!((0.0..=1.0).contains(&alpha));
+        }
+    }
+
+    /// This test makes sure the simulation is paused while backgrounded, and resumes without
+    /// catching up afterwards.
+    #[test]
+    fn it_pauses_while_backgrounded() {
+        let mut app = create_app(1.5 / 60., default());
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let before = frame(&app);
+        assert!(before > 0);
+
+        app.world.resource_mut::<SimulationPacing>().backgrounded = true;
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_eq!(frame(&app), before);
+
+        app.world.resource_mut::<SimulationPacing>().backgrounded = false;
+        app.update();
+
+        assert!(frame(&app) - before <= 2);
+    }
+
+    /// This test makes sure frames are advanced at the backgrounded rate while backgrounded.
+    #[test]
+    fn it_throttles_while_backgrounded() {
+        let mut app = create_app(
+            1. / 60.,
+            SimulationPacing {
+                backgrounded_fps: Some(30),
+                backgrounded: true,
+                ..default()
+            },
+        );
+
+        for _ in 0..11 {
+            app.update();
+        }
+
+        // 10 updates of elapsed time at half the rate
+        assert!((4..=5).contains(&frame(&app)));
+    }
+}